@@ -0,0 +1,142 @@
+use std::path::{Component, Path};
+
+use actix_web::http::header::CacheControl;
+
+/// A `Cache-Control` value applied to files whose path matches a glob pattern.
+#[derive(Debug, Clone)]
+pub(crate) struct CachePolicy {
+    pattern: String,
+    cache_control: CacheControl,
+}
+
+impl CachePolicy {
+    pub(crate) fn new(pattern: &str, cache_control: CacheControl) -> Self {
+        Self {
+            pattern: pattern.trim_start_matches('/').to_owned(),
+            cache_control,
+        }
+    }
+
+    pub(crate) fn cache_control(&self) -> &CacheControl {
+        &self.cache_control
+    }
+
+    /// Returns true if `path`, relative to the served directory, matches this policy's pattern.
+    ///
+    /// Patterns without a `/` are matched against the file name only; other patterns are matched
+    /// against the whole relative path.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let segments = path
+            .components()
+            .filter_map(|comp| match comp {
+                Component::Normal(seg) => seg.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if self.pattern.contains('/') {
+            let pattern = self.pattern.split('/').collect::<Vec<_>>();
+            match_segments(&pattern, &segments)
+        } else {
+            match segments.last() {
+                Some(name) => match_segment(self.pattern.as_bytes(), name.as_bytes()),
+                None => false,
+            }
+        }
+    }
+}
+
+/// Returns the first policy matching `path`, if any.
+pub(crate) fn find_policy<'a>(policies: &'a [CachePolicy], path: &Path) -> Option<&'a CachePolicy> {
+    policies.iter().find(|policy| policy.matches(path))
+}
+
+/// Matches path segments, where a `**` pattern segment matches zero or more path segments.
+fn match_segments(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| match_segments(rest, &segments[skip..]))
+        }
+
+        Some((pat, rest)) => match segments.split_first() {
+            Some((seg, segments)) => {
+                match_segment(pat.as_bytes(), seg.as_bytes()) && match_segments(rest, segments)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Matches a single path segment, supporting `*` (any run of characters) and `?` (any single
+/// character) wildcards.
+fn match_segment(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+
+    // position of last `*` in pattern and the text position it was tried against
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+
+            Some(&ch) if ch == b'?' || ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, t));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&ch| ch == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header::CacheDirective;
+
+    use super::*;
+
+    fn policy(pattern: &str) -> CachePolicy {
+        CachePolicy::new(pattern, CacheControl(vec![CacheDirective::NoCache]))
+    }
+
+    #[test]
+    fn file_name_patterns() {
+        assert!(policy("*.js").matches(Path::new("app.js")));
+        assert!(policy("*.js").matches(Path::new("assets/app.js")));
+        assert!(policy("index.html").matches(Path::new("index.html")));
+        assert!(policy("app.????????.js").matches(Path::new("app.0123abcd.js")));
+
+        assert!(!policy("*.js").matches(Path::new("app.css")));
+        assert!(!policy("*.js").matches(Path::new("")));
+        assert!(!policy("app.????????.js").matches(Path::new("app.js")));
+    }
+
+    #[test]
+    fn path_patterns() {
+        assert!(policy("assets/*").matches(Path::new("assets/app.js")));
+        assert!(policy("/assets/*").matches(Path::new("assets/app.js")));
+        assert!(policy("assets/**").matches(Path::new("assets/js/app.js")));
+        assert!(policy("**/*.css").matches(Path::new("style.css")));
+        assert!(policy("**/*.css").matches(Path::new("a/b/style.css")));
+        assert!(policy("a/**/c/*.txt").matches(Path::new("a/c/d.txt")));
+        assert!(policy("a/**/c/*.txt").matches(Path::new("a/b/b/c/d.txt")));
+
+        assert!(!policy("assets/*").matches(Path::new("assets/js/app.js")));
+        assert!(!policy("assets/*").matches(Path::new("app.js")));
+        assert!(!policy("a/**/c/*.txt").matches(Path::new("a/b/d.txt")));
+    }
+}
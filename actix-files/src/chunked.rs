@@ -6,7 +6,13 @@ use std::{
     task::{Context, Poll},
 };
 
-use actix_web::{error::Error, web::Bytes};
+#[cfg(not(feature = "experimental-io-uring"))]
+use actix_web::body::FileRegion;
+use actix_web::{
+    body::{BodySize, MessageBody},
+    error::Error,
+    web::Bytes,
+};
 #[cfg(feature = "experimental-io-uring")]
 use bytes::BytesMut;
 use futures_core::{ready, Stream};
@@ -57,7 +63,7 @@ pub(crate) fn new_chunked_read(
     size: u64,
     offset: u64,
     file: File,
-) -> impl Stream<Item = Result<Bytes, Error>> {
+) -> impl Stream<Item = Result<Bytes, Error>> + MessageBody<Error = Error> {
     ChunkedReadFile {
         size,
         offset,
@@ -149,7 +155,7 @@ where
                     this.state
                         .project_replace(ChunkedReadFileState::Future { fut });
 
-                    self.poll_next(cx)
+                    Stream::poll_next(self, cx)
                 }
             }
             ChunkedReadFileStateProj::Future { fut } => {
@@ -198,7 +204,7 @@ where
                     this.state
                         .project_replace(ChunkedReadFileState::Future { fut });
 
-                    self.poll_next(cx)
+                    Stream::poll_next(self, cx)
                 }
             }
             ChunkedReadFileStateProj::Future { fut } => {
@@ -215,3 +221,32 @@ where
         }
     }
 }
+
+impl<F, Fut> MessageBody for ChunkedReadFile<F, Fut>
+where
+    Self: Stream<Item = Result<Bytes, Error>>,
+{
+    type Error = Error;
+
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.size)
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Stream::poll_next(self, cx)
+    }
+
+    #[cfg(not(feature = "experimental-io-uring"))]
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        // the file is only available between reads
+        match &self.state {
+            ChunkedReadFileState::File { file: Some(file) } => {
+                Some(FileRegion::new(file, self.offset, self.size - self.counter))
+            }
+            _ => None,
+        }
+    }
+}
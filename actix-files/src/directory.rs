@@ -1,11 +1,19 @@
 use std::{
+    cmp::Ordering,
+    collections::HashMap,
     fmt::Write,
     fs::DirEntry,
     io,
     path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use actix_web::{dev::ServiceResponse, HttpRequest, HttpResponse};
+use actix_web::{
+    dev::ServiceResponse,
+    http::header::{self, HttpDate},
+    web::Query,
+    HttpRequest, HttpResponse,
+};
 use percent_encoding::{utf8_percent_encode, CONTROLS};
 use v_htmlescape::escape as escape_html_entity;
 
@@ -124,3 +132,453 @@ pub(crate) fn directory_listing(
             .body(html),
     ))
 }
+
+/// Built-in directory listing formats.
+///
+/// See [`Files::files_listing_format()`](crate::Files::files_listing_format).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ListingFormat {
+    /// Plain HTML list of entry names.
+    ///
+    /// This is the default format.
+    #[default]
+    Html,
+
+    /// HTML table of entries with sizes and modification dates, sortable by column.
+    Table,
+
+    /// JSON document containing entry names, sizes, modification times and types.
+    #[cfg(feature = "json-listing")]
+    Json,
+
+    /// Chooses between [`Table`](Self::Table) and [`Json`](Self::Json) based on the request's
+    /// `Accept` header, preferring HTML.
+    #[cfg(feature = "json-listing")]
+    Negotiate,
+}
+
+/// Options for the built-in directory listing renderers.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ListingOptions {
+    pub(crate) format: ListingFormat,
+    pub(crate) page_size: Option<usize>,
+}
+
+/// Type of directory listing entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryType {
+    File,
+    Dir,
+    Symlink,
+}
+
+#[cfg(feature = "json-listing")]
+impl EntryType {
+    fn as_str(self) -> &'static str {
+        match self {
+            EntryType::File => "file",
+            EntryType::Dir => "dir",
+            EntryType::Symlink => "symlink",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ListingEntry {
+    name: String,
+    entry_type: EntryType,
+    size: u64,
+    /// Modification time, in seconds since the Unix epoch.
+    #[cfg_attr(not(feature = "json-listing"), allow(dead_code))]
+    mtime: Option<u64>,
+    modified: Option<SystemTime>,
+    url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Mtime,
+}
+
+impl SortKey {
+    fn parse(val: &str) -> Option<Self> {
+        match val {
+            "name" => Some(SortKey::Name),
+            "size" => Some(SortKey::Size),
+            "mtime" => Some(SortKey::Mtime),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Mtime => "mtime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn parse(val: &str) -> Option<Self> {
+        match val {
+            "asc" => Some(SortOrder::Asc),
+            "desc" => Some(SortOrder::Desc),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+/// Query parameters understood by the sortable and paginated listing formats.
+#[derive(Debug, Default)]
+struct ListingQuery {
+    sort: SortKey,
+    order: SortOrder,
+    page: Option<usize>,
+}
+
+impl ListingQuery {
+    /// Parses the listing parameters from a query string, ignoring malformed values.
+    fn parse(query: &str) -> Self {
+        let mut parsed = ListingQuery::default();
+
+        let params = match Query::<HashMap<String, String>>::from_query(query) {
+            Ok(params) => params.into_inner(),
+            Err(_) => return parsed,
+        };
+
+        if let Some(sort) = params.get("sort").and_then(|val| SortKey::parse(val)) {
+            parsed.sort = sort;
+        }
+
+        if let Some(order) = params.get("order").and_then(|val| SortOrder::parse(val)) {
+            parsed.order = order;
+        }
+
+        parsed.page = params.get("page").and_then(|val| val.parse().ok());
+
+        parsed
+    }
+}
+
+/// Renders a directory listing according to `opts`.
+pub(crate) fn render_listing(
+    dir: &Directory,
+    req: &HttpRequest,
+    opts: ListingOptions,
+) -> Result<ServiceResponse, io::Error> {
+    let (format, negotiated) = match opts.format {
+        ListingFormat::Html if opts.page_size.is_none() => return directory_listing(dir, req),
+        #[cfg(feature = "json-listing")]
+        ListingFormat::Negotiate => (negotiate_format(req), true),
+        format => (format, false),
+    };
+
+    let query = ListingQuery::parse(req.query_string());
+
+    let mut entries = read_entries(dir, req)?;
+    sort_entries(&mut entries, query.sort, query.order);
+
+    let total = entries.len();
+    let page_size = opts.page_size.unwrap_or(usize::MAX).max(1);
+    let pages = total.div_ceil(page_size).max(1);
+    let page = query.page.unwrap_or(1).clamp(1, pages);
+    let entries = entries
+        .into_iter()
+        .skip((page - 1).saturating_mul(page_size))
+        .take(page_size)
+        .collect::<Vec<_>>();
+
+    let mut res = HttpResponse::Ok();
+
+    if negotiated {
+        res.insert_header((header::VARY, "Accept"));
+    }
+
+    let res = match format {
+        #[cfg(feature = "json-listing")]
+        ListingFormat::Json => res
+            .content_type(mime::APPLICATION_JSON)
+            .body(json_listing(req, &entries, page, pages, total)),
+
+        ListingFormat::Html => res
+            .content_type("text/html; charset=utf-8")
+            .body(html_list(req, &entries, &query, page, pages)),
+
+        _ => res
+            .content_type("text/html; charset=utf-8")
+            .body(html_table(req, &entries, &query, page, pages)),
+    };
+
+    Ok(ServiceResponse::new(req.clone(), res))
+}
+
+#[cfg(feature = "json-listing")]
+fn json_listing(
+    req: &HttpRequest,
+    entries: &[ListingEntry],
+    page: usize,
+    pages: usize,
+    total: usize,
+) -> String {
+    let entries = entries
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "name": entry.name,
+                "type": entry.entry_type.as_str(),
+                "size": entry.size,
+                "mtime": entry.mtime,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::json!({
+        "path": req.path(),
+        "page": page,
+        "pages": pages,
+        "total": total,
+        "entries": entries,
+    })
+    .to_string()
+}
+
+/// Picks the preferred listing format from the request's `Accept` header.
+#[cfg(feature = "json-listing")]
+fn negotiate_format(req: &HttpRequest) -> ListingFormat {
+    use actix_web::http::header::Header as _;
+
+    let accept = match header::Accept::parse(req) {
+        Ok(accept) => accept,
+        Err(_) => return ListingFormat::Table,
+    };
+
+    for mime in accept.ranked() {
+        if mime == mime::APPLICATION_JSON {
+            return ListingFormat::Json;
+        }
+
+        if mime == mime::TEXT_HTML || mime.type_() == mime::STAR || mime.type_() == mime::TEXT {
+            return ListingFormat::Table;
+        }
+    }
+
+    ListingFormat::Table
+}
+
+fn read_entries(dir: &Directory, req: &HttpRequest) -> io::Result<Vec<ListingEntry>> {
+    let base = Path::new(req.path());
+    let mut entries = Vec::new();
+
+    for entry in dir.path.read_dir()? {
+        if !dir.is_visible(&entry) {
+            continue;
+        }
+
+        let entry = entry.unwrap();
+
+        let url = match entry.path().strip_prefix(&dir.path) {
+            Ok(p) if cfg!(windows) => base.join(p).to_string_lossy().replace('\\', "/"),
+            Ok(p) => base.join(p).to_string_lossy().into_owned(),
+            Err(_) => continue,
+        };
+
+        let entry_type = match entry.file_type() {
+            Ok(ft) if ft.is_symlink() => EntryType::Symlink,
+            Ok(ft) if ft.is_dir() => EntryType::Dir,
+            Ok(_) => EntryType::File,
+            Err(_) => continue,
+        };
+
+        // follows symlinks so that sizes and dates reflect the target
+        let (size, modified) = match entry.path().metadata() {
+            Ok(md) if md.is_dir() => (0, md.modified().ok()),
+            Ok(md) => (md.len(), md.modified().ok()),
+            Err(_) => (0, None),
+        };
+
+        let mtime = modified
+            .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+            .map(|dur| dur.as_secs());
+
+        entries.push(ListingEntry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            entry_type,
+            size,
+            mtime,
+            modified,
+            url,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn sort_entries(entries: &mut [ListingEntry], key: SortKey, order: SortOrder) {
+    entries.sort_by(|a, b| {
+        let ord = match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Mtime => a.modified.cmp(&b.modified),
+        }
+        .then_with(|| a.name.cmp(&b.name));
+
+        match order {
+            SortOrder::Asc => ord,
+            SortOrder::Desc => ord.reverse(),
+        }
+    });
+}
+
+fn html_list(
+    req: &HttpRequest,
+    entries: &[ListingEntry],
+    query: &ListingQuery,
+    page: usize,
+    pages: usize,
+) -> String {
+    let index_of = format!("Index of {}", req.path());
+    let mut body = String::new();
+
+    for entry in entries {
+        let _ = write!(
+            body,
+            "<li><a href=\"{}\">{}{}</a></li>",
+            utf8_percent_encode(&entry.url, CONTROLS),
+            escape_html_entity(&entry.name),
+            if entry.entry_type == EntryType::Dir {
+                "/"
+            } else {
+                ""
+            },
+        );
+    }
+
+    format!(
+        "<html>\
+         <head><title>{}</title></head>\
+         <body><h1>{}</h1>\
+         <ul>\
+         {}\
+         </ul>{}</body>\n</html>",
+        index_of,
+        index_of,
+        body,
+        pagination_links(query, page, pages),
+    )
+}
+
+fn html_table(
+    req: &HttpRequest,
+    entries: &[ListingEntry],
+    query: &ListingQuery,
+    page: usize,
+    pages: usize,
+) -> String {
+    let index_of = format!("Index of {}", req.path());
+    let mut rows = String::new();
+
+    for entry in entries {
+        let is_dir = entry.entry_type == EntryType::Dir;
+
+        let _ = write!(
+            rows,
+            "<tr><td><a href=\"{}\">{}{}</a></td><td>{}</td><td>{}</td></tr>",
+            utf8_percent_encode(&entry.url, CONTROLS),
+            escape_html_entity(&entry.name),
+            if is_dir { "/" } else { "" },
+            if is_dir {
+                "-".to_owned()
+            } else {
+                entry.size.to_string()
+            },
+            entry
+                .modified
+                .map(|mtime| HttpDate::from(mtime).to_string())
+                .unwrap_or_default(),
+        );
+    }
+
+    format!(
+        "<html>\
+         <head><title>{}</title></head>\
+         <body><h1>{}</h1>\
+         <table>\
+         <thead><tr><th>{}</th><th>{}</th><th>{}</th></tr></thead>\
+         <tbody>{}</tbody>\
+         </table>{}</body>\n</html>",
+        index_of,
+        index_of,
+        sort_link(query, SortKey::Name, "Name"),
+        sort_link(query, SortKey::Size, "Size"),
+        sort_link(query, SortKey::Mtime, "Last Modified"),
+        rows,
+        pagination_links(query, page, pages),
+    )
+}
+
+/// Returns a column header link that sorts by `key`, toggling the order if already sorted by it.
+fn sort_link(query: &ListingQuery, key: SortKey, label: &str) -> String {
+    let order = match (query.sort == key, query.order) {
+        (true, SortOrder::Asc) => SortOrder::Desc,
+        _ => SortOrder::Asc,
+    };
+
+    format!(
+        "<a href=\"?sort={}&amp;order={}\">{}</a>",
+        key.as_str(),
+        order.as_str(),
+        label
+    )
+}
+
+fn pagination_links(query: &ListingQuery, page: usize, pages: usize) -> String {
+    if pages <= 1 {
+        return String::new();
+    }
+
+    let link = |page: usize, label: &str| {
+        format!(
+            "<a href=\"?sort={}&amp;order={}&amp;page={}\">{}</a>",
+            query.sort.as_str(),
+            query.order.as_str(),
+            page,
+            label
+        )
+    };
+
+    let mut nav = String::from("<nav>");
+
+    if page > 1 {
+        nav.push_str(&link(page - 1, "Previous"));
+        nav.push(' ');
+    }
+
+    let _ = write!(nav, "Page {} of {}", page, pages);
+
+    if page < pages {
+        nav.push(' ');
+        nav.push_str(&link(page + 1, "Next"));
+    }
+
+    nav.push_str("</nav>");
+    nav
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path},
+    sync::Arc,
+};
+
+use actix_web::http::header::EntityTag;
+
+/// Precomputed `ETag`s for files, keyed by their path relative to the served directory.
+///
+/// A manifest allows replicas serving identical content to send identical validators without
+/// hashing files at runtime. Files not listed in the manifest fall back to content hashing, if
+/// enabled, or to the default metadata-derived `ETag`.
+///
+/// Cloning a manifest is cheap; build it once and share it between workers.
+///
+/// # Panics
+/// Collecting a manifest from an iterator panics if a tag contains characters not allowed in an
+/// `ETag`, such as `"`.
+///
+/// # Examples
+/// ```
+/// use actix_files::{EtagManifest, Files};
+///
+/// let manifest = EtagManifest::from_iter([
+///     ("index.html", "6f1ed002ab5595859014ebf0951522d9"),
+///     ("assets/app.js", "5bd4f4c4a1b1e8b1ba1cde6e8c2ab4ff"),
+/// ]);
+///
+/// let files = Files::new("/", "./dist").etag_manifest(manifest);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EtagManifest {
+    entries: Arc<HashMap<String, EntityTag>>,
+}
+
+impl EtagManifest {
+    /// Loads a manifest from a checksum file, such as the output of `b3sum` or `sha256sum`.
+    ///
+    /// Each non-empty line must contain a hash followed by whitespace and a file path relative
+    /// to the served directory. A `*` marking binary mode before the path is ignored.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut entries = HashMap::new();
+
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (hash, path) = line
+                .split_once(char::is_whitespace)
+                .map(|(hash, path)| (hash, path.trim_start().trim_start_matches('*')))
+                .filter(|(hash, path)| is_valid_tag(hash) && !path.is_empty())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("malformed ETag manifest entry on line {}", idx + 1),
+                    )
+                })?;
+
+            entries.insert(normalize_key(path), EntityTag::new_strong(hash.to_owned()));
+        }
+
+        Ok(Self {
+            entries: Arc::new(entries),
+        })
+    }
+
+    /// Returns the number of entries in the manifest.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the manifest contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the `ETag` listed for `path`, relative to the served directory.
+    pub(crate) fn get(&self, path: &Path) -> Option<&EntityTag> {
+        self.entries.get(&path_key(path))
+    }
+}
+
+impl<P: AsRef<str>, T: Into<String>> FromIterator<(P, T)> for EtagManifest {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        let entries = iter
+            .into_iter()
+            .map(|(path, tag)| {
+                (
+                    normalize_key(path.as_ref()),
+                    EntityTag::new_strong(tag.into()),
+                )
+            })
+            .collect();
+
+        Self {
+            entries: Arc::new(entries),
+        }
+    }
+}
+
+/// Returns true if `tag` contains only characters allowed in an entity tag.
+fn is_valid_tag(tag: &str) -> bool {
+    tag.bytes()
+        .all(|b| b == b'!' || (b'#'..=b'~').contains(&b) || b >= 0x80)
+}
+
+fn normalize_key(path: &str) -> String {
+    path_key(Path::new(path.trim_start_matches("./")))
+}
+
+/// Joins normal path components with `/`, regardless of platform.
+fn path_key(path: &Path) -> String {
+    path.components()
+        .filter_map(|comp| match comp {
+            Component::Normal(seg) => Some(seg.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_lookup() {
+        let manifest = EtagManifest::from_iter([("./index.html", "abc"), ("assets/app.js", "def")]);
+
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(
+            manifest.get(Path::new("index.html")),
+            Some(&EntityTag::new_strong("abc".to_owned()))
+        );
+        assert_eq!(
+            manifest.get(&Path::new("assets").join("app.js")),
+            Some(&EntityTag::new_strong("def".to_owned()))
+        );
+        assert_eq!(manifest.get(Path::new("app.js")), None);
+    }
+
+    #[test]
+    fn manifest_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SUMS");
+        fs::write(
+            &path,
+            "abc  index.html\n\ndef *assets/app.js\n0123 ./assets/app.css\n",
+        )
+        .unwrap();
+
+        let manifest = EtagManifest::load(&path).unwrap();
+        assert_eq!(manifest.len(), 3);
+        assert_eq!(
+            manifest.get(Path::new("assets/app.js")),
+            Some(&EntityTag::new_strong("def".to_owned()))
+        );
+        assert_eq!(
+            manifest.get(Path::new("assets/app.css")),
+            Some(&EntityTag::new_strong("0123".to_owned()))
+        );
+
+        fs::write(&path, "abc\n").unwrap();
+        let err = EtagManifest::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::write(&path, "\"abc\" index.html\n").unwrap();
+        let err = EtagManifest::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
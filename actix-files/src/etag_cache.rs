@@ -0,0 +1,215 @@
+//! Cache of `ETag`s derived from file contents.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use actix_web::{http::header::EntityTag, web};
+use xxhash_rust::xxh3::Xxh3;
+
+/// Default maximum number of cached content-hash `ETag`s.
+const DEFAULT_ETAG_CACHE_CAPACITY: usize = 4096;
+
+#[derive(Debug)]
+struct CachedEtag {
+    modified: SystemTime,
+    len: u64,
+    etag: EntityTag,
+    /// Value of the cache's use counter when this entry was last used.
+    last_used: u64,
+}
+
+/// Cache of content-hash `ETag`s, invalidated when a file's modification time or size changes.
+///
+/// Holds at most `capacity` entries, evicting the least recently used entry when full.
+#[derive(Debug)]
+pub(crate) struct EtagCache {
+    capacity: usize,
+    inner: RefCell<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    entries: HashMap<PathBuf, CachedEtag>,
+    /// Paths of cached entries, ordered by their last use.
+    recency: BTreeMap<u64, PathBuf>,
+    counter: u64,
+}
+
+impl CacheInner {
+    fn get(&mut self, path: &Path, modified: SystemTime, len: u64) -> Option<EntityTag> {
+        let cached = self.entries.get_mut(path)?;
+
+        if cached.modified != modified || cached.len != len {
+            return None;
+        }
+
+        self.counter += 1;
+        let path = self.recency.remove(&cached.last_used).unwrap();
+        self.recency.insert(self.counter, path);
+        cached.last_used = self.counter;
+
+        Some(cached.etag.clone())
+    }
+
+    fn insert(&mut self, path: PathBuf, modified: SystemTime, len: u64, etag: EntityTag) {
+        self.counter += 1;
+
+        let cached = CachedEtag {
+            modified,
+            len,
+            etag,
+            last_used: self.counter,
+        };
+
+        if let Some(stale) = self.entries.insert(path.clone(), cached) {
+            self.recency.remove(&stale.last_used);
+        }
+
+        self.recency.insert(self.counter, path);
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let (_, path) = self.recency.pop_first().unwrap();
+            self.entries.remove(&path);
+        }
+    }
+}
+
+impl Default for EtagCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_ETAG_CACHE_CAPACITY)
+    }
+}
+
+impl EtagCache {
+    /// Constructs a cache holding at most `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: RefCell::new(CacheInner::default()),
+        }
+    }
+
+    /// Returns the content-hash `ETag` for the file at `path`, hashing it on the blocking thread
+    /// pool if there is no up-to-date cached value.
+    pub(crate) async fn get_or_hash(
+        &self,
+        path: &Path,
+        modified: SystemTime,
+        len: u64,
+    ) -> io::Result<EntityTag> {
+        if let Some(etag) = self.inner.borrow_mut().get(path, modified, len) {
+            return Ok(etag);
+        }
+
+        let hash_path = path.to_owned();
+        let etag = web::block(move || hash_file(&hash_path))
+            .await
+            .map_err(io::Error::other)??;
+
+        if self.capacity > 0 {
+            let mut inner = self.inner.borrow_mut();
+            inner.insert(path.to_owned(), modified, len, etag.clone());
+            inner.evict_to(self.capacity);
+        }
+
+        Ok(etag)
+    }
+}
+
+/// Creates a strong `ETag` from the XXH3-128 hash of a file's contents.
+fn hash_file(path: &Path) -> io::Result<EntityTag> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = HashWriter(Xxh3::new());
+    io::copy(&mut file, &mut hasher)?;
+
+    Ok(EntityTag::new_strong(format!(
+        "{:032x}",
+        hasher.0.digest128()
+    )))
+}
+
+struct HashWriter(Xxh3);
+
+impl io::Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn cache_hashes_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (dir.path().join("a.txt"), dir.path().join("b.txt"));
+        fs::write(&a, "identical").unwrap();
+        fs::write(&b, "identical").unwrap();
+
+        let cache = EtagCache::default();
+        let modified = SystemTime::UNIX_EPOCH;
+
+        let etag_a = cache.get_or_hash(&a, modified, 9).await.unwrap();
+        let etag_b = cache.get_or_hash(&b, modified, 9).await.unwrap();
+        assert!(etag_a.strong_eq(&etag_b));
+
+        // cached value is returned while metadata is unchanged
+        fs::write(&a, "different").unwrap();
+        let cached = cache.get_or_hash(&a, modified, 9).await.unwrap();
+        assert!(cached.strong_eq(&etag_a));
+
+        let rehashed = cache.get_or_hash(&a, SystemTime::now(), 9).await.unwrap();
+        assert!(!rehashed.strong_eq(&etag_a));
+    }
+
+    #[actix_rt::test]
+    async fn cache_evicts_least_recently_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = ["a.txt", "b.txt", "c.txt"].map(|name| dir.path().join(name));
+        for path in &paths {
+            fs::write(path, "old").unwrap();
+        }
+
+        let cache = EtagCache::new(2);
+        let modified = SystemTime::UNIX_EPOCH;
+        let [a, b, c] = &paths;
+
+        let etag_a = cache.get_or_hash(a, modified, 3).await.unwrap();
+        let etag_b = cache.get_or_hash(b, modified, 3).await.unwrap();
+
+        // using `a` makes `b` the least recently used entry
+        cache.get_or_hash(a, modified, 3).await.unwrap();
+        cache.get_or_hash(c, modified, 3).await.unwrap();
+        assert_eq!(cache.inner.borrow().entries.len(), 2);
+        assert_eq!(cache.inner.borrow().recency.len(), 2);
+
+        // changed contents are only noticed for evicted entries
+        for path in &paths {
+            fs::write(path, "new").unwrap();
+        }
+
+        let cached_a = cache.get_or_hash(a, modified, 3).await.unwrap();
+        assert!(cached_a.strong_eq(&etag_a));
+
+        let rehashed_b = cache.get_or_hash(b, modified, 3).await.unwrap();
+        assert!(!rehashed_b.strong_eq(&etag_b));
+
+        // caching can be disabled
+        let cache = EtagCache::new(0);
+        cache.get_or_hash(a, modified, 3).await.unwrap();
+        assert!(cache.inner.borrow().entries.is_empty());
+    }
+}
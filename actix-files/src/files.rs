@@ -12,15 +12,19 @@ use actix_web::{
     },
     error::Error,
     guard::Guard,
-    http::header::DispositionType,
+    http::header::{CacheControl, DispositionType},
     HttpRequest,
 };
 use futures_core::future::LocalBoxFuture;
 
+#[cfg(feature = "content-hash-etag")]
+use crate::EtagCache;
 use crate::{
-    directory_listing, named,
+    named, render_listing,
     service::{FilesService, FilesServiceInner},
-    Directory, DirectoryRenderer, HttpNewService, MimeOverride, PathFilter,
+    write::DEFAULT_MAX_UPLOAD_SIZE,
+    CachePolicy, Directory, DirectoryRenderer, EtagManifest, HttpNewService, ListingFormat,
+    ListingOptions, MimeOverride, PathFilter,
 };
 
 /// Static files handling service.
@@ -42,13 +46,23 @@ pub struct Files {
     show_index: bool,
     redirect_to_slash: bool,
     default: Rc<RefCell<Option<Rc<HttpNewService>>>>,
-    renderer: Rc<DirectoryRenderer>,
+    renderer: Option<Rc<DirectoryRenderer>>,
+    listing_options: ListingOptions,
     mime_override: Option<Rc<MimeOverride>>,
     path_filter: Option<Rc<PathFilter>>,
     file_flags: named::Flags,
     use_guards: Option<Rc<dyn Guard>>,
+    write_guard: Option<Rc<dyn Guard>>,
+    max_upload_size: u64,
     guards: Vec<Rc<dyn Guard>>,
     hidden_files: bool,
+    spa_index: Option<String>,
+    cache_policies: Vec<CachePolicy>,
+    #[cfg(feature = "content-hash-etag")]
+    content_hash_etag: bool,
+    etag_manifest: Option<EtagManifest>,
+    #[cfg(feature = "content-hash-etag")]
+    etag_cache: Rc<EtagCache>,
 }
 
 impl fmt::Debug for Files {
@@ -66,13 +80,23 @@ impl Clone for Files {
             redirect_to_slash: self.redirect_to_slash,
             default: self.default.clone(),
             renderer: self.renderer.clone(),
+            listing_options: self.listing_options,
             file_flags: self.file_flags,
             mount_path: self.mount_path.clone(),
             mime_override: self.mime_override.clone(),
             path_filter: self.path_filter.clone(),
             use_guards: self.use_guards.clone(),
+            write_guard: self.write_guard.clone(),
+            max_upload_size: self.max_upload_size,
             guards: self.guards.clone(),
             hidden_files: self.hidden_files,
+            spa_index: self.spa_index.clone(),
+            cache_policies: self.cache_policies.clone(),
+            #[cfg(feature = "content-hash-etag")]
+            content_hash_etag: self.content_hash_etag,
+            etag_manifest: self.etag_manifest.clone(),
+            #[cfg(feature = "content-hash-etag")]
+            etag_cache: self.etag_cache.clone(),
         }
     }
 }
@@ -112,13 +136,23 @@ impl Files {
             show_index: false,
             redirect_to_slash: false,
             default: Rc::new(RefCell::new(None)),
-            renderer: Rc::new(directory_listing),
+            renderer: None,
+            listing_options: ListingOptions::default(),
             mime_override: None,
             path_filter: None,
             file_flags: named::Flags::default(),
             use_guards: None,
+            write_guard: None,
+            max_upload_size: DEFAULT_MAX_UPLOAD_SIZE,
             guards: Vec::new(),
             hidden_files: false,
+            spa_index: None,
+            cache_policies: Vec::new(),
+            #[cfg(feature = "content-hash-etag")]
+            content_hash_etag: false,
+            etag_manifest: None,
+            #[cfg(feature = "content-hash-etag")]
+            etag_cache: Rc::new(EtagCache::default()),
         }
     }
 
@@ -142,12 +176,43 @@ impl Files {
     }
 
     /// Set custom directory renderer.
+    ///
+    /// A custom renderer takes precedence over [`Files::files_listing_format()`].
     pub fn files_listing_renderer<F>(mut self, f: F) -> Self
     where
         for<'r, 's> F:
             Fn(&'r Directory, &'s HttpRequest) -> Result<ServiceResponse, io::Error> + 'static,
     {
-        self.renderer = Rc::new(f);
+        self.renderer = Some(Rc::new(f));
+        self
+    }
+
+    /// Sets the format of the built-in files listing.
+    ///
+    /// By default, a plain HTML list is rendered. The [`Table`](ListingFormat::Table) and `Json`
+    /// formats can be sorted using the `sort` (`name`, `size` or `mtime`) and `order` (`asc` or
+    /// `desc`) query parameters. The `Json` and `Negotiate` formats require the `json-listing`
+    /// feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_files::{Files, ListingFormat};
+    ///
+    /// let files = Files::new("/", "./static")
+    ///     .show_files_listing()
+    ///     .files_listing_format(ListingFormat::Table);
+    /// ```
+    pub fn files_listing_format(mut self, format: ListingFormat) -> Self {
+        self.listing_options.format = format;
+        self
+    }
+
+    /// Splits the built-in files listing into pages of at most `size` entries.
+    ///
+    /// Pages are selected using the `page` query parameter, starting from 1. By default, all
+    /// entries are listed on one page.
+    pub fn files_listing_page_size(mut self, size: usize) -> Self {
+        self.listing_options.page_size = Some(size);
         self
     }
 
@@ -212,6 +277,50 @@ impl Files {
         self
     }
 
+    /// Specifies whether to derive ETags from file contents instead of file metadata.
+    ///
+    /// By default, ETags are derived from the file's inode, size and modification time, which
+    /// differ between machines and deployments even when the contents are identical. When enabled,
+    /// a hash of the file's contents is used instead so that replicas serving the same content
+    /// produce the same validators.
+    ///
+    /// Hashes are computed on the blocking thread pool the first time a file is served and cached
+    /// until its size or modification time changes. Files listed in an
+    /// [ETag manifest](Self::etag_manifest) are never hashed.
+    ///
+    /// Requires the `content-hash-etag` crate feature.
+    ///
+    /// Default is false.
+    #[cfg(feature = "content-hash-etag")]
+    pub fn use_content_hash_etag(mut self, value: bool) -> Self {
+        self.content_hash_etag = value;
+        self
+    }
+
+    /// Sets the maximum number of content hash ETags cached by this service.
+    ///
+    /// When the cache is full, the least recently used ETag is evicted and is hashed again the
+    /// next time its file is served. A capacity of zero disables caching. Each worker keeps its own
+    /// cache.
+    ///
+    /// Requires the `content-hash-etag` crate feature.
+    ///
+    /// Default is 4096.
+    #[cfg(feature = "content-hash-etag")]
+    pub fn etag_cache_capacity(mut self, capacity: usize) -> Self {
+        self.etag_cache = Rc::new(EtagCache::new(capacity));
+        self
+    }
+
+    /// Sets precomputed ETags to use for the files listed in `manifest`.
+    ///
+    /// Files not listed in the manifest use content hash ETags, if enabled, or the default
+    /// metadata-derived ETags.
+    pub fn etag_manifest(mut self, manifest: EtagManifest) -> Self {
+        self.etag_manifest = Some(manifest);
+        self
+    }
+
     /// Specifies whether to use Last-Modified or not.
     ///
     /// Default is true.
@@ -262,6 +371,51 @@ impl Files {
         self
     }
 
+    /// Enables `PUT`, `DELETE` and `MKCOL` requests for requests that pass `guard`.
+    ///
+    /// Writes are disabled by default. Once enabled, write requests that do not pass the guard are
+    /// rejected with `403 Forbidden`. Use [`guard::fn_guard`](actix_web::guard::fn_guard) to
+    /// authorize writes with a callback.
+    ///
+    /// - `PUT` writes the request body to a temporary file in the target directory, then atomically
+    ///   renames it over the target file. New files get the default permissions for files created
+    ///   by the server's process, and replaced files keep their permissions. Responds with
+    ///   `201 Created` for new files or `204 No Content` for replaced files.
+    /// - `DELETE` removes a file or an empty directory.
+    /// - `MKCOL` creates a directory.
+    ///
+    /// Missing parent directories are not created and result in `409 Conflict`. `If-Match` and
+    /// `If-None-Match` preconditions are evaluated against the same ETags used when serving files,
+    /// allowing clients to avoid lost updates. Request paths are validated in the same way as
+    /// for reads, and the [path filter](Self::path_filter) applies to writes, too. Uploads larger
+    /// than the [maximum upload size](Self::max_upload_size) are rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_files::Files;
+    /// use actix_web::guard;
+    ///
+    /// let files = Files::new("/artifacts", "./artifacts").write_guard(guard::fn_guard(|ctx| {
+    ///     ctx.head().headers().get("authorization").is_some_and(|auth| auth == "Bearer s3cr3t")
+    /// }));
+    /// ```
+    pub fn write_guard<G: Guard + 'static>(mut self, guard: G) -> Self {
+        self.write_guard = Some(Rc::new(guard));
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of files uploaded with `PUT` requests.
+    ///
+    /// Requests with a larger `Content-Length`, or whose body turns out to be larger while it is
+    /// being written, are rejected with `413 Payload Too Large` and leave the target file
+    /// untouched.
+    ///
+    /// Default is 16MiB.
+    pub fn max_upload_size(mut self, limit: u64) -> Self {
+        self.max_upload_size = limit;
+        self
+    }
+
     /// See [`Files::method_guard`].
     #[doc(hidden)]
     #[deprecated(since = "0.6.0", note = "Renamed to `method_guard`.")]
@@ -316,6 +470,63 @@ impl Files {
         self.hidden_files = true;
         self
     }
+
+    /// Enables single-page application (SPA) mode, serving the given file for unmatched paths.
+    ///
+    /// The `index` path is relative to `serve_from`. It is served in place of a missing file only
+    /// when the request is a `GET` or `HEAD` request that accepts `text/html` and the last path
+    /// segment has no file extension. Requests for missing assets (e.g., `/app.js`) or from
+    /// clients not asking for HTML are still passed to the [default handler](Self::default_handler)
+    /// or answered with `404 Not Found`.
+    ///
+    /// The fallback file is served like any other file, including conditional request handling
+    /// and any matching [cache control policy](Self::cache_control).
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_files::Files;
+    ///
+    /// let files = Files::new("/", "./dist")
+    ///     .index_file("index.html")
+    ///     .spa_fallback("index.html");
+    /// ```
+    pub fn spa_fallback<T: Into<String>>(mut self, index: T) -> Self {
+        self.spa_index = Some(index.into());
+        self
+    }
+
+    /// Adds a `Cache-Control` policy for files matching a glob pattern.
+    ///
+    /// Patterns are matched against the file path relative to `serve_from`. A pattern containing
+    /// no `/` is matched against the file name only. Supported wildcards are `*` (any characters
+    /// except `/`), `?` (any single character except `/`) and `**` as a whole path segment (any
+    /// number of directories).
+    ///
+    /// Policies are checked in the order they were added and the first match wins. The header is
+    /// not added to files served by the default handler.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_files::Files;
+    /// use actix_web::http::header::{CacheControl, CacheDirective};
+    ///
+    /// let files = Files::new("/", "./dist")
+    ///     .index_file("index.html")
+    ///     .cache_control("index.html", CacheControl(vec![CacheDirective::NoCache]))
+    ///     .cache_control(
+    ///         "assets/**",
+    ///         CacheControl(vec![
+    ///             CacheDirective::Public,
+    ///             CacheDirective::MaxAge(31_536_000),
+    ///             CacheDirective::Extension("immutable".to_owned(), None),
+    ///         ]),
+    ///     );
+    /// ```
+    pub fn cache_control(mut self, pattern: &str, cache_control: CacheControl) -> Self {
+        self.cache_policies
+            .push(CachePolicy::new(pattern, cache_control));
+        self
+    }
 }
 
 impl HttpServiceFactory for Files {
@@ -361,12 +572,24 @@ impl ServiceFactory<ServiceRequest> for Files {
             show_index: self.show_index,
             redirect_to_slash: self.redirect_to_slash,
             default: None,
-            renderer: self.renderer.clone(),
+            renderer: self.renderer.clone().unwrap_or_else(|| {
+                let opts = self.listing_options;
+                Rc::new(move |dir: &Directory, req: &HttpRequest| render_listing(dir, req, opts))
+            }),
             mime_override: self.mime_override.clone(),
             path_filter: self.path_filter.clone(),
             file_flags: self.file_flags,
             guards: self.use_guards.clone(),
+            write_guard: self.write_guard.clone(),
+            max_upload_size: self.max_upload_size,
             hidden_files: self.hidden_files,
+            spa_index: self.spa_index.clone(),
+            cache_policies: self.cache_policies.clone(),
+            #[cfg(feature = "content-hash-etag")]
+            content_hash_etag: self.content_hash_etag,
+            etag_manifest: self.etag_manifest.clone(),
+            #[cfg(feature = "content-hash-etag")]
+            etag_cache: self.etag_cache.clone(),
         };
 
         if let Some(ref default) = *self.default.borrow() {
@@ -26,25 +26,38 @@ use actix_web::{
 };
 use mime_guess::from_ext;
 
+mod cache_policy;
 mod chunked;
 mod directory;
 mod encoding;
 mod error;
+mod etag;
+#[cfg(feature = "content-hash-etag")]
+mod etag_cache;
 mod files;
 mod named;
 mod path_buf;
 mod range;
 mod service;
+mod write;
 
 pub use self::{
-    chunked::ChunkedReadFile, directory::Directory, files::Files, named::NamedFile,
-    range::HttpRange, service::FilesService,
+    chunked::ChunkedReadFile,
+    directory::{Directory, ListingFormat},
+    etag::EtagManifest,
+    files::Files,
+    named::NamedFile,
+    range::HttpRange,
+    service::FilesService,
 };
 use self::{
-    directory::{directory_listing, DirectoryRenderer},
+    cache_policy::CachePolicy,
+    directory::{render_listing, DirectoryRenderer, ListingOptions},
     error::FilesError,
     path_buf::PathBufWrap,
 };
+#[cfg(feature = "content-hash-etag")]
+use self::etag_cache::EtagCache;
 
 type HttpService = BoxService<ServiceRequest, ServiceResponse, Error>;
 type HttpNewService = BoxServiceFactory<(), ServiceRequest, ServiceResponse, Error, ()>;
@@ -551,6 +564,84 @@ mod tests {
         assert_eq!(content_length, "100");
     }
 
+    #[actix_rt::test]
+    async fn test_large_file_over_tcp() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let data = (0..5 * 1024 * 1024 + 3)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        fs::write(temp_dir.path().join("large.bin"), &data).unwrap();
+
+        let root = temp_dir.path().to_owned();
+        let srv = actix_test::start(move || App::new().service(Files::new("/", &root)));
+
+        let mut response = srv.get("/large.bin").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.body().limit(usize::MAX).await.unwrap();
+        assert_eq!(bytes, data);
+
+        let mut response = srv
+            .get("/large.bin")
+            .insert_header((header::RANGE, "bytes=1000-2099999"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        let bytes = response.body().limit(usize::MAX).await.unwrap();
+        assert_eq!(bytes, &data[1000..2_100_000]);
+    }
+
+    #[actix_rt::test]
+    async fn test_file_region_through_middleware() {
+        use actix_web::{
+            body::{BodySize, MessageBody as _},
+            middleware::Logger,
+        };
+
+        let srv = test::init_service(
+            App::new()
+                .wrap(Logger::default())
+                .wrap(Compress::default())
+                .service(Files::new("/", ".")),
+        )
+        .await;
+
+        let len = fs::metadata("Cargo.toml").unwrap().len();
+
+        // unencoded file bodies expose their file region for sending with sendfile
+        let req = TestRequest::with_uri("/Cargo.toml").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert!(!res.headers().contains_key(header::CONTENT_ENCODING));
+        let body = res.into_body();
+        assert_eq!(body.size(), BodySize::Sized(len));
+        assert_eq!(body.file_region().map(|region| region.len()), Some(len));
+
+        // encoded bodies do not
+        let req = TestRequest::with_uri("/Cargo.toml")
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+        assert!(res.into_body().file_region().is_none());
+
+        let srv = actix_test::start(|| {
+            App::new()
+                .wrap(Logger::default())
+                .wrap(Compress::default())
+                .service(Files::new("/", "."))
+        });
+
+        let mut res = srv
+            .get("/Cargo.toml")
+            .insert_header((header::ACCEPT_ENCODING, "identity"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let bytes = res.body().await.unwrap();
+        assert_eq!(bytes, fs::read("Cargo.toml").unwrap());
+    }
+
     #[actix_rt::test]
     async fn test_static_files_with_spaces() {
         let srv =
@@ -1035,4 +1126,353 @@ mod tests {
         let bytes = test::read_body(resp).await;
         assert_eq!(bytes, web::Bytes::from_static(b"default content"));
     }
+
+    #[actix_rt::test]
+    async fn test_spa_fallback() {
+        let st = Files::new("/", "./tests/fixtures/spa")
+            .spa_fallback("index.html")
+            .new_service(())
+            .await
+            .unwrap();
+
+        let index = Bytes::from(fs::read("./tests/fixtures/spa/index.html").unwrap());
+
+        let req = TestRequest::with_uri("/users/42")
+            .insert_header((header::ACCEPT, "text/html,application/xhtml+xml,*/*;q=0.8"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, index);
+
+        // media type parameters are ignored
+        let req = TestRequest::with_uri("/users/42")
+            .insert_header((header::ACCEPT, "text/html;charset=utf-8"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, index);
+
+        // existing files are still served
+        let req = TestRequest::with_uri("/assets/app.js")
+            .insert_header((header::ACCEPT, "text/html"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(test::read_body(resp).await, index);
+
+        // missing assets are not replaced with the index file
+        let req = TestRequest::with_uri("/assets/missing.js")
+            .insert_header((header::ACCEPT, "text/html"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // neither are requests that do not accept HTML
+        let req = TestRequest::with_uri("/users/42")
+            .insert_header((header::ACCEPT, "application/json"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = TestRequest::with_uri("/users/42")
+            .insert_header((header::ACCEPT, "text/html;q=0"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // conditional requests are honoured for the fallback file
+        let req = TestRequest::with_uri("/users/42")
+            .insert_header((header::ACCEPT, "text/html"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+
+        let req = TestRequest::with_uri("/settings")
+            .insert_header((header::ACCEPT, "text/html"))
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_rt::test]
+    async fn test_cache_control_policies() {
+        use actix_web::http::header::{CacheControl, CacheDirective};
+
+        let st = Files::new("/", "./tests/fixtures/spa")
+            .index_file("index.html")
+            .spa_fallback("index.html")
+            .cache_control("index.html", CacheControl(vec![CacheDirective::NoCache]))
+            .cache_control(
+                "assets/*.js",
+                CacheControl(vec![
+                    CacheDirective::MaxAge(31_536_000),
+                    CacheDirective::Extension("immutable".to_owned(), None),
+                ]),
+            )
+            .new_service(())
+            .await
+            .unwrap();
+
+        let req = TestRequest::with_uri("/assets/app.js").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CACHE_CONTROL).unwrap(),
+            "max-age=31536000, immutable"
+        );
+
+        let req = TestRequest::with_uri("/").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CACHE_CONTROL).unwrap(),
+            "no-cache"
+        );
+
+        let req = TestRequest::with_uri("/some/route")
+            .insert_header((header::ACCEPT, "text/html"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CACHE_CONTROL).unwrap(),
+            "no-cache"
+        );
+
+        let st = Files::new("/", ".")
+            .cache_control("*.png", CacheControl(vec![CacheDirective::NoStore]))
+            .new_service(())
+            .await
+            .unwrap();
+
+        let req = TestRequest::with_uri("/Cargo.toml").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(!resp.headers().contains_key(header::CACHE_CONTROL));
+    }
+
+    #[cfg(feature = "json-listing")]
+    #[actix_rt::test]
+    async fn test_files_listing_json() {
+        let st = Files::new("/", ".")
+            .show_files_listing()
+            .files_listing_format(ListingFormat::Json)
+            .new_service(())
+            .await
+            .unwrap();
+
+        let req = TestRequest::with_uri("/tests/?sort=size&order=desc").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        let body = test::read_body(resp).await;
+        let listing: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(listing["path"], "/tests/");
+        assert_eq!(listing["page"], 1);
+
+        let entries = listing["entries"].as_array().unwrap();
+        assert_eq!(listing["total"], entries.len());
+
+        let sizes = entries
+            .iter()
+            .map(|entry| entry["size"].as_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert!(sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let utf8 = entries
+            .iter()
+            .find(|entry| entry["name"] == "utf8.txt")
+            .unwrap();
+        assert_eq!(utf8["type"], "file");
+        assert_eq!(utf8["size"], fs::metadata("tests/utf8.txt").unwrap().len());
+        assert!(utf8["mtime"].is_u64());
+
+        let fixtures = entries
+            .iter()
+            .find(|entry| entry["name"] == "fixtures")
+            .unwrap();
+        assert_eq!(fixtures["type"], "dir");
+    }
+
+    #[cfg(feature = "json-listing")]
+    #[actix_rt::test]
+    async fn test_files_listing_negotiated() {
+        let st = Files::new("/", ".")
+            .show_files_listing()
+            .files_listing_format(ListingFormat::Negotiate)
+            .new_service(())
+            .await
+            .unwrap();
+
+        let req = TestRequest::with_uri("/tests")
+            .insert_header((header::ACCEPT, "application/json, text/html;q=0.9"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(resp.headers().get(header::VARY).unwrap(), "Accept");
+
+        let req = TestRequest::with_uri("/tests")
+            .insert_header((header::ACCEPT, "text/html,application/xhtml+xml,*/*;q=0.8"))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+
+        let body = test::read_body(resp).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.contains("<table>"));
+        assert!(body.contains("<a href=\"?sort=size&amp;order=asc\">Size</a>"));
+        assert!(body.contains("<a href=\"/tests/utf8.txt\">utf8.txt</a>"));
+        assert!(body.contains("<td>44</td>"));
+
+        let req = TestRequest::with_uri("/tests").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+    }
+
+    #[cfg(feature = "json-listing")]
+    #[actix_rt::test]
+    async fn test_files_listing_pagination() {
+        let st = Files::new("/", ".")
+            .show_files_listing()
+            .files_listing_format(ListingFormat::Json)
+            .files_listing_page_size(3)
+            .new_service(())
+            .await
+            .unwrap();
+
+        let total = fs::read_dir("tests").unwrap().count();
+        let pages = total.div_ceil(3);
+        let mut names = Vec::new();
+
+        for page in 1..=pages {
+            let req = TestRequest::with_uri(&format!("/tests/?page={}", page)).to_srv_request();
+            let resp = test::call_service(&st, req).await;
+            let listing: serde_json::Value =
+                serde_json::from_slice(&test::read_body(resp).await).unwrap();
+
+            assert_eq!(listing["page"], page);
+            assert_eq!(listing["pages"], pages);
+            assert_eq!(listing["total"], total);
+
+            let entries = listing["entries"].as_array().unwrap();
+            assert!(entries.len() <= 3);
+            names.extend(entries.iter().map(|entry| entry["name"].to_string()));
+        }
+
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(names, sorted);
+        assert_eq!(names.len(), total);
+
+        // out of range pages are clamped
+        let req = TestRequest::with_uri("/tests/?page=999").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        let listing: serde_json::Value =
+            serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(listing["page"], pages);
+    }
+
+    #[actix_rt::test]
+    async fn test_files_listing_pagination_html() {
+        let st = Files::new("/", ".")
+            .show_files_listing()
+            .files_listing_page_size(3)
+            .new_service(())
+            .await
+            .unwrap();
+
+        let pages = fs::read_dir("tests").unwrap().count().div_ceil(3);
+
+        let req = TestRequest::with_uri("/tests/?page=2&sort=bogus").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        let body = test::read_body(resp).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.contains(&format!("Page 2 of {}", pages)));
+        assert!(body.contains("<a href=\"?sort=name&amp;order=asc&amp;page=1\">Previous</a>"));
+    }
+
+    #[cfg(feature = "content-hash-etag")]
+    #[actix_rt::test]
+    async fn test_content_hash_etag() {
+        let dir_a = tempfile::tempdir().unwrap();
+        let dir_b = tempfile::tempdir().unwrap();
+        fs::write(dir_a.path().join("app.js"), "let a = 1;").unwrap();
+        fs::write(dir_b.path().join("app.js"), "let a = 1;").unwrap();
+
+        let mut etags = Vec::new();
+
+        for dir in [&dir_a, &dir_b] {
+            let st = Files::new("/", dir.path())
+                .use_content_hash_etag(true)
+                .new_service(())
+                .await
+                .unwrap();
+
+            let req = TestRequest::with_uri("/app.js").to_srv_request();
+            let resp = test::call_service(&st, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            etags.push(resp.headers().get(header::ETAG).unwrap().clone());
+
+            let req = TestRequest::with_uri("/app.js")
+                .insert_header((header::IF_NONE_MATCH, etags[0].clone()))
+                .to_srv_request();
+            let resp = test::call_service(&st, req).await;
+            assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        }
+
+        assert_eq!(etags[0], etags[1]);
+
+        let st = Files::new("/", dir_a.path()).new_service(()).await.unwrap();
+        let req = TestRequest::with_uri("/app.js").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_ne!(resp.headers().get(header::ETAG).unwrap(), etags[0]);
+    }
+
+    #[actix_rt::test]
+    async fn test_etag_manifest() {
+        let manifest = EtagManifest::from_iter([("test.js", "0123456789abcdef")]);
+
+        let st = Files::new("/", "./tests")
+            .etag_manifest(manifest)
+            .new_service(())
+            .await
+            .unwrap();
+
+        let req = TestRequest::with_uri("/test.js").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::ETAG).unwrap(),
+            "\"0123456789abcdef\""
+        );
+
+        let req = TestRequest::with_uri("/test.js")
+            .insert_header((header::IF_NONE_MATCH, "\"0123456789abcdef\""))
+            .to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+        // files missing from the manifest keep metadata-derived ETags
+        let req = TestRequest::with_uri("/utf8.txt").to_srv_request();
+        let resp = test::call_service(&st, req).await;
+        let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap();
+        assert!(etag.contains(':'));
+    }
 }
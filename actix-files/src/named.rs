@@ -6,7 +6,7 @@ use std::{
 };
 
 use actix_web::{
-    body::{self, BoxBody, SizedStream},
+    body::{self, BoxBody},
     dev::{
         self, AppService, HttpServiceFactory, ResourceDef, Service, ServiceFactory, ServiceRequest,
         ServiceResponse,
@@ -80,6 +80,7 @@ pub struct NamedFile {
     pub(crate) content_type: Mime,
     pub(crate) content_disposition: ContentDisposition,
     pub(crate) encoding: Option<ContentEncoding>,
+    pub(crate) custom_etag: Option<header::EntityTag>,
 }
 
 #[cfg(not(feature = "experimental-io-uring"))]
@@ -198,6 +199,7 @@ impl NamedFile {
             md,
             modified,
             encoding,
+            custom_etag: None,
             status_code: StatusCode::OK,
             flags: Flags::default(),
         })
@@ -362,6 +364,17 @@ impl NamedFile {
         self
     }
 
+    /// Sets the `ETag` to use for this file instead of one derived from its metadata.
+    ///
+    /// This is useful for validators that must be identical across deployments, such as hashes of
+    /// the file contents. The `ETag` is only sent if enabled with [`use_etag`](Self::use_etag),
+    /// which is the default.
+    #[inline]
+    pub fn set_etag(mut self, etag: header::EntityTag) -> Self {
+        self.custom_etag = Some(etag);
+        self
+    }
+
     /// Specifies whether to return `Last-Modified` header in response.
     ///
     /// Default is true.
@@ -380,8 +393,12 @@ impl NamedFile {
         self
     }
 
-    /// Creates an `ETag` in a format is similar to Apache's.
+    /// Returns the custom `ETag`, if set, or creates one in a format is similar to Apache's.
     pub(crate) fn etag(&self) -> Option<header::EntityTag> {
+        if let Some(ref etag) = self.custom_etag {
+            return Some(etag.clone());
+        }
+
         self.modified.as_ref().map(|mtime| {
             let ino = {
                 #[cfg(unix)]
@@ -583,12 +600,12 @@ impl NamedFile {
             res.status(StatusCode::PARTIAL_CONTENT);
         }
 
-        res.body(SizedStream::new(length, reader))
+        res.body(reader)
     }
 }
 
 /// Returns true if `req` has no `If-Match` header or one which matches `etag`.
-fn any_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
+pub(crate) fn any_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
     match req.get_header::<header::IfMatch>() {
         None | Some(header::IfMatch::Any) => true,
 
@@ -607,7 +624,7 @@ fn any_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
 }
 
 /// Returns true if `req` doesn't have an `If-None-Match` header matching `req`.
-fn none_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
+pub(crate) fn none_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
     match req.get_header::<header::IfNoneMatch>() {
         Some(header::IfNoneMatch::Any) => false,
 
@@ -1,18 +1,28 @@
-use std::{fmt, io, ops::Deref, path::PathBuf, rc::Rc};
+use std::{
+    fmt, io,
+    ops::Deref,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use actix_web::{
     body::BoxBody,
     dev::{self, Service, ServiceRequest, ServiceResponse},
     error::Error,
     guard::Guard,
-    http::{header, Method},
-    HttpResponse,
+    http::{
+        header::{self, TryIntoHeaderValue as _},
+        Method,
+    },
+    HttpMessage as _, HttpResponse,
 };
 use futures_core::future::LocalBoxFuture;
 
+#[cfg(feature = "content-hash-etag")]
+use crate::EtagCache;
 use crate::{
-    named, Directory, DirectoryRenderer, FilesError, HttpService, MimeOverride, NamedFile,
-    PathBufWrap, PathFilter,
+    cache_policy, named, write, CachePolicy, Directory, DirectoryRenderer, EtagManifest,
+    FilesError, HttpService, MimeOverride, NamedFile, PathBufWrap, PathFilter,
 };
 
 /// Assembled file serving service.
@@ -38,7 +48,16 @@ pub struct FilesServiceInner {
     pub(crate) path_filter: Option<Rc<PathFilter>>,
     pub(crate) file_flags: named::Flags,
     pub(crate) guards: Option<Rc<dyn Guard>>,
+    pub(crate) write_guard: Option<Rc<dyn Guard>>,
+    pub(crate) max_upload_size: u64,
     pub(crate) hidden_files: bool,
+    pub(crate) spa_index: Option<String>,
+    pub(crate) cache_policies: Vec<CachePolicy>,
+    #[cfg(feature = "content-hash-etag")]
+    pub(crate) content_hash_etag: bool,
+    pub(crate) etag_manifest: Option<EtagManifest>,
+    #[cfg(feature = "content-hash-etag")]
+    pub(crate) etag_cache: Rc<EtagCache>,
 }
 
 impl fmt::Debug for FilesServiceInner {
@@ -55,6 +74,19 @@ impl FilesService {
     ) -> Result<ServiceResponse, Error> {
         log::debug!("error handling {}: {}", req.path(), err);
 
+        if err.kind() == io::ErrorKind::NotFound && is_spa_navigation(&req) {
+            if let Some(ref spa_index) = self.spa_index {
+                match NamedFile::open_async(self.directory.join(spa_index)).await {
+                    Ok(named_file) => {
+                        return Ok(self
+                            .serve_named_file(req, named_file, Path::new(spa_index))
+                            .await);
+                    }
+                    Err(err) => log::debug!("error opening SPA fallback {}: {}", spa_index, err),
+                }
+            }
+        }
+
         if let Some(ref default) = self.default {
             default.call(req).await
         } else {
@@ -62,18 +94,79 @@ impl FilesService {
         }
     }
 
-    fn serve_named_file(&self, req: ServiceRequest, mut named_file: NamedFile) -> ServiceResponse {
+    /// Serves `named_file`, located at `rel_path` relative to the served directory.
+    async fn serve_named_file(
+        &self,
+        req: ServiceRequest,
+        mut named_file: NamedFile,
+        rel_path: &Path,
+    ) -> ServiceResponse {
         if let Some(ref mime_override) = self.mime_override {
             let new_disposition = mime_override(&named_file.content_type.type_());
             named_file.content_disposition.disposition = new_disposition;
         }
         named_file.flags = self.file_flags;
 
+        if named_file.flags.contains(named::Flags::ETAG) {
+            named_file = self.set_content_etag(named_file, rel_path).await;
+        }
+
         let (req, _) = req.into_parts();
-        let res = named_file.into_response(&req);
+        let mut res = named_file.into_response(&req);
+
+        if let Some(policy) = cache_policy::find_policy(&self.cache_policies, rel_path) {
+            if !res.headers().contains_key(header::CACHE_CONTROL) {
+                if let Ok(value) = policy.cache_control().clone().try_into_value() {
+                    res.headers_mut().insert(header::CACHE_CONTROL, value);
+                }
+            }
+        }
+
         ServiceResponse::new(req, res)
     }
 
+    /// Sets the file's `ETag` from the manifest or its content hash, if configured.
+    pub(crate) async fn set_content_etag(
+        &self,
+        named_file: NamedFile,
+        rel_path: &Path,
+    ) -> NamedFile {
+        if let Some(etag) = self
+            .etag_manifest
+            .as_ref()
+            .and_then(|manifest| manifest.get(rel_path))
+        {
+            return named_file.set_etag(etag.clone());
+        }
+
+        #[cfg(feature = "content-hash-etag")]
+        if self.content_hash_etag {
+            return self.set_content_hash_etag(named_file, rel_path).await;
+        }
+
+        named_file
+    }
+
+    /// Sets the file's `ETag` from its content hash, computing it if it is not cached.
+    #[cfg(feature = "content-hash-etag")]
+    async fn set_content_hash_etag(&self, named_file: NamedFile, rel_path: &Path) -> NamedFile {
+        let modified = match named_file.modified() {
+            Some(modified) => modified,
+            None => return named_file,
+        };
+
+        let len = named_file.metadata().len();
+        let path = self.directory.join(rel_path);
+
+        match self.etag_cache.get_or_hash(&path, modified, len).await {
+            Ok(etag) => named_file.set_etag(etag),
+            Err(err) => {
+                log::debug!("error hashing {}: {}", path.display(), err);
+                named_file
+            }
+        }
+    }
+
     fn show_index(&self, req: ServiceRequest, path: PathBuf) -> ServiceResponse {
         let dir = Directory::new(self.directory.clone(), path);
 
@@ -97,7 +190,16 @@ impl Service<ServiceRequest> for FilesService {
     dev::always_ready!();
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
-        let is_method_valid = if let Some(guard) = &self.guards {
+        let is_write_allowed = match self.write_guard {
+            Some(ref guard) if write::is_write_method(req.method()) => {
+                Some((**guard).check(&req.guard_ctx()))
+            }
+            _ => None,
+        };
+
+        let is_method_valid = if is_write_allowed.is_some() {
+            true
+        } else if let Some(guard) = &self.guards {
             // execute user defined guards
             (**guard).check(&req.guard_ctx())
         } else {
@@ -116,6 +218,10 @@ impl Service<ServiceRequest> for FilesService {
                 ));
             }
 
+            if is_write_allowed == Some(false) {
+                return Ok(req.into_response(HttpResponse::Forbidden().finish()));
+            }
+
             let path_on_disk =
                 match PathBufWrap::parse_path(req.match_info().unprocessed(), this.hidden_files) {
                     Ok(item) => item,
@@ -132,6 +238,10 @@ impl Service<ServiceRequest> for FilesService {
                 }
             }
 
+            if is_write_allowed.is_some() {
+                return write::handle_write(&this, req, path_on_disk.as_ref()).await;
+            }
+
             // full file path
             let path = this.directory.join(&path_on_disk);
             if let Err(err) = path.canonicalize() {
@@ -156,7 +266,13 @@ impl Service<ServiceRequest> for FilesService {
                     Some(ref index) => {
                         let named_path = path.join(index);
                         match NamedFile::open_async(named_path).await {
-                            Ok(named_file) => Ok(this.serve_named_file(req, named_file)),
+                            Ok(named_file) => Ok(this
+                                .serve_named_file(
+                                    req,
+                                    named_file,
+                                    &path_on_disk.as_ref().join(index),
+                                )
+                                .await),
                             Err(_) if this.show_index => Ok(this.show_index(req, path)),
                             Err(err) => this.handle_err(err, req).await,
                         }
@@ -169,20 +285,41 @@ impl Service<ServiceRequest> for FilesService {
                 }
             } else {
                 match NamedFile::open_async(&path).await {
-                    Ok(mut named_file) => {
-                        if let Some(ref mime_override) = this.mime_override {
-                            let new_disposition = mime_override(&named_file.content_type.type_());
-                            named_file.content_disposition.disposition = new_disposition;
-                        }
-                        named_file.flags = this.file_flags;
-
-                        let (req, _) = req.into_parts();
-                        let res = named_file.into_response(&req);
-                        Ok(ServiceResponse::new(req, res))
-                    }
+                    Ok(named_file) => Ok(this
+                        .serve_named_file(req, named_file, path_on_disk.as_ref())
+                        .await),
                     Err(err) => this.handle_err(err, req).await,
                 }
             }
         })
     }
 }
+
+/// Returns true if `req` looks like a browser navigation to a client-side route.
+///
+/// That is, a `GET` or `HEAD` request accepting `text/html` for a path whose last segment has no
+/// file extension.
+fn is_spa_navigation(req: &ServiceRequest) -> bool {
+    if !matches!(*req.method(), Method::GET | Method::HEAD) {
+        return false;
+    }
+
+    let accepts_html = req
+        .get_header::<header::Accept>()
+        .map(|accept| {
+            accept.iter().any(|item| {
+                item.item.essence_str() == mime::TEXT_HTML.essence_str()
+                    && item.quality > header::Quality::ZERO
+            })
+        })
+        .unwrap_or(false);
+
+    let last_segment = req
+        .match_info()
+        .unprocessed()
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+
+    accepts_html && Path::new(last_segment).extension().is_none()
+}
@@ -0,0 +1,326 @@
+//! Write support for `Files`: `PUT`, `DELETE` and `MKCOL` requests.
+
+use std::{
+    fs,
+    future::poll_fn,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    pin::Pin,
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use actix_web::{
+    dev::{ServiceRequest, ServiceResponse},
+    error::{self, Error},
+    http::{header, Method, StatusCode},
+    web, HttpMessage as _, HttpResponse,
+};
+use bytes::BytesMut;
+use futures_core::Stream as _;
+
+use crate::{named, service::FilesService, NamedFile};
+
+/// Default maximum size of uploaded files.
+pub(crate) const DEFAULT_MAX_UPLOAD_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Amount of the request body buffered before each write to the temporary file.
+const WRITE_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Returns the `MKCOL` method, defined in [RFC 4918 §9.3].
+///
+/// [RFC 4918 §9.3]: https://datatracker.ietf.org/doc/html/rfc4918#section-9.3
+fn mkcol() -> Method {
+    Method::from_bytes(b"MKCOL").unwrap()
+}
+
+/// Returns true if `method` modifies the served directory.
+pub(crate) fn is_write_method(method: &Method) -> bool {
+    *method == Method::PUT || *method == Method::DELETE || *method == mkcol()
+}
+
+/// Handles a write request for `rel_path`, relative to the served directory.
+///
+/// The caller is responsible for checking the write guard and the path filter.
+pub(crate) async fn handle_write(
+    files: &FilesService,
+    req: ServiceRequest,
+    rel_path: &Path,
+) -> Result<ServiceResponse, Error> {
+    // writing to, or deleting, the served directory itself is not allowed
+    if rel_path.as_os_str().is_empty() {
+        return Ok(req.into_response(HttpResponse::Forbidden().finish()));
+    }
+
+    let path = files.directory.join(rel_path);
+
+    // the parent directory must exist, and symlinks must not lead outside of the served directory
+    if !is_parent_within(&files.directory, &path) {
+        return Ok(req.into_response(HttpResponse::Conflict().finish()));
+    }
+
+    if *req.method() == Method::PUT {
+        put(files, req, rel_path, path).await
+    } else if *req.method() == Method::DELETE {
+        delete(files, req, rel_path, path).await
+    } else {
+        make_collection(req, path).await
+    }
+}
+
+/// Returns true if the parent of `path` exists and resolves to a location inside `root`.
+fn is_parent_within(root: &Path, path: &Path) -> bool {
+    path.parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .is_some_and(|parent| parent.starts_with(root))
+}
+
+async fn put(
+    files: &FilesService,
+    mut req: ServiceRequest,
+    rel_path: &Path,
+    path: PathBuf,
+) -> Result<ServiceResponse, Error> {
+    if path.is_dir() {
+        return Ok(req.into_response(HttpResponse::MethodNotAllowed().finish()));
+    }
+
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok())
+        .and_then(|len| len.parse::<u64>().ok());
+
+    if content_length.is_some_and(|len| len > files.max_upload_size) {
+        return Ok(req.into_response(HttpResponse::PayloadTooLarge().finish()));
+    }
+
+    let existed = path.is_file();
+
+    if let Some(res) = check_preconditions(files, &req, rel_path, &path, existed).await {
+        return Ok(req.into_response(res));
+    }
+
+    // write to a temporary file in the same directory so the final rename is atomic
+    let dest = path.clone();
+    let mut tmp = web::block(move || TempFile::new_for(&dest))
+        .await?
+        .map_err(error::ErrorInternalServerError)?;
+
+    let mut payload = req.take_payload();
+    let mut buf = BytesMut::new();
+    let mut size = 0;
+
+    while let Some(chunk) = poll_fn(|cx| Pin::new(&mut payload).poll_next(cx)).await {
+        let chunk = chunk?;
+
+        size += chunk.len() as u64;
+
+        // the temporary file is removed when dropped
+        if size > files.max_upload_size {
+            return Ok(req.into_response(HttpResponse::PayloadTooLarge().finish()));
+        }
+
+        buf.extend_from_slice(&chunk);
+
+        if buf.len() >= WRITE_BUFFER_SIZE {
+            let data = buf.split().freeze();
+
+            tmp = web::block(move || tmp.file.write_all(&data).map(|_| tmp))
+                .await?
+                .map_err(error::ErrorInternalServerError)?;
+        }
+    }
+
+    let dest = path.clone();
+    web::block(move || {
+        tmp.file.write_all(&buf)?;
+        tmp.persist(&dest)
+    })
+    .await?
+    .map_err(error::ErrorInternalServerError)?;
+
+    let mut res = if existed {
+        HttpResponse::NoContent()
+    } else {
+        HttpResponse::Created()
+    };
+
+    if let Some(etag) = current_etag(files, rel_path, &path).await {
+        res.insert_header(header::ETag(etag));
+    }
+
+    Ok(req.into_response(res.finish()))
+}
+
+/// Temporary file next to the destination of an upload, removed when dropped unless persisted.
+struct TempFile {
+    file: fs::File,
+    path: Option<PathBuf>,
+}
+
+impl TempFile {
+    /// Creates an empty hidden file in the directory of `dest`.
+    ///
+    /// The file gets the same permissions as one created by `File::create`.
+    fn new_for(dest: &Path) -> io::Result<Self> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let parent = dest.parent().unwrap_or_else(|| Path::new(""));
+
+        loop {
+            let name = format!(
+                ".upload-{}-{}.tmp",
+                process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = parent.join(name);
+
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => {
+                    return Ok(Self {
+                        file,
+                        path: Some(path),
+                    })
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Flushes the file to disk and renames it to `dest`, replacing any existing file.
+    ///
+    /// A replaced file's permissions are copied to the new file.
+    fn persist(mut self, dest: &Path) -> io::Result<()> {
+        match fs::metadata(dest) {
+            Ok(md) => self.file.set_permissions(md.permissions())?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        self.file.sync_all()?;
+
+        if let Some(path) = &self.path {
+            fs::rename(path, dest)?;
+        }
+
+        self.path = None;
+        Ok(())
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+async fn delete(
+    files: &FilesService,
+    req: ServiceRequest,
+    rel_path: &Path,
+    path: PathBuf,
+) -> Result<ServiceResponse, Error> {
+    let md = match path.symlink_metadata() {
+        Ok(md) => md,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(req.into_response(HttpResponse::NotFound().finish()));
+        }
+        Err(err) => return Err(error::ErrorInternalServerError(err)),
+    };
+
+    if md.is_dir() {
+        // only empty directories can be deleted
+        return match web::block(move || fs::remove_dir(path)).await? {
+            Ok(()) => Ok(req.into_response(HttpResponse::NoContent().finish())),
+            Err(_) => Ok(req.into_response(HttpResponse::Conflict().finish())),
+        };
+    }
+
+    if let Some(res) = check_preconditions(files, &req, rel_path, &path, true).await {
+        return Ok(req.into_response(res));
+    }
+
+    web::block(move || fs::remove_file(path))
+        .await?
+        .map_err(error::ErrorInternalServerError)?;
+
+    Ok(req.into_response(HttpResponse::NoContent().finish()))
+}
+
+async fn make_collection(req: ServiceRequest, path: PathBuf) -> Result<ServiceResponse, Error> {
+    if path.symlink_metadata().is_ok() {
+        return Ok(req.into_response(HttpResponse::MethodNotAllowed().finish()));
+    }
+
+    // request bodies for MKCOL are not supported
+    if req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .is_some_and(|len| len != "0")
+    {
+        return Ok(req.into_response(HttpResponse::UnsupportedMediaType().finish()));
+    }
+
+    web::block(move || fs::create_dir(path))
+        .await?
+        .map_err(error::ErrorInternalServerError)?;
+
+    Ok(req.into_response(HttpResponse::Created().finish()))
+}
+
+/// Returns the `ETag` that would be served for the file at `path`.
+async fn current_etag(
+    files: &FilesService,
+    rel_path: &Path,
+    path: &Path,
+) -> Option<header::EntityTag> {
+    if !files.file_flags.contains(named::Flags::ETAG) {
+        return None;
+    }
+
+    let named_file = NamedFile::open_async(path).await.ok()?;
+    let named_file = files.set_content_etag(named_file, rel_path).await;
+    named_file.etag()
+}
+
+/// Evaluates `If-Match` and `If-None-Match` preconditions against the current file.
+///
+/// Returns a `412 Precondition Failed` response if they do not hold.
+async fn check_preconditions(
+    files: &FilesService,
+    req: &ServiceRequest,
+    rel_path: &Path,
+    path: &Path,
+    exists: bool,
+) -> Option<HttpResponse> {
+    let has_if_match = req.headers().contains_key(header::IF_MATCH);
+    let has_if_none_match = req.headers().contains_key(header::IF_NONE_MATCH);
+
+    if !has_if_match && !has_if_none_match {
+        return None;
+    }
+
+    let etag = if exists {
+        current_etag(files, rel_path, path).await
+    } else {
+        None
+    };
+
+    let failed = if !exists {
+        // `If-Match` requires a current representation; `If-None-Match` is always satisfied
+        has_if_match
+    } else {
+        !named::any_match(etag.as_ref(), req.request())
+            || (has_if_none_match && !named::none_match(etag.as_ref(), req.request()))
+    };
+
+    failed.then(|| HttpResponse::new(StatusCode::PRECONDITION_FAILED))
+}
@@ -0,0 +1,272 @@
+use std::fs;
+
+use actix_files::Files;
+use actix_web::{
+    guard,
+    http::{header, Method, StatusCode},
+    test::{self, TestRequest},
+    App,
+};
+use bytes::Bytes;
+
+fn mkcol() -> Method {
+    Method::from_bytes(b"MKCOL").unwrap()
+}
+
+#[actix_web::test]
+async fn test_write_methods() {
+    let root = tempfile::tempdir().unwrap();
+
+    let srv = test::init_service(App::new().service(
+        Files::new("/", root.path()).write_guard(guard::Header("authorization", "Bearer token")),
+    ))
+    .await;
+
+    let put = |uri: &str, body: &'static str| {
+        TestRequest::put()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .set_payload(body)
+    };
+
+    let res = test::call_service(&srv, put("/artifact.txt", "v1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let etag = res.headers().get(header::ETAG).unwrap().clone();
+    assert_eq!(fs::read(root.path().join("artifact.txt")).unwrap(), b"v1");
+
+    let req = TestRequest::get().uri("/artifact.txt").to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.headers().get(header::ETAG).unwrap(), etag);
+    assert_eq!(test::read_body(res).await, Bytes::from_static(b"v1"));
+
+    // creating only if missing
+    let req = put("/artifact.txt", "v2")
+        .insert_header((header::IF_NONE_MATCH, "*"))
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+    // replacing only a known version
+    let req = put("/artifact.txt", "v2")
+        .insert_header((header::IF_MATCH, "\"stale\""))
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(fs::read(root.path().join("artifact.txt")).unwrap(), b"v1");
+
+    let req = put("/artifact.txt", "v2")
+        .insert_header((header::IF_MATCH, etag))
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(fs::read(root.path().join("artifact.txt")).unwrap(), b"v2");
+
+    // missing parent directory
+    let res = test::call_service(&srv, put("/builds/1.txt", "v1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let req = TestRequest::default()
+        .method(mkcol())
+        .uri("/builds")
+        .insert_header((header::AUTHORIZATION, "Bearer token"))
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(root.path().join("builds").is_dir());
+
+    let res = test::call_service(&srv, put("/builds/1.txt", "v1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let delete = |uri: &str| {
+        TestRequest::delete()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .to_request()
+    };
+
+    // non-empty directories are not deleted
+    let res = test::call_service(&srv, delete("/builds")).await;
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = test::call_service(&srv, delete("/builds/1.txt")).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    let res = test::call_service(&srv, delete("/builds")).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert!(!root.path().join("builds").exists());
+
+    let res = test::call_service(&srv, delete("/builds")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn test_write_rejected() {
+    let root = tempfile::tempdir().unwrap();
+    let outside = tempfile::tempdir().unwrap();
+
+    let srv = test::init_service(App::new().service(Files::new("/ro", root.path())).service(
+        Files::new("/", root.path()).write_guard(guard::Header("authorization", "Bearer token")),
+    ))
+    .await;
+
+    // unauthorized
+    let req = TestRequest::put()
+        .uri("/file.txt")
+        .set_payload("data")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert!(!root.path().join("file.txt").exists());
+
+    // writes are disabled by default
+    let req = TestRequest::put()
+        .uri("/ro/file.txt")
+        .insert_header((header::AUTHORIZATION, "Bearer token"))
+        .set_payload("data")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+    // path traversal is confined to the served directory
+    let req = TestRequest::put()
+        .uri("/../file.txt")
+        .insert_header((header::AUTHORIZATION, "Bearer token"))
+        .set_payload("data")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(root.path().join("file.txt").exists());
+    assert!(!root.path().parent().unwrap().join("file.txt").exists());
+
+    let req = TestRequest::delete()
+        .uri("/")
+        .insert_header((header::AUTHORIZATION, "Bearer token"))
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let req = TestRequest::put()
+        .uri("/.hidden")
+        .insert_header((header::AUTHORIZATION, "Bearer token"))
+        .set_payload("data")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(outside.path(), root.path().join("escape")).unwrap();
+
+        let req = TestRequest::put()
+            .uri("/escape/file.txt")
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .set_payload("data")
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert!(!outside.path().join("file.txt").exists());
+    }
+}
+
+#[actix_web::test]
+async fn test_write_size_limit() {
+    let root = tempfile::tempdir().unwrap();
+
+    let srv = test::init_service(
+        App::new().service(
+            Files::new("/", root.path())
+                .write_guard(guard::Header("authorization", "Bearer token"))
+                .max_upload_size(1024 * 1024),
+        ),
+    )
+    .await;
+
+    let put = |uri: &str, body: Bytes| {
+        TestRequest::put()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, "Bearer token"))
+            .set_payload(body)
+            .to_request()
+    };
+
+    // bodies up to the limit are stored intact
+    let data = (0..1024 * 1024)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    let res = test::call_service(&srv, put("/large.bin", Bytes::from(data.clone()))).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(fs::read(root.path().join("large.bin")).unwrap(), data);
+
+    let too_large = Bytes::from(vec![0; 1024 * 1024 + 1]);
+
+    // rejected based on the declared length
+    let res = test::call_service(&srv, put("/large.bin", too_large.clone())).await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // rejected while streaming a body of unknown length
+    let mut req = put("/large.bin", too_large.clone());
+    req.headers_mut().remove(header::CONTENT_LENGTH);
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let mut req = put("/new.bin", too_large);
+    req.headers_mut().remove(header::CONTENT_LENGTH);
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // the existing file is untouched and no temporary files are left behind
+    assert_eq!(fs::read(root.path().join("large.bin")).unwrap(), data);
+    assert_eq!(fs::read_dir(root.path()).unwrap().count(), 1);
+}
+
+#[cfg(unix)]
+#[actix_web::test]
+async fn test_write_permissions() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let root = tempfile::tempdir().unwrap();
+    let srv = test::init_service(
+        App::new().service(Files::new("/", root.path()).write_guard(guard::Put())),
+    )
+    .await;
+
+    let mode = |name: &str| {
+        fs::metadata(root.path().join(name))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o7777
+    };
+
+    // new files get the same mode as ones created by `File::create`
+    fs::File::create(root.path().join("reference.txt")).unwrap();
+
+    let req = TestRequest::put()
+        .uri("/new.txt")
+        .set_payload("v1")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(mode("new.txt"), mode("reference.txt"));
+
+    // replaced files keep their mode
+    let existing = root.path().join("existing.txt");
+    fs::write(&existing, "v1").unwrap();
+    fs::set_permissions(&existing, fs::Permissions::from_mode(0o640)).unwrap();
+
+    let req = TestRequest::put()
+        .uri("/existing.txt")
+        .set_payload("v2")
+        .to_request();
+    let res = test::call_service(&srv, req).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(fs::read(&existing).unwrap(), b"v2");
+    assert_eq!(mode("existing.txt"), 0o640);
+
+    // no temporary files are left behind
+    let mut names = fs::read_dir(root.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["existing.txt", "new.txt", "reference.txt"]);
+}
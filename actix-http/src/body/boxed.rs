@@ -7,7 +7,7 @@ use std::{
 
 use bytes::Bytes;
 
-use super::{BodySize, MessageBody, MessageBodyMapErr};
+use super::{BodySize, FileRegion, MessageBody, MessageBodyMapErr};
 use crate::body;
 
 /// A boxed message body with boxed errors.
@@ -83,6 +83,14 @@ impl MessageBody for BoxBody {
         }
     }
 
+    #[inline]
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        match &self.0 {
+            BoxBodyInner::Stream(stream) => stream.as_ref().get_ref().file_region(),
+            _ => None,
+        }
+    }
+
     #[inline]
     fn try_into_bytes(self) -> Result<Bytes, Self> {
         match self.0 {
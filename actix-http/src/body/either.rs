@@ -6,7 +6,7 @@ use std::{
 use bytes::Bytes;
 use pin_project_lite::pin_project;
 
-use super::{BodySize, BoxBody, MessageBody};
+use super::{BodySize, BoxBody, FileRegion, MessageBody};
 use crate::Error;
 
 pin_project! {
@@ -87,6 +87,14 @@ where
         }
     }
 
+    #[inline]
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        match self {
+            EitherBody::Left { body } => body.file_region(),
+            EitherBody::Right { body } => body.file_region(),
+        }
+    }
+
     #[inline]
     fn try_into_bytes(self) -> Result<Bytes, Self> {
         match self {
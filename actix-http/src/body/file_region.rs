@@ -0,0 +1,41 @@
+use std::fs::File;
+
+/// A region of an open file that a body streams, unchanged, as its contents.
+///
+/// Returned from [`MessageBody::file_region`] to allow HTTP/1 connections over plain TCP to send
+/// the body using `sendfile(2)` instead of reading it through user space.
+///
+/// [`MessageBody::file_region`]: super::MessageBody::file_region
+#[derive(Debug, Clone, Copy)]
+pub struct FileRegion<'a> {
+    file: &'a File,
+    offset: u64,
+    len: u64,
+}
+
+impl<'a> FileRegion<'a> {
+    /// Constructs a region of `len` bytes of `file`, starting at `offset`.
+    pub fn new(file: &'a File, offset: u64, len: u64) -> Self {
+        Self { file, offset, len }
+    }
+
+    /// Returns the file this region is part of.
+    pub fn file(&self) -> &'a File {
+        self.file
+    }
+
+    /// Returns the position in the file the region starts at.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the length of the region in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -12,7 +12,7 @@ use bytes::{Bytes, BytesMut};
 use futures_core::ready;
 use pin_project_lite::pin_project;
 
-use super::{BodySize, BoxBody};
+use super::{BodySize, BoxBody, FileRegion};
 
 /// An interface for types that can be used as a response body.
 ///
@@ -105,6 +105,20 @@ pub trait MessageBody {
         Err(self)
     }
 
+    /// Returns the region of an open file that this body streams, if any.
+    ///
+    /// HTTP/1 connections over plain TCP may use this to send the body with `sendfile(2)` instead
+    /// of calling `poll_next`. A returned region must contain exactly the bytes the body would
+    /// otherwise produce, its length must equal the [`size`](Self::size) of the body, and it must
+    /// not change while the body is being sent.
+    ///
+    /// The default implementation returns `None`. Wrappers that transform the body's bytes must
+    /// not forward this method.
+    #[inline]
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        None
+    }
+
     /// Wraps this body into a `BoxBody`.
     ///
     /// No-op when called on a `BoxBody`, meaning there is no risk of double boxing when calling
@@ -134,6 +148,10 @@ mod foreign_impls {
             (**self).size()
         }
 
+        fn file_region(&self) -> Option<FileRegion<'_>> {
+            (**self).file_region()
+        }
+
         fn poll_next(
             mut self: Pin<&mut Self>,
             cx: &mut Context<'_>,
@@ -197,6 +215,11 @@ mod foreign_impls {
         ) -> Poll<Option<Result<Bytes, Self::Error>>> {
             Pin::new(self.get_mut().as_mut()).poll_next(cx)
         }
+
+        #[inline]
+        fn file_region(&self) -> Option<FileRegion<'_>> {
+            self.as_ref().file_region()
+        }
     }
 
     impl<T, B> MessageBody for Pin<T>
@@ -520,6 +543,11 @@ where
         }
     }
 
+    #[inline]
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        self.body.file_region()
+    }
+
     #[inline]
     fn try_into_bytes(self) -> Result<Bytes, Self> {
         let Self { body, mapper } = self;
@@ -152,6 +152,14 @@ where
             _ => Err(self),
         }
     }
+
+    #[inline]
+    fn file_region(&self) -> Option<body::FileRegion<'_>> {
+        match self {
+            EncoderBody::Stream { body } => body.file_region(),
+            _ => None,
+        }
+    }
 }
 
 impl<B> MessageBody for Encoder<B>
@@ -243,6 +251,16 @@ where
         }
     }
 
+    #[inline]
+    fn file_region(&self) -> Option<body::FileRegion<'_>> {
+        // bytes are only passed through unchanged when no encoding is applied
+        if self.encoder.is_none() && self.fut.is_none() {
+            self.body.file_region()
+        } else {
+            None
+        }
+    }
+
     #[inline]
     fn try_into_bytes(mut self) -> Result<Bytes, Self>
     where
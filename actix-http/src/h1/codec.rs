@@ -103,6 +103,15 @@ impl Codec {
     pub fn config(&self) -> &ServiceConfig {
         &self.config
     }
+
+    /// Takes over writing the body of the last encoded response, if it has a `Content-Length`.
+    ///
+    /// Returns the length of the body, which the caller must write to the I/O stream itself before
+    /// encoding the end of the body. Returns `None`, leaving the encoder untouched, if the body is
+    /// empty, is not length-delimited or must be omitted (e.g., for `HEAD` requests).
+    pub(crate) fn take_body_length(&mut self) -> Option<u64> {
+        self.encoder.take_length()
+    }
 }
 
 impl Decoder for Codec {
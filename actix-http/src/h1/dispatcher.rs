@@ -23,11 +23,12 @@ use super::{
     codec::Codec,
     decoder::MAX_BUFFER_SIZE,
     payload::{Payload, PayloadSender, PayloadStatus},
+    sendfile::SendFileFn,
     timer::TimerState,
     Message, MessageType,
 };
 use crate::{
-    body::{BodySize, BoxBody, MessageBody},
+    body::{BodySize, BoxBody, FileRegion, MessageBody},
     config::ServiceConfig,
     error::{DispatchError, ParseError, PayloadError},
     service::HttpFlow,
@@ -167,6 +168,9 @@ pin_project! {
         read_buf: BytesMut,
         write_buf: BytesMut,
         codec: Codec,
+
+        // when Some(_) file-backed bodies are sent with sendfile(2)
+        sendfile: Option<SendFileFn<T>>,
     }
 }
 
@@ -188,6 +192,7 @@ pin_project! {
         ExpectCall { #[pin] fut: X::Future },
         ServiceCall { #[pin] fut: S::Future },
         SendPayload { #[pin] body: B },
+        SendFile { #[pin] body: B, sent: u64 },
         SendErrorPayload { #[pin] body: BoxBody },
     }
 }
@@ -219,6 +224,10 @@ where
             Self::SendPayload { .. } => {
                 f.debug_struct("State::SendPayload").finish_non_exhaustive()
             }
+            Self::SendFile { sent, .. } => f
+                .debug_struct("State::SendFile")
+                .field("sent", sent)
+                .finish_non_exhaustive(),
             Self::SendErrorPayload { .. } => f
                 .debug_struct("State::SendErrorPayload")
                 .finish_non_exhaustive(),
@@ -279,6 +288,8 @@ where
                     read_buf: BytesMut::with_capacity(HW_BUFFER_SIZE),
                     write_buf: BytesMut::with_capacity(HW_BUFFER_SIZE),
                     codec: Codec::new(config),
+
+                    sendfile: None,
                 },
             },
 
@@ -286,6 +297,15 @@ where
             poll_count: 0,
         }
     }
+
+    /// Sets the function used to send file-backed response bodies without copying them.
+    pub(crate) fn with_sendfile(mut self, sendfile: Option<SendFileFn<T>>) -> Self {
+        if let DispatcherState::Normal { ref mut inner } = self.inner {
+            inner.sendfile = sendfile;
+        }
+
+        self
+    }
 }
 
 impl<T, S, B, X, U> InnerDispatcher<T, S, B, X, U>
@@ -389,6 +409,16 @@ where
                 this.flags.insert(Flags::FINISHED);
                 State::None
             }
+
+            // file-backed body can be sent directly from the file to the I/O stream
+            BodySize::Sized(len)
+                if this.sendfile.is_some()
+                    && body.file_region().is_some_and(|region| region.len() == len)
+                    && this.codec.take_body_length() == Some(len) =>
+            {
+                State::SendFile { body, sent: 0 }
+            }
+
             _ => State::SendPayload { body },
         });
 
@@ -527,6 +557,62 @@ where
                     return Ok(PollResponse::DrainWriteBuf);
                 }
 
+                StateProj::SendFile { body, sent } => {
+                    // response head must be written out before the file contents
+                    if !this.write_buf.is_empty() {
+                        return Ok(PollResponse::DrainWriteBuf);
+                    }
+
+                    let sendfile = this.sendfile.expect("sendfile state requires sendfile fn");
+                    let io = this.io.as_ref().unwrap();
+
+                    let region = match body.as_ref().get_ref().file_region() {
+                        Some(region) => region,
+                        None => {
+                            this.flags.insert(Flags::FINISHED);
+                            return Err(DispatchError::Io(io::Error::other(
+                                "body file region is no longer available",
+                            )));
+                        }
+                    };
+
+                    while *sent < region.len() {
+                        let remaining = FileRegion::new(
+                            region.file(),
+                            region.offset() + *sent,
+                            region.len() - *sent,
+                        );
+
+                        match sendfile(io, cx, remaining) {
+                            Poll::Ready(Ok(0)) => {
+                                tracing::error!("Response file is shorter than its size");
+                                this.flags.insert(Flags::FINISHED);
+                                return Err(DispatchError::Io(io::Error::new(
+                                    io::ErrorKind::UnexpectedEof,
+                                    "file ended before the response body was sent",
+                                )));
+                            }
+
+                            Poll::Ready(Ok(n)) => *sent += n as u64,
+
+                            Poll::Ready(Err(err)) => {
+                                this.flags.insert(Flags::FINISHED);
+                                return Err(DispatchError::Io(err));
+                            }
+
+                            Poll::Pending => return Ok(PollResponse::DoNothing),
+                        }
+                    }
+
+                    this.codec.encode(Message::Chunk(None), this.write_buf)?;
+
+                    // file sent. set state to None and handle next message
+                    this.state.set(State::None);
+                    this.flags.insert(Flags::FINISHED);
+
+                    continue 'res;
+                }
+
                 StateProj::SendErrorPayload { mut body } => {
                     // TODO: de-dupe impl with SendPayload
 
@@ -2,6 +2,7 @@ use std::{
     cmp,
     io::{self, Write as _},
     marker::PhantomData,
+    mem,
     ptr::copy_nonoverlapping,
     slice::from_raw_parts_mut,
 };
@@ -328,6 +329,13 @@ impl<T: MessageType> MessageEncoder<T> {
         self.te.encode_eof(buf)
     }
 
+    /// Takes over writing a body of known length, returning its remaining length.
+    ///
+    /// Returns `None` if the body is not encoded with a `Content-Length`, or if it is empty.
+    pub(crate) fn take_length(&mut self) -> Option<u64> {
+        self.te.take_length()
+    }
+
     /// Encode message.
     pub fn encode(
         &mut self,
@@ -414,6 +422,16 @@ impl TransferEncoding {
         }
     }
 
+    /// Marks a `Content-Length` body as written, returning the number of bytes remaining.
+    pub(crate) fn take_length(&mut self) -> Option<u64> {
+        match self.kind {
+            TransferEncodingKind::Length(ref mut remaining) if *remaining > 0 => {
+                Some(mem::take(remaining))
+            }
+            _ => None,
+        }
+    }
+
     /// Encode message. Return `EOF` state of encoder
     #[inline]
     pub fn encode(&mut self, msg: &[u8], buf: &mut BytesMut) -> io::Result<bool> {
@@ -12,11 +12,13 @@ mod dispatcher_tests;
 mod encoder;
 mod expect;
 mod payload;
+mod sendfile;
 mod service;
 mod timer;
 mod upgrade;
 mod utils;
 
+pub(crate) use self::sendfile::{sendfile_fn, SendFileFn};
 pub use self::{
     client::{ClientCodec, ClientPayloadCodec},
     codec::Codec,
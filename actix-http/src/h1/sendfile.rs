@@ -0,0 +1,92 @@
+//! Zero-copy transmission of file-backed response bodies.
+
+use std::{
+    io,
+    task::{Context, Poll},
+};
+
+use crate::body::FileRegion;
+
+/// Sends up to `region.len()` bytes of a file region to the I/O stream.
+///
+/// Resolves to the number of bytes sent, which is zero only if the file is shorter than expected.
+pub(crate) type SendFileFn<T> = fn(&T, &mut Context<'_>, FileRegion<'_>) -> Poll<io::Result<usize>>;
+
+/// Returns a `sendfile(2)` implementation if the I/O stream type supports one.
+///
+/// Only plain TCP streams on Linux are supported; other streams, such as TLS, send file bodies by
+/// polling them like any other body.
+pub(crate) fn sendfile_fn<T: 'static>() -> Option<SendFileFn<T>> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::any::TypeId;
+
+        if TypeId::of::<T>() == TypeId::of::<actix_rt::net::TcpStream>() {
+            return Some(linux::poll_sendfile::<T>);
+        }
+    }
+
+    None
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        any::Any,
+        io,
+        os::fd::AsRawFd as _,
+        task::{Context, Poll},
+    };
+
+    use actix_rt::net::TcpStream;
+    use futures_core::ready;
+    use tokio::io::Interest;
+
+    use crate::body::FileRegion;
+
+    /// Upper bound on the bytes sent by a single call, to avoid monopolizing the worker thread.
+    const MAX_SENDFILE_CHUNK: u64 = 1024 * 1024;
+
+    pub(super) fn poll_sendfile<T: 'static>(
+        io: &T,
+        cx: &mut Context<'_>,
+        region: FileRegion<'_>,
+    ) -> Poll<io::Result<usize>> {
+        let stream = (io as &dyn Any)
+            .downcast_ref::<TcpStream>()
+            .expect("sendfile is only used with TCP streams");
+
+        let mut offset = libc::off_t::try_from(region.offset())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file offset overflow"))?;
+        let count = region.len().min(MAX_SENDFILE_CHUNK) as usize;
+
+        loop {
+            ready!(stream.poll_write_ready(cx))?;
+
+            let res = stream.try_io(Interest::WRITABLE, || {
+                // SAFETY: both file descriptors are valid for the duration of the call, being
+                // borrowed from the stream and the region, and `offset` outlives the call
+                let sent = unsafe {
+                    libc::sendfile(
+                        stream.as_raw_fd(),
+                        region.file().as_raw_fd(),
+                        &mut offset,
+                        count,
+                    )
+                };
+
+                if sent < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(sent as usize)
+                }
+            });
+
+            match res {
+                Ok(sent) => return Poll::Ready(Ok(sent)),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+        }
+    }
+}
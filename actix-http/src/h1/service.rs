@@ -504,6 +504,7 @@ where
                 expect,
                 upgrade,
                 on_connect_ext,
+                super::sendfile_fn::<T>(),
             ))
         })
     }
@@ -542,5 +543,6 @@ where
     fn call(&self, (io, addr): (T, Option<net::SocketAddr>)) -> Self::Future {
         let conn_data = OnConnectData::from_io(&io, self.on_connect_ext.as_deref());
         Dispatcher::new(io, Rc::clone(&self.flow), self.cfg.clone(), addr, conn_data)
+            .with_sendfile(self.sendfile)
     }
 }
@@ -799,6 +799,7 @@ where
                 expect,
                 upgrade,
                 on_connect_ext,
+                h1::sendfile_fn::<T>(),
             ))
         })
     }
@@ -814,6 +815,7 @@ where
     pub(super) flow: Rc<HttpFlow<S, X, U>>,
     pub(super) cfg: ServiceConfig,
     pub(super) on_connect_ext: Option<Rc<ConnectCallback<T>>>,
+    pub(super) sendfile: Option<h1::SendFileFn<T>>,
     _phantom: PhantomData<B>,
 }
 
@@ -832,10 +834,12 @@ where
         expect: X,
         upgrade: Option<U>,
         on_connect_ext: Option<Rc<ConnectCallback<T>>>,
+        sendfile: Option<h1::SendFileFn<T>>,
     ) -> HttpServiceHandler<T, S, B, X, U> {
         HttpServiceHandler {
             cfg,
             on_connect_ext,
+            sendfile,
             flow: HttpFlow::new(service, expect, upgrade),
             _phantom: PhantomData,
         }
@@ -930,7 +934,8 @@ where
                         self.cfg.clone(),
                         peer_addr,
                         conn_data,
-                    ),
+                    )
+                    .with_sendfile(self.sendfile),
                 },
             },
 
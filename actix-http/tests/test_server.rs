@@ -1,12 +1,19 @@
 use std::{
     convert::Infallible,
-    io::{Read, Write},
-    net, thread,
+    io::{Read, Seek as _, SeekFrom, Write},
+    net,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    thread,
     time::{Duration, Instant},
 };
 
 use actix_http::{
-    body::{self, BodyStream, BoxBody, SizedStream},
+    body::{self, BodySize, BodyStream, BoxBody, FileRegion, MessageBody, SizedStream},
     header, Error, HttpService, KeepAlive, Request, Response, StatusCode, Version,
 };
 use actix_http_test::test_server;
@@ -625,6 +632,105 @@ async fn h1_body_length() {
     srv.stop().await;
 }
 
+/// Body that streams part of a file, exposing it as a file region.
+struct FileBody {
+    file: std::fs::File,
+    offset: u64,
+    len: u64,
+    polled: Arc<AtomicBool>,
+}
+
+impl MessageBody for FileBody {
+    type Error = std::io::Error;
+
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.len)
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        self.polled.store(true, Ordering::SeqCst);
+
+        if self.len == 0 {
+            return Poll::Ready(None);
+        }
+
+        let mut buf = vec![0; self.len as usize];
+        let offset = self.offset;
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.read_exact(&mut buf)?;
+        self.len = 0;
+
+        Poll::Ready(Some(Ok(Bytes::from(buf))))
+    }
+
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        Some(FileRegion::new(&self.file, self.offset, self.len))
+    }
+}
+
+#[actix_rt::test]
+async fn h1_body_file_region() {
+    let path = std::env::temp_dir().join(format!("actix-http-file-region-{}", std::process::id()));
+    let data = (0..3 * 1024 * 1024 + 7)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    std::fs::write(&path, &data).unwrap();
+
+    let polled = Arc::new(AtomicBool::new(false));
+
+    let mut srv = test_server({
+        let path = path.clone();
+        let polled = Arc::clone(&polled);
+
+        move || {
+            let path = path.clone();
+            let polled = Arc::clone(&polled);
+
+            HttpService::build()
+                .h1(move |_| {
+                    let body = FileBody {
+                        file: std::fs::File::open(&path).unwrap(),
+                        offset: 13,
+                        len: 2 * 1024 * 1024 + 100,
+                        polled: Arc::clone(&polled),
+                    };
+
+                    ok::<_, Infallible>(Response::ok().set_body(body))
+                })
+                .tcp()
+        }
+    })
+    .await;
+
+    let response = srv.get("/").send().await.unwrap();
+    assert!(response.status().is_success());
+    let bytes = srv.load_body(response).await.unwrap();
+    assert_eq!(bytes, &data[13..13 + 2 * 1024 * 1024 + 100]);
+
+    // plain TCP connections on Linux send the file region without polling the body
+    assert_eq!(polled.load(Ordering::SeqCst), !cfg!(target_os = "linux"));
+
+    // body is omitted for HEAD requests
+    let response = srv.head("/").send().await.unwrap();
+    assert_eq!(
+        response.headers().get(header::CONTENT_LENGTH).unwrap(),
+        &(2 * 1024 * 1024 + 100).to_string()
+    );
+    let bytes = srv.load_body(response).await.unwrap();
+    assert!(bytes.is_empty());
+
+    // connection is still usable after a file region is sent
+    let response = srv.get("/").send().await.unwrap();
+    let bytes = srv.load_body(response).await.unwrap();
+    assert_eq!(bytes.len(), 2 * 1024 * 1024 + 100);
+
+    srv.stop().await;
+    std::fs::remove_file(path).unwrap();
+}
+
 #[actix_rt::test]
 async fn h1_body_chunked_explicit() {
     let mut srv = test_server(|| {
@@ -9,6 +9,9 @@ macro_rules! register {
     (brackets) => {{
         register!(finish => "{p1}", "{p2}", "{p3}", "{p4}")
     }};
+    (custom) => {{
+        register!(finish => "{p1:[^/]+}", "{p2:[^/]+}", "{p3:[^/]+}", "{p4:[^/]+}")
+    }};
     (regex) => {{
         register!(finish => "(.*)", "(.*)", "(.*)", "(.*)")
     }};
@@ -179,6 +182,21 @@ fn compare_routers(c: &mut Criterion) {
         });
     });
 
+    // same routes, but with custom regexes that force dynamic segments to use the regex engine
+    let mut actix_custom = actix_router::Router::<bool>::build();
+    for route in register!(custom) {
+        actix_custom.path(route, true);
+    }
+    let actix_custom = actix_custom.finish();
+    group.bench_function("actix (custom regex)", |b| {
+        b.iter(|| {
+            for route in call() {
+                let mut path = actix_router::Path::new(route);
+                black_box(actix_custom.recognize(&mut path).unwrap());
+            }
+        });
+    });
+
     let regex_set = regex::RegexSet::new(register!(regex)).unwrap();
     group.bench_function("regex", |b| {
         b.iter(|| {
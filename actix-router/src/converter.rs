@@ -0,0 +1,234 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::regex_set::Regex;
+
+/// Named constraint for dynamic segments, used with the `{name:converter}` pattern syntax.
+///
+/// A converter is made of a regular expression that segment values must match and an optional
+/// check function for constraints that are impractical to express as a regular expression, like
+/// numeric ranges. Segments with a converter only match paths where the value passes both, so
+/// that non-conforming paths fall through to later routes instead of failing extraction.
+///
+/// Converters are looked up by name when a [`ResourceDef`](crate::ResourceDef) is constructed, so
+/// they must be registered beforehand. If the text after the colon is not the name of a
+/// registered converter, it is used as a custom regex.
+///
+/// The following converters are built in:
+///
+/// | Name | Matches |
+/// |------|---------|
+/// | `u8`, `u16`, `u32`, `u64`, `u128`, `usize` | Decimal digits, within the range of the type. |
+/// | `i8`, `i16`, `i32`, `i64`, `i128`, `isize` | Optional `-` and decimal digits, within the range of the type. |
+/// | `uuid` | Hyphenated UUIDs, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`. |
+/// | `slug` | Lowercase ASCII letters and digits, separated by single hyphens. |
+///
+/// # Examples
+/// ```
+/// use actix_router::{Converter, ResourceDef};
+///
+/// let resource = ResourceDef::new("/user/{id:u8}");
+/// assert!(resource.is_match("/user/255"));
+/// assert!(!resource.is_match("/user/256"));
+/// assert!(!resource.is_match("/user/alice"));
+///
+/// Converter::new("[0-9a-f]+")
+///     .check(|val| val.len() % 2 == 0)
+///     .register("hex_bytes");
+///
+/// let resource = ResourceDef::new("/blob/{digest:hex_bytes}");
+/// assert!(resource.is_match("/blob/c0ffee"));
+/// assert!(!resource.is_match("/blob/c0ffe"));
+/// ```
+#[derive(Clone)]
+pub struct Converter {
+    pattern: Cow<'static, str>,
+    check: Option<fn(&str) -> bool>,
+}
+
+impl Converter {
+    /// Constructs a converter matching values with the given regular expression.
+    ///
+    /// The expression is matched against whole segment values. It should not match `/`
+    /// characters or contain capture groups.
+    pub fn new(pattern: impl Into<Cow<'static, str>>) -> Self {
+        Converter {
+            pattern: pattern.into(),
+            check: None,
+        }
+    }
+
+    /// Sets a function that values matching the regular expression must also pass.
+    pub fn check(mut self, check: fn(&str) -> bool) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    /// Registers the converter under `name`.
+    ///
+    /// Converters are shared by all resource definitions in the process, so they are best
+    /// registered once at startup, before any routes are defined. Only resource definitions
+    /// constructed after registration use the converter.
+    ///
+    /// # Panics
+    /// Panics if the regular expression is invalid or if a converter, including a built-in one, is
+    /// already registered under `name`.
+    pub fn register(self, name: impl Into<String>) {
+        assert!(
+            Regex::new(&self.pattern).is_ok(),
+            "invalid converter regex: {}",
+            self.pattern
+        );
+
+        let name = name.into();
+        let mut registry = registry().write().unwrap_or_else(|err| err.into_inner());
+
+        assert!(
+            !registry.contains_key(&name),
+            "converter `{name}` is already registered"
+        );
+
+        registry.insert(name, self);
+    }
+
+    /// Returns the regular expression that values must match.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns the registered converter with the given name.
+    pub(crate) fn get(name: &str) -> Option<Converter> {
+        registry()
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Compiles the converter into a constraint that checks whole values.
+    pub(crate) fn constraint(&self) -> Constraint {
+        let re = format!("{}^(?:{})$", crate::resource::REGEX_FLAGS, self.pattern);
+
+        Constraint {
+            re: Regex::new(&re).expect("converter regex is checked on registration"),
+            check: self.check,
+        }
+    }
+}
+
+impl fmt::Debug for Converter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Converter")
+            .field("pattern", &self.pattern)
+            .field("check", &self.check.is_some())
+            .finish()
+    }
+}
+
+/// Compiled converter, checking whole segment values.
+#[derive(Debug, Clone)]
+pub(crate) struct Constraint {
+    re: Regex,
+    check: Option<fn(&str) -> bool>,
+}
+
+impl Constraint {
+    /// Returns `true` if `value` matches the converter's regex and passes its check function.
+    pub(crate) fn is_match(&self, value: &str) -> bool {
+        self.re.is_match(value) && self.passes_check(value)
+    }
+
+    /// Returns `true` if `value` passes the converter's check function, assuming that it matches
+    /// the converter's regex.
+    pub(crate) fn passes_check(&self, value: &str) -> bool {
+        self.check.iter().all(|check| check(value))
+    }
+
+    /// Returns `true` if the converter has a check function.
+    pub(crate) fn has_check(&self) -> bool {
+        self.check.is_some()
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Converter>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Converter>>> = OnceLock::new();
+
+    REGISTRY.get_or_init(|| RwLock::new(builtin_converters()))
+}
+
+fn builtin_converters() -> HashMap<String, Converter> {
+    macro_rules! int_converters {
+        ($pattern:literal => $($ty:ident),+) => {
+            [$((
+                stringify!($ty),
+                Converter::new($pattern).check(|val| val.parse::<$ty>().is_ok()),
+            )),+]
+        };
+    }
+
+    const HEX: &str = "[0-9a-fA-F]";
+
+    let uuid = format!("{HEX}{{8}}-{HEX}{{4}}-{HEX}{{4}}-{HEX}{{4}}-{HEX}{{12}}");
+
+    int_converters!("[0-9]+" => u8, u16, u32, u64, u128, usize)
+        .into_iter()
+        .chain(int_converters!("-?[0-9]+" => i8, i16, i32, i64, i128, isize))
+        .chain([
+            ("uuid", Converter::new(uuid)),
+            ("slug", Converter::new("[a-z0-9]+(?:-[a-z0-9]+)*")),
+        ])
+        .map(|(name, converter)| (name.to_owned(), converter))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_constraints() {
+        let matches =
+            |name: &str, value: &str| Converter::get(name).unwrap().constraint().is_match(value);
+
+        assert!(matches("u8", "0"));
+        assert!(matches("u8", "255"));
+        assert!(!matches("u8", "256"));
+        assert!(!matches("u8", "+1"));
+        assert!(!matches("u8", "-1"));
+        assert!(!matches("u8", ""));
+        assert!(matches("u64", "18446744073709551615"));
+        assert!(!matches("u64", "18446744073709551616"));
+        assert!(matches("i32", "-2147483648"));
+        assert!(!matches("i32", "2147483648"));
+        assert!(!matches("i32", "1.5"));
+
+        assert!(matches("uuid", "67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(matches("uuid", "67E55044-10B1-426F-9247-BB680E5FE0C8"));
+        assert!(!matches("uuid", "67e5504410b1426f9247bb680e5fe0c8"));
+        assert!(!matches("uuid", "67e55044-10b1-426f-9247-bb680e5fe0c8a"));
+
+        assert!(matches("slug", "hello-world-2"));
+        assert!(!matches("slug", "Hello-World"));
+        assert!(!matches("slug", "hello--world"));
+        assert!(!matches("slug", "-hello"));
+
+        assert!(Converter::get("unknown").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "converter `u8` is already registered")]
+    fn duplicate_builtin_registration() {
+        Converter::new("[0-9]").register("u8");
+    }
+
+    #[test]
+    #[should_panic(expected = "converter `test_duplicate` is already registered")]
+    fn duplicate_registration() {
+        Converter::new("[a-z]+").register("test_duplicate");
+        Converter::new("[0-9]+").register("test_duplicate");
+    }
+}
@@ -4,14 +4,17 @@
 #![doc(html_favicon_url = "https://actix.rs/favicon.ico")]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+mod converter;
 mod de;
 mod path;
 mod pattern;
+mod prefix_tree;
 mod quoter;
 mod regex_set;
 mod resource;
 mod resource_path;
 mod router;
+mod shape;
 
 #[cfg(feature = "http")]
 mod url;
@@ -19,6 +22,7 @@ mod url;
 #[cfg(feature = "http")]
 pub use self::url::Url;
 pub use self::{
+    converter::Converter,
     de::PathDeserializer,
     path::Path,
     pattern::{IntoPatterns, Patterns},
@@ -0,0 +1,167 @@
+use smallvec::SmallVec;
+
+/// Radix tree mapping the static prefixes of route patterns to route indices.
+///
+/// Looking up a path yields every route whose static prefix is a prefix of the path, which are the
+/// only routes that can possibly match it.
+#[derive(Debug, Default)]
+pub(crate) struct PrefixTree {
+    root: Node,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    /// Bytes on the edge leading to this node.
+    label: Vec<u8>,
+
+    /// Indices of routes whose static prefix ends at this node, in ascending order.
+    routes: Vec<usize>,
+
+    /// Child nodes, each with a distinct first label byte.
+    children: Vec<Node>,
+}
+
+impl PrefixTree {
+    /// Adds route `idx` with the given static prefix.
+    ///
+    /// Routes must be inserted in ascending index order.
+    pub(crate) fn insert(&mut self, prefix: &str, idx: usize) {
+        let mut node = &mut self.root;
+        let mut rem = prefix.as_bytes();
+
+        while !rem.is_empty() {
+            let child_idx = match node.children.iter().position(|c| c.label[0] == rem[0]) {
+                Some(child_idx) => child_idx,
+                None => {
+                    node.children.push(Node {
+                        label: rem.to_vec(),
+                        ..Node::default()
+                    });
+                    node.children.len() - 1
+                }
+            };
+
+            let child = &mut node.children[child_idx];
+
+            let common = child
+                .label
+                .iter()
+                .zip(rem)
+                .take_while(|(a, b)| a == b)
+                .count();
+
+            if common < child.label.len() {
+                // split edge so that the common part ends at a node
+                let suffix = child.label.split_off(common);
+
+                let split = Node {
+                    label: suffix,
+                    routes: std::mem::take(&mut child.routes),
+                    children: std::mem::take(&mut child.children),
+                };
+
+                child.children.push(split);
+            }
+
+            node = child;
+            rem = &rem[common..];
+        }
+
+        node.routes.push(idx);
+    }
+
+    /// Returns the indices of routes whose static prefix is a prefix of `path`, in ascending order.
+    pub(crate) fn find(&self, path: &str) -> Candidates<'_> {
+        let mut lists = SmallVec::new();
+        let mut node = &self.root;
+        let mut rem = path.as_bytes();
+
+        if !node.routes.is_empty() {
+            lists.push(node.routes.as_slice());
+        }
+
+        while let Some(child) = rem
+            .first()
+            .and_then(|b| node.children.iter().find(|c| c.label[0] == *b))
+        {
+            match rem.strip_prefix(child.label.as_slice()) {
+                Some(child_rem) => {
+                    if !child.routes.is_empty() {
+                        lists.push(child.routes.as_slice());
+                    }
+
+                    node = child;
+                    rem = child_rem;
+                }
+                None => break,
+            }
+        }
+
+        Candidates { lists }
+    }
+}
+
+/// Iterator over candidate route indices, in ascending order.
+///
+/// Lazily merges the sorted route lists of the nodes along a path, so that lookups neither
+/// allocate nor visit candidates after the first matching route.
+pub(crate) struct Candidates<'a> {
+    /// Non-empty, sorted lists of remaining route indices; one for each node along the path.
+    lists: SmallVec<[&'a [usize]; 8]>,
+}
+
+impl Iterator for Candidates<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        // most paths have candidates at a single node
+        if let [list] = self.lists.as_mut_slice() {
+            let (&idx, rest) = list.split_first()?;
+            *list = rest;
+            return Some(idx);
+        }
+
+        let (pos, &idx) = self
+            .lists
+            .iter()
+            .enumerate()
+            .map(|(pos, list)| (pos, &list[0]))
+            .min_by_key(|(_, idx)| **idx)?;
+
+        let list = &mut self.lists[pos];
+        *list = &list[1..];
+
+        if list.is_empty() {
+            self.lists.swap_remove(pos);
+        }
+
+        Some(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(tree: &PrefixTree, path: &str) -> Vec<usize> {
+        tree.find(path).collect()
+    }
+
+    #[test]
+    fn split_edges() {
+        let mut tree = PrefixTree::default();
+        tree.insert("/users/", 0);
+        tree.insert("/user", 1);
+        tree.insert("", 2);
+        tree.insert("/users/", 3);
+        tree.insert("/posts", 4);
+        tree.insert("/us", 5);
+
+        assert_eq!(find(&tree, "/users/1"), [0, 1, 2, 3, 5]);
+        assert_eq!(find(&tree, "/user"), [1, 2, 5]);
+        assert_eq!(find(&tree, "/use"), [2, 5]);
+        assert_eq!(find(&tree, "/posts/1"), [2, 4]);
+        assert_eq!(find(&tree, "/p"), [2]);
+        assert_eq!(find(&tree, ""), [2]);
+    }
+}
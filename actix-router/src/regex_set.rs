@@ -3,9 +3,9 @@
 
 use cfg_if::cfg_if;
 #[cfg(feature = "unicode")]
-pub(crate) use regex::{escape, Regex};
+pub(crate) use regex::{escape, Captures, Regex};
 #[cfg(not(feature = "unicode"))]
-pub(crate) use regex_lite::{escape, Regex};
+pub(crate) use regex_lite::{escape, Captures, Regex};
 
 #[cfg(feature = "unicode")]
 #[derive(Debug, Clone)]
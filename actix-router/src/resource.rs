@@ -8,9 +8,11 @@ use std::{
 use tracing::error;
 
 use crate::{
+    converter::{Constraint, Converter},
     path::PathItem,
-    regex_set::{escape, Regex, RegexSet},
-    IntoPatterns, Patterns, Resource, ResourcePath,
+    regex_set::{escape, Captures, Regex, RegexSet},
+    shape::Shape,
+    IntoPatterns, Patterns, Resource,
 };
 
 const MAX_DYNAMIC_SEGMENTS: usize = 16;
@@ -18,7 +20,7 @@ const MAX_DYNAMIC_SEGMENTS: usize = 16;
 /// Regex flags to allow '.' in regex to match '\n'
 ///
 /// See the docs under: https://docs.rs/regex/1/regex/#grouping-and-flags
-const REGEX_FLAGS: &str = "(?s-m)";
+pub(crate) const REGEX_FLAGS: &str = "(?s-m)";
 
 /// Describes the set of paths that match to a resource.
 ///
@@ -155,6 +157,24 @@ const REGEX_FLAGS: &str = "(?s-m)";
 /// assert!(!resource.is_match("/user/abc"));
 /// ```
 ///
+/// # Typed Segments
+/// Instead of a regex, the name of a registered [`Converter`] can follow the colon, as in
+/// `{id:u64}` or `{id:uuid}`. Paths only match if the segment value conforms to the converter, so
+/// paths with values that could not be extracted fall through to other routes. Building paths
+/// from segment values also fails for non-conforming values.
+///
+/// ## Examples
+/// ```
+/// # use actix_router::ResourceDef;
+/// let resource = ResourceDef::new("/user/{id:u32}");
+/// assert!(resource.is_match("/user/123"));
+/// assert!(!resource.is_match("/user/99999999999"));
+/// assert!(!resource.is_match("/user/abc"));
+///
+/// let mut path = String::new();
+/// assert!(!resource.resource_path_from_iter(&mut path, ["abc"]));
+/// ```
+///
 /// # Tail Segments
 /// As a shortcut to defining a custom regex for matching _all_ remaining characters (not just those
 /// up until a `/` character), there is a special pattern to match (and capture) the remaining
@@ -227,13 +247,26 @@ pub struct ResourceDef {
     segments: Vec<PatternSegment>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 enum PatternSegment {
     /// Literal slice of pattern.
     Const(String),
 
-    /// Name of dynamic segment.
-    Var(String),
+    /// Name of dynamic segment and constraint of its converter, if any.
+    Var(String, Option<Constraint>),
+}
+
+#[derive(Debug, Clone)]
+enum SegmentMatcher {
+    /// Literal slice of pattern.
+    Const(String),
+
+    /// Dynamic segment matching up to the next path delimiter, with the constraint of its
+    /// converter, if any.
+    Var(Option<Constraint>),
+
+    /// Tail segment matching the rest of the path.
+    Tail,
 }
 
 #[derive(Debug, Clone)]
@@ -242,13 +275,24 @@ enum PatternType {
     /// Single constant/literal segment.
     Static(String),
 
-    /// Single regular expression and list of dynamic segment names.
-    Dynamic(Regex, Vec<&'static str>),
+    /// Segment matchers and list of dynamic segment names.
+    ///
+    /// Used instead of `Dynamic` when all dynamic segments use the default patterns and end at a
+    /// path delimiter, so that no regular expression needs to be run.
+    Segments(Vec<SegmentMatcher>, Vec<&'static str>),
+
+    /// Single regular expression, list of dynamic segment names and converter checks.
+    Dynamic(Regex, Vec<&'static str>, Checks),
 
-    /// Regular expression set and list of component expressions plus dynamic segment names.
-    DynamicSet(RegexSet, Vec<(Regex, Vec<&'static str>)>),
+    /// Regular expression set and list of component expressions plus dynamic segment names and
+    /// converter checks.
+    DynamicSet(RegexSet, Vec<(Regex, Vec<&'static str>, Checks)>),
 }
 
+/// Dynamic segment names and constraints of converters with check functions, which values must
+/// pass in addition to matching the regular expression.
+type Checks = Vec<(&'static str, Constraint)>;
+
 impl ResourceDef {
     /// Constructs a new resource definition from patterns.
     ///
@@ -490,6 +534,18 @@ impl ResourceDef {
         }
     }
 
+    /// Returns the literal text that all matching paths must start with.
+    ///
+    /// Multi-pattern resources return an empty string.
+    pub(crate) fn static_prefix(&self) -> &str {
+        match (&self.pat_type, self.segments.first()) {
+            (PatternType::Static(pattern), _) => pattern,
+            (PatternType::DynamicSet(..), _) => "",
+            (_, Some(PatternSegment::Const(prefix))) => prefix,
+            (_, _) => "",
+        }
+    }
+
     /// Joins two resources.
     ///
     /// Resulting resource is prefix if `other` is prefix.
@@ -518,6 +574,64 @@ impl ResourceDef {
         }
     }
 
+    /// Returns `true` if every path that matches `other` also matches this resource.
+    ///
+    /// Resources can only be compared if all of their dynamic segments use the default patterns and
+    /// are not mixed with literal text between slashes. Returns `false` if either resource can not be
+    /// compared.
+    ///
+    /// # Examples
+    /// ```
+    /// # use actix_router::ResourceDef;
+    /// let resource = ResourceDef::new("/user/{id}");
+    /// assert!(resource.covers(&ResourceDef::new("/user/me")));
+    /// assert!(!resource.covers(&ResourceDef::new("/user/me/stars")));
+    ///
+    /// let resource = ResourceDef::prefix("/user");
+    /// assert!(resource.covers(&ResourceDef::new("/user/{id}/stars")));
+    /// assert!(!resource.covers(&ResourceDef::new("/users")));
+    /// ```
+    pub fn covers(&self, other: &ResourceDef) -> bool {
+        let (Some(shapes), Some(other_shapes)) = (self.shapes(), other.shapes()) else {
+            return false;
+        };
+
+        !other_shapes.is_empty()
+            && other_shapes
+                .iter()
+                .all(|other| shapes.iter().any(|shape| shape.covers(other)))
+    }
+
+    /// Returns `true` if some path matches both this resource and `other`.
+    ///
+    /// Resources can only be compared if all of their dynamic segments use the default patterns and
+    /// are not mixed with literal text between slashes. Returns `false` if either resource can not be
+    /// compared.
+    ///
+    /// # Examples
+    /// ```
+    /// # use actix_router::ResourceDef;
+    /// let resource = ResourceDef::new("/{user}/posts");
+    /// assert!(resource.overlaps(&ResourceDef::new("/admin/{section}")));
+    /// assert!(!resource.overlaps(&ResourceDef::new("/admin/{section}/{id}")));
+    /// ```
+    pub fn overlaps(&self, other: &ResourceDef) -> bool {
+        let (Some(shapes), Some(other_shapes)) = (self.shapes(), other.shapes()) else {
+            return false;
+        };
+
+        shapes
+            .iter()
+            .any(|shape| other_shapes.iter().any(|other| shape.overlaps(other)))
+    }
+
+    /// Returns simplified forms of all patterns, or `None` if any can not be simplified.
+    fn shapes(&self) -> Option<Vec<Shape<'_>>> {
+        self.pattern_iter()
+            .map(|pattern| Shape::parse(pattern, self.is_prefix))
+            .collect()
+    }
+
     /// Returns `true` if `path` matches this resource.
     ///
     /// The behavior of this method depends on how the `ResourceDef` was constructed. For example,
@@ -559,8 +673,18 @@ impl ResourceDef {
 
         match &self.pat_type {
             PatternType::Static(pattern) => self.static_match(pattern, path).is_some(),
-            PatternType::Dynamic(re, _) => re.is_match(path),
-            PatternType::DynamicSet(re, _) => re.is_match(path),
+            PatternType::Segments(matchers, _) => self
+                .segments_match(matchers, path, &mut Default::default())
+                .is_some(),
+            PatternType::Dynamic(re, _, checks) if checks.is_empty() => re.is_match(path),
+            PatternType::DynamicSet(re, params)
+                if params.iter().all(|(.., checks)| checks.is_empty()) =>
+            {
+                re.is_match(path)
+            }
+
+            // values captured for typed segments need checking
+            _ => self.find_match(path).is_some(),
         }
     }
 
@@ -603,12 +727,18 @@ impl ResourceDef {
         match &self.pat_type {
             PatternType::Static(pattern) => self.static_match(pattern, path),
 
-            PatternType::Dynamic(re, _) => Some(re.captures(path)?[1].len()),
+            PatternType::Segments(matchers, _) => {
+                self.segments_match(matchers, path, &mut Default::default())
+            }
+
+            PatternType::Dynamic(re, _, checks) => {
+                let captures = re.captures(path)?;
+                checks_pass(checks, &captures).then(|| captures[1].len())
+            }
 
             PatternType::DynamicSet(re, params) => {
-                let idx = re.first_match_idx(path)?;
-                let (ref pattern, _) = params[idx];
-                Some(pattern.captures(path)?[1].len())
+                let (captures, _) = set_captures(re, params, path)?;
+                Some(captures[1].len())
             }
         }
     }
@@ -689,9 +819,16 @@ impl ResourceDef {
                 None => return false,
             },
 
-            PatternType::Dynamic(re, names) => {
+            PatternType::Segments(matchers, names) => {
+                match self.segments_match(matchers, path_str, &mut segments) {
+                    Some(len) => (len, Some(names.as_slice())),
+                    None => return false,
+                }
+            }
+
+            PatternType::Dynamic(re, names, checks) => {
                 let captures = match re.captures(path.unprocessed()) {
-                    Some(captures) => captures,
+                    Some(captures) if checks_pass(checks, &captures) => captures,
                     _ => return false,
                 };
 
@@ -704,18 +841,12 @@ impl ResourceDef {
                     }
                 }
 
-                (captures[1].len(), Some(names))
+                (captures[1].len(), Some(names.as_slice()))
             }
 
             PatternType::DynamicSet(re, params) => {
-                let path = path.unprocessed();
-                let (pattern, names) = match re.first_match_idx(path) {
-                    Some(idx) => &params[idx],
-                    _ => return false,
-                };
-
-                let captures = match pattern.captures(path.path()) {
-                    Some(captures) => captures,
+                let (captures, names) = match set_captures(re, params, path.unprocessed()) {
+                    Some(res) => res,
                     _ => return false,
                 };
 
@@ -759,8 +890,10 @@ impl ResourceDef {
         for segment in &self.segments {
             match segment {
                 PatternSegment::Const(val) => path.push_str(val),
-                PatternSegment::Var(name) => match vars(name) {
-                    Some(val) => path.push_str(val.as_ref()),
+                PatternSegment::Var(name, constraint) => match vars(name) {
+                    Some(val) if constraint.iter().all(|c| c.is_match(val.as_ref())) => {
+                        path.push_str(val.as_ref())
+                    }
                     _ => return false,
                 },
             }
@@ -771,7 +904,8 @@ impl ResourceDef {
 
     /// Assembles full resource path from iterator of dynamic segment values.
     ///
-    /// Returns `true` on success.
+    /// Returns `true` on success. Fails if there are not enough values or if a value does not
+    /// conform to the [`Converter`] of its segment.
     ///
     /// For multi-pattern resources, the first pattern is used under the assumption that it would be
     /// equivalent to any other choice.
@@ -796,7 +930,8 @@ impl ResourceDef {
 
     /// Assembles resource path from map of dynamic segment values.
     ///
-    /// Returns `true` on success.
+    /// Returns `true` on success. Fails if a value is missing or if a value does not conform to the
+    /// [`Converter`] of its segment.
     ///
     /// For multi-pattern resources, the first pattern is used under the assumption that it would be
     /// equivalent to any other choice.
@@ -844,6 +979,63 @@ impl ResourceDef {
         }
     }
 
+    /// Matches `path` against a list of segment matchers, storing the position of each dynamic
+    /// segment value in `segments`.
+    ///
+    /// Returns the length of the matched part of `path`.
+    fn segments_match(
+        &self,
+        matchers: &[SegmentMatcher],
+        path: &str,
+        segments: &mut [PathItem; MAX_DYNAMIC_SEGMENTS],
+    ) -> Option<usize> {
+        let mut pos = 0;
+        let mut var_idx = 0;
+
+        for matcher in matchers {
+            let start = pos;
+
+            match matcher {
+                SegmentMatcher::Const(lit) => {
+                    if !path[pos..].starts_with(lit.as_str()) {
+                        return None;
+                    }
+
+                    pos += lit.len();
+                    continue;
+                }
+
+                SegmentMatcher::Var(constraint) => {
+                    pos = path[pos..].find('/').map_or(path.len(), |idx| pos + idx);
+
+                    if pos == start {
+                        return None;
+                    }
+
+                    if let Some(constraint) = constraint {
+                        if !constraint.is_match(&path[start..pos]) {
+                            return None;
+                        }
+                    }
+                }
+
+                SegmentMatcher::Tail => pos = path.len(),
+            }
+
+            segments[var_idx] = PathItem::Segment(start as u16, pos as u16);
+            var_idx += 1;
+        }
+
+        let rem = &path[pos..];
+
+        // same segment boundary rules as the regular expression built by `parse`
+        match self.is_prefix {
+            false if rem.is_empty() => Some(pos),
+            true if rem.is_empty() || rem.starts_with('/') => Some(pos),
+            _ => None,
+        }
+    }
+
     fn construct<T: IntoPatterns>(paths: T, is_prefix: bool) -> Self {
         let patterns = paths.patterns();
 
@@ -864,9 +1056,9 @@ impl ResourceDef {
 
                 for pattern in patterns {
                     match ResourceDef::parse(pattern, is_prefix, true) {
-                        (PatternType::Dynamic(re, names), segs) => {
+                        (PatternType::Dynamic(re, names, checks), segs) => {
                             re_set.push(re.as_str().to_owned());
-                            pattern_data.push((re, names));
+                            pattern_data.push((re, names, checks));
                             segments.get_or_insert(segs);
                         }
                         _ => unreachable!(),
@@ -900,10 +1092,12 @@ impl ResourceDef {
     /// - the segment's regex to check values against
     /// - the remaining, unprocessed string slice
     /// - whether the parsed parameter represents a tail pattern
+    /// - whether the parsed parameter uses a custom regex, rather than the default pattern or a
+    ///   registered converter
     ///
     /// # Panics
     /// Panics if given patterns does not contain a dynamic segment.
-    fn parse_param(pattern: &str) -> (PatternSegment, String, &str, bool) {
+    fn parse_param(pattern: &str) -> (PatternSegment, String, &str, bool, bool) {
         const DEFAULT_PATTERN: &str = "[^/]+";
         const DEFAULT_PATTERN_TAIL: &str = ".*";
 
@@ -933,29 +1127,42 @@ impl ResourceDef {
         param = &param[1..param.len() - 1];
 
         let tail = unprocessed == "*";
+        let mut custom = false;
+        let mut constraint = None;
 
         let (name, pattern) = match param.find(':') {
             Some(idx) => {
                 assert!(!tail, "custom regex is not supported for tail match");
 
                 let (name, pattern) = param.split_at(idx);
-                (name, &pattern[1..])
+                let pattern = &pattern[1..];
+
+                match Converter::get(pattern) {
+                    Some(converter) => {
+                        constraint = Some(converter.constraint());
+                        (name, Cow::Owned(converter.pattern().to_owned()))
+                    }
+                    None => {
+                        custom = true;
+                        (name, Cow::Borrowed(pattern))
+                    }
+                }
             }
             None => (
                 param,
-                if tail {
+                Cow::Borrowed(if tail {
                     unprocessed = &unprocessed[1..];
                     DEFAULT_PATTERN_TAIL
                 } else {
                     DEFAULT_PATTERN
-                },
+                }),
             ),
         };
 
-        let segment = PatternSegment::Var(name.to_string());
+        let segment = PatternSegment::Var(name.to_string(), constraint);
         let regex = format!(r"(?P<{}>{})", &name, &pattern);
 
-        (segment, regex, unprocessed, tail)
+        (segment, regex, unprocessed, tail, custom)
     }
 
     /// Parse `pattern` using `is_prefix` and `force_dynamic` flags.
@@ -966,7 +1173,7 @@ impl ResourceDef {
     /// - `force_dynamic`: Use `true` to disallow the return of static and prefix segments.
     ///
     /// The returned tuple includes:
-    /// - the pattern type detected, either `Static`, `Prefix`, or `Dynamic`
+    /// - the pattern type detected, either `Static`, `Segments`, or `Dynamic`
     /// - a list of segment descriptors from the pattern
     fn parse(
         pattern: &str,
@@ -986,6 +1193,10 @@ impl ResourceDef {
         let mut re = format!("{}^", REGEX_FLAGS);
         let mut dyn_segment_count = 0;
         let mut has_tail_segment = false;
+        let mut checks = Vec::new();
+
+        // regex-free matchers; abandoned if any segment requires the regex
+        let mut matchers = (!force_dynamic).then(Vec::new);
 
         while let Some(idx) = unprocessed.find('{') {
             let (prefix, rem) = unprocessed.split_at(idx);
@@ -993,12 +1204,35 @@ impl ResourceDef {
             segments.push(PatternSegment::Const(prefix.to_owned()));
             re.push_str(&escape(prefix));
 
-            let (param_pattern, re_part, rem, tail) = Self::parse_param(rem);
+            let (param_pattern, re_part, rem, tail, custom) = Self::parse_param(rem);
 
             if tail {
                 has_tail_segment = true;
             }
 
+            // a default dynamic segment ends at the next delimiter only if one follows it
+            let delimited = tail || rem.is_empty() || rem.starts_with('/');
+
+            if custom || !delimited {
+                matchers = None;
+            } else if let Some(matchers) = &mut matchers {
+                if !prefix.is_empty() {
+                    matchers.push(SegmentMatcher::Const(prefix.to_owned()));
+                }
+
+                matchers.push(match &param_pattern {
+                    _ if tail => SegmentMatcher::Tail,
+                    PatternSegment::Var(_, constraint) => SegmentMatcher::Var(constraint.clone()),
+                    PatternSegment::Const(_) => unreachable!(),
+                });
+            }
+
+            if let PatternSegment::Var(name, Some(constraint)) = &param_pattern {
+                if constraint.has_check() {
+                    checks.push((name.clone(), constraint.clone()));
+                }
+            }
+
             segments.push(param_pattern);
             re.push_str(&re_part);
 
@@ -1039,6 +1273,10 @@ impl ResourceDef {
 
             segments.push(PatternSegment::Const(unprocessed.to_owned()));
             re.push_str(&escape(unprocessed));
+
+            if let Some(matchers) = &mut matchers {
+                matchers.push(SegmentMatcher::Const(unprocessed.to_owned()));
+            }
         }
 
         assert!(
@@ -1072,12 +1310,53 @@ impl ResourceDef {
         let names = re
             .capture_names()
             .filter_map(|name| name.map(|name| Box::leak(Box::new(name.to_owned())).as_str()))
-            .collect();
+            .collect::<Vec<_>>();
+
+        match matchers {
+            // unnamed tail segments are left to the regex
+            Some(matchers) if !unprocessed.ends_with('*') => {
+                (PatternType::Segments(matchers, names), segments)
+            }
 
-        (PatternType::Dynamic(re, names), segments)
+            _ => {
+                let checks = checks
+                    .into_iter()
+                    .filter_map(|(name, constraint)| {
+                        let name = names.iter().find(|n| **n == name)?;
+                        Some((*name, constraint))
+                    })
+                    .collect();
+
+                (PatternType::Dynamic(re, names, checks), segments)
+            }
+        }
     }
 }
 
+/// Returns `true` if the values captured for dynamic segments pass the checks of their converters.
+fn checks_pass(checks: &Checks, captures: &Captures<'_>) -> bool {
+    checks.iter().all(|(name, constraint)| {
+        captures
+            .name(name)
+            .is_some_and(|m| constraint.passes_check(m.as_str()))
+    })
+}
+
+/// Finds the first pattern of a set that matches `path` and whose captured values pass the checks
+/// of their converters, returning its captures and dynamic segment names.
+fn set_captures<'a, 'p>(
+    re: &RegexSet,
+    params: &'a [(Regex, Vec<&'static str>, Checks)],
+    path: &'p str,
+) -> Option<(Captures<'p>, &'a [&'static str])> {
+    let idx = re.first_match_idx(path)?;
+
+    params[idx..].iter().find_map(|(pattern, names, checks)| {
+        let captures = pattern.captures(path)?;
+        checks_pass(checks, &captures).then_some((captures, names.as_slice()))
+    })
+}
+
 impl Eq for ResourceDef {}
 
 impl PartialEq for ResourceDef {
@@ -1730,6 +2009,155 @@ mod tests {
         match_methods_agree!(["/v{v}", "/ver/{v}"] => "", "s/v", "/v1", "/v1/xx", "/ver/i3/5", "/ver/1");
     }
 
+    #[test]
+    fn segment_matchers_agree_with_regex() {
+        fn captures(re: &ResourceDef, path: &str) -> Option<(Vec<String>, String)> {
+            let mut path = Path::new(path);
+
+            if !re.capture_match_info(&mut path) {
+                return None;
+            }
+
+            let segments = path.iter().map(|(_, val)| val.to_owned()).collect();
+            Some((segments, path.unprocessed().to_owned()))
+        }
+
+        macro_rules! agree {
+            ($ctor:ident, $fast:expr, $regex:expr => $($test:expr),+) => {{
+                let fast = ResourceDef::$ctor($fast);
+                let regex = ResourceDef::$ctor($regex);
+
+                assert!(matches!(fast.pat_type, PatternType::Segments(..)));
+                assert!(matches!(regex.pat_type, PatternType::Dynamic(..)));
+
+                $({
+                    assert_eq!(fast.is_match($test), regex.is_match($test), "{}", $test);
+                    assert_eq!(fast.find_match($test), regex.find_match($test), "{}", $test);
+                    assert_eq!(captures(&fast, $test), captures(&regex, $test), "{}", $test);
+                })+
+            }};
+        }
+
+        agree!(new, "/user/{id}", "/user/{id:[^/]+}" =>
+            "/user/", "/user/123", "/user/123/", "/user/123/stars", "/users/123", "/user/ü");
+        agree!(new, "/{a}/{b}/", "/{a:[^/]+}/{b:[^/]+}/" =>
+            "//b/", "/a/b", "/a/b/", "/a/b/c", "/a\nb/c/");
+        agree!(new, "/v{v}/{tail}*", "/v{v:[^/]+}/{tail}*" =>
+            "/v", "/v1", "/v1/", "/v1/a/b", "/v/a");
+        agree!(prefix, "/user/{id}", "/user/{id:[^/]+}" =>
+            "/user/", "/user/123", "/user/123/", "/user/123/stars", "/user/123stars");
+        agree!(prefix, "/{a}/x", "/{a:[^/]+}/x" =>
+            "/a/x", "/a/x/", "/a/xy", "/a/x/y", "/a/b/x");
+    }
+
+    #[test]
+    fn segment_matchers_fallback() {
+        assert!(matches!(
+            ResourceDef::new("/{file}.{ext}").pat_type,
+            PatternType::Dynamic(..)
+        ));
+        assert!(matches!(
+            ResourceDef::new("/v{a}-x").pat_type,
+            PatternType::Dynamic(..)
+        ));
+        assert!(matches!(
+            ResourceDef::new(r"/user/{id:\d+}").pat_type,
+            PatternType::Dynamic(..)
+        ));
+        assert!(matches!(
+            ResourceDef::new(["/user/{id}", "/u/{id}"]).pat_type,
+            PatternType::DynamicSet(..)
+        ));
+    }
+
+    #[test]
+    fn typed_segments() {
+        let re = ResourceDef::new("/user/{id:u8}/{slug:slug}");
+        assert!(matches!(re.pat_type, PatternType::Segments(..)));
+        assert!(re.is_match("/user/255/hello-world"));
+        assert!(!re.is_match("/user/256/hello-world"));
+        assert!(!re.is_match("/user/12/Hello"));
+
+        let mut path = Path::new("/user/12/hi");
+        assert!(re.capture_match_info(&mut path));
+        assert_eq!(path.get("id").unwrap(), "12");
+        assert_eq!(path.get("slug").unwrap(), "hi");
+
+        // converter regexes bound non-delimited segments
+        let re = ResourceDef::new("/v{ver:u8}{rest}");
+        assert!(matches!(re.pat_type, PatternType::Dynamic(..)));
+        assert_eq!(re.find_match("/v12.json"), Some(9));
+        assert!(!re.is_match("/v256.json"));
+
+        let mut path = Path::new("/v7-beta");
+        assert!(re.capture_match_info(&mut path));
+        assert_eq!(path.get("ver").unwrap(), "7");
+        assert_eq!(path.get("rest").unwrap(), "-beta");
+
+        // later patterns of a set are tried when checks fail
+        let re = ResourceDef::new(["/{id:u8}", "/{id:u16}"]);
+        assert!(re.is_match("/300"));
+        assert!(!re.is_match("/70000"));
+        let mut path = Path::new("/300");
+        assert!(re.capture_match_info(&mut path));
+        assert_eq!(path.get("id").unwrap(), "300");
+
+        // unknown converter names are custom regexes
+        let re = ResourceDef::new("/{id:a+}");
+        assert!(re.is_match("/aaa"));
+
+        let mut s = String::new();
+        let re = ResourceDef::new("/user/{id:u8}");
+        assert!(re.resource_path_from_iter(&mut s, ["255"]));
+        assert_eq!(s, "/user/255");
+        assert!(!re.resource_path_from_iter(&mut String::new(), ["256"]));
+        assert!(!re.resource_path_from_iter(&mut String::new(), ["abc"]));
+    }
+
+    #[test]
+    fn covers_and_overlaps() {
+        let new = ResourceDef::new;
+        let prefix = ResourceDef::prefix;
+
+        assert!(new("/user").covers(&new("/user")));
+        assert!(!new("/user").covers(&new("/user/")));
+        assert!(new("/user/{id}").covers(&new("/user/me")));
+        assert!(!new("/user/{id}").covers(&new("/user/")));
+        assert!(!new("/user/me").covers(&new("/user/{id}")));
+        assert!(new("/{a}/{b}").covers(&new("/user/{id}")));
+
+        assert!(prefix("/user").covers(&new("/user")));
+        assert!(prefix("/user").covers(&prefix("/user/{id}")));
+        assert!(prefix("").covers(&new("/")));
+        assert!(!prefix("/user").covers(&new("/users")));
+        assert!(!prefix("/user/{id}").covers(&new("/user")));
+        assert!(!new("/user").covers(&prefix("/user")));
+
+        assert!(new("/files/{tail}*").covers(&new("/files/")));
+        assert!(new("/files/{tail}*").covers(&new("/files/{a}/{b}")));
+        assert!(new("/files/{tail}*").covers(&new("/files/{a}/{tail}*")));
+        assert!(!new("/files/{tail}*").covers(&new("/files")));
+        assert!(prefix("/files").covers(&new("/files/{tail}*")));
+        assert!(!new("/files/{a}/{tail}*").covers(&new("/files/{tail}*")));
+
+        assert!(ResourceDef::new(["/a", "/b/{id}"]).covers(&ResourceDef::new(["/b/1", "/a"])));
+        assert!(!ResourceDef::new(["/a", "/b/{id}"]).covers(&ResourceDef::new(["/b/1", "/c"])));
+
+        // not comparable
+        assert!(!new("/{file}.{ext}").covers(&new("/a.b")));
+        assert!(!new(r"/{id:\d+}").covers(&new("/1")));
+        assert!(!new("/{a}").covers(&new("/v{v}")));
+
+        assert!(new("/{a}/x").overlaps(&new("/y/{b}")));
+        assert!(new("/{a}").overlaps(&new("/{b}")));
+        assert!(!new("/{a}").overlaps(&new("/")));
+        assert!(!new("/{a}").overlaps(&new("/a/b")));
+        assert!(prefix("/a").overlaps(&new("/{x}/b")));
+        assert!(new("/a/{tail}*").overlaps(&prefix("/a/b")));
+        assert!(!new("/a/{tail}*").overlaps(&new("/a")));
+        assert!(ResourceDef::new(["/x", "/y/{id}"]).overlaps(&new("/y/1")));
+    }
+
     #[test]
     #[should_panic]
     fn duplicate_segment_name() {
@@ -1,4 +1,4 @@
-use crate::{IntoPatterns, Resource, ResourceDef};
+use crate::{prefix_tree::PrefixTree, IntoPatterns, Resource, ResourceDef};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ResourceId(pub u16);
@@ -11,8 +11,12 @@ pub struct ResourceId(pub u16);
 /// 1. Some _context_ data, of the generic type `U`, which is only provided to the check function in
 ///    [`recognize_fn`](Self::recognize_fn). This parameter defaults to `()` and can be omitted if
 ///    not required.
+///
+/// Routes are indexed by the literal text at the start of their patterns so that only routes which
+/// could match a path are tried. Candidates are still tried in the order they were added.
 pub struct Router<T, U = ()> {
     routes: Vec<(ResourceDef, T, U)>,
+    tree: PrefixTree,
 }
 
 impl<T, U> Router<T, U> {
@@ -46,18 +50,14 @@ impl<T, U> Router<T, U> {
     /// the `check` closure is executed, passing the resource and each route's context data. If the
     /// closure returns true then the match result is stored into `resource` and a reference to
     /// the matched _value_ is returned.
-    pub fn recognize_fn<R, F>(&self, resource: &mut R, mut check: F) -> Option<(&T, ResourceId)>
+    pub fn recognize_fn<R, F>(&self, resource: &mut R, check: F) -> Option<(&T, ResourceId)>
     where
         R: Resource,
         F: FnMut(&R, &U) -> bool,
     {
-        for (rdef, val, ctx) in self.routes.iter() {
-            if rdef.capture_match_info_fn(resource, |res| check(res, ctx)) {
-                return Some((val, ResourceId(rdef.id())));
-            }
-        }
-
-        None
+        let idx = self.find_route(resource, check)?;
+        let (rdef, val, _) = &self.routes[idx];
+        Some((val, ResourceId(rdef.id())))
     }
 
     /// Same as [`recognize_fn`](Self::recognize_fn) but returns a mutable reference to the matched
@@ -65,19 +65,29 @@ impl<T, U> Router<T, U> {
     pub fn recognize_mut_fn<R, F>(
         &mut self,
         resource: &mut R,
-        mut check: F,
+        check: F,
     ) -> Option<(&mut T, ResourceId)>
     where
         R: Resource,
         F: FnMut(&R, &U) -> bool,
     {
-        for (rdef, val, ctx) in self.routes.iter_mut() {
-            if rdef.capture_match_info_fn(resource, |res| check(res, ctx)) {
-                return Some((val, ResourceId(rdef.id())));
-            }
-        }
+        let idx = self.find_route(resource, check)?;
+        let (rdef, val, _) = &mut self.routes[idx];
+        Some((val, ResourceId(rdef.id())))
+    }
 
-        None
+    /// Returns the index of the first route that matches `resource` and passes the `check`.
+    fn find_route<R, F>(&self, resource: &mut R, mut check: F) -> Option<usize>
+    where
+        R: Resource,
+        F: FnMut(&R, &U) -> bool,
+    {
+        let mut candidates = self.tree.find(resource.resource_path().unprocessed());
+
+        candidates.find(|&idx| {
+            let (rdef, _, ctx) = &self.routes[idx];
+            rdef.capture_match_info_fn(resource, |res| check(res, ctx))
+        })
     }
 }
 
@@ -106,8 +116,15 @@ impl<T, U> RouterBuilder<T, U> {
 
     /// Finish configuration and create router instance.
     pub fn finish(self) -> Router<T, U> {
+        let mut tree = PrefixTree::default();
+
+        for (idx, (rdef, _, _)) in self.routes.iter().enumerate() {
+            tree.insert(rdef.static_prefix(), idx);
+        }
+
         Router {
             routes: self.routes,
+            tree,
         }
     }
 }
@@ -142,6 +159,7 @@ mod tests {
     use crate::{
         path::Path,
         router::{ResourceId, Router},
+        ResourceDef,
     };
 
     #[allow(clippy::cognitive_complexity)]
@@ -279,4 +297,71 @@ mod tests {
         assert_eq!(*h, 11);
         assert_eq!(&path["val"], "ttt");
     }
+
+    #[test]
+    fn registration_order_across_prefixes() {
+        let mut router = Router::<usize>::build();
+        router.path("/{a}/{b}", 10);
+        router.path("/user/{id}", 11);
+        router.prefix("/user", 12);
+        router.path("/user/{tail}*", 13);
+        router.path(["/other", "/user/me"], 14);
+        let mut router = router.finish();
+
+        let mut path = Path::new("/user/me");
+        let (h, _) = router.recognize_mut(&mut path).unwrap();
+        assert_eq!(*h, 10);
+        assert_eq!(path.get("a").unwrap(), "user");
+
+        let mut path = Path::new("/user/me/stars");
+        let (h, _) = router.recognize_mut(&mut path).unwrap();
+        assert_eq!(*h, 12);
+        assert_eq!(path.unprocessed(), "/me/stars");
+
+        let mut path = Path::new("/other");
+        let (h, _) = router.recognize_mut(&mut path).unwrap();
+        assert_eq!(*h, 14);
+
+        let mut path = Path::new("/users");
+        assert!(router.recognize_mut(&mut path).is_none());
+    }
+
+    #[test]
+    fn check_fn_falls_through() {
+        let mut router = Router::<usize, bool>::build();
+        router.push(ResourceDef::new("/user/{id}"), 10, false);
+        router.push(ResourceDef::new("/{tail}*"), 11, true);
+        router.push(ResourceDef::new("/user/{name}"), 12, true);
+        let router = router.finish();
+
+        let mut path = Path::new("/user/123");
+        let (h, _) = router.recognize_fn(&mut path, |_, &allow| allow).unwrap();
+        assert_eq!(*h, 11);
+        assert_eq!(path.get("tail").unwrap(), "user/123");
+        assert!(path.get("id").is_none());
+
+        let mut path = Path::new("/user/123");
+        let (h, _) = router.recognize_fn(&mut path, |_, _| true).unwrap();
+        assert_eq!(*h, 10);
+    }
+
+    #[test]
+    fn typed_segments_fall_through() {
+        let mut router = Router::<usize>::build();
+        router.path("/user/{id:u32}", 10);
+        router.path("/user/{name:slug}", 11);
+        let router = router.finish();
+
+        let mut path = Path::new("/user/123");
+        let (h, _) = router.recognize(&mut path).unwrap();
+        assert_eq!(*h, 10);
+
+        let mut path = Path::new("/user/99999999999");
+        let (h, _) = router.recognize(&mut path).unwrap();
+        assert_eq!(*h, 11);
+        assert_eq!(path.get("name").unwrap(), "99999999999");
+
+        let mut path = Path::new("/user/Alice");
+        assert!(router.recognize(&mut path).is_none());
+    }
 }
@@ -0,0 +1,137 @@
+/// Simplified form of a path pattern, used to compare the sets of paths matched by patterns.
+///
+/// Patterns are split at each `/` into pieces. Only patterns whose dynamic segments use the default
+/// patterns and span a whole piece can be represented.
+#[derive(Debug)]
+pub(crate) struct Shape<'a> {
+    pieces: Vec<Piece<'a>>,
+    end: End,
+}
+
+#[derive(Debug)]
+enum Piece<'a> {
+    /// Piece that matches exactly this text.
+    Lit(&'a str),
+
+    /// Piece that matches any non-empty text.
+    Var,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum End {
+    /// Paths have exactly as many pieces as the pattern.
+    Exact,
+
+    /// Paths may have more pieces than the pattern.
+    Prefix,
+
+    /// Paths have more pieces than the pattern, which are matched by a tail segment.
+    Tail,
+}
+
+impl<'a> Shape<'a> {
+    /// Parses `pattern`, returning `None` if it can not be represented.
+    pub(crate) fn parse(pattern: &'a str, is_prefix: bool) -> Option<Self> {
+        let mut pieces = Vec::new();
+        let mut end = if is_prefix { End::Prefix } else { End::Exact };
+
+        let mut iter = pattern.split('/').peekable();
+
+        while let Some(piece) = iter.next() {
+            let last = iter.peek().is_none();
+
+            if !piece.contains('{') {
+                // unnamed tail segment
+                if last && piece.ends_with('*') {
+                    return None;
+                }
+
+                pieces.push(Piece::Lit(piece));
+                continue;
+            }
+
+            let param = piece.strip_prefix('{')?;
+
+            let (name, tail) = match param.strip_suffix("}*") {
+                Some(name) if last => (name, true),
+                _ => (param.strip_suffix('}')?, false),
+            };
+
+            // custom regexes and segments with literal text are not supported
+            if name.contains(['{', '}', ':']) {
+                return None;
+            }
+
+            if tail {
+                end = End::Tail;
+            } else {
+                pieces.push(Piece::Var);
+            }
+        }
+
+        Some(Shape { pieces, end })
+    }
+
+    /// Returns `true` if every path matching `other` also matches `self`.
+    pub(crate) fn covers(&self, other: &Shape<'_>) -> bool {
+        let len = self.pieces.len();
+        let other_len = other.pieces.len();
+
+        let lengths_covered = match (self.end, other.end) {
+            (End::Exact, End::Exact) => other_len == len,
+            (End::Exact, _) => false,
+            (End::Prefix, _) => other_len >= len,
+            (End::Tail, End::Tail) => other_len >= len,
+            (End::Tail, _) => other_len > len,
+        };
+
+        lengths_covered
+            && self
+                .pieces
+                .iter()
+                .zip(&other.pieces)
+                .all(|pieces| match pieces {
+                    (Piece::Lit(lit), Piece::Lit(other)) => lit == other,
+                    (Piece::Lit(_), Piece::Var) => false,
+                    (Piece::Var, Piece::Lit(other)) => !other.is_empty(),
+                    (Piece::Var, Piece::Var) => true,
+                })
+    }
+
+    /// Returns `true` if some path matches both `self` and `other`.
+    pub(crate) fn overlaps(&self, other: &Shape<'_>) -> bool {
+        let (min, max) = self.piece_counts();
+        let (other_min, other_max) = other.piece_counts();
+
+        let lengths_overlap = match (max, other_max) {
+            (Some(max), Some(other_max)) => min.max(other_min) <= max.min(other_max),
+            (Some(max), None) => other_min <= max,
+            (None, Some(other_max)) => min <= other_max,
+            (None, None) => true,
+        };
+
+        lengths_overlap
+            && self
+                .pieces
+                .iter()
+                .zip(&other.pieces)
+                .all(|pieces| match pieces {
+                    (Piece::Lit(lit), Piece::Lit(other)) => lit == other,
+                    (Piece::Lit(lit), Piece::Var) | (Piece::Var, Piece::Lit(lit)) => {
+                        !lit.is_empty()
+                    }
+                    (Piece::Var, Piece::Var) => true,
+                })
+    }
+
+    /// Returns the minimum and, if bounded, maximum number of pieces in matching paths.
+    fn piece_counts(&self) -> (usize, Option<usize>) {
+        let len = self.pieces.len();
+
+        match self.end {
+            End::Exact => (len, Some(len)),
+            End::Prefix => (len, None),
+            End::Tail => (len + 1, None),
+        }
+    }
+}
@@ -35,6 +35,8 @@
 extern crate tls_openssl as openssl;
 
 use std::{fmt, net, thread, time::Duration};
+#[cfg(feature = "__tls")]
+use std::{fs, io, path::Path};
 
 use actix_codec::{AsyncRead, AsyncWrite, Framed};
 pub use actix_http::{body::to_bytes, test::TestBuffer};
@@ -776,3 +778,87 @@ impl Drop for TestServer {
         self.system.stop();
     }
 }
+
+/// A self-signed certificate and private key for testing TLS servers.
+///
+/// The PEM files written by [`write()`](Self::write) can be loaded by TLS configurations, such as
+/// Actix Web's `CertStore`, and overwritten with a newly generated certificate to test reloading.
+///
+/// # Examples
+/// ```
+/// use actix_test::SelfSignedCert;
+///
+/// let cert = SelfSignedCert::generate(["localhost", "*.example.com"]);
+/// assert!(cert.cert_pem().starts_with("-----BEGIN CERTIFICATE-----"));
+/// ```
+#[cfg(feature = "__tls")]
+#[derive(Debug, Clone)]
+pub struct SelfSignedCert {
+    cert_pem: String,
+    cert_der: Vec<u8>,
+    key_pem: String,
+}
+
+#[cfg(feature = "__tls")]
+impl SelfSignedCert {
+    /// Generates a certificate for the given DNS names, with a new key pair.
+    ///
+    /// The first name is also used as the subject common name.
+    ///
+    /// # Panics
+    /// Panics if no names are given or a name is not a valid DNS name.
+    pub fn generate<I>(names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let common_name = names.first().expect("no DNS names given").clone();
+
+        let mut params = rcgen::CertificateParams::new(names).expect("invalid DNS name");
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, common_name);
+
+        let key_pair = rcgen::KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key_pair).unwrap();
+
+        SelfSignedCert {
+            cert_pem: cert.pem(),
+            cert_der: cert.der().to_vec(),
+            key_pem: key_pair.serialize_pem(),
+        }
+    }
+
+    /// Returns the PEM-encoded certificate.
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    /// Returns the DER-encoded certificate.
+    pub fn cert_der(&self) -> &[u8] {
+        &self.cert_der
+    }
+
+    /// Returns the PEM-encoded private key, in PKCS#8 format.
+    pub fn key_pem(&self) -> &str {
+        &self.key_pem
+    }
+
+    /// Writes the PEM-encoded certificate and private key to files.
+    ///
+    /// Each file is written to a temporary file first and then renamed, so that a watcher never
+    /// reads a partially written file.
+    pub fn write(&self, cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> io::Result<()> {
+        fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+            let mut tmp = path.as_os_str().to_owned();
+            tmp.push(".tmp");
+
+            fs::write(&tmp, contents)?;
+            fs::rename(&tmp, path)
+        }
+
+        write_file(key_path.as_ref(), &self.key_pem)?;
+        write_file(cert_path.as_ref(), &self.cert_pem)
+    }
+}
@@ -94,11 +94,25 @@ mod scope;
 /// - `"path"`: Raw literal string with path for which to register handler.
 /// - `name = "resource_name"`: Specifies resource name for the handler. If not set, the function
 ///   name of handler is used.
+///   When set, typed URL builders are also generated, see [below](#url-generation).
 /// - `method = "HTTP_METHOD"`: Registers HTTP method to provide guard for. Upper-case string,
 ///   "GET", "POST" for example.
 /// - `guard = "function_name"`: Registers function as guard using `actix_web::guard::fn_guard`.
 /// - `wrap = "Middleware"`: Registers a resource middleware.
 ///
+/// # URL Generation
+/// When `name` is set, the generated struct gets `url(&req, ..)` and `url_with_query(&req, ..,
+/// &query)` methods that take one argument per dynamic segment of the path, in order, and call
+/// [`HttpRequest::url_for`] and [`HttpRequest::url_for_with_query`] respectively. Segments with an
+/// integer type, like `{id:u32}`, take that integer type and other segments take any
+/// [`Display`](std::fmt::Display) type, so that missing or mistyped arguments fail to compile.
+///
+/// The path includes the prefix of an enclosing [`scope`](macro@scope) module, but not of scopes
+/// that the handler is registered in at runtime; elements for those cannot be passed.
+///
+/// [`HttpRequest::url_for`]: https://docs.rs/actix-web/4/actix_web/struct.HttpRequest.html#method.url_for
+/// [`HttpRequest::url_for_with_query`]: https://docs.rs/actix-web/4/actix_web/struct.HttpRequest.html#method.url_for_with_query
+///
 /// # Notes
 /// Function name can be specified as any expression that is going to be accessible to the generate
 /// code, e.g `my_guard` or `my_module::my_guard`.
@@ -112,6 +126,22 @@ mod scope;
 ///     HttpResponse::Ok().finish()
 /// }
 /// ```
+///
+/// Generating URLs for a named route:
+/// ```
+/// # use actix_web::{web, HttpRequest, HttpResponse};
+/// # use actix_web_codegen::route;
+/// #[route("/users/{id:u64}/posts/{slug}", method = "GET", name = "post")]
+/// async fn post(path: web::Path<(u64, String)>) -> HttpResponse {
+///     HttpResponse::Ok().finish()
+/// }
+///
+/// fn links(req: &HttpRequest) -> (String, String) {
+///     let url = post::url(req, 42, "hello-world").unwrap();
+///     let with_query = post::url_with_query(req, 42, "hello-world", &[("page", 2)]).unwrap();
+///     (url.into(), with_query.into())
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
     route::with_method(None, args, input)
@@ -133,6 +163,8 @@ pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// These helper attributes take the same parameters as the [single method handlers](crate#single-method-handler).
 ///
+/// Typed URL builders are only generated if exactly one of the helper attributes sets `name`.
+///
 /// # Examples
 /// ```
 /// # use actix_web::HttpResponse;
@@ -163,6 +195,7 @@ macro_rules! method_macro {
         /// - `"path"`: Raw literal string with path for which to register handler.
         /// - `name = "resource_name"`: Specifies resource name for the handler. If not set, the
         ///   function name of handler is used.
+        ///   When set, typed URL builders are also generated, see [`route`](macro@route).
         /// - `guard = "function_name"`: Registers function as guard using `actix_web::guard::fn_guard`.
         /// - `wrap = "Middleware"`: Registers a resource middleware.
         ///
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use actix_router::ResourceDef;
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 use syn::{punctuated::Punctuated, Ident, LitStr, Path, Token};
 
 use crate::input_and_compile_error;
@@ -227,7 +227,7 @@ struct Args {
     resource_name: Option<syn::LitStr>,
     guards: Vec<Path>,
     wrappers: Vec<syn::Expr>,
-    methods: HashSet<MethodTypeExt>,
+    methods: Vec<MethodTypeExt>,
 }
 
 impl Args {
@@ -235,11 +235,11 @@ impl Args {
         let mut resource_name = None;
         let mut guards = Vec::new();
         let mut wrappers = Vec::new();
-        let mut methods = HashSet::new();
+        let mut methods = Vec::new();
 
         let is_route_macro = method.is_none();
         if let Some(method) = method {
-            methods.insert(MethodTypeExt::Standard(method));
+            methods.push(MethodTypeExt::Standard(method));
         }
 
         for nv in args.options {
@@ -293,12 +293,17 @@ impl Args {
                     ..
                 }) = nv.value.clone()
                 {
-                    if !methods.insert(MethodTypeExt::try_from(&lit)?) {
+                    let method = MethodTypeExt::try_from(&lit)?;
+
+                    // keep declaration order, since the first method gets the plain operation ID
+                    if methods.contains(&method) {
                         return Err(syn::Error::new_spanned(
                             nv.value,
                             format!("HTTP method defined more than once: `{}`", lit.value()),
                         ));
                     }
+
+                    methods.push(method);
                 } else {
                     return Err(syn::Error::new_spanned(
                         nv.value,
@@ -420,6 +425,11 @@ impl ToTokens for Route {
         #[cfg(feature = "compat-routing-macros-force-pub")]
         let vis = syn::Visibility::Public(<Token![pub]>::default());
 
+        #[cfg(feature = "openapi")]
+        let operation = operation_tokens(name, ast, doc_attributes);
+        #[cfg(not(feature = "openapi"))]
+        let operation = TokenStream2::new();
+
         let registrations: TokenStream2 = args
             .iter()
             .map(|args| {
@@ -456,6 +466,7 @@ impl ToTokens for Route {
                     let __resource = ::actix_web::Resource::new(#path)
                         .name(#resource_name)
                         #method_guards
+                        #operation
                         #(.guard(::actix_web::guard::fn_guard(#guards)))*
                         #(.wrap(#wrappers))*
                         .to(#name);
@@ -464,11 +475,15 @@ impl ToTokens for Route {
             })
             .collect();
 
+        let url_builder = url_builder_tokens(name, &vis, args);
+
         let stream = quote! {
             #(#doc_attributes)*
             #[allow(non_camel_case_types, missing_docs)]
             #vis struct #name;
 
+            #url_builder
+
             impl ::actix_web::dev::HttpServiceFactory for #name {
                 fn register(self, __config: &mut actix_web::dev::AppService) {
                     #ast
@@ -481,6 +496,197 @@ impl ToTokens for Route {
     }
 }
 
+/// Returns typed URL builder methods for the handler struct if exactly one of its routes has an
+/// explicit resource name.
+///
+/// Builders take one argument per dynamic segment of the route's path, in order. Segments with an
+/// integer converter take that integer type, so that arguments always conform to the segment.
+fn url_builder_tokens(name: &Ident, vis: &syn::Visibility, args: &[Args]) -> TokenStream2 {
+    let mut named = args.iter().filter(|args| args.resource_name.is_some());
+
+    let (Some(args), None) = (named.next(), named.next()) else {
+        return TokenStream2::new();
+    };
+
+    let resource_name = args.resource_name.as_ref().unwrap();
+    let path = args.path.value();
+    let segments = dynamic_segments(&path);
+
+    let mut seen = HashSet::new();
+    let params = segments
+        .iter()
+        .enumerate()
+        .map(|(idx, (segment, _))| {
+            syn::parse_str::<Ident>(segment)
+                .or_else(|_| syn::parse_str::<Ident>(&format!("r#{segment}")))
+                .ok()
+                .filter(|ident| *ident != "req" && *ident != "query")
+                .filter(|ident| seen.insert(ident.to_string()))
+                .unwrap_or_else(|| format_ident!("arg{idx}"))
+        })
+        .collect::<Vec<_>>();
+
+    let types = segments
+        .iter()
+        .map(|(_, converter)| match converter {
+            Some(
+                ty @ ("u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32"
+                | "i64" | "i128" | "isize"),
+            ) => {
+                let ty = Ident::new(ty, Span::call_site());
+                quote! { #ty }
+            }
+            _ => quote! { impl ::std::fmt::Display },
+        })
+        .collect::<Vec<_>>();
+
+    let count = params.len();
+    let url_doc = format!(
+        " Generates the URL of the `{}` route, substituting the arguments into `{path}`.",
+        resource_name.value(),
+    );
+
+    quote! {
+        impl #name {
+            #[doc = #url_doc]
+            ///
+            /// See [`HttpRequest::url_for`](::actix_web::HttpRequest::url_for).
+            #[allow(dead_code)]
+            #vis fn url(
+                req: &::actix_web::HttpRequest,
+                #(#params: #types),*
+            ) -> ::std::result::Result<::actix_web::__private::Url, ::actix_web::error::UrlGenerationError> {
+                let __elements: [::std::string::String; #count] =
+                    [#(::std::string::ToString::to_string(&#params)),*];
+                req.url_for(#resource_name, __elements)
+            }
+
+            #[doc = #url_doc]
+            ///
+            /// The query string is serialized from `query`. See
+            /// [`HttpRequest::url_for_with_query`](::actix_web::HttpRequest::url_for_with_query).
+            #[allow(dead_code)]
+            #vis fn url_with_query(
+                req: &::actix_web::HttpRequest,
+                #(#params: #types,)*
+                query: &(impl ::actix_web::__private::Serialize + ?::std::marker::Sized),
+            ) -> ::std::result::Result<::actix_web::__private::Url, ::actix_web::error::UrlGenerationError> {
+                let __elements: [::std::string::String; #count] =
+                    [#(::std::string::ToString::to_string(&#params)),*];
+                req.url_for_with_query(#resource_name, __elements, query)
+            }
+        }
+    }
+}
+
+/// Returns the names of the dynamic segments of a path pattern, together with their converter or
+/// custom regex, if any.
+fn dynamic_segments(pattern: &str) -> Vec<(&str, Option<&str>)> {
+    let mut segments = Vec::new();
+    let mut rem = pattern;
+
+    while let Some(start) = rem.find('{') {
+        // find the matching closing brace, skipping braces in custom regexes
+        let mut depth = 0;
+        let end = rem[start..]
+            .char_indices()
+            .find_map(|(idx, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+
+                (depth == 0).then_some(start + idx)
+            })
+            .unwrap_or(rem.len() - 1);
+
+        let param = &rem[start + 1..end];
+        segments.push(match param.split_once(':') {
+            Some((name, converter)) => (name, Some(converter)),
+            None => (param, None),
+        });
+
+        rem = &rem[end + 1..];
+    }
+
+    segments
+}
+
+/// Returns a `.operation(..)` call describing the handler's operation using the types of its
+/// arguments and return value, and its doc comments.
+#[cfg(feature = "openapi")]
+fn operation_tokens(
+    name: &Ident,
+    ast: &syn::ItemFn,
+    doc_attributes: &[syn::Attribute],
+) -> TokenStream2 {
+    /// Returns `true` if the type can be named in an expression, i.e. it has no `impl Trait`.
+    fn is_nameable(ty: &syn::Type) -> bool {
+        fn walk(tokens: TokenStream2) -> bool {
+            tokens.into_iter().all(|tt| match tt {
+                proc_macro2::TokenTree::Ident(ident) => ident != "impl",
+                proc_macro2::TokenTree::Group(group) => walk(group.stream()),
+                _ => true,
+            })
+        }
+
+        walk(ty.to_token_stream())
+    }
+
+    let doc = doc_attributes
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .flat_map(|doc| {
+            doc.split('\n')
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let operation_id = name.to_string();
+
+    let arg_types = ast.sig.inputs.iter().filter_map(|arg| match arg {
+        syn::FnArg::Typed(arg) if is_nameable(&arg.ty) => Some(&arg.ty),
+        _ => None,
+    });
+
+    let output_type = match &ast.sig.output {
+        syn::ReturnType::Type(_, ty) if is_nameable(ty) => Some(ty),
+        _ => None,
+    }
+    .into_iter();
+
+    quote! {
+        .operation({
+            #[allow(unused_imports)]
+            use ::actix_web::openapi::__private::{
+                DescribeRequest as _, DescribeRequestFallback as _,
+                DescribeResponse as _, DescribeResponseFallback as _,
+                Probe,
+            };
+
+            let mut __operation = ::actix_web::openapi::Operation::new();
+            __operation.operation_id(#operation_id);
+            __operation.doc(#doc);
+            #((&Probe::<#arg_types>::new()).describe_request(&mut __operation);)*
+            #((&Probe::<#output_type>::new()).describe_response(&mut __operation);)*
+            __operation
+        })
+    }
+}
+
 pub(crate) fn with_method(
     method: Option<MethodType>,
     args: TokenStream,
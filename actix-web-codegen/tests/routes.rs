@@ -139,6 +139,10 @@ async fn routes_overlapping_inaccessible_test(req: HttpRequest) -> impl Responde
 async fn custom_resource_name_test<'a>(req: HttpRequest) -> impl Responder {
     assert!(req.url_for_static("custom").is_ok());
     assert!(req.url_for_static("custom_resource_name_test").is_err());
+    assert_eq!(
+        custom_resource_name_test::url(&req).unwrap(),
+        req.url_for_static("custom").unwrap()
+    );
     HttpResponse::Ok()
 }
 
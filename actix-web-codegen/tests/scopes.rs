@@ -1,4 +1,6 @@
-use actix_web::{guard::GuardContext, http, http::header, web, App, HttpResponse, Responder};
+use actix_web::{
+    guard::GuardContext, http, http::header, web, App, HttpRequest, HttpResponse, Responder,
+};
 use actix_web_codegen::{delete, get, post, route, routes, scope};
 
 pub fn image_guard(ctx: &GuardContext<'_>) -> bool {
@@ -91,6 +93,29 @@ mod mod_scope_v2 {
     }
 }
 
+#[scope("/tenants/{tenant}")]
+mod mod_scope_named {
+    use super::*;
+
+    #[get("/users/{id:u32}", name = "user_detail")]
+    pub async fn user_detail(path: web::Path<(String, u32)>) -> impl Responder {
+        let (tenant, id) = path.into_inner();
+        format!("{tenant}/{id}")
+    }
+
+    #[get("/links")]
+    pub async fn links(req: HttpRequest, tenant: web::Path<String>) -> impl Responder {
+        let detail = user_detail::url(&req, &tenant, 7).unwrap();
+        let page = user_detail::url_with_query(&req, &tenant, 7, &[("tab", "posts")]).unwrap();
+        format!(
+            "{} {}?{}",
+            detail.path(),
+            page.path(),
+            page.query().unwrap()
+        )
+    }
+}
+
 #[actix_rt::test]
 async fn scope_get_async() {
     let srv = actix_test::start(|| App::new().service(scope_module::test));
@@ -198,3 +223,25 @@ async fn scope_v1_v2_async() {
     let body_str = String::from_utf8(body.to_vec()).unwrap();
     assert_eq!(body_str, "version2 works");
 }
+
+#[actix_rt::test]
+async fn scope_named_url_builder_async() {
+    let srv = actix_test::start(|| {
+        App::new()
+            .service(mod_scope_named::user_detail)
+            .service(mod_scope_named::links)
+    });
+
+    let request = srv.request(http::Method::GET, srv.url("/tenants/acme/links"));
+    let mut response = request.send().await.unwrap();
+    let body = response.body().await.unwrap();
+    assert_eq!(
+        body,
+        "/tenants/acme/users/7 /tenants/acme/users/7?tab=posts"
+    );
+
+    let request = srv.request(http::Method::GET, srv.url("/tenants/acme/users/7"));
+    let mut response = request.send().await.unwrap();
+    let body = response.body().await.unwrap();
+    assert_eq!(body, "acme/7");
+}
@@ -30,6 +30,8 @@ pub struct App<T> {
     data_factories: Vec<FnDataFactory>,
     external: Vec<ResourceDef>,
     extensions: Extensions,
+    strict_routing: bool,
+    middleware: usize,
 }
 
 impl App<AppEntry> {
@@ -46,6 +48,8 @@ impl App<AppEntry> {
             factory_ref,
             external: Vec::new(),
             extensions: Extensions::new(),
+            strict_routing: false,
+            middleware: 0,
         }
     }
 }
@@ -309,6 +313,38 @@ where
         self
     }
 
+    /// Sets whether route conflicts are errors.
+    ///
+    /// When the app starts, services registered at the same level (in the `App` or in the same
+    /// [`Scope`](crate::Scope)) are checked for conflicts between their paths:
+    /// - a service is _shadowed_ if an earlier service, without guards, matches all of its paths, so
+    ///   that it will never receive any requests;
+    /// - a service is _ambiguous_ if it and an earlier service, without guards, match some of the
+    ///   same paths, but neither matches all of the other's paths.
+    ///
+    /// Conflicts are logged as warnings by default. When strict routing is enabled, conflicts cause
+    /// a panic instead, so that tests which start the app fail. Only paths whose dynamic segments
+    /// use the default patterns, and span the whole text between slashes, are checked.
+    ///
+    /// # Examples
+    /// ```should_panic
+    /// use actix_web::{test, web, App, HttpResponse};
+    ///
+    /// # actix_web::rt::System::new().block_on(async {
+    /// let app = App::new()
+    ///     .strict_routing(true)
+    ///     .service(web::resource("/users/{id}").to(HttpResponse::Ok))
+    ///     // shadowed by "/users/{id}"
+    ///     .service(web::resource("/users/me").to(HttpResponse::Ok));
+    ///
+    /// test::init_service(app).await;
+    /// # });
+    /// ```
+    pub fn strict_routing(mut self, strict: bool) -> Self {
+        self.strict_routing = strict;
+        self
+    }
+
     /// Registers an app-wide middleware.
     ///
     /// Registers middleware, in the form of a middleware component (type), that runs during
@@ -366,6 +402,8 @@ where
             factory_ref: self.factory_ref,
             external: self.external,
             extensions: self.extensions,
+            strict_routing: self.strict_routing,
+            middleware: self.middleware + 1,
         }
     }
 
@@ -429,6 +467,8 @@ where
             factory_ref: self.factory_ref,
             external: self.external,
             extensions: self.extensions,
+            strict_routing: self.strict_routing,
+            middleware: self.middleware + 1,
         }
     }
 }
@@ -453,6 +493,8 @@ where
             default: self.default,
             factory_ref: self.factory_ref,
             extensions: RefCell::new(Some(self.extensions)),
+            strict_routing: self.strict_routing,
+            middleware: self.middleware,
         }
     }
 }
@@ -681,6 +723,59 @@ mod tests {
         assert_eq!(body, Bytes::from_static(b"https://youtube.com/watch/12345"));
     }
 
+    #[actix_rt::test]
+    async fn strict_routing_allows_ordered_overlaps() {
+        init_service(
+            App::new()
+                .strict_routing(true)
+                .route("/users/me", web::get().to(HttpResponse::Ok))
+                .route("/users/{id}", web::get().to(HttpResponse::Ok))
+                .route("/users/{id}", web::post().to(HttpResponse::Ok))
+                .service(web::resource("/items/new").to(HttpResponse::Ok))
+                .service(web::resource("/items/{id}").to(HttpResponse::Ok))
+                .service(web::resource(r"/items/{id:\d+}/edit").to(HttpResponse::Ok))
+                .service(web::scope("/api").route("/{tail}*", web::get().to(HttpResponse::Ok))),
+        )
+        .await;
+    }
+
+    #[actix_rt::test]
+    #[should_panic = "route \"/users/me\" is shadowed by earlier route \"/users/{id}\""]
+    async fn strict_routing_shadowed() {
+        init_service(
+            App::new()
+                .strict_routing(true)
+                .service(web::resource("/users/{id}").to(HttpResponse::Ok))
+                .service(web::resource("/users/me").to(HttpResponse::Ok)),
+        )
+        .await;
+    }
+
+    #[actix_rt::test]
+    #[should_panic = "route \"/api/users\" is shadowed by earlier route \"/api\""]
+    async fn strict_routing_shadowed_by_scope() {
+        init_service(
+            App::new()
+                .strict_routing(true)
+                .service(web::scope("/api").route("/items", web::get().to(HttpResponse::Ok)))
+                .route("/api/users", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+    }
+
+    #[actix_rt::test]
+    #[should_panic = "route \"/{section}/x\" is ambiguous with earlier route \"/y/{id}\""]
+    async fn strict_routing_ambiguous_in_scope() {
+        init_service(
+            App::new().strict_routing(true).service(
+                web::scope("/api")
+                    .service(web::resource("/y/{id}").to(HttpResponse::Ok))
+                    .service(web::resource("/{section}/x").to(HttpResponse::Ok)),
+            ),
+        )
+        .await;
+    }
+
     #[test]
     fn can_be_returned_from_fn() {
         /// compile-only test for returning app type from function
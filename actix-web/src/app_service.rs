@@ -11,9 +11,9 @@ use crate::{
     config::{AppConfig, AppService},
     data::FnDataFactory,
     dev::Extensions,
-    guard::Guard,
+    guard::{Guard, HostParams},
     request::{HttpRequest, HttpRequestPool},
-    rmap::ResourceMap,
+    rmap::{ResourceMap, ServiceDetails},
     service::{
         AppServiceFactory, BoxedHttpService, BoxedHttpServiceFactory, ServiceRequest,
         ServiceResponse,
@@ -41,6 +41,8 @@ where
     pub(crate) default: Option<Rc<BoxedHttpServiceFactory>>,
     pub(crate) factory_ref: Rc<RefCell<Option<AppRoutingFactory>>>,
     pub(crate) external: RefCell<Vec<ResourceDef>>,
+    pub(crate) strict_routing: bool,
+    pub(crate) middleware: usize,
 }
 
 impl<T, B> ServiceFactory<Request> for AppInit<T, B>
@@ -71,14 +73,17 @@ where
         });
 
         // create App config to pass to child services
-        let mut config = AppService::new(config, Rc::clone(&default));
+        let mut config = AppService::new(config, Rc::clone(&default), self.strict_routing);
 
         // register services
         mem::take(&mut *self.services.borrow_mut())
             .into_iter()
             .for_each(|mut srv| srv.register(&mut config));
 
-        let mut rmap = ResourceMap::new(ResourceDef::prefix(""));
+        let mut rmap = ResourceMap::with_details(
+            ResourceDef::prefix(""),
+            ServiceDetails::new(&[], self.middleware),
+        );
 
         let (config, services) = config.into_services();
 
@@ -87,8 +92,8 @@ where
             default,
             services: services
                 .into_iter()
-                .map(|(mut rdef, srv, guards, nested)| {
-                    rmap.add(&mut rdef, nested);
+                .map(|(mut rdef, srv, guards, nested, details)| {
+                    rmap.add_service(&mut rdef, nested, details);
                     (rdef, srv, RefCell::new(guards))
                 })
                 .collect::<Vec<_>>()
@@ -187,6 +192,16 @@ impl AppInitServiceState {
     }
 }
 
+impl<T, B> AppInitService<T, B>
+where
+    T: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    /// Returns a reference to the application's resource map.
+    pub(crate) fn rmap(&self) -> &ResourceMap {
+        self.app_state.rmap()
+    }
+}
+
 impl<T, B> Service<Request> for AppInitService<T, B>
 where
     T: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
@@ -308,10 +323,17 @@ impl Service<ServiceRequest> for AppRouting {
     fn call(&self, mut req: ServiceRequest) -> Self::Future {
         let res = self.router.recognize_fn(&mut req, |req, guards| {
             let guard_ctx = req.guard_ctx();
-            guards.iter().all(|guard| guard.check(&guard_ctx))
+            let matched = guards.iter().all(|guard| guard.check(&guard_ctx));
+
+            if !matched && !guards.is_empty() {
+                HostParams::discard(req);
+            }
+
+            matched
         });
 
         if let Some((srv, _info)) = res {
+            HostParams::merge(&mut req);
             srv.call(req)
         } else {
             self.default.call(req)
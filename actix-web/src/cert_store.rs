@@ -0,0 +1,688 @@
+//! Certificate store for TLS listeners, with SNI-based selection and hot reloading.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use rustls_pemfile::Item;
+
+use crate::dev::PeerCertificates;
+
+/// A set of server certificates for TLS listeners, selected by the server name that clients send
+/// using Server Name Indication (SNI).
+///
+/// Each certificate is loaded from a pair of PEM files: a certificate chain, leaf certificate
+/// first, and its private key in PKCS#8, PKCS#1 (RSA) or SEC1 (EC) format. A certificate serves
+/// the DNS names of its subject alternative names, including wildcards such as `*.example.com`, or
+/// its subject common name if it has no DNS names. The first certificate added is the default, used
+/// when the client does not send a server name or when no other certificate matches.
+///
+/// Certificates can be replaced without restarting the server, either by calling
+/// [`reload()`](Self::reload) or by [watching](Self::watch) the files for changes. New handshakes
+/// use the new certificates while established connections are unaffected. If a reload fails, the
+/// previous certificates stay in use.
+///
+/// A store is cheap to clone; clones share the same certificates. Use
+/// [`rustls_0_23_config()`](Self::rustls_0_23_config) or
+/// [`openssl_acceptor()`](Self::openssl_acceptor) to create a TLS configuration for
+/// [`HttpServer`](crate::HttpServer).
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use actix_web::{dev::CertStore, App, HttpResponse, HttpServer, web};
+///
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let certs = CertStore::new()
+///         .add("certs/example.com.pem", "certs/example.com.key")?
+///         .add("certs/example.org.pem", "certs/example.org.key")?
+///         .watch(Duration::from_secs(60));
+///
+///     let server = HttpServer::new(|| App::new().default_service(web::to(HttpResponse::Ok)));
+///
+///     # #[cfg(feature = "rustls-0_23")]
+///     # use tls_rustls as rustls;
+///     # #[cfg(feature = "rustls-0_23")]
+///     let provider = std::sync::Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+///     # #[cfg(feature = "rustls-0_23")]
+///     let config = certs.rustls_0_23_config(provider).map_err(std::io::Error::other)?;
+///     # #[cfg(feature = "rustls-0_23")]
+///     let server = server.bind_rustls_0_23(("0.0.0.0", 443), config)?;
+///     # #[cfg(not(feature = "rustls-0_23"))]
+///     # let server = server.bind_openssl(("0.0.0.0", 443), certs.openssl_acceptor()?)?;
+///
+///     server.run().await
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CertStore {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    sources: Mutex<Vec<Source>>,
+    certs: RwLock<Arc<Certs>>,
+}
+
+/// Files of a certificate and the modification times seen when they were last loaded.
+struct Source {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    modified: Option<(SystemTime, SystemTime)>,
+}
+
+impl Source {
+    fn modified(&self) -> Option<(SystemTime, SystemTime)> {
+        let cert = fs::metadata(&self.cert_path).and_then(|meta| meta.modified());
+        let key = fs::metadata(&self.key_path).and_then(|meta| meta.modified());
+        Some((cert.ok()?, key.ok()?))
+    }
+}
+
+#[derive(Default)]
+struct Certs {
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    /// Lowercase DNS names served by the certificate.
+    names: Vec<String>,
+    chain: Vec<Vec<u8>>,
+    key: PrivateKey,
+}
+
+struct PrivateKey {
+    #[allow(dead_code)] // only read by Rustls
+    format: KeyFormat,
+    der: Vec<u8>,
+}
+
+#[derive(Clone, Copy)]
+enum KeyFormat {
+    Pkcs1,
+    Sec1,
+    Pkcs8,
+}
+
+impl CertStore {
+    /// Constructs an empty certificate store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a certificate chain and private key from PEM files and adds it to the store.
+    ///
+    /// # Errors
+    /// Returns an error if the files cannot be read or do not contain a valid certificate chain
+    /// and private key. The store is left unchanged in that case.
+    pub fn add(
+        self,
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+    ) -> io::Result<Self> {
+        let mut sources = self.inner.sources.lock().unwrap();
+
+        sources.push(Source {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            modified: None,
+        });
+
+        match load(&mut sources) {
+            Ok(certs) => *self.inner.certs.write().unwrap() = Arc::new(certs),
+            Err(err) => {
+                sources.pop();
+                return Err(err);
+            }
+        }
+
+        drop(sources);
+        Ok(self)
+    }
+
+    /// Reloads all certificates from their files.
+    ///
+    /// Certificates are replaced all at once, and only if every one of them loads successfully.
+    ///
+    /// # Errors
+    /// Returns an error if any of the files cannot be read or do not contain a valid certificate
+    /// chain and private key. The previous certificates stay in use in that case.
+    pub fn reload(&self) -> io::Result<()> {
+        let mut sources = self.inner.sources.lock().unwrap();
+        let certs = load(&mut sources)?;
+        *self.inner.certs.write().unwrap() = Arc::new(certs);
+        Ok(())
+    }
+
+    /// Reloads the certificates whenever their files change.
+    ///
+    /// A background thread checks the modification times of the files at the given interval and
+    /// calls [`reload()`](Self::reload) when any of them has changed. Reload errors are logged, and
+    /// the files are checked again after their next change. The thread stops once the store and
+    /// all TLS configurations created from it have been dropped.
+    pub fn watch(self, interval: Duration) -> Self {
+        let inner = Arc::downgrade(&self.inner);
+
+        thread::Builder::new()
+            .name("actix-web-cert-store".to_owned())
+            .spawn(move || loop {
+                thread::sleep(interval);
+
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+
+                let store = CertStore { inner };
+
+                if store.is_modified() {
+                    match store.reload() {
+                        Ok(()) => log::info!("reloaded TLS certificates"),
+                        Err(err) => log::error!("failed to reload TLS certificates: {err}"),
+                    }
+                }
+            })
+            .expect("failed to spawn certificate watcher thread");
+
+        self
+    }
+
+    /// Returns the DNS names served by the certificates in the store, in the order they were
+    /// added.
+    pub fn server_names(&self) -> Vec<String> {
+        self.certs()
+            .entries
+            .iter()
+            .flat_map(|entry| entry.names.iter().cloned())
+            .collect()
+    }
+
+    fn certs(&self) -> Arc<Certs> {
+        Arc::clone(&self.inner.certs.read().unwrap())
+    }
+
+    fn is_modified(&self) -> bool {
+        self.inner
+            .sources
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|source| source.modified() != source.modified)
+    }
+}
+
+impl fmt::Debug for CertStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertStore")
+            .field("server_names", &self.server_names())
+            .finish()
+    }
+}
+
+impl Certs {
+    /// Returns the index of the certificate for a server name.
+    fn find(&self, server_name: Option<&str>) -> Option<usize> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let Some(server_name) = server_name.map(|name| name.trim_end_matches('.')) else {
+            return Some(0);
+        };
+
+        let matches = |pattern: &str| match pattern.strip_prefix("*.") {
+            // wildcards match exactly one label
+            Some(domain) => server_name
+                .split_once('.')
+                .is_some_and(|(_, parent)| parent.eq_ignore_ascii_case(domain)),
+            None => pattern.eq_ignore_ascii_case(server_name),
+        };
+
+        let exact = self.entries.iter().position(|entry| {
+            entry
+                .names
+                .iter()
+                .any(|name| !name.starts_with("*.") && matches(name))
+        });
+
+        let wildcard = || {
+            self.entries
+                .iter()
+                .position(|entry| entry.names.iter().any(|name| matches(name)))
+        };
+
+        Some(exact.or_else(wildcard).unwrap_or(0))
+    }
+}
+
+/// Loads the certificates of all sources, recording the modification times of their files.
+fn load(sources: &mut [Source]) -> io::Result<Certs> {
+    let mut entries = Vec::with_capacity(sources.len());
+
+    for source in sources.iter_mut() {
+        source.modified = source.modified();
+
+        let entry = load_entry(&source.cert_path, &source.key_path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("{} ({})", err, source.cert_path.display()),
+            )
+        })?;
+
+        entries.push(entry);
+    }
+
+    Ok(Certs { entries })
+}
+
+fn load_entry(cert_path: &Path, key_path: &Path) -> io::Result<Entry> {
+    let chain = parse_chain(&fs::read(cert_path)?)?;
+
+    let leaf = PeerCertificates::from_der(chain.iter().cloned())
+        .ok_or_else(|| invalid_data("no valid certificate found"))?;
+
+    let mut names = leaf
+        .dns_names()
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        names.extend(leaf.subject().common_name().map(str::to_ascii_lowercase));
+    }
+
+    let key = parse_key(&fs::read(key_path)?)?;
+
+    let entry = Entry { names, chain, key };
+
+    // check that OpenSSL accepts the key and that it belongs to the certificate
+    #[cfg(feature = "openssl")]
+    openssl::OpensslCert::new(&entry)?;
+
+    Ok(entry)
+}
+
+/// Decodes the certificates of a PEM file.
+fn parse_chain(mut pem: &[u8]) -> io::Result<Vec<Vec<u8>>> {
+    rustls_pemfile::certs(&mut pem)
+        .map(|cert| cert.map(|cert| cert.as_ref().to_vec()))
+        .collect()
+}
+
+/// Decodes the first private key of a PEM file.
+fn parse_key(mut pem: &[u8]) -> io::Result<PrivateKey> {
+    for item in rustls_pemfile::read_all(&mut pem) {
+        let (format, der) = match item? {
+            Item::Pkcs1Key(key) => (KeyFormat::Pkcs1, key.secret_pkcs1_der().to_vec()),
+            Item::Sec1Key(key) => (KeyFormat::Sec1, key.secret_sec1_der().to_vec()),
+            Item::Pkcs8Key(key) => (KeyFormat::Pkcs8, key.secret_pkcs8_der().to_vec()),
+            _ => continue,
+        };
+
+        return Ok(PrivateKey { format, der });
+    }
+
+    Err(invalid_data("no unencrypted private key found"))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Backend-specific certificates, converted once for each set of loaded certificates.
+#[allow(clippy::type_complexity)]
+struct Converted<T> {
+    cache: Mutex<Option<(Arc<Certs>, Vec<Option<T>>)>>,
+}
+
+impl<T: Clone> Converted<T> {
+    fn new() -> Self {
+        Self {
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Returns the converted certificate for a server name.
+    fn get<E: fmt::Display>(
+        &self,
+        store: &CertStore,
+        server_name: Option<&str>,
+        convert: impl Fn(&Entry) -> Result<T, E>,
+    ) -> Option<T> {
+        let certs = store.certs();
+        let idx = certs.find(server_name)?;
+
+        let mut cache = self.cache.lock().unwrap();
+
+        match &*cache {
+            Some((cached, converted)) if Arc::ptr_eq(cached, &certs) => converted[idx].clone(),
+
+            _ => {
+                let converted = certs
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        convert(entry)
+                            .map_err(|err| {
+                                log::error!("invalid TLS certificate for {:?}: {err}", entry.names);
+                            })
+                            .ok()
+                    })
+                    .collect::<Vec<_>>();
+
+                let cert = converted[idx].clone();
+                *cache = Some((certs, converted));
+                cert
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rustls-0_23")]
+mod rustls_0_23 {
+    use std::{fmt, sync::Arc};
+
+    use tls_rustls::{
+        crypto::CryptoProvider,
+        pki_types::{
+            CertificateDer, PrivateKeyDer, PrivatePkcs1KeyDer, PrivatePkcs8KeyDer,
+            PrivateSec1KeyDer,
+        },
+        server::{ClientHello, ResolvesServerCert},
+        sign::CertifiedKey,
+        ServerConfig,
+    };
+
+    use super::{CertStore, Converted, Entry, KeyFormat};
+
+    impl CertStore {
+        /// Creates a Rustls v0.23 server configuration that selects certificates from the store.
+        ///
+        /// The configuration uses the given crypto provider with its safe default protocol
+        /// versions and does not request client certificates. Use
+        /// [`rustls_0_23_resolver()`](Self::rustls_0_23_resolver) to customize it.
+        ///
+        /// # Errors
+        /// Returns an error if the provider does not support the default protocol versions.
+        pub fn rustls_0_23_config(
+            &self,
+            provider: Arc<CryptoProvider>,
+        ) -> Result<ServerConfig, tls_rustls::Error> {
+            Ok(ServerConfig::builder_with_provider(Arc::clone(&provider))
+                .with_safe_default_protocol_versions()?
+                .with_no_client_auth()
+                .with_cert_resolver(self.rustls_0_23_resolver(provider)))
+        }
+
+        /// Creates a Rustls v0.23 certificate resolver that selects certificates from the store.
+        ///
+        /// Private keys are loaded using the given crypto provider.
+        pub fn rustls_0_23_resolver(
+            &self,
+            provider: Arc<CryptoProvider>,
+        ) -> Arc<dyn ResolvesServerCert> {
+            Arc::new(Resolver {
+                store: self.clone(),
+                provider,
+                keys: Converted::new(),
+            })
+        }
+    }
+
+    struct Resolver {
+        store: CertStore,
+        provider: Arc<CryptoProvider>,
+        keys: Converted<Arc<CertifiedKey>>,
+    }
+
+    impl fmt::Debug for Resolver {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("CertStoreResolver")
+                .field("store", &self.store)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl ResolvesServerCert for Resolver {
+        fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+            self.keys
+                .get(&self.store, client_hello.server_name(), |entry| {
+                    certified_key(entry, &self.provider).map(Arc::new)
+                })
+        }
+    }
+
+    fn certified_key(
+        entry: &Entry,
+        provider: &CryptoProvider,
+    ) -> Result<CertifiedKey, tls_rustls::Error> {
+        let chain = entry
+            .chain
+            .iter()
+            .map(|der| CertificateDer::from(der.clone()))
+            .collect();
+
+        let der = entry.key.der.clone();
+        let key = match entry.key.format {
+            KeyFormat::Pkcs1 => PrivateKeyDer::Pkcs1(PrivatePkcs1KeyDer::from(der)),
+            KeyFormat::Sec1 => PrivateKeyDer::Sec1(PrivateSec1KeyDer::from(der)),
+            KeyFormat::Pkcs8 => PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(der)),
+        };
+
+        CertifiedKey::from_der(chain, key, provider)
+    }
+}
+
+#[cfg(feature = "openssl")]
+mod openssl {
+    use std::{io, sync::Arc};
+
+    use tls_openssl::{
+        error::ErrorStack,
+        pkey::{PKey, Private},
+        ssl::{NameType, SniError, SslAcceptor, SslAcceptorBuilder, SslMethod, SslRef},
+        x509::X509,
+    };
+
+    use super::{invalid_data, CertStore, Converted, Entry};
+
+    impl CertStore {
+        /// Creates an OpenSSL acceptor builder that selects certificates from the store.
+        ///
+        /// The builder uses Mozilla's intermediate server configuration and sets the certificate
+        /// of each connection in its server name callback, so that callback must not be replaced.
+        ///
+        /// # Errors
+        /// Returns an error if OpenSSL fails to create the builder.
+        pub fn openssl_acceptor(&self) -> io::Result<SslAcceptorBuilder> {
+            let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
+
+            let store = self.clone();
+            let certs = Converted::new();
+
+            // the callback is invoked for every handshake, including those without a server name
+            builder.set_servername_callback(move |ssl, _alert| {
+                let server_name = ssl.servername(NameType::HOST_NAME);
+
+                let cert = certs
+                    .get(&store, server_name, |entry| {
+                        OpensslCert::new(entry).map(Arc::new)
+                    })
+                    .ok_or(SniError::ALERT_FATAL)?;
+
+                cert.apply(ssl).map_err(|err| {
+                    log::error!("failed to set TLS certificate: {err}");
+                    SniError::ALERT_FATAL
+                })
+            });
+
+            Ok(builder)
+        }
+    }
+
+    pub(super) struct OpensslCert {
+        leaf: X509,
+        intermediates: Vec<X509>,
+        key: PKey<Private>,
+    }
+
+    impl OpensslCert {
+        pub(super) fn new(entry: &Entry) -> io::Result<Self> {
+            let mut chain = entry
+                .chain
+                .iter()
+                .map(|der| X509::from_der(der))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let leaf = chain.remove(0);
+            let key = PKey::private_key_from_der(&entry.key.der)?;
+
+            // an invalid key would otherwise only be noticed during handshakes
+            if !leaf.public_key()?.public_eq(&key) {
+                return Err(invalid_data("private key does not match certificate"));
+            }
+
+            Ok(OpensslCert {
+                leaf,
+                intermediates: chain,
+                key,
+            })
+        }
+
+        fn apply(&self, ssl: &mut SslRef) -> Result<(), ErrorStack> {
+            ssl.set_certificate(&self.leaf)?;
+            ssl.set_private_key(&self.key)?;
+
+            for cert in &self.intermediates {
+                ssl.add_chain_cert(cert.clone())?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use actix_test::SelfSignedCert;
+
+    use super::*;
+
+    fn entry(names: &[&str]) -> Entry {
+        Entry {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            chain: Vec::new(),
+            key: PrivateKey {
+                format: KeyFormat::Pkcs8,
+                der: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn find_by_server_name() {
+        assert_eq!(Certs::default().find(Some("example.com")), None);
+
+        let certs = Certs {
+            entries: vec![
+                entry(&["example.com"]),
+                entry(&["*.example.org", "example.org"]),
+                entry(&["www.example.org"]),
+            ],
+        };
+
+        assert_eq!(certs.find(None), Some(0));
+        assert_eq!(certs.find(Some("example.com")), Some(0));
+        assert_eq!(certs.find(Some("EXAMPLE.org.")), Some(1));
+        assert_eq!(certs.find(Some("api.example.org")), Some(1));
+        assert_eq!(certs.find(Some("www.example.org")), Some(2));
+        assert_eq!(certs.find(Some("a.b.example.org")), Some(0));
+        assert_eq!(certs.find(Some("example.net")), Some(0));
+    }
+
+    #[test]
+    fn pem() {
+        let cert = SelfSignedCert::generate(["localhost"]);
+
+        let chain = parse_chain(cert.cert_pem().as_bytes()).unwrap();
+        assert_eq!(chain, [cert.cert_der().to_vec()]);
+
+        let key = parse_key(cert.key_pem().as_bytes()).unwrap();
+        assert!(matches!(key.format, KeyFormat::Pkcs8));
+
+        assert!(parse_chain(b"-----BEGIN CERTIFICATE-----\nAAAA\n").is_err());
+        assert!(
+            parse_chain(b"-----BEGIN CERTIFICATE-----\n!!\n-----END CERTIFICATE-----").is_err()
+        );
+        assert!(parse_key(cert.cert_pem().as_bytes()).is_err());
+    }
+
+    #[test]
+    fn add_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name);
+
+        SelfSignedCert::generate(["example.com"])
+            .write(path("com.pem"), path("com.key"))
+            .unwrap();
+
+        let store = CertStore::new()
+            .add(path("com.pem"), path("com.key"))
+            .unwrap();
+        assert_eq!(store.server_names(), ["example.com"]);
+
+        // failed additions leave the store unchanged
+        store
+            .clone()
+            .add(path("org.pem"), path("org.key"))
+            .unwrap_err();
+        assert_eq!(store.server_names(), ["example.com"]);
+
+        SelfSignedCert::generate(["example.org", "*.example.org"])
+            .write(path("com.pem"), path("com.key"))
+            .unwrap();
+
+        store.reload().unwrap();
+        assert_eq!(store.server_names(), ["example.org", "*.example.org"]);
+
+        // failed reloads keep the previous certificates
+        fs::write(path("com.pem"), "not a certificate").unwrap();
+        store.reload().unwrap_err();
+        assert_eq!(store.server_names(), ["example.org", "*.example.org"]);
+    }
+
+    #[test]
+    fn watch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name);
+
+        SelfSignedCert::generate(["example.com"])
+            .write(path("cert.pem"), path("key.pem"))
+            .unwrap();
+
+        let store = CertStore::new()
+            .add(path("cert.pem"), path("key.pem"))
+            .unwrap()
+            .watch(Duration::from_millis(10));
+
+        // let the modification times of the rewritten files differ
+        thread::sleep(Duration::from_millis(50));
+
+        SelfSignedCert::generate(["example.org"])
+            .write(path("cert.pem"), path("key.pem"))
+            .unwrap();
+
+        let start = Instant::now();
+        while store.server_names() != ["example.org"] {
+            assert!(start.elapsed() < Duration::from_secs(5), "not reloaded");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
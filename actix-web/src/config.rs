@@ -8,7 +8,7 @@ use crate::{
     error::Error,
     guard::Guard,
     resource::Resource,
-    rmap::ResourceMap,
+    rmap::{ResourceMap, ServiceDetails},
     route::Route,
     service::{
         AppServiceFactory, BoxedHttpServiceFactory, HttpServiceFactory, ServiceFactoryWrapper,
@@ -22,6 +22,7 @@ type Guards = Vec<Box<dyn Guard>>;
 pub struct AppService {
     config: AppConfig,
     root: bool,
+    strict_routing: bool,
     default: Rc<BoxedHttpServiceFactory>,
     #[allow(clippy::type_complexity)]
     services: Vec<(
@@ -29,16 +30,22 @@ pub struct AppService {
         BoxedHttpServiceFactory,
         Option<Guards>,
         Option<Rc<ResourceMap>>,
+        ServiceDetails,
     )>,
 }
 
 impl AppService {
     /// Crate server settings instance.
-    pub(crate) fn new(config: AppConfig, default: Rc<BoxedHttpServiceFactory>) -> Self {
+    pub(crate) fn new(
+        config: AppConfig,
+        default: Rc<BoxedHttpServiceFactory>,
+        strict_routing: bool,
+    ) -> Self {
         AppService {
             config,
             default,
             root: true,
+            strict_routing,
             services: Vec::new(),
         }
     }
@@ -58,11 +65,58 @@ impl AppService {
             BoxedHttpServiceFactory,
             Option<Guards>,
             Option<Rc<ResourceMap>>,
+            ServiceDetails,
         )>,
     ) {
+        self.check_route_conflicts();
         (self.config, self.services)
     }
 
+    /// Reports services that are shadowed by, or ambiguous with, an earlier service.
+    ///
+    /// See [`App::strict_routing`](crate::App::strict_routing).
+    fn check_route_conflicts(&self) {
+        let mut conflicts = Vec::new();
+
+        for (idx, (rdef, ..)) in self.services.iter().enumerate() {
+            for (earlier, _, guards, ..) in &self.services[..idx] {
+                // guarded services may not handle every request for their paths
+                if guards.as_ref().is_some_and(|guards| !guards.is_empty()) {
+                    continue;
+                }
+
+                if earlier.covers(rdef) {
+                    conflicts.push(format!(
+                        "route {:?} is shadowed by earlier route {:?}",
+                        rdef.pattern().unwrap_or_default(),
+                        earlier.pattern().unwrap_or_default(),
+                    ));
+                    break;
+                }
+
+                if earlier.overlaps(rdef) && !rdef.covers(earlier) {
+                    conflicts.push(format!(
+                        "route {:?} is ambiguous with earlier route {:?}",
+                        rdef.pattern().unwrap_or_default(),
+                        earlier.pattern().unwrap_or_default(),
+                    ));
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            return;
+        }
+
+        if self.strict_routing {
+            panic!("route conflicts found:\n{}", conflicts.join("\n"));
+        }
+
+        for conflict in conflicts {
+            log::warn!("{}", conflict);
+        }
+    }
+
     /// Clones inner config and default service, returning new `AppService` with empty service list
     /// marked as non-root.
     pub(crate) fn clone_config(&self) -> Self {
@@ -71,6 +125,7 @@ impl AppService {
             default: Rc::clone(&self.default),
             services: Vec::new(),
             root: false,
+            strict_routing: self.strict_routing,
         }
     }
 
@@ -101,8 +156,35 @@ impl AppService {
                 InitError = (),
             > + 'static,
     {
-        self.services
-            .push((rdef, boxed::factory(factory.into_factory()), guards, nested));
+        let details = ServiceDetails::new(guards.as_deref().unwrap_or_default(), 0);
+        self.register_service_with_details(rdef, guards, factory, nested, details);
+    }
+
+    /// Register HTTP service, with routing details for the route table.
+    pub(crate) fn register_service_with_details<F, S>(
+        &mut self,
+        rdef: ResourceDef,
+        guards: Option<Vec<Box<dyn Guard>>>,
+        factory: F,
+        nested: Option<Rc<ResourceMap>>,
+        details: ServiceDetails,
+    ) where
+        F: IntoServiceFactory<S, ServiceRequest>,
+        S: ServiceFactory<
+                ServiceRequest,
+                Response = ServiceResponse,
+                Error = Error,
+                Config = (),
+                InitError = (),
+            > + 'static,
+    {
+        self.services.push((
+            rdef,
+            boxed::factory(factory.into_factory()),
+            guards,
+            nested,
+            details,
+        ));
     }
 }
 
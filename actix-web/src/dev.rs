@@ -6,23 +6,28 @@
 //! # Request Extractors
 //! - [`ConnectionInfo`]: Connection information
 //! - [`PeerAddr`]: Connection information
+//! - `PeerCertificates`: Client certificates of mutual TLS connections (requires a TLS feature)
 
 #[cfg(feature = "__compress")]
 pub use actix_http::encoding::Decoder as Decompress;
 pub use actix_http::{Extensions, Payload, RequestHead, Response, ResponseHead};
 use actix_router::Patterns;
-pub use actix_router::{Path, ResourceDef, ResourcePath, Url};
+pub use actix_router::{Converter, Path, ResourceDef, ResourcePath, Url};
 pub use actix_server::{Server, ServerHandle};
 pub use actix_service::{
     always_ready, fn_factory, fn_service, forward_ready, Service, ServiceFactory, Transform,
 };
 
+#[cfg(any(feature = "openssl", feature = "rustls-0_23"))]
+pub use crate::cert_store::CertStore;
 #[doc(hidden)]
 pub use crate::handler::Handler;
+#[cfg(feature = "__tls")]
+pub use crate::peer_certificates::{DistinguishedName, PeerCertificates, SubjectAltName};
 pub use crate::{
     config::{AppConfig, AppService},
     info::{ConnectionInfo, PeerAddr},
-    rmap::ResourceMap,
+    rmap::{ResourceMap, RouteInfo},
     service::{HttpServiceFactory, ServiceRequest, ServiceResponse, WebService},
     types::{JsonBody, Readlines, UrlEncoded},
 };
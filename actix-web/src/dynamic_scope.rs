@@ -0,0 +1,526 @@
+use std::{
+    fmt,
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+use actix_router::{ResourceDef, Router};
+use actix_service::{Service, ServiceFactory};
+use futures_core::future::LocalBoxFuture;
+
+use crate::{
+    dev::AppService,
+    http::{header, Method},
+    rmap::ResourceMap,
+    service::{
+        BoxedHttpService, BoxedHttpServiceFactory, HttpServiceFactory, ServiceRequest,
+        ServiceResponse,
+    },
+    Error, FromRequest, Handler, HttpResponse, Responder,
+};
+
+type DynamicHandler = Arc<
+    dyn Fn(ServiceRequest) -> LocalBoxFuture<'static, Result<ServiceResponse, Error>> + Send + Sync,
+>;
+
+/// A path prefix whose routes can be added and removed while the server is running.
+///
+/// The routes of an [`App`](crate::App) are fixed once its factory has run on each worker. The
+/// routes of a `DynamicScope` are instead looked up in a [`DynamicRoutes`] table, which is shared
+/// by all workers and can be changed at any time through any clone of it. Changes are atomic:
+/// each request is routed using a consistent snapshot of the table, and requests arriving after a
+/// change returns see the change on every worker.
+///
+/// Only requests with paths under the scope's prefix consult the table, so other routes of the app
+/// are unaffected. Requests that match no route in the table are handled by the app's default
+/// service, while requests that match a route but not its methods get a `405 Method Not Allowed`
+/// response.
+///
+/// URLs of named dynamic routes can be generated with
+/// [`HttpRequest::url_for`](crate::HttpRequest::url_for), using the table at the time of the call.
+///
+/// # Examples
+/// ```
+/// use actix_web::{
+///     dev::ResourceDef, http::Method, web, App, DynamicRoute, DynamicRoutes, DynamicScope,
+///     HttpServer,
+/// };
+///
+/// let hooks = DynamicRoutes::new();
+///
+/// let app_hooks = hooks.clone();
+/// let app = move || {
+///     App::new()
+///         .service(DynamicScope::new("/hooks", app_hooks.clone()))
+///         .route("/", web::get().to(|| async { "static routes are unaffected" }))
+/// };
+/// let server = HttpServer::new(app);
+///
+/// // later, e.g. when a tenant registers a webhook
+/// hooks.add(
+///     DynamicRoute::new(ResourceDef::new("/acme/{event}"), |event: web::Path<String>| async move {
+///         format!("received {event}")
+///     })
+///     .method(Method::POST),
+/// );
+///
+/// // and when it is deleted
+/// hooks.retain(|route| route.rdef().pattern() != Some("/acme/{event}"));
+/// ```
+pub struct DynamicScope {
+    rdef: String,
+    routes: DynamicRoutes,
+}
+
+impl DynamicScope {
+    /// Constructs a new dynamic scope for the given path prefix, routing requests using `routes`.
+    ///
+    /// As with [`Scope`](crate::Scope), avoid trailing slashes in the prefix.
+    pub fn new(path: &str, routes: DynamicRoutes) -> Self {
+        DynamicScope {
+            rdef: path.to_owned(),
+            routes,
+        }
+    }
+}
+
+impl HttpServiceFactory for DynamicScope {
+    fn register(self, config: &mut AppService) {
+        let rdef = ResourceDef::root_prefix(&self.rdef);
+        let rmap = ResourceMap::dynamic(rdef.clone(), self.routes.clone());
+
+        let factory = DynamicScopeFactory {
+            routes: self.routes,
+            default: config.default_service(),
+        };
+
+        config.register_service(rdef, None, factory, Some(Rc::new(rmap)))
+    }
+}
+
+/// A route of a [`DynamicScope`], made of a resource definition and a handler.
+///
+/// Unlike handlers of static routes, handlers of dynamic routes are shared between workers, so
+/// they must be `Send` and `Sync`.
+#[derive(Clone)]
+pub struct DynamicRoute {
+    rdef: ResourceDef,
+    methods: Vec<Method>,
+    handler: DynamicHandler,
+}
+
+impl DynamicRoute {
+    /// Constructs a route for the given resource definition, handling requests of any method.
+    ///
+    /// The resource definition is relative to the prefix of the dynamic scope. Set a name on it to
+    /// be able to generate URLs for the route.
+    pub fn new<F, Args>(rdef: ResourceDef, handler: F) -> Self
+    where
+        F: Handler<Args> + Send + Sync,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        let handler: DynamicHandler = Arc::new(move |req: ServiceRequest| {
+            let handler = handler.clone();
+
+            Box::pin(async move {
+                let (req, mut payload) = req.into_parts();
+
+                let res = match Args::from_request(&req, &mut payload).await {
+                    Err(err) => HttpResponse::from_error(err),
+
+                    Ok(data) => handler
+                        .call(data)
+                        .await
+                        .respond_to(&req)
+                        .map_into_boxed_body(),
+                };
+
+                Ok(ServiceResponse::new(req, res))
+            })
+        });
+
+        DynamicRoute {
+            rdef,
+            methods: Vec::new(),
+            handler,
+        }
+    }
+
+    /// Restricts the route to requests of the given method.
+    ///
+    /// May be called multiple times to allow several methods.
+    pub fn method(mut self, method: Method) -> Self {
+        if !self.methods.contains(&method) {
+            self.methods.push(method);
+        }
+
+        self
+    }
+
+    /// Returns the resource definition of the route.
+    pub fn rdef(&self) -> &ResourceDef {
+        &self.rdef
+    }
+
+    /// Returns the methods the route is restricted to, or an empty slice if it handles any method.
+    pub fn methods(&self) -> &[Method] {
+        &self.methods
+    }
+}
+
+impl fmt::Debug for DynamicRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicRoute")
+            .field("rdef", &self.rdef)
+            .field("methods", &self.methods)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Shared routing table of a [`DynamicScope`].
+///
+/// Cloning a `DynamicRoutes` is cheap and returns a handle to the same table. Routes are matched
+/// in the order they were added.
+#[derive(Clone, Default)]
+pub struct DynamicRoutes {
+    table: Arc<RwLock<Arc<RouteTable>>>,
+}
+
+impl DynamicRoutes {
+    /// Constructs an empty routing table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route to the end of the table.
+    pub fn add(&self, route: DynamicRoute) {
+        self.update(|routes| routes.push(route));
+    }
+
+    /// Removes all routes whose resource name is `name`, returning `true` if there were any.
+    pub fn remove(&self, name: &str) -> bool {
+        let mut removed = false;
+
+        self.update(|routes| {
+            let len = routes.len();
+            routes.retain(|route| route.rdef.name() != Some(name));
+            removed = routes.len() != len;
+        });
+
+        removed
+    }
+
+    /// Retains only the routes for which `keep` returns `true`.
+    pub fn retain(&self, keep: impl FnMut(&DynamicRoute) -> bool) {
+        self.update(|routes| routes.retain(keep));
+    }
+
+    /// Replaces all routes in the table.
+    pub fn replace(&self, routes: impl IntoIterator<Item = DynamicRoute>) {
+        let routes = routes.into_iter().collect();
+        self.update(|current| *current = routes);
+    }
+
+    /// Changes the routes in the table.
+    ///
+    /// The closure is given a copy of the current routes. Requests keep being routed using the
+    /// current routes until the closure returns, then all changes are applied at once.
+    pub fn update(&self, f: impl FnOnce(&mut Vec<DynamicRoute>)) {
+        let mut table = self.table.write().unwrap_or_else(|err| err.into_inner());
+
+        let mut routes = table.routes.clone();
+        f(&mut routes);
+
+        *table = Arc::new(RouteTable::new(routes));
+    }
+
+    /// Returns a copy of the routes currently in the table.
+    pub fn routes(&self) -> Vec<DynamicRoute> {
+        self.snapshot().routes.clone()
+    }
+
+    /// Returns the current table.
+    pub(crate) fn snapshot(&self) -> Arc<RouteTable> {
+        Arc::clone(&self.table.read().unwrap_or_else(|err| err.into_inner()))
+    }
+}
+
+impl fmt::Debug for DynamicRoutes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicRoutes")
+            .field("routes", &self.snapshot().routes)
+            .finish()
+    }
+}
+
+/// Immutable snapshot of a dynamic routing table.
+pub(crate) struct RouteTable {
+    routes: Vec<DynamicRoute>,
+
+    /// Indexes into `routes`, with the methods of each route as context.
+    router: Router<usize, Vec<Method>>,
+}
+
+impl RouteTable {
+    fn new(routes: Vec<DynamicRoute>) -> Self {
+        let mut router = Router::build();
+
+        for (idx, route) in routes.iter().enumerate() {
+            router.push(route.rdef.clone(), idx, route.methods.clone());
+        }
+
+        RouteTable {
+            routes,
+            router: router.finish(),
+        }
+    }
+
+    /// Returns the routes of the table.
+    pub(crate) fn routes(&self) -> &[DynamicRoute] {
+        &self.routes
+    }
+
+    /// Returns `true` if a route of the table matches `path`, ignoring methods.
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        self.routes.iter().any(|route| route.rdef.is_match(path))
+    }
+
+    /// Returns the resource definition of the first route named `name`.
+    pub(crate) fn named(&self, name: &str) -> Option<&ResourceDef> {
+        self.routes
+            .iter()
+            .map(|route| &route.rdef)
+            .find(|rdef| rdef.name() == Some(name))
+    }
+}
+
+impl Default for RouteTable {
+    fn default() -> Self {
+        RouteTable::new(Vec::new())
+    }
+}
+
+pub struct DynamicScopeFactory {
+    routes: DynamicRoutes,
+    default: Rc<BoxedHttpServiceFactory>,
+}
+
+impl ServiceFactory<ServiceRequest> for DynamicScopeFactory {
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Config = ();
+    type Service = DynamicScopeService;
+    type InitError = ();
+    type Future = LocalBoxFuture<'static, Result<Self::Service, Self::InitError>>;
+
+    fn new_service(&self, _: ()) -> Self::Future {
+        let routes = self.routes.clone();
+        let default_fut = self.default.new_service(());
+
+        Box::pin(async move {
+            Ok(DynamicScopeService {
+                routes,
+                default: default_fut.await?,
+            })
+        })
+    }
+}
+
+pub struct DynamicScopeService {
+    routes: DynamicRoutes,
+    default: BoxedHttpService,
+}
+
+impl Service<ServiceRequest> for DynamicScopeService {
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_service::always_ready!();
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let table = self.routes.snapshot();
+
+        let res = table.router.recognize_fn(&mut req, |req, methods| {
+            methods.is_empty() || methods.contains(req.method())
+        });
+
+        if let Some((&idx, _)) = res {
+            return (table.routes[idx].handler)(req);
+        }
+
+        let path = req.match_info().unprocessed();
+
+        let mut allowed = table
+            .routes
+            .iter()
+            .filter(|route| route.rdef.is_match(path))
+            .flat_map(|route| &route.methods)
+            .peekable();
+
+        if allowed.peek().is_none() {
+            return self.default.call(req);
+        }
+
+        let mut methods = Vec::new();
+        for method in allowed {
+            if !methods.contains(method) {
+                methods.push(method.clone());
+            }
+        }
+
+        let allow = methods
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let res = HttpResponse::MethodNotAllowed()
+            .insert_header((header::ALLOW, allow))
+            .finish();
+
+        Box::pin(async move { Ok(req.into_response(res)) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::StatusCode,
+        test::{call_service, init_service, read_body, TestRequest},
+        web, App,
+    };
+
+    #[actix_rt::test]
+    async fn routes_can_change_after_startup() {
+        let routes = DynamicRoutes::new();
+
+        let srv = init_service(
+            App::new()
+                .service(DynamicScope::new("/hooks", routes.clone()))
+                .route("/hooks/static", web::get().to(|| async { "static" })),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/hooks/acme/push").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let mut rdef = ResourceDef::new("/{tenant}/{event}");
+        rdef.set_name("hook");
+        routes.add(
+            DynamicRoute::new(rdef, |path: web::Path<(String, String)>| async move {
+                let (tenant, event) = path.into_inner();
+                format!("{tenant}:{event}")
+            })
+            .method(Method::POST),
+        );
+
+        let req = TestRequest::post().uri("/hooks/acme/push").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(read_body(res).await, "acme:push");
+
+        let req = TestRequest::get().uri("/hooks/acme/push").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers().get(header::ALLOW).unwrap(), "POST");
+
+        // unmatched paths in the scope fall through to the default service
+        let req = TestRequest::post().uri("/hooks/acme").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        assert!(routes.remove("hook"));
+        assert!(!routes.remove("hook"));
+
+        let req = TestRequest::post().uri("/hooks/acme/push").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn replace_and_update() {
+        let routes = DynamicRoutes::new();
+        let srv = init_service(App::new().service(DynamicScope::new("", routes.clone()))).await;
+
+        routes.replace([
+            DynamicRoute::new(ResourceDef::new("/a"), || async { "a" }),
+            DynamicRoute::new(ResourceDef::new("/{any}"), || async { "any" }),
+        ]);
+
+        let req = TestRequest::with_uri("/a").to_request();
+        assert_eq!(read_body(call_service(&srv, req).await).await, "a");
+        let req = TestRequest::with_uri("/b").to_request();
+        assert_eq!(read_body(call_service(&srv, req).await).await, "any");
+
+        routes.update(|routes| {
+            routes.swap(0, 1);
+            routes.push(DynamicRoute::new(ResourceDef::new("/b/c"), || async {
+                "c"
+            }));
+        });
+
+        let req = TestRequest::with_uri("/a").to_request();
+        assert_eq!(read_body(call_service(&srv, req).await).await, "any");
+        let req = TestRequest::with_uri("/b/c").to_request();
+        assert_eq!(read_body(call_service(&srv, req).await).await, "c");
+
+        routes.retain(|route| route.rdef().pattern() == Some("/b/c"));
+        assert_eq!(routes.routes().len(), 1);
+
+        let req = TestRequest::with_uri("/a").to_request();
+        let res = call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn resource_map_integration() {
+        let routes = DynamicRoutes::new();
+
+        let srv = init_service(
+            App::new()
+                .service(
+                    web::scope("/{version}")
+                        .service(DynamicScope::new("/hooks", routes.clone()))
+                        .route(
+                            "/links",
+                            web::get().to(|req: crate::HttpRequest| async move {
+                                let url = req.url_for("hook", ["v1", "acme"]);
+                                let rmap = req.resource_map();
+
+                                format!(
+                                    "{} {} {}",
+                                    url.map_or_else(|err| err.to_string(), String::from),
+                                    rmap.has_resource("/v1/hooks/acme"),
+                                    rmap.routes()
+                                        .iter()
+                                        .map(|route| route.pattern())
+                                        .collect::<Vec<_>>()
+                                        .join(","),
+                                )
+                            }),
+                        ),
+                )
+                .route("/", web::get().to(|| async { "" })),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/v1/links").to_request();
+        let body = read_body(call_service(&srv, req).await).await;
+        assert_eq!(body, "Resource not found false /{version}/links,/");
+
+        let mut rdef = ResourceDef::new("/{tenant}");
+        rdef.set_name("hook");
+        routes.add(DynamicRoute::new(rdef, || async { "" }).method(Method::POST));
+
+        let req = TestRequest::with_uri("/v1/links").to_request();
+        let body = read_body(call_service(&srv, req).await).await;
+        assert_eq!(
+            body,
+            "http://localhost:8080/v1/hooks/acme true /{version}/hooks/{tenant},/{version}/links,/"
+        );
+    }
+}
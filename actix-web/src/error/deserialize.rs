@@ -0,0 +1,429 @@
+//! Deserialization errors annotated with the path of the offending field.
+
+use std::{error::Error as StdError, fmt, ops};
+
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use serde_path_to_error::Segment;
+
+use crate::{
+    body::BoxBody,
+    http::{header, StatusCode},
+    HttpResponse,
+};
+
+/// Location of a value within a deserialized document, such as `items[3].price`.
+///
+/// Paths are made of the map keys, struct field names, enum variant names and sequence indices
+/// leading to a value. The empty path refers to the whole document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    segments: Vec<PathSegment>,
+}
+
+/// A single step in a [`FieldPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PathSegment {
+    /// Map key, struct field or enum variant name.
+    Field(String),
+
+    /// Sequence or tuple index.
+    Index(usize),
+
+    /// Map key or enum variant that is not representable as a string.
+    Unknown,
+}
+
+impl FieldPath {
+    /// Constructs the empty path, which refers to the whole document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a map key, struct field or enum variant name to the path.
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.segments.push(PathSegment::Field(name.into()));
+        self
+    }
+
+    /// Appends a sequence index to the path.
+    pub fn index(mut self, index: usize) -> Self {
+        self.segments.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Returns this path followed by the segments of `other`.
+    pub fn join(&self, other: &FieldPath) -> FieldPath {
+        let mut segments = self.segments.clone();
+        segments.extend_from_slice(&other.segments);
+        FieldPath { segments }
+    }
+
+    /// Returns the segments of the path, starting from the root of the document.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Returns true if this path refers to the whole document.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+/// Formats the path with dots between fields and brackets around indices, such as
+/// `items[3].price`. The empty path is formatted as `.`.
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.segments.is_empty() {
+            return f.write_str(".");
+        }
+
+        for (idx, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+
+                segment => {
+                    if idx > 0 {
+                        f.write_str(".")?;
+                    }
+
+                    match segment {
+                        PathSegment::Field(name) => f.write_str(name)?,
+                        _ => f.write_str("?")?,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses paths in the format produced by `Display`, such as `items[3].price`.
+impl From<&str> for FieldPath {
+    fn from(path: &str) -> Self {
+        let mut segments = Vec::new();
+
+        for part in path.split('.') {
+            let (name, mut rest) = part.split_at(part.find('[').unwrap_or(part.len()));
+
+            if !name.is_empty() {
+                segments.push(PathSegment::Field(name.to_owned()));
+            }
+
+            while let Some((index, tail)) =
+                rest.strip_prefix('[').and_then(|rest| rest.split_once(']'))
+            {
+                segments.push(match index.parse() {
+                    Ok(index) => PathSegment::Index(index),
+                    Err(_) => PathSegment::Field(index.to_owned()),
+                });
+                rest = tail;
+            }
+        }
+
+        FieldPath { segments }
+    }
+}
+
+impl From<String> for FieldPath {
+    fn from(path: String) -> Self {
+        FieldPath::from(path.as_str())
+    }
+}
+
+/// Deserialization error with the path of the field that failed to deserialize.
+///
+/// Dereferences to the underlying error `E`.
+#[derive(Debug)]
+pub struct DeserializeError<E> {
+    source: E,
+    path: FieldPath,
+    location: Option<(usize, usize)>,
+}
+
+impl<E> DeserializeError<E> {
+    /// Constructs new deserialization error for the given field.
+    pub fn new(source: E, path: FieldPath) -> Self {
+        Self {
+            source,
+            path,
+            location: None,
+        }
+    }
+
+    pub(crate) fn with_location(mut self, location: Option<(usize, usize)>) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Returns the path of the field that failed to deserialize.
+    ///
+    /// The path is empty if the error does not relate to a specific field, such as a syntax error
+    /// at the start of the document.
+    pub fn path(&self) -> &FieldPath {
+        &self.path
+    }
+
+    /// Returns the one-based line number at which the error occurred, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.location.map(|(line, _)| line)
+    }
+
+    /// Returns the one-based column number at which the error occurred, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.location.map(|(_, column)| column)
+    }
+
+    /// Returns a reference to the underlying error.
+    pub fn get_ref(&self) -> &E {
+        &self.source
+    }
+
+    /// Unwraps into the underlying error.
+    pub fn into_inner(self) -> E {
+        self.source
+    }
+
+    /// Creates a JSON response describing the error, using `message` as the error message.
+    pub(crate) fn error_response(&self, status: StatusCode, message: String) -> HttpResponse {
+        error_response(status, message, Some(&self.path), self.location)
+    }
+}
+
+/// Wraps an error that does not relate to a specific field.
+impl<E> From<E> for DeserializeError<E> {
+    fn from(source: E) -> Self {
+        Self::new(source, FieldPath::default())
+    }
+}
+
+impl<E> ops::Deref for DeserializeError<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.source
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for DeserializeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            fmt::Display::fmt(&self.source, f)
+        } else {
+            write!(f, "{}: {}", self.path, self.source)
+        }
+    }
+}
+
+impl<E: StdError + 'static> StdError for DeserializeError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Deserializes a `T` again after deserialization has failed, returning the path of the field that
+/// caused the failure.
+///
+/// Tracking the path allocates for every map key, so it is only done on the error path, using
+/// `serde_path_to_error`. The input must be the same as in the failed attempt.
+pub(crate) fn field_path<'de, D, T>(de: D) -> FieldPath
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let Err(err) = serde_path_to_error::deserialize::<D, T>(de) else {
+        return FieldPath::new();
+    };
+
+    let segments = err
+        .path()
+        .iter()
+        .map(|segment| match segment {
+            Segment::Seq { index } => PathSegment::Index(*index),
+            Segment::Map { key } => PathSegment::Field(key.clone()),
+            Segment::Enum { variant } => PathSegment::Field(variant.clone()),
+            Segment::Unknown => PathSegment::Unknown,
+        })
+        .collect();
+
+    FieldPath { segments }
+}
+
+/// Returns the path of the field that caused JSON deserialization of a `T` to fail.
+pub(crate) fn json_field_path<T: DeserializeOwned>(body: &[u8]) -> FieldPath {
+    field_path::<_, T>(&mut serde_json::Deserializer::from_slice(body))
+}
+
+/// Returns the path of the field that caused URL encoded deserialization of a `T` to fail.
+pub(crate) fn urlencoded_field_path<T: DeserializeOwned>(input: &[u8]) -> FieldPath {
+    field_path::<_, T>(serde_urlencoded::Deserializer::new(
+        url::form_urlencoded::parse(input),
+    ))
+}
+
+/// Creates a JSON response describing a deserialization error.
+pub(crate) fn error_response(
+    status: StatusCode,
+    message: String,
+    path: Option<&FieldPath>,
+    location: Option<(usize, usize)>,
+) -> HttpResponse {
+    let mut body = serde_json::Map::new();
+    body.insert("error".to_owned(), message.into());
+
+    if let Some(path) = path.filter(|path| !path.is_empty()) {
+        body.insert("path".to_owned(), path.to_string().into());
+    }
+
+    if let Some((line, column)) = location {
+        body.insert("line".to_owned(), line.into());
+        body.insert("column".to_owned(), column.into());
+    }
+
+    let mut res = HttpResponse::new(status);
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    res.set_body(BoxBody::new(serde_json::Value::Object(body).to_string()))
+}
+
+/// Returns the location of a JSON error, if known.
+pub(crate) fn json_location(err: &serde_json::Error) -> Option<(usize, usize)> {
+    // serde_json reports zero for errors without a position
+    match (err.line(), err.column()) {
+        (0, _) => None,
+        location => Some(location),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        body,
+        error::{JsonPayloadError, QueryPayloadError, ResponseError as _},
+    };
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Order {
+        id: u32,
+        items: Vec<Item>,
+        #[serde(default)]
+        notes: Option<HashMap<String, Note>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Item {
+        price: u32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    enum Note {
+        Text(String),
+        Rating { stars: u8 },
+    }
+
+    fn json_path(body: &str) -> String {
+        json_field_path::<Order>(body.as_bytes()).to_string()
+    }
+
+    #[test]
+    fn json_paths() {
+        assert_eq!(json_path(r#"{"id": "one", "items": []}"#), "id");
+        assert_eq!(
+            json_path(r#"{"id": 1, "items": [{"price": 1}, {"price": -1}]}"#),
+            "items[1].price"
+        );
+        assert_eq!(json_path(r#"{"id": 1, "items": [{}]}"#), "items[0]");
+        assert_eq!(
+            json_path(r#"{"id": 1, "items": [], "notes": {"a": {"Rating": {"stars": 600}}}}"#),
+            "notes.a.Rating.stars"
+        );
+        assert_eq!(
+            json_path(r#"{"id": 1, "items": [], "notes": {"a": {"Text": 1}}}"#),
+            "notes.a.Text"
+        );
+        assert_eq!(json_path(r#"{"id": 1}"#), ".");
+        assert_eq!(json_path(r#"{"id": 1, "items": []} trailing"#), ".");
+
+        let body = b"{\n  \"id\": 1,\n  \"items\": [{\"price\": true}]\n}";
+        let err = serde_json::from_slice::<Order>(body).unwrap_err();
+        let err = match JsonPayloadError::deserialize(err, json_field_path::<Order>(body)) {
+            JsonPayloadError::DeserializeField(err) => err,
+            err => panic!("unexpected error: {err:?}"),
+        };
+        assert_eq!(
+            err.path().segments(),
+            [
+                PathSegment::Field("items".to_owned()),
+                PathSegment::Index(0),
+                PathSegment::Field("price".to_owned()),
+            ]
+        );
+        assert_eq!((err.line(), err.column()), (Some(3), Some(26)));
+        assert!(err.to_string().starts_with("items[0].price: invalid type"));
+    }
+
+    #[test]
+    fn urlencoded_paths() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Search {
+            q: String,
+            page: u32,
+        }
+
+        assert_eq!(
+            urlencoded_field_path::<Search>(b"q=rust&page=two").to_string(),
+            "page"
+        );
+        assert!(urlencoded_field_path::<Search>(b"q=rust").is_empty());
+
+        let err = serde_urlencoded::from_str::<Search>("q=rust").unwrap_err();
+        let path = urlencoded_field_path::<Search>(b"q=rust");
+        assert!(matches!(
+            QueryPayloadError::deserialize(err, path),
+            QueryPayloadError::Deserialize(_)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn error_response() {
+        let body = br#"{"id": 1, "items": [{"price": "x"}]}"#;
+        let err = serde_json::from_slice::<Order>(body).unwrap_err();
+        let res =
+            JsonPayloadError::deserialize(err, json_field_path::<Order>(body)).error_response();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["path"], "items[0].price");
+        assert_eq!(body["line"], 1);
+        assert_eq!(body["column"], 33);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Json deserialize error: items[0].price: invalid type"));
+
+        let err = serde_urlencoded::from_str::<HashMap<String, u32>>("a=b").unwrap_err();
+        let path = urlencoded_field_path::<HashMap<String, u32>>(b"a=b");
+        let res = QueryPayloadError::deserialize(err, path).error_response();
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["path"], "a");
+        assert!(body.get("line").is_none());
+    }
+}
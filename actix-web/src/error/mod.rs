@@ -11,16 +11,34 @@ use serde_json::error::Error as JsonError;
 use serde_urlencoded::{de::Error as FormDeError, ser::Error as FormError};
 use url::ParseError as UrlParseError;
 
-use crate::http::StatusCode;
-
+use crate::{
+    http::{
+        header::{Challenge, WwwAuthenticate},
+        StatusCode,
+    },
+    HttpResponse,
+};
+
+mod deserialize;
 #[allow(clippy::module_inception)]
 mod error;
 mod internal;
 mod macros;
 mod response_error;
-
-pub(crate) use self::macros::{downcast_dyn, downcast_get_type_id};
-pub use self::{error::Error, internal::*, response_error::ResponseError};
+mod validation;
+
+pub(crate) use self::{
+    deserialize::{field_path, json_field_path, urlencoded_field_path},
+    macros::{downcast_dyn, downcast_get_type_id},
+    response_error::default_error_response,
+};
+pub use self::{
+    deserialize::{DeserializeError, FieldPath, PathSegment},
+    error::Error,
+    internal::*,
+    response_error::ResponseError,
+    validation::{ValidationErrors, Violation},
+};
 
 /// A convenience [`Result`](std::result::Result) for Actix Web operations.
 ///
@@ -47,6 +65,14 @@ pub enum UrlGenerationError {
     #[display("Not all URL parameters covered")]
     NotEnoughElements,
 
+    /// A URL parameter does not conform to the converter of its typed segment.
+    #[display("URL parameter does not conform to its segment type")]
+    InvalidElement,
+
+    /// Query string serialization error.
+    #[display("{}", _0)]
+    QueryString(FormError),
+
     /// URL parse error.
     #[display("{}", _0)]
     ParseError(UrlParseError),
@@ -82,6 +108,14 @@ pub enum UrlencodedError {
     #[display("Parse error: {}.", _0)]
     Parse(FormDeError),
 
+    /// Parse error of a specific field.
+    #[display("Parse error: {}.", _0)]
+    ParseField(DeserializeError<FormDeError>),
+
+    /// Validation error of the parsed value.
+    #[display("{}", _0)]
+    Validate(ValidationErrors),
+
     /// Encoding error.
     #[display("Encoding error.")]
     Encoding,
@@ -101,10 +135,33 @@ impl ResponseError for UrlencodedError {
             Self::Overflow { .. } => StatusCode::PAYLOAD_TOO_LARGE,
             Self::UnknownLength => StatusCode::LENGTH_REQUIRED,
             Self::ContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Validate(err) => err.status_code(),
             Self::Payload(err) => err.status_code(),
             _ => StatusCode::BAD_REQUEST,
         }
     }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            Self::Parse(_) => {
+                deserialize::error_response(self.status_code(), self.to_string(), None, None)
+            }
+            Self::ParseField(err) => err.error_response(self.status_code(), self.to_string()),
+            Self::Validate(err) => err.error_response(),
+            _ => default_error_response(self),
+        }
+    }
+}
+
+impl UrlencodedError {
+    /// Creates a parse error, annotated with the path of the field that failed if there is one.
+    pub(crate) fn parse(err: FormDeError, path: FieldPath) -> Self {
+        if path.is_empty() {
+            Self::Parse(err)
+        } else {
+            Self::ParseField(DeserializeError::new(err, path))
+        }
+    }
 }
 
 /// A set of errors that can occur during parsing json payloads
@@ -131,6 +188,14 @@ pub enum JsonPayloadError {
     #[display("Json deserialize error: {}", _0)]
     Deserialize(JsonError),
 
+    /// Deserialize error of a specific field
+    #[display("Json deserialize error: {}", _0)]
+    DeserializeField(DeserializeError<JsonError>),
+
+    /// Validation error of the deserialized value
+    #[display("{}", _0)]
+    Validate(ValidationErrors),
+
     /// Serialize error
     #[display("Json serialize error: {}", _0)]
     Serialize(JsonError),
@@ -155,12 +220,188 @@ impl ResponseError for JsonPayloadError {
             } => StatusCode::PAYLOAD_TOO_LARGE,
             Self::Overflow { limit: _ } => StatusCode::PAYLOAD_TOO_LARGE,
             Self::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Validate(err) => err.status_code(),
+            Self::Payload(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            Self::Deserialize(err) => deserialize::error_response(
+                self.status_code(),
+                self.to_string(),
+                None,
+                deserialize::json_location(err),
+            ),
+            Self::DeserializeField(err) => err.error_response(self.status_code(), self.to_string()),
+            Self::Validate(err) => err.error_response(),
+            _ => default_error_response(self),
+        }
+    }
+}
+
+impl JsonPayloadError {
+    /// Creates a deserialize error, annotated with the path of the field that failed if there is
+    /// one.
+    pub(crate) fn deserialize(err: JsonError, path: FieldPath) -> Self {
+        if path.is_empty() {
+            Self::Deserialize(err)
+        } else {
+            let location = deserialize::json_location(&err);
+            Self::DeserializeField(DeserializeError::new(err, path).with_location(location))
+        }
+    }
+}
+
+/// A set of errors that can occur during parsing MessagePack payloads.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum MsgPackPayloadError {
+    /// Payload size is bigger than allowed & content length header set. (default: 2MB)
+    #[display(
+        "MessagePack payload ({} bytes) is larger than allowed (limit: {} bytes).",
+        length,
+        limit
+    )]
+    OverflowKnownLength { length: usize, limit: usize },
+
+    /// Payload size is bigger than allowed but no content length header set. (default: 2MB)
+    #[display("MessagePack payload has exceeded limit ({} bytes).", limit)]
+    Overflow { limit: usize },
+
+    /// Content type error
+    #[display("Content type error")]
+    ContentType,
+
+    /// Deserialize error
+    #[display("MessagePack deserialize error: {}", _0)]
+    Deserialize(rmp_serde::decode::Error),
+
+    /// Serialize error
+    #[display("MessagePack serialize error: {}", _0)]
+    Serialize(rmp_serde::encode::Error),
+
+    /// Payload error
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+#[cfg(feature = "msgpack")]
+impl From<PayloadError> for MsgPackPayloadError {
+    fn from(err: PayloadError) -> Self {
+        Self::Payload(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl ResponseError for MsgPackPayloadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::OverflowKnownLength { .. } | Self::Overflow { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Self::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Payload(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// A set of errors that can occur during parsing CBOR payloads.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum CborPayloadError {
+    /// Payload size is bigger than allowed & content length header set. (default: 2MB)
+    #[display(
+        "CBOR payload ({} bytes) is larger than allowed (limit: {} bytes).",
+        length,
+        limit
+    )]
+    OverflowKnownLength { length: usize, limit: usize },
+
+    /// Payload size is bigger than allowed but no content length header set. (default: 2MB)
+    #[display("CBOR payload has exceeded limit ({} bytes).", limit)]
+    Overflow { limit: usize },
+
+    /// Content type error
+    #[display("Content type error")]
+    ContentType,
+
+    /// Deserialize error
+    #[display("CBOR deserialize error: {}", _0)]
+    Deserialize(ciborium::de::Error<std::io::Error>),
+
+    /// Serialize error
+    #[display("CBOR serialize error: {}", _0)]
+    Serialize(ciborium::ser::Error<std::io::Error>),
+
+    /// Payload error
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+#[cfg(feature = "cbor")]
+impl From<PayloadError> for CborPayloadError {
+    fn from(err: PayloadError) -> Self {
+        Self::Payload(err)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl ResponseError for CborPayloadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::OverflowKnownLength { .. } | Self::Overflow { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Self::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Payload(err) => err.status_code(),
             _ => StatusCode::BAD_REQUEST,
         }
     }
 }
 
+/// A set of errors that can occur during content negotiation of
+/// [`Negotiated`](crate::web::Negotiated) payloads.
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum NegotiationError {
+    /// No codec produces a media type that is acceptable to the client.
+    #[display("No acceptable media type")]
+    NotAcceptable,
+
+    /// No codec supports the content type of the payload.
+    #[display("Unsupported media type")]
+    UnsupportedMediaType,
+
+    /// Deserialize error.
+    #[display("Deserialize error: {}", _0)]
+    Deserialize(#[error(not(source))] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Serialize error.
+    #[display("Serialize error: {}", _0)]
+    Serialize(#[error(not(source))] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Payload error.
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+impl ResponseError for NegotiationError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Payload(err) => err.status_code(),
+            Self::Deserialize(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
 /// A set of errors that can occur during parsing request paths
 #[derive(Debug, Display, Error)]
 #[non_exhaustive]
@@ -168,12 +409,45 @@ pub enum PathError {
     /// Deserialize error
     #[display("Path deserialize error: {}", _0)]
     Deserialize(serde::de::value::Error),
+
+    /// Deserialize error of a specific field
+    #[display("Path deserialize error: {}", _0)]
+    DeserializeField(DeserializeError<serde::de::value::Error>),
+
+    /// Validation error of the deserialized value
+    #[display("{}", _0)]
+    Validate(ValidationErrors),
 }
 
-/// Return `BadRequest` for `PathError`
+impl PathError {
+    /// Creates a deserialize error, annotated with the path of the field that failed if there is
+    /// one.
+    pub(crate) fn deserialize(err: serde::de::value::Error, path: FieldPath) -> Self {
+        if path.is_empty() {
+            Self::Deserialize(err)
+        } else {
+            Self::DeserializeField(DeserializeError::new(err, path))
+        }
+    }
+}
+
+/// Return `BadRequest` for `PathError`, or `UnprocessableEntity` for validation errors
 impl ResponseError for PathError {
     fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
+        match self {
+            Self::Validate(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            Self::Deserialize(_) => {
+                deserialize::error_response(self.status_code(), self.to_string(), None, None)
+            }
+            Self::DeserializeField(err) => err.error_response(self.status_code(), self.to_string()),
+            Self::Validate(err) => err.error_response(),
+        }
     }
 }
 
@@ -184,11 +458,44 @@ pub enum QueryPayloadError {
     /// Query deserialize error.
     #[display("Query deserialize error: {}", _0)]
     Deserialize(serde::de::value::Error),
+
+    /// Query deserialize error of a specific field.
+    #[display("Query deserialize error: {}", _0)]
+    DeserializeField(DeserializeError<serde::de::value::Error>),
+
+    /// Validation error of the deserialized value.
+    #[display("{}", _0)]
+    Validate(ValidationErrors),
+}
+
+impl QueryPayloadError {
+    /// Creates a deserialize error, annotated with the path of the field that failed if there is
+    /// one.
+    pub(crate) fn deserialize(err: serde::de::value::Error, path: FieldPath) -> Self {
+        if path.is_empty() {
+            Self::Deserialize(err)
+        } else {
+            Self::DeserializeField(DeserializeError::new(err, path))
+        }
+    }
 }
 
 impl ResponseError for QueryPayloadError {
     fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
+        match self {
+            Self::Validate(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            Self::Deserialize(_) => {
+                deserialize::error_response(self.status_code(), self.to_string(), None, None)
+            }
+            Self::DeserializeField(err) => err.error_response(self.status_code(), self.to_string()),
+            Self::Validate(err) => err.error_response(),
+        }
     }
 }
 
@@ -222,6 +529,85 @@ impl ResponseError for ReadlinesError {
     }
 }
 
+/// A set of errors that can occur while reading newline-delimited JSON payloads with
+/// [`JsonLines`](crate::web::JsonLines).
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum JsonLinesError {
+    /// Content type error.
+    #[display("Content type error")]
+    ContentType,
+
+    /// A record is bigger than allowed. (default: 256kB)
+    #[display("JSON record has exceeded limit ({} bytes).", limit)]
+    Overflow { limit: usize },
+
+    /// A record could not be deserialized. Records are numbered from 1.
+    #[display("JSON record {} deserialize error: {}", record, source)]
+    Deserialize { record: usize, source: JsonError },
+
+    /// Payload error.
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+impl From<PayloadError> for JsonLinesError {
+    fn from(err: PayloadError) -> Self {
+        Self::Payload(err)
+    }
+}
+
+impl ResponseError for JsonLinesError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Overflow { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Payload(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// Error returned when a request could not be authenticated.
+///
+/// Responds with a `WWW-Authenticate` header carrying the challenge, using the challenge's status
+/// code (usually `401 Unauthorized`).
+#[derive(Debug, Clone, Display)]
+#[display("Authentication failed: {}", challenge)]
+pub struct AuthenticationError<C: Challenge> {
+    challenge: C,
+}
+
+impl<C: Challenge> AuthenticationError<C> {
+    /// Constructs new authentication error with the given challenge.
+    pub fn new(challenge: C) -> Self {
+        AuthenticationError { challenge }
+    }
+
+    /// Returns the challenge.
+    pub fn challenge(&self) -> &C {
+        &self.challenge
+    }
+
+    /// Returns a mutable reference to the challenge.
+    pub fn challenge_mut(&mut self) -> &mut C {
+        &mut self.challenge
+    }
+}
+
+impl<C: Challenge> std::error::Error for AuthenticationError<C> {}
+
+impl<C: Challenge + 'static> ResponseError for AuthenticationError<C> {
+    fn status_code(&self) -> StatusCode {
+        self.challenge.status_code()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .insert_header(WwwAuthenticate(self.challenge.clone()))
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,18 +37,26 @@ pub trait ResponseError: fmt::Debug + fmt::Display {
     /// By default, the generated response uses a 500 Internal Server Error status code, a
     /// `Content-Type` of `text/plain`, and the body is set to `Self`'s `Display` impl.
     fn error_response(&self) -> HttpResponse<BoxBody> {
-        let mut res = HttpResponse::new(self.status_code());
+        default_error_response(self)
+    }
+
+    downcast_get_type_id!();
+}
 
-        let mut buf = BytesMut::new();
-        let _ = write!(helpers::MutWriter(&mut buf), "{}", self);
+/// Creates a plain text response with the error's status code and `Display` impl.
+///
+/// Used by [`ResponseError::error_response`] by default, and by implementations that only
+/// override the response for some of their variants.
+pub(crate) fn default_error_response<E: ResponseError + ?Sized>(err: &E) -> HttpResponse<BoxBody> {
+    let mut res = HttpResponse::new(err.status_code());
 
-        let mime = mime::TEXT_PLAIN_UTF_8.try_into_value().unwrap();
-        res.headers_mut().insert(header::CONTENT_TYPE, mime);
+    let mut buf = BytesMut::new();
+    let _ = write!(helpers::MutWriter(&mut buf), "{}", err);
 
-        res.set_body(BoxBody::new(buf))
-    }
+    let mime = mime::TEXT_PLAIN_UTF_8.try_into_value().unwrap();
+    res.headers_mut().insert(header::CONTENT_TYPE, mime);
 
-    downcast_get_type_id!();
+    res.set_body(BoxBody::new(buf))
 }
 
 downcast_dyn!(ResponseError);
@@ -0,0 +1,265 @@
+//! Validation errors reported by the [`Valid`](crate::web::Valid) extractor.
+
+use std::{borrow::Cow, fmt};
+
+use serde_json::json;
+
+use super::{FieldPath, ResponseError};
+use crate::{
+    body::BoxBody,
+    http::{header, StatusCode},
+    web::Validate,
+    HttpResponse,
+};
+
+/// A single failed validation rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    path: FieldPath,
+    message: Cow<'static, str>,
+    code: Option<Cow<'static, str>>,
+}
+
+impl Violation {
+    /// Constructs new violation of a rule for the value at `path`.
+    pub fn new(path: impl Into<FieldPath>, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Sets a machine-readable code identifying the violated rule, such as `"range"`.
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Returns the path of the invalid value.
+    pub fn path(&self) -> &FieldPath {
+        &self.path
+    }
+
+    /// Returns the human-readable description of the violation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the code identifying the violated rule, if set.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Collection of all violations found while validating a value.
+///
+/// Responds with `422 Unprocessable Entity` and a JSON body listing the violations:
+///
+/// ```json
+/// {
+///   "error": "Validation failed",
+///   "violations": [
+///     { "path": "items[0].price", "message": "must be positive", "code": "range" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    violations: Vec<Violation>,
+}
+
+impl ValidationErrors {
+    /// Constructs an empty collection of violations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a violation of a rule for the value at `path`.
+    pub fn add(
+        &mut self,
+        path: impl Into<FieldPath>,
+        message: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        self.violations.push(Violation::new(path, message));
+        self
+    }
+
+    /// Adds a violation.
+    pub fn add_violation(&mut self, violation: Violation) -> &mut Self {
+        self.violations.push(violation);
+        self
+    }
+
+    /// Adds the violations of a nested value, prefixing their paths with `path`.
+    pub fn nest(&mut self, path: impl Into<FieldPath>, errors: ValidationErrors) -> &mut Self {
+        let path = path.into();
+
+        self.violations
+            .extend(errors.violations.into_iter().map(|violation| Violation {
+                path: path.join(&violation.path),
+                ..violation
+            }));
+
+        self
+    }
+
+    /// Validates each item, adding their violations under `path` followed by the item's index.
+    pub fn nest_each<'a, T>(
+        &mut self,
+        path: impl Into<FieldPath>,
+        items: impl IntoIterator<Item = &'a T>,
+    ) -> &mut Self
+    where
+        T: Validate + ?Sized + 'a,
+    {
+        let path = path.into();
+
+        for (idx, item) in items.into_iter().enumerate() {
+            if let Err(errors) = item.validate() {
+                self.nest(path.clone().index(idx), errors);
+            }
+        }
+
+        self
+    }
+
+    /// Returns true if no violations were added.
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Returns the violations in the order they were added.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Returns `Ok(())` if there are no violations, or `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl From<Violation> for ValidationErrors {
+    fn from(violation: Violation) -> Self {
+        Self {
+            violations: vec![violation],
+        }
+    }
+}
+
+/// Converts the errors of the `validator` crate, using the rule's message if it has one and the
+/// rule's code otherwise.
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for ValidationErrors {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut this = Self::new();
+        this.add_validator_errors(&FieldPath::new(), errors);
+        this
+    }
+}
+
+#[cfg(feature = "validator")]
+impl ValidationErrors {
+    fn add_validator_errors(&mut self, path: &FieldPath, errors: validator::ValidationErrors) {
+        use validator::ValidationErrorsKind;
+
+        // fields are stored in a hash map; sort them for a stable violation order
+        let mut fields = errors.0.into_iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(field, _)| *field);
+
+        for (field, kind) in fields {
+            // `__all__` holds struct-level errors and `_tmp_validator` wraps errors of collections
+            // validated on their own; neither names a field
+            let path = match field {
+                "__all__" | "_tmp_validator" => path.clone(),
+                field => path.clone().field(field),
+            };
+
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    for err in errors {
+                        let message = err.message.unwrap_or_else(|| err.code.clone());
+                        self.add_violation(
+                            Violation::new(path.clone(), message).with_code(err.code),
+                        );
+                    }
+                }
+
+                ValidationErrorsKind::Struct(errors) => self.add_validator_errors(&path, *errors),
+
+                ValidationErrorsKind::List(items) => {
+                    for (idx, errors) in items {
+                        self.add_validator_errors(&path.clone().index(idx), *errors);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Validation failed")?;
+
+        for (idx, violation) in self.violations.iter().enumerate() {
+            f.write_str(if idx == 0 { ": " } else { "; " })?;
+            fmt::Display::fmt(violation, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl ResponseError for ValidationErrors {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        let violations = self
+            .violations
+            .iter()
+            .map(|violation| {
+                let mut obj = json!({
+                    "path": violation.path.to_string(),
+                    "message": violation.message,
+                });
+
+                if let Some(code) = &violation.code {
+                    obj["code"] = code.as_ref().into();
+                }
+
+                obj
+            })
+            .collect::<Vec<_>>();
+
+        let body = json!({
+            "error": "Validation failed",
+            "violations": violations,
+        });
+
+        let mut res = HttpResponse::new(self.status_code());
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        res.set_body(BoxBody::new(body.to_string()))
+    }
+}
@@ -63,6 +63,10 @@ impl Guard for Acceptable {
 
         false
     }
+
+    fn description(&self) -> String {
+        format!("Acceptable({})", self.mime)
+    }
 }
 
 #[cfg(test)]
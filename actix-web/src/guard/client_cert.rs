@@ -0,0 +1,202 @@
+use super::{Guard, GuardContext};
+use crate::dev::{PeerCertificates, SubjectAltName};
+
+/// Creates a guard that matches requests on connections authenticated with a client certificate.
+///
+/// Without any conditions, the guard matches if the client presented a certificate that was
+/// accepted by the TLS acceptor. Conditions on the subject and subject alternative names (SANs) of
+/// the leaf certificate can be added using the builder methods; the guard then matches if *all* of
+/// them hold. Use [`any_of`](ClientCertGuard::any_of) to accept any of several alternatives.
+///
+/// Client certificates are read from the [`PeerCertificates`] connection data, which is set by the
+/// TLS listeners of [`HttpServer`](crate::HttpServer).
+///
+/// # Examples
+/// ```
+/// use actix_web::{guard, web, App, HttpResponse};
+///
+/// App::new()
+///     .service(
+///         web::scope("/admin")
+///             .guard(
+///                 guard::ClientCert()
+///                     .subject("O", "Example, Inc.")
+///                     .any_of([
+///                         guard::ClientCert().common_name("ops-console"),
+///                         guard::ClientCert().uri("spiffe://example.com/ops"),
+///                     ]),
+///             )
+///             .default_service(web::to(HttpResponse::Ok)),
+///     )
+///     .service(
+///         web::resource("/metrics")
+///             .guard(guard::ClientCert().dns_name("prometheus.internal"))
+///             .to(HttpResponse::Ok),
+///     );
+/// ```
+#[allow(non_snake_case)]
+pub fn ClientCert() -> ClientCertGuard {
+    ClientCertGuard {
+        conditions: Vec::new(),
+    }
+}
+
+/// Guard that matches requests on connections authenticated with a client certificate.
+///
+/// Construct a `ClientCertGuard` using [`ClientCert()`].
+#[derive(Debug, Clone)]
+pub struct ClientCertGuard {
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Subject { attr: String, value: String },
+    SubjectAltName(SubjectAltName),
+    AnyOf(Vec<Vec<Condition>>),
+}
+
+impl Condition {
+    fn matches(&self, certs: &PeerCertificates) -> bool {
+        match self {
+            Condition::Subject { attr, value } => certs
+                .subject()
+                .iter()
+                .any(|(name, val)| name.eq_ignore_ascii_case(attr) && val == value),
+
+            Condition::SubjectAltName(expected) => certs
+                .subject_alt_names()
+                .iter()
+                .any(|name| name.matches(expected)),
+
+            Condition::AnyOf(alternatives) => alternatives
+                .iter()
+                .any(|conditions| conditions.iter().all(|cond| cond.matches(certs))),
+        }
+    }
+}
+
+impl ClientCertGuard {
+    /// Matches certificates whose subject has an attribute with the given value.
+    ///
+    /// The attribute is named by its short name, such as `O` or `OU`, or by its dotted OID. Names
+    /// are compared case-insensitively and values are compared exactly.
+    pub fn subject(mut self, attr: impl Into<String>, value: impl Into<String>) -> Self {
+        self.conditions.push(Condition::Subject {
+            attr: attr.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Matches certificates whose subject has the given common name (`CN`).
+    pub fn common_name(self, value: impl Into<String>) -> Self {
+        self.subject("CN", value)
+    }
+
+    /// Matches certificates with the given subject alternative name.
+    ///
+    /// DNS names and email addresses are compared case-insensitively.
+    pub fn subject_alt_name(mut self, name: SubjectAltName) -> Self {
+        self.conditions.push(Condition::SubjectAltName(name));
+        self
+    }
+
+    /// Matches certificates with the given DNS name SAN.
+    pub fn dns_name(self, name: impl Into<String>) -> Self {
+        self.subject_alt_name(SubjectAltName::Dns(name.into()))
+    }
+
+    /// Matches certificates with the given email address SAN.
+    pub fn email(self, email: impl Into<String>) -> Self {
+        self.subject_alt_name(SubjectAltName::Email(email.into()))
+    }
+
+    /// Matches certificates with the given URI SAN, such as a SPIFFE ID.
+    pub fn uri(self, uri: impl Into<String>) -> Self {
+        self.subject_alt_name(SubjectAltName::Uri(uri.into()))
+    }
+
+    /// Matches certificates that satisfy all conditions of any of the given guards.
+    ///
+    /// Guards without conditions match any certificate. If `alternatives` is empty, no certificate
+    /// matches.
+    pub fn any_of(mut self, alternatives: impl IntoIterator<Item = ClientCertGuard>) -> Self {
+        let alternatives = alternatives
+            .into_iter()
+            .map(|guard| guard.conditions)
+            .collect();
+
+        self.conditions.push(Condition::AnyOf(alternatives));
+        self
+    }
+}
+
+impl Guard for ClientCertGuard {
+    fn check(&self, ctx: &GuardContext<'_>) -> bool {
+        let Some(certs) = ctx.conn_data::<PeerCertificates>() else {
+            return false;
+        };
+
+        self.conditions.iter().all(|cond| cond.matches(certs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{peer_certificates::tests::client_cert, test::TestRequest};
+
+    #[test]
+    fn client_cert_match() {
+        let certs = PeerCertificates::from_der([client_cert()]).unwrap();
+
+        let req = TestRequest::default().to_srv_request();
+        assert!(!ClientCert().check(&req.guard_ctx()));
+
+        let req = TestRequest::default().conn_data(certs).to_srv_request();
+        let ctx = req.guard_ctx();
+
+        assert!(ClientCert().check(&ctx));
+        assert!(ClientCert().common_name("client").check(&ctx));
+        assert!(ClientCert().subject("o", "Example, Inc.").check(&ctx));
+        assert!(ClientCert().dns_name("CLIENT.example.com").check(&ctx));
+        assert!(ClientCert().email("ops@example.com").check(&ctx));
+        assert!(ClientCert().uri("spiffe://example.com/client").check(&ctx));
+        assert!(ClientCert()
+            .subject_alt_name(SubjectAltName::Ip("10.0.0.1".parse().unwrap()))
+            .check(&ctx));
+
+        assert!(!ClientCert().common_name("Client").check(&ctx));
+        assert!(!ClientCert().subject("CN", "client.example.com").check(&ctx));
+        assert!(!ClientCert().uri("spiffe://example.com/CLIENT").check(&ctx));
+
+        // all conditions must match
+        assert!(ClientCert()
+            .common_name("client")
+            .dns_name("client.example.com")
+            .check(&ctx));
+        assert!(!ClientCert()
+            .common_name("server")
+            .dns_name("client.example.com")
+            .check(&ctx));
+
+        // unless they are alternatives
+        assert!(ClientCert()
+            .any_of([
+                ClientCert().common_name("server"),
+                ClientCert().dns_name("client.example.com"),
+            ])
+            .check(&ctx));
+        assert!(!ClientCert()
+            .email("ops@example.com")
+            .any_of([
+                ClientCert().common_name("server"),
+                ClientCert()
+                    .common_name("client")
+                    .uri("spiffe://example.com/server"),
+            ])
+            .check(&ctx));
+        assert!(!ClientCert().any_of([]).check(&ctx));
+    }
+}
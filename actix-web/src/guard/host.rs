@@ -1,6 +1,8 @@
 use actix_http::{header, uri::Uri, RequestHead};
+use actix_router::{Path, ResourceDef};
 
 use super::{Guard, GuardContext};
+use crate::{service::ServiceRequest, HttpMessage as _};
 
 /// Creates a guard that matches requests targeting a specific host.
 ///
@@ -10,6 +12,19 @@ use super::{Guard, GuardContext};
 /// - fall-back to matching against the request target's host, if present;
 /// - return false if host cannot be determined;
 ///
+/// # Host Patterns
+/// Hosts containing `{` are matched as patterns, using the same syntax as
+/// [resource definitions](crate::dev::ResourceDef). For example, `{tenant}.example.com` matches
+/// `acme.example.com`. Dynamic segments match up to the next `.` by default, and can have custom
+/// regexes or [converters](crate::dev::Converter), like `{shard:u8}.db.example.com`.
+///
+/// Values captured from the host are added to the request's
+/// [match info](crate::HttpRequest::match_info) when the guarded service is selected, so they can
+/// be extracted with [`web::Path`](crate::web::Path). They are added after the path segments matched
+/// by enclosing scopes and before the path segments of the guarded service, so extracting them by
+/// name is recommended. See also [`Scope::host`](crate::Scope::host), which additionally makes
+/// [`url_for`](crate::HttpRequest::url_for) generate URLs with the host.
+///
 /// # Matching Scheme
 /// Optionally, this guard can match against the host's scheme. Set the scheme for matching using
 /// `Host(host).scheme(protocol)`. If the request's scheme cannot be determined, it will not prevent
@@ -53,12 +68,85 @@ use super::{Guard, GuardContext};
 ///     }));
 /// ```
 ///
+/// Routing tenants by subdomain with a host pattern:
+/// ```
+/// use actix_web::{guard::Host, web, HttpResponse};
+///
+/// web::scope("")
+///     .guard(Host("{tenant}.example.com"))
+///     .route("/", web::get().to(|tenant: web::Path<String>| async move {
+///         HttpResponse::Ok().body(format!("home page of {tenant}"))
+///     }));
+/// ```
+///
 /// [virtual hosting]: https://en.wikipedia.org/wiki/Virtual_hosting
 #[allow(non_snake_case)]
 pub fn Host(host: impl AsRef<str>) -> HostGuard {
+    let host = host.as_ref();
+
     HostGuard {
-        host: host.as_ref().to_string(),
+        host: host.to_string(),
         scheme: None,
+        pattern: host.contains('{').then(|| host_pattern(host)),
+    }
+}
+
+/// Constructs a resource definition matching hosts with the given pattern.
+///
+/// Dynamic segments without a custom regex or converter only match up to the next `.`.
+pub(crate) fn host_pattern(host: &str) -> ResourceDef {
+    let mut pattern = String::with_capacity(host.len() * 2);
+    let mut depth = 0;
+    let mut custom = false;
+
+    for ch in host.chars() {
+        match ch {
+            '{' => {
+                depth += 1;
+                custom = custom && depth > 1;
+            }
+            ':' if depth == 1 => custom = true,
+            '}' if depth == 1 => {
+                depth -= 1;
+
+                if !custom {
+                    pattern.push_str(":[^.]+");
+                }
+
+                custom = false;
+            }
+            '}' => depth -= 1,
+            _ => {}
+        }
+
+        pattern.push(ch);
+    }
+
+    ResourceDef::new(pattern)
+}
+
+/// Dynamic segments captured from the request's host by [`Host`] guards with a pattern.
+///
+/// Guards can not modify the request, so the segments are stored in the request-local data
+/// container until routing selects the guarded service.
+pub(crate) struct HostParams(Vec<(String, String)>);
+
+impl HostParams {
+    /// Adds segments captured by the guards of the service selected by routing to the request's
+    /// match info.
+    pub(crate) fn merge(req: &mut ServiceRequest) {
+        let params = req.extensions_mut().remove::<HostParams>();
+
+        if let Some(HostParams(params)) = params {
+            for (name, value) in params {
+                req.match_info_mut().add_static(name, value);
+            }
+        }
+    }
+
+    /// Discards segments captured by the guards of a service that was not selected by routing.
+    pub(crate) fn discard(req: &ServiceRequest) {
+        req.extensions_mut().remove::<HostParams>();
     }
 }
 
@@ -74,6 +162,7 @@ fn get_host_uri(req: &RequestHead) -> Option<Uri> {
 pub struct HostGuard {
     host: String,
     scheme: Option<String>,
+    pattern: Option<ResourceDef>,
 }
 
 impl HostGuard {
@@ -94,28 +183,55 @@ impl Guard for HostGuard {
             None => return false,
         };
 
-        match req_host_uri.host() {
+        let params = match (req_host_uri.host(), &self.pattern) {
             // fall through to scheme checks
-            Some(uri_host) if self.host == uri_host => {}
+            (Some(uri_host), None) if self.host == uri_host => None,
+
+            (Some(uri_host), Some(pattern)) => {
+                let mut path = Path::new(uri_host.to_owned());
+
+                if !pattern.capture_match_info(&mut path) {
+                    return false;
+                }
+
+                Some(
+                    path.iter()
+                        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                        .collect(),
+                )
+            }
 
             // Either:
-            // - request's host does not match guard's host;
+            // - request's host does not match guard's host or pattern;
             // - It was possible that the parsed URI from request target did not contain a host.
             _ => return false,
-        }
+        };
 
         if let Some(ref scheme) = self.scheme {
             if let Some(ref req_host_uri_scheme) = req_host_uri.scheme_str() {
-                return scheme == req_host_uri_scheme;
+                if scheme != req_host_uri_scheme {
+                    return false;
+                }
             }
 
             // TODO: is this the correct behavior?
             // falls through if scheme cannot be determined
         }
 
+        if let Some(params) = params {
+            ctx.req_data_mut().insert(HostParams(params));
+        }
+
         // all conditions passed
         true
     }
+
+    fn description(&self) -> String {
+        match &self.scheme {
+            Some(scheme) => format!("Host({}://{})", scheme, self.host),
+            None => format!("Host({})", self.host),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -206,4 +322,42 @@ mod tests {
         let host = Host("localhost");
         assert!(!host.check(&req.guard_ctx()));
     }
+
+    #[test]
+    fn host_pattern_segments() {
+        assert!(host_pattern("{tenant}.example.com").is_match("acme.example.com"));
+        assert!(!host_pattern("{tenant}.example.com").is_match("a.b.example.com"));
+        assert!(host_pattern("{sub:.+}.example.com").is_match("a.b.example.com"));
+        assert!(host_pattern("{shard:u8}.db.example.com").is_match("12.db.example.com"));
+        assert!(!host_pattern("{shard:u8}.db.example.com").is_match("256.db.example.com"));
+        assert!(host_pattern("{id:[0-9]{2}}.example.com").is_match("42.example.com"));
+    }
+
+    #[test]
+    fn host_pattern_match() {
+        let req = TestRequest::default()
+            .insert_header((header::HOST, "acme.example.com:8080"))
+            .to_srv_request();
+
+        let host = Host("{tenant}.example.com");
+        assert!(host.check(&req.guard_ctx()));
+
+        let host = Host("{tenant}.example.org");
+        assert!(!host.check(&req.guard_ctx()));
+
+        let host = Host("{tenant}.example.com").scheme("https");
+        assert!(host.check(&req.guard_ctx()));
+
+        let req = TestRequest::default()
+            .insert_header((header::HOST, "https://acme.example.com"))
+            .to_srv_request();
+
+        let host = Host("{tenant}.example.com").scheme("http");
+        assert!(!host.check(&req.guard_ctx()));
+        assert!(req.extensions().get::<HostParams>().is_none());
+
+        let host = Host("{tenant}.example.com").scheme("https");
+        assert!(host.check(&req.guard_ctx()));
+        assert!(req.extensions().get::<HostParams>().is_some());
+    }
 }
@@ -59,8 +59,13 @@ use actix_http::{header, Extensions, Method as HttpMethod, RequestHead};
 use crate::{http::header::Header, service::ServiceRequest, HttpMessage as _};
 
 mod acceptable;
+#[cfg(feature = "__tls")]
+mod client_cert;
 mod host;
 
+#[cfg(feature = "__tls")]
+pub use self::client_cert::{ClientCert, ClientCertGuard};
+pub(crate) use self::host::{host_pattern, HostParams};
 pub use self::{
     acceptable::Acceptable,
     host::{Host, HostGuard},
@@ -116,6 +121,12 @@ impl<'a> GuardContext<'a> {
     pub fn app_data<T: 'static>(&self) -> Option<&T> {
         self.req.app_data()
     }
+
+    /// Counterpart to [HttpRequest::conn_data](crate::HttpRequest::conn_data).
+    #[inline]
+    pub fn conn_data<T: 'static>(&self) -> Option<&T> {
+        self.req.conn_data()
+    }
 }
 
 /// Interface for routing guards.
@@ -124,12 +135,57 @@ impl<'a> GuardContext<'a> {
 pub trait Guard {
     /// Returns true if predicate condition is met for a given request.
     fn check(&self, ctx: &GuardContext<'_>) -> bool;
+
+    /// Returns a short description of the condition checked by this guard.
+    ///
+    /// Used in the [route table](crate::dev::ResourceMap::routes). The default implementation
+    /// returns the guard's type name.
+    fn description(&self) -> String {
+        std::any::type_name::<Self>().to_owned()
+    }
+
+    /// Returns the request methods accepted by this guard, if it rejects all other methods.
+    ///
+    /// Used to list the methods handled by each resource in the
+    /// [route table](crate::dev::ResourceMap::routes). The default implementation returns `None`.
+    fn methods(&self) -> Option<Vec<HttpMethod>> {
+        None
+    }
 }
 
 impl Guard for Rc<dyn Guard> {
     fn check(&self, ctx: &GuardContext<'_>) -> bool {
         (**self).check(ctx)
     }
+
+    fn description(&self) -> String {
+        (**self).description()
+    }
+
+    fn methods(&self) -> Option<Vec<HttpMethod>> {
+        (**self).methods()
+    }
+}
+
+/// Returns the methods accepted by all of `guards`, or `None` if they accept any method.
+pub(crate) fn guard_methods(guards: &[Box<dyn Guard>]) -> Option<Vec<HttpMethod>> {
+    guards
+        .iter()
+        .filter_map(|guard| guard.methods())
+        .reduce(|mut methods, other| {
+            methods.retain(|method| other.contains(method));
+            methods
+        })
+}
+
+/// Formats a list of guard descriptions, for composite guards.
+fn describe_all(name: &str, guards: &[Box<dyn Guard>]) -> String {
+    let descriptions = guards
+        .iter()
+        .map(|guard| guard.description())
+        .collect::<Vec<_>>();
+
+    format!("{}({})", name, descriptions.join(", "))
 }
 
 /// Creates a guard using the given function.
@@ -219,6 +275,25 @@ impl Guard for AnyGuard {
 
         false
     }
+
+    fn description(&self) -> String {
+        describe_all("Any", &self.guards)
+    }
+
+    fn methods(&self) -> Option<Vec<HttpMethod>> {
+        let mut methods = Vec::new();
+
+        // any guard without method restrictions allows every method
+        for guard in &self.guards {
+            for method in guard.methods()? {
+                if !methods.contains(&method) {
+                    methods.push(method);
+                }
+            }
+        }
+
+        Some(methods)
+    }
 }
 
 /// Creates a guard that matches if all added guards match.
@@ -271,6 +346,14 @@ impl Guard for AllGuard {
 
         true
     }
+
+    fn description(&self) -> String {
+        describe_all("All", &self.guards)
+    }
+
+    fn methods(&self) -> Option<Vec<HttpMethod>> {
+        guard_methods(&self.guards)
+    }
 }
 
 /// Wraps a guard and inverts the outcome of its `Guard` implementation.
@@ -291,6 +374,10 @@ impl<G: Guard> Guard for Not<G> {
     fn check(&self, ctx: &GuardContext<'_>) -> bool {
         !self.0.check(ctx)
     }
+
+    fn description(&self) -> String {
+        format!("Not({})", self.0.description())
+    }
 }
 
 /// Creates a guard that matches a specified HTTP method.
@@ -320,6 +407,14 @@ impl Guard for MethodGuard {
 
         ctx.head().method == self.0
     }
+
+    fn description(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn methods(&self) -> Option<Vec<HttpMethod>> {
+        Some(vec![self.0.clone()])
+    }
 }
 
 macro_rules! method_guard {
@@ -382,6 +477,14 @@ impl Guard for HeaderGuard {
 
         false
     }
+
+    fn description(&self) -> String {
+        format!(
+            "Header({}: {})",
+            self.0,
+            self.1.to_str().unwrap_or("<binary>")
+        )
+    }
 }
 
 #[cfg(test)]
@@ -410,6 +513,32 @@ mod tests {
         assert!(!hdr.check(&req.guard_ctx()));
     }
 
+    #[test]
+    fn descriptions_and_methods() {
+        assert_eq!(Get().description(), "GET");
+        assert_eq!(Get().methods(), Some(vec![Method::GET]));
+        assert_eq!(
+            Header("x-guarded", "secret").description(),
+            "Header(x-guarded: secret)"
+        );
+        assert_eq!(Header("x-guarded", "secret").methods(), None);
+
+        let any = Any(Get()).or(Post()).or(Get());
+        assert_eq!(any.description(), "Any(GET, POST, GET)");
+        assert_eq!(any.methods(), Some(vec![Method::GET, Method::POST]));
+        assert_eq!(Any(Get()).or(Header("x", "y")).methods(), None);
+
+        let all = All(Any(Get()).or(Post())).and(Post()).and(Header("x", "y"));
+        assert_eq!(all.description(), "All(Any(GET, POST), POST, Header(x: y))");
+        assert_eq!(all.methods(), Some(vec![Method::POST]));
+
+        assert_eq!(Not(Get()).description(), "Not(GET)");
+        assert_eq!(Not(Get()).methods(), None);
+
+        let guard = fn_guard(|_| true);
+        assert!(guard.description().contains("FnGuard"));
+    }
+
     #[test]
     fn method_guards() {
         let get_req = TestRequest::get().to_srv_request();
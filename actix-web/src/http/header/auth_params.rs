@@ -0,0 +1,178 @@
+//! Parsing and formatting utilities shared by the `Authorization` and `WWW-Authenticate` headers.
+//!
+//! See [RFC 7235 §2.1](https://datatracker.ietf.org/doc/html/rfc7235#section-2.1).
+
+use std::fmt;
+
+/// Returns the remainder of a credentials or challenge value if it starts with the given
+/// (case-insensitive) auth scheme.
+pub(crate) fn strip_scheme<'a>(value: &'a str, scheme: &str) -> Option<&'a str> {
+    let (name, rest) = value.split_once(' ').unwrap_or((value, ""));
+
+    if name.eq_ignore_ascii_case(scheme) {
+        Some(rest.trim_start_matches(' '))
+    } else {
+        None
+    }
+}
+
+/// Returns true if `c` is a `tchar`, as defined in
+/// [RFC 7230 §3.2.6](https://datatracker.ietf.org/doc/html/rfc7230#section-3.2.6).
+fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+/// Returns true if `s` is a valid `token68`.
+pub(crate) fn is_token68(s: &str) -> bool {
+    let trimmed = s.trim_end_matches('=');
+
+    !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._~+/".contains(c))
+}
+
+/// Parses a comma-separated list of `auth-param`s.
+///
+/// Parameter names are lowercased and quoted values are unescaped. Parsing stops at the start of
+/// a following challenge (a token that is not followed by `=`). Returns `None` if the list is
+/// malformed.
+pub(crate) fn parse_params(s: &str) -> Option<Vec<(String, String)>> {
+    let mut params = Vec::new();
+    let mut rest = s;
+
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+
+        if rest.is_empty() {
+            break;
+        }
+
+        let name_len = rest.find(|c| !is_tchar(c)).unwrap_or(rest.len());
+        if name_len == 0 {
+            return None;
+        }
+
+        let (name, after_name) = rest.split_at(name_len);
+
+        let Some(after_eq) = after_name.trim_start().strip_prefix('=') else {
+            // start of the next challenge
+            break;
+        };
+        let after_eq = after_eq.trim_start();
+
+        let (value, remaining) = match after_eq.strip_prefix('"') {
+            Some(quoted) => parse_quoted(quoted)?,
+            None => {
+                let len = after_eq.find(|c| !is_tchar(c)).unwrap_or(after_eq.len());
+                let (value, remaining) = after_eq.split_at(len);
+                (value.to_owned(), remaining)
+            }
+        };
+
+        params.push((name.to_ascii_lowercase(), value));
+
+        rest = remaining.trim_start();
+        if !rest.is_empty() && !rest.starts_with(',') {
+            return None;
+        }
+    }
+
+    Some(params)
+}
+
+/// Parses the remainder of a `quoted-string` after its opening quote, returning the unescaped
+/// value and the input following the closing quote.
+fn parse_quoted(s: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &s[idx + 1..])),
+            '\\' => value.push(chars.next()?.1),
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+/// Writes `value` as a `quoted-string`.
+pub(crate) fn write_quoted(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_str("\"")?;
+
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+
+        write!(f, "{}", c)?;
+    }
+
+    f.write_str("\"")
+}
+
+/// Writes a list of `auth-param`s with quoted values, preceded by the auth scheme.
+pub(crate) fn write_challenge<'a>(
+    f: &mut fmt::Formatter<'_>,
+    scheme: &str,
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> fmt::Result {
+    f.write_str(scheme)?;
+
+    for (idx, (name, value)) in params.into_iter().enumerate() {
+        f.write_str(if idx == 0 { " " } else { ", " })?;
+        f.write_str(name)?;
+        f.write_str("=")?;
+        write_quoted(f, value)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheme() {
+        assert_eq!(strip_scheme("Basic abc", "basic"), Some("abc"));
+        assert_eq!(strip_scheme("bearer   abc", "Bearer"), Some("abc"));
+        assert_eq!(strip_scheme("Bearer", "Bearer"), Some(""));
+        assert_eq!(strip_scheme("Bearerabc", "Bearer"), None);
+        assert_eq!(strip_scheme("Basic abc", "Bearer"), None);
+    }
+
+    #[test]
+    fn token68() {
+        assert!(is_token68("abc"));
+        assert!(is_token68("a-b.c_d~e+f/g=="));
+        assert!(!is_token68(""));
+        assert!(!is_token68("=="));
+        assert!(!is_token68("a b"));
+        assert!(!is_token68("a=b"));
+    }
+
+    #[test]
+    fn params() {
+        assert_eq!(
+            parse_params(r#"realm="a \"b\" c", Scope=x , , charset=UTF-8"#).unwrap(),
+            vec![
+                ("realm".to_owned(), r#"a "b" c"#.to_owned()),
+                ("scope".to_owned(), "x".to_owned()),
+                ("charset".to_owned(), "UTF-8".to_owned()),
+            ]
+        );
+
+        assert_eq!(
+            parse_params(r#"realm="a", Basic realm="b""#).unwrap(),
+            vec![("realm".to_owned(), "a".to_owned())]
+        );
+
+        assert_eq!(parse_params("").unwrap(), vec![]);
+        assert!(parse_params(r#"realm="a"#).is_none());
+        assert!(parse_params(r#"realm="a" b"#).is_none());
+        assert!(parse_params("=a").is_none());
+    }
+}
@@ -0,0 +1,322 @@
+use std::{borrow::Cow, fmt};
+
+use actix_http::{error::ParseError, header, HttpMessage};
+use base64::prelude::*;
+
+use super::{
+    auth_params::{is_token68, strip_scheme},
+    Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue,
+};
+
+/// `Authorization` header, defined in
+/// [RFC 7235 §4.2](https://datatracker.ietf.org/doc/html/rfc7235#section-4.2).
+///
+/// The "Authorization" header field allows a user agent to authenticate itself with an origin
+/// server. Its value consists of credentials containing the authentication information of the
+/// user agent for the realm of the resource being requested.
+///
+/// The credentials are typed by their auth [`Scheme`], such as [`Basic`] or [`Bearer`].
+///
+/// # ABNF
+/// ```plain
+/// Authorization = credentials
+/// credentials = auth-scheme [ 1*SP ( token68 / #auth-param ) ]
+/// ```
+///
+/// # Example Values
+/// * `Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==`
+/// * `Bearer mF_9.B5f-4.1JqM`
+///
+/// # Examples
+/// ```
+/// use actix_web::{get, web, http::header::{Authorization, Basic}};
+///
+/// #[get("/")]
+/// async fn index(auth: web::Header<Authorization<Basic>>) -> String {
+///     format!("Hello, {}!", auth.as_ref().user_id())
+/// }
+/// ```
+///
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::{Authorization, Bearer};
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(Authorization(Bearer::new("mF_9.B5f-4.1JqM")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization<S: Scheme>(pub S);
+
+impl<S: Scheme> Authorization<S> {
+    /// Unwraps the credentials.
+    pub fn into_scheme(self) -> S {
+        self.0
+    }
+}
+
+impl<S: Scheme> From<S> for Authorization<S> {
+    fn from(scheme: S) -> Authorization<S> {
+        Authorization(scheme)
+    }
+}
+
+impl<S: Scheme> AsRef<S> for Authorization<S> {
+    fn as_ref(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: Scheme> fmt::Display for Authorization<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<S: Scheme> Header for Authorization<S> {
+    fn name() -> HeaderName {
+        header::AUTHORIZATION
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        let value = msg.headers().get(Self::name()).ok_or(ParseError::Header)?;
+        S::parse(value).map(Authorization)
+    }
+}
+
+impl<S: Scheme> TryIntoHeaderValue for Authorization<S> {
+    type Error = S::Error;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        self.0.try_into_value()
+    }
+}
+
+/// An authentication scheme, used for the credentials of an [`Authorization`] header.
+pub trait Scheme: TryIntoHeaderValue + fmt::Debug + fmt::Display + Clone + Send + Sync {
+    /// Parses credentials of this scheme from a header value.
+    fn parse(value: &HeaderValue) -> Result<Self, ParseError>;
+}
+
+/// Credentials for the `Basic` authentication scheme, defined in
+/// [RFC 7617](https://datatracker.ietf.org/doc/html/rfc7617).
+///
+/// User IDs and passwords are encoded as UTF-8. The password is not included in the `Debug`
+/// representation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Basic {
+    user_id: Cow<'static, str>,
+    password: Cow<'static, str>,
+}
+
+impl Basic {
+    /// Constructs new `Basic` credentials.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::header::Basic;
+    ///
+    /// let credentials = Basic::new("Aladdin", "open sesame");
+    /// assert_eq!(credentials.to_string(), "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+    /// ```
+    pub fn new<U, P>(user_id: U, password: P) -> Basic
+    where
+        U: Into<Cow<'static, str>>,
+        P: Into<Cow<'static, str>>,
+    {
+        Basic {
+            user_id: user_id.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Returns the user ID.
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
+
+impl Scheme for Basic {
+    fn parse(value: &HeaderValue) -> Result<Self, ParseError> {
+        let value = value.to_str().map_err(|_| ParseError::Header)?;
+        let encoded = strip_scheme(value, "Basic").ok_or(ParseError::Header)?;
+
+        let decoded = BASE64_STANDARD
+            .decode(encoded.trim_end())
+            .map_err(|_| ParseError::Header)?;
+        let decoded =
+            String::from_utf8(decoded).map_err(|err| ParseError::Utf8(err.utf8_error()))?;
+
+        let (user_id, password) = decoded.split_once(':').ok_or(ParseError::Header)?;
+
+        Ok(Basic::new(user_id.to_owned(), password.to_owned()))
+    }
+}
+
+impl fmt::Debug for Basic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Basic")
+            .field("user_id", &self.user_id)
+            .field("password", &"******")
+            .finish()
+    }
+}
+
+impl fmt::Display for Basic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let credentials = format!("{}:{}", self.user_id, self.password);
+        write!(f, "Basic {}", BASE64_STANDARD.encode(credentials))
+    }
+}
+
+impl TryIntoHeaderValue for Basic {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+/// Credentials for the `Bearer` authentication scheme, defined in
+/// [RFC 6750](https://datatracker.ietf.org/doc/html/rfc6750).
+///
+/// The token is not included in the `Debug` representation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bearer {
+    token: Cow<'static, str>,
+}
+
+impl Bearer {
+    /// Constructs new `Bearer` credentials.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::header::Bearer;
+    ///
+    /// let credentials = Bearer::new("mF_9.B5f-4.1JqM");
+    /// assert_eq!(credentials.to_string(), "Bearer mF_9.B5f-4.1JqM");
+    /// ```
+    pub fn new<T>(token: T) -> Bearer
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Bearer {
+            token: token.into(),
+        }
+    }
+
+    /// Returns the bearer token.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Scheme for Bearer {
+    fn parse(value: &HeaderValue) -> Result<Self, ParseError> {
+        let value = value.to_str().map_err(|_| ParseError::Header)?;
+        let token = strip_scheme(value, "Bearer")
+            .map(str::trim_end)
+            .ok_or(ParseError::Header)?;
+
+        if !is_token68(token) {
+            return Err(ParseError::Header);
+        }
+
+        Ok(Bearer::new(token.to_owned()))
+    }
+}
+
+impl fmt::Debug for Bearer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bearer").field("token", &"******").finish()
+    }
+}
+
+impl fmt::Display for Bearer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bearer {}", self.token)
+    }
+}
+
+impl TryIntoHeaderValue for Bearer {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    #[test]
+    fn basic_parse() {
+        let value = HeaderValue::from_static("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        let basic = Basic::parse(&value).unwrap();
+        assert_eq!(basic.user_id(), "Aladdin");
+        assert_eq!(basic.password(), "open sesame");
+
+        // scheme is case-insensitive; passwords may contain colons or be empty
+        let value = HeaderValue::from_static("basic dXNlcjpwYTpzcw==");
+        assert_eq!(Basic::parse(&value).unwrap(), Basic::new("user", "pa:ss"));
+        let value = HeaderValue::from_static("Basic dXNlcjo=");
+        assert_eq!(Basic::parse(&value).unwrap(), Basic::new("user", ""));
+
+        // no colon
+        let value = HeaderValue::from_static("Basic dXNlcg==");
+        assert!(Basic::parse(&value).is_err());
+
+        let value = HeaderValue::from_static("Basic");
+        assert!(Basic::parse(&value).is_err());
+        let value = HeaderValue::from_static("Basic !!!");
+        assert!(Basic::parse(&value).is_err());
+        let value = HeaderValue::from_static("Bearer QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert!(Basic::parse(&value).is_err());
+    }
+
+    #[test]
+    fn basic_format() {
+        let basic = Basic::new("Aladdin", "open sesame");
+        assert_eq!(
+            basic.clone().try_into_value().unwrap(),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+        assert!(!format!("{:?}", basic).contains("open sesame"));
+    }
+
+    #[test]
+    fn bearer_parse() {
+        let value = HeaderValue::from_static("Bearer mF_9.B5f-4.1JqM");
+        assert_eq!(Bearer::parse(&value).unwrap().token(), "mF_9.B5f-4.1JqM");
+
+        let value = HeaderValue::from_static("bearer abc==");
+        assert_eq!(Bearer::parse(&value).unwrap().token(), "abc==");
+
+        let value = HeaderValue::from_static("Bearer");
+        assert!(Bearer::parse(&value).is_err());
+        let value = HeaderValue::from_static("Bearer a b");
+        assert!(Bearer::parse(&value).is_err());
+        let value = HeaderValue::from_static("Basic abc");
+        assert!(Bearer::parse(&value).is_err());
+    }
+
+    #[test]
+    fn authorization_header() {
+        let req = TestRequest::default()
+            .insert_header(Authorization(Bearer::new("abc")))
+            .to_http_request();
+
+        let auth = Authorization::<Bearer>::parse(&req).unwrap();
+        assert_eq!(auth.as_ref().token(), "abc");
+        assert!(Authorization::<Basic>::parse(&req).is_err());
+
+        let req = TestRequest::default().to_http_request();
+        assert!(Authorization::<Bearer>::parse(&req).is_err());
+    }
+}
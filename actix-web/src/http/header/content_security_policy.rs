@@ -0,0 +1,385 @@
+use std::fmt;
+
+use actix_http::{error::ParseError, header, HttpMessage};
+
+use super::{Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue};
+
+/// `Content-Security-Policy` header, defined in
+/// [CSP Level 3 §3.1](https://www.w3.org/TR/CSP3/#csp-header).
+///
+/// The "Content-Security-Policy" header field restricts which resources a document may load or
+/// execute. A policy is a list of directives, each naming a fetch or navigation capability followed
+/// by its allowed sources.
+///
+/// Per-request nonces are supported by adding the [`NONCE`](Self::NONCE) placeholder as a source
+/// and calling [`with_nonce`](Self::with_nonce) for each response. The
+/// [`SecureHeaders`](crate::middleware::SecureHeaders) middleware does this automatically.
+///
+/// When parsing, only the first policy of the header is used.
+///
+/// # ABNF
+/// ```plain
+/// Content-Security-Policy = 1#serialized-policy
+/// serialized-policy = serialized-directive *( OWS ";" [ OWS serialized-directive ] )
+/// serialized-directive = directive-name [ RWS directive-value ]
+/// ```
+///
+/// # Example Values
+/// * `default-src 'self'`
+/// * `script-src 'self' 'nonce-rAnd0m'; object-src 'none'; report-to csp-endpoint`
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::ContentSecurityPolicy;
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(
+///     ContentSecurityPolicy::new()
+///         .directive("default-src", ["'self'"])
+///         .directive("img-src", ["'self'", "https://img.example.com"]),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentSecurityPolicy {
+    directives: Vec<(String, Vec<String>)>,
+}
+
+impl ContentSecurityPolicy {
+    /// Source placeholder that is replaced by a `'nonce-…'` source in [`with_nonce`].
+    ///
+    /// [`with_nonce`]: Self::with_nonce
+    pub const NONCE: &'static str = "'nonce'";
+
+    /// Constructs an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a directive, replacing any existing directive with the same name.
+    ///
+    /// Directive names are case-insensitive and are stored in lowercase.
+    ///
+    /// # Panics
+    /// Panics if the name is not a valid directive name or if a source is empty or contains
+    /// whitespace, `;` or `,`.
+    pub fn directive<I>(mut self, name: &str, sources: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        assert!(
+            is_directive_name(name),
+            "Invalid CSP directive name: {name:?}"
+        );
+
+        let sources = sources
+            .into_iter()
+            .map(Into::into)
+            .inspect(|source: &String| {
+                assert!(is_source(source), "Invalid CSP source: {source:?}");
+            })
+            .collect::<Vec<_>>();
+
+        let name = name.to_ascii_lowercase();
+
+        match self.directives.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, existing)) => *existing = sources,
+            None => self.directives.push((name, sources)),
+        }
+
+        self
+    }
+
+    /// Returns the sources of the named directive, if present.
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.directives
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, sources)| sources.as_slice())
+    }
+
+    /// Returns an iterator over directive names and their sources, in order.
+    pub fn directives(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.directives
+            .iter()
+            .map(|(name, sources)| (name.as_str(), sources.as_slice()))
+    }
+
+    /// Returns true if the policy contains the [`NONCE`](Self::NONCE) placeholder.
+    pub fn has_nonce(&self) -> bool {
+        self.directives
+            .iter()
+            .any(|(_, sources)| sources.iter().any(|source| source == Self::NONCE))
+    }
+
+    /// Returns a copy of the policy with each [`NONCE`](Self::NONCE) placeholder replaced by
+    /// `'nonce-{nonce}'`.
+    ///
+    /// # Panics
+    /// Panics if the nonce is not a valid base64 value.
+    pub fn with_nonce(&self, nonce: &str) -> Self {
+        assert!(
+            !nonce.is_empty()
+                && nonce
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"+/-_=".contains(&b)),
+            "Invalid CSP nonce: {nonce:?}"
+        );
+
+        let source = format!("'nonce-{nonce}'");
+
+        let directives = self
+            .directives
+            .iter()
+            .map(|(name, sources)| {
+                let sources = sources
+                    .iter()
+                    .map(|s| {
+                        if s == Self::NONCE {
+                            source.clone()
+                        } else {
+                            s.clone()
+                        }
+                    })
+                    .collect();
+
+                (name.clone(), sources)
+            })
+            .collect();
+
+        ContentSecurityPolicy { directives }
+    }
+
+    fn parse_value(msg: &impl HttpMessage, name: HeaderName) -> Result<Self, ParseError> {
+        let value = msg
+            .headers()
+            .get(name)
+            .ok_or(ParseError::Header)?
+            .to_str()
+            .map_err(|_| ParseError::Header)?;
+
+        // only the first of several comma-separated policies is used
+        let policy = value.split(',').next().unwrap_or_default();
+
+        let mut directives: Vec<(String, Vec<String>)> = Vec::new();
+
+        for directive in policy.split(';') {
+            let mut tokens = directive.split_ascii_whitespace();
+
+            let Some(name) = tokens.next() else {
+                continue;
+            };
+
+            if !is_directive_name(name) {
+                return Err(ParseError::Header);
+            }
+
+            let name = name.to_ascii_lowercase();
+
+            // duplicate directives are ignored (CSP Level 3 §2.2.1)
+            if directives.iter().any(|(n, _)| *n == name) {
+                continue;
+            }
+
+            directives.push((name, tokens.map(str::to_owned).collect()));
+        }
+
+        if directives.is_empty() {
+            return Err(ParseError::Header);
+        }
+
+        Ok(ContentSecurityPolicy { directives })
+    }
+}
+
+impl fmt::Display for ContentSecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (name, sources)) in self.directives.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("; ")?;
+            }
+
+            f.write_str(name)?;
+
+            for source in sources {
+                write!(f, " {source}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Header for ContentSecurityPolicy {
+    fn name() -> HeaderName {
+        header::CONTENT_SECURITY_POLICY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        Self::parse_value(msg, Self::name())
+    }
+}
+
+impl TryIntoHeaderValue for ContentSecurityPolicy {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+/// `Content-Security-Policy-Report-Only` header, defined in
+/// [CSP Level 3 §3.2](https://www.w3.org/TR/CSP3/#cspro-header).
+///
+/// The policy is monitored rather than enforced: violations are reported to the policy's
+/// `report-to` (or deprecated `report-uri`) endpoint but not blocked. This allows a new policy to
+/// be trialed alongside an enforced one.
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::{ContentSecurityPolicy, ContentSecurityPolicyReportOnly};
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(ContentSecurityPolicyReportOnly(
+///     ContentSecurityPolicy::new()
+///         .directive("default-src", ["'self'"])
+///         .directive("report-uri", ["/csp-reports"]),
+/// ));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentSecurityPolicyReportOnly(pub ContentSecurityPolicy);
+
+impl ContentSecurityPolicyReportOnly {
+    /// Unwraps the policy.
+    pub fn into_policy(self) -> ContentSecurityPolicy {
+        self.0
+    }
+}
+
+impl From<ContentSecurityPolicy> for ContentSecurityPolicyReportOnly {
+    fn from(policy: ContentSecurityPolicy) -> Self {
+        ContentSecurityPolicyReportOnly(policy)
+    }
+}
+
+impl AsRef<ContentSecurityPolicy> for ContentSecurityPolicyReportOnly {
+    fn as_ref(&self) -> &ContentSecurityPolicy {
+        &self.0
+    }
+}
+
+impl fmt::Display for ContentSecurityPolicyReportOnly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Header for ContentSecurityPolicyReportOnly {
+    fn name() -> HeaderName {
+        header::CONTENT_SECURITY_POLICY_REPORT_ONLY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        ContentSecurityPolicy::parse_value(msg, Self::name()).map(ContentSecurityPolicyReportOnly)
+    }
+}
+
+impl TryIntoHeaderValue for ContentSecurityPolicyReportOnly {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        self.0.try_into_value()
+    }
+}
+
+fn is_directive_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+fn is_source(source: &str) -> bool {
+    !source.is_empty()
+        && source
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && b != b';' && b != b',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    #[test]
+    fn format() {
+        let csp = ContentSecurityPolicy::new()
+            .directive("default-src", ["'self'"])
+            .directive("Script-Src", ["'self'", ContentSecurityPolicy::NONCE])
+            .directive("upgrade-insecure-requests", [""; 0]);
+
+        assert_eq!(
+            csp.to_string(),
+            "default-src 'self'; script-src 'self' 'nonce'; upgrade-insecure-requests"
+        );
+
+        let csp = csp.directive("default-src", ["'none'"]);
+        assert_eq!(csp.get("default-src").unwrap(), ["'none'"]);
+        assert_eq!(csp.directives().count(), 3);
+    }
+
+    #[test]
+    fn nonce() {
+        let csp = ContentSecurityPolicy::new().directive("img-src", ["*"]);
+        assert!(!csp.has_nonce());
+
+        let csp = csp.directive(
+            "script-src",
+            [ContentSecurityPolicy::NONCE, "'strict-dynamic'"],
+        );
+        assert!(csp.has_nonce());
+
+        let with_nonce = csp.with_nonce("rAnd0m+/w==");
+        assert!(!with_nonce.has_nonce());
+        assert_eq!(
+            with_nonce.to_string(),
+            "img-src *; script-src 'nonce-rAnd0m+/w==' 'strict-dynamic'"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_source() {
+        let _ = ContentSecurityPolicy::new().directive("default-src", ["'self'; script-src *"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_nonce() {
+        let _ = ContentSecurityPolicy::new().with_nonce("' *");
+    }
+
+    #[test]
+    fn parse() {
+        let req = TestRequest::default()
+            .insert_header((
+                header::CONTENT_SECURITY_POLICY,
+                "DEFAULT-SRC 'self' ;; img-src * ; default-src 'none', script-src 'none'",
+            ))
+            .insert_header((
+                header::CONTENT_SECURITY_POLICY_REPORT_ONLY,
+                "default-src https:; report-uri /csp",
+            ))
+            .to_http_request();
+
+        let csp = ContentSecurityPolicy::parse(&req).unwrap();
+        assert_eq!(csp.to_string(), "default-src 'self'; img-src *");
+
+        let csp = ContentSecurityPolicyReportOnly::parse(&req).unwrap();
+        assert_eq!(csp.0.get("report-uri").unwrap(), ["/csp"]);
+
+        let req = TestRequest::default()
+            .insert_header((header::CONTENT_SECURITY_POLICY, " ; "))
+            .to_http_request();
+        assert!(ContentSecurityPolicy::parse(&req).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{fmt, str::FromStr};
+
+use actix_http::{error::ParseError, header, HttpMessage};
+
+use super::{Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue};
+
+/// `Cross-Origin-Opener-Policy` header, defined in the
+/// [HTML Standard](https://html.spec.whatwg.org/multipage/browsers.html#cross-origin-opener-policies).
+///
+/// The "Cross-Origin-Opener-Policy" (COOP) header field controls whether a top-level document
+/// shares its browsing context group with cross-origin documents it opens or is opened by.
+///
+/// # Example Values
+/// * `same-origin`
+/// * `same-origin-allow-popups`
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::CrossOriginOpenerPolicy;
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(CrossOriginOpenerPolicy::SameOrigin);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossOriginOpenerPolicy {
+    /// `unsafe-none`; the default behavior of sharing the browsing context group.
+    UnsafeNone,
+
+    /// `same-origin-allow-popups`; isolates the document but keeps references to popups it opens
+    /// that do not set a COOP header.
+    SameOriginAllowPopups,
+
+    /// `same-origin`; isolates the document from all cross-origin documents.
+    SameOrigin,
+}
+
+impl CrossOriginOpenerPolicy {
+    /// Returns the policy's token.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossOriginOpenerPolicy::UnsafeNone => "unsafe-none",
+            CrossOriginOpenerPolicy::SameOriginAllowPopups => "same-origin-allow-popups",
+            CrossOriginOpenerPolicy::SameOrigin => "same-origin",
+        }
+    }
+}
+
+impl FromStr for CrossOriginOpenerPolicy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match policy_token(s) {
+            "unsafe-none" => Ok(CrossOriginOpenerPolicy::UnsafeNone),
+            "same-origin-allow-popups" => Ok(CrossOriginOpenerPolicy::SameOriginAllowPopups),
+            "same-origin" => Ok(CrossOriginOpenerPolicy::SameOrigin),
+            _ => Err(ParseError::Header),
+        }
+    }
+}
+
+impl fmt::Display for CrossOriginOpenerPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Header for CrossOriginOpenerPolicy {
+    fn name() -> HeaderName {
+        header::CROSS_ORIGIN_OPENER_POLICY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        parse_policy(msg, Self::name())
+    }
+}
+
+impl TryIntoHeaderValue for CrossOriginOpenerPolicy {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        Ok(HeaderValue::from_static(self.as_str()))
+    }
+}
+
+/// `Cross-Origin-Embedder-Policy` header, defined in the
+/// [HTML Standard](https://html.spec.whatwg.org/multipage/browsers.html#coep).
+///
+/// The "Cross-Origin-Embedder-Policy" (COEP) header field controls whether a document may load
+/// cross-origin resources that do not explicitly grant it permission. Together with
+/// [`CrossOriginOpenerPolicy::SameOrigin`], it enables cross-origin isolation.
+///
+/// # Example Values
+/// * `require-corp`
+/// * `credentialless`
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::CrossOriginEmbedderPolicy;
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(CrossOriginEmbedderPolicy::RequireCorp);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossOriginEmbedderPolicy {
+    /// `unsafe-none`; cross-origin resources may be loaded without restriction.
+    UnsafeNone,
+
+    /// `require-corp`; cross-origin resources must be allowed by CORS or
+    /// `Cross-Origin-Resource-Policy`.
+    RequireCorp,
+
+    /// `credentialless`; cross-origin `no-cors` requests are sent without credentials.
+    Credentialless,
+}
+
+impl CrossOriginEmbedderPolicy {
+    /// Returns the policy's token.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossOriginEmbedderPolicy::UnsafeNone => "unsafe-none",
+            CrossOriginEmbedderPolicy::RequireCorp => "require-corp",
+            CrossOriginEmbedderPolicy::Credentialless => "credentialless",
+        }
+    }
+}
+
+impl FromStr for CrossOriginEmbedderPolicy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match policy_token(s) {
+            "unsafe-none" => Ok(CrossOriginEmbedderPolicy::UnsafeNone),
+            "require-corp" => Ok(CrossOriginEmbedderPolicy::RequireCorp),
+            "credentialless" => Ok(CrossOriginEmbedderPolicy::Credentialless),
+            _ => Err(ParseError::Header),
+        }
+    }
+}
+
+impl fmt::Display for CrossOriginEmbedderPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Header for CrossOriginEmbedderPolicy {
+    fn name() -> HeaderName {
+        header::CROSS_ORIGIN_EMBEDDER_POLICY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        parse_policy(msg, Self::name())
+    }
+}
+
+impl TryIntoHeaderValue for CrossOriginEmbedderPolicy {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        Ok(HeaderValue::from_static(self.as_str()))
+    }
+}
+
+/// Returns the token of a structured header item, ignoring its parameters (e.g., `report-to`).
+fn policy_token(value: &str) -> &str {
+    value.split(';').next().unwrap_or_default().trim()
+}
+
+fn parse_policy<M: HttpMessage, T: FromStr<Err = ParseError>>(
+    msg: &M,
+    name: HeaderName,
+) -> Result<T, ParseError> {
+    msg.headers()
+        .get(name)
+        .ok_or(ParseError::Header)?
+        .to_str()
+        .map_err(|_| ParseError::Header)?
+        .parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    #[test]
+    fn parse() {
+        let req = TestRequest::default()
+            .insert_header((
+                header::CROSS_ORIGIN_OPENER_POLICY,
+                r#"same-origin; report-to="coop""#,
+            ))
+            .insert_header((header::CROSS_ORIGIN_EMBEDDER_POLICY, "credentialless"))
+            .to_http_request();
+
+        assert_eq!(
+            CrossOriginOpenerPolicy::parse(&req).unwrap(),
+            CrossOriginOpenerPolicy::SameOrigin
+        );
+        assert_eq!(
+            CrossOriginEmbedderPolicy::parse(&req).unwrap(),
+            CrossOriginEmbedderPolicy::Credentialless
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::CROSS_ORIGIN_OPENER_POLICY, "same-site"))
+            .to_http_request();
+        assert!(CrossOriginOpenerPolicy::parse(&req).is_err());
+        assert!(CrossOriginEmbedderPolicy::parse(&req).is_err());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            CrossOriginOpenerPolicy::SameOriginAllowPopups
+                .try_into_value()
+                .unwrap(),
+            "same-origin-allow-popups"
+        );
+        assert_eq!(
+            CrossOriginEmbedderPolicy::RequireCorp.to_string(),
+            "require-corp"
+        );
+    }
+}
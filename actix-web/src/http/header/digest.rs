@@ -0,0 +1,598 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use actix_http::{error::ParseError, Method};
+use md5::Md5;
+use sha2::{Digest as _, Sha256};
+
+use super::{
+    auth_params::{parse_params, strip_scheme, write_quoted},
+    Challenge, HeaderValue, InvalidHeaderValue, Scheme, TryIntoHeaderValue,
+};
+
+/// Hash algorithms of the `Digest` authentication scheme, defined in
+/// [RFC 7616 §3.2](https://datatracker.ietf.org/doc/html/rfc7616#section-3.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    /// `MD5`, for compatibility with RFC 2617 clients.
+    Md5,
+
+    /// `SHA-256`.
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    /// Returns the algorithm name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Computes H(A1), the hash of `username:realm:password`, as a lowercase hex digest.
+    ///
+    /// Servers can store this value instead of the plaintext password and check credentials with
+    /// [`Digest::verify_ha1`]. H(A1) depends on both the realm and the algorithm, so a stored
+    /// value is only valid for the realm it was computed with, and one value must be stored for
+    /// each algorithm offered.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::header::DigestAlgorithm;
+    ///
+    /// let ha1 = DigestAlgorithm::Md5.ha1("Mufasa", "testrealm@host.com", "Circle Of Life");
+    /// assert_eq!(ha1, "939e7578ed9e3c518a452acee763bce9");
+    /// ```
+    pub fn ha1(self, username: &str, realm: &str, password: &str) -> String {
+        self.hash(&format!("{username}:{realm}:{password}"))
+    }
+
+    /// Hashes `data`, returning the lowercase hex digest.
+    pub(crate) fn hash(self, data: &str) -> String {
+        match self {
+            DigestAlgorithm::Md5 => hex(&Md5::digest(data)),
+            DigestAlgorithm::Sha256 => hex(&Sha256::digest(data)),
+        }
+    }
+}
+
+impl FromStr for DigestAlgorithm {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("MD5") {
+            Ok(DigestAlgorithm::Md5)
+        } else if s.eq_ignore_ascii_case("SHA-256") {
+            Ok(DigestAlgorithm::Sha256)
+        } else {
+            Err(ParseError::Header)
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Credentials for the `Digest` authentication scheme, defined in
+/// [RFC 7616 §3.4](https://datatracker.ietf.org/doc/html/rfc7616#section-3.4).
+///
+/// Only the `auth` quality of protection is supported. Credentials are usually checked using the
+/// [`DigestAuth`](crate::web::DigestAuth) extractor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    username: Cow<'static, str>,
+    realm: Cow<'static, str>,
+    uri: Cow<'static, str>,
+    algorithm: DigestAlgorithm,
+    nonce: Cow<'static, str>,
+    nc: u32,
+    cnonce: Cow<'static, str>,
+    response: String,
+    opaque: Option<Cow<'static, str>>,
+}
+
+impl Digest {
+    /// Computes credentials answering `challenge` for a request with the given method and URI.
+    ///
+    /// The first algorithm offered by the challenge is used. `nc` counts the requests that have
+    /// used the challenge's nonce, starting at 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::{header::{Digest, DigestChallenge}, Method};
+    ///
+    /// let challenge = DigestChallenge::new("api", "dcd98b7102dd2f0e8b11d0f600bfb0c093");
+    /// let credentials = Digest::compute(
+    ///     &challenge, "Mufasa", "Circle of Life", &Method::GET, "/dir/index.html", "0a4f113b", 1,
+    /// );
+    /// assert_eq!(credentials.username(), "Mufasa");
+    /// ```
+    pub fn compute(
+        challenge: &DigestChallenge,
+        username: impl Into<Cow<'static, str>>,
+        password: &str,
+        method: &Method,
+        uri: impl Into<Cow<'static, str>>,
+        cnonce: impl Into<Cow<'static, str>>,
+        nc: u32,
+    ) -> Digest {
+        let mut credentials = Digest {
+            username: username.into(),
+            realm: challenge.realm.clone(),
+            uri: uri.into(),
+            algorithm: challenge.algorithms[0],
+            nonce: challenge.nonce.clone(),
+            nc,
+            cnonce: cnonce.into(),
+            response: String::new(),
+            opaque: challenge.opaque.clone(),
+        };
+
+        let ha1 = credentials.ha1(password);
+        credentials.response = credentials.expected_response(&ha1, method);
+        credentials
+    }
+
+    /// Returns the user name.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the realm.
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Returns the effective request URI.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the hash algorithm.
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the server nonce.
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Returns the nonce count.
+    pub fn nc(&self) -> u32 {
+        self.nc
+    }
+
+    /// Returns the client nonce.
+    pub fn cnonce(&self) -> &str {
+        &self.cnonce
+    }
+
+    /// Returns the opaque value, if the challenge contained one.
+    pub fn opaque(&self) -> Option<&str> {
+        self.opaque.as_deref()
+    }
+
+    /// Returns true if the credentials' response matches the given password and request method.
+    ///
+    /// The comparison takes the same time regardless of where the responses differ.
+    pub fn verify(&self, password: &str, method: &Method) -> bool {
+        self.verify_ha1(&self.ha1(password), method)
+    }
+
+    /// Returns true if the credentials' response matches the given H(A1) and request method.
+    ///
+    /// H(A1) is computed by [`DigestAlgorithm::ha1`] and depends on both the realm and the
+    /// algorithm, so it must have been computed with the credentials' [`realm`](Self::realm) and
+    /// [`algorithm`](Self::algorithm). The comparison takes the same time regardless of where the
+    /// responses differ.
+    pub fn verify_ha1(&self, ha1: &str, method: &Method) -> bool {
+        let expected = self.expected_response(ha1, method);
+
+        expected.len() == self.response.len()
+            && expected
+                .bytes()
+                .zip(self.response.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+
+    fn ha1(&self, password: &str) -> String {
+        self.algorithm.ha1(&self.username, &self.realm, password)
+    }
+
+    fn expected_response(&self, ha1: &str, method: &Method) -> String {
+        let hash = |data: String| self.algorithm.hash(&data);
+
+        let ha2 = hash(format!("{}:{}", method, self.uri));
+
+        hash(format!(
+            "{}:{}:{:08x}:{}:auth:{}",
+            ha1, self.nonce, self.nc, self.cnonce, ha2
+        ))
+    }
+}
+
+impl Scheme for Digest {
+    fn parse(value: &HeaderValue) -> Result<Self, ParseError> {
+        let value = value.to_str().map_err(|_| ParseError::Header)?;
+        let params = strip_scheme(value, "Digest")
+            .and_then(parse_params)
+            .ok_or(ParseError::Header)?;
+
+        let mut username = None;
+        let mut realm = None;
+        let mut uri = None;
+        let mut algorithm = DigestAlgorithm::Md5;
+        let mut nonce = None;
+        let mut nc = None;
+        let mut cnonce = None;
+        let mut qop = None;
+        let mut response = None;
+        let mut opaque = None;
+
+        for (name, value) in params {
+            match name.as_str() {
+                "username" => username = Some(value),
+                "realm" => realm = Some(value),
+                "uri" => uri = Some(value),
+                "algorithm" => algorithm = value.parse()?,
+                "nonce" => nonce = Some(value),
+                "nc" => nc = u32::from_str_radix(&value, 16).ok(),
+                "cnonce" => cnonce = Some(value),
+                "qop" => qop = Some(value),
+                "response" => response = Some(value.to_ascii_lowercase()),
+                "opaque" => opaque = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        if qop.as_deref() != Some("auth") {
+            return Err(ParseError::Header);
+        }
+
+        Ok(Digest {
+            username: username.ok_or(ParseError::Header)?.into(),
+            realm: realm.ok_or(ParseError::Header)?.into(),
+            uri: uri.ok_or(ParseError::Header)?.into(),
+            algorithm,
+            nonce: nonce.ok_or(ParseError::Header)?.into(),
+            nc: nc.ok_or(ParseError::Header)?,
+            cnonce: cnonce.ok_or(ParseError::Header)?.into(),
+            response: response.ok_or(ParseError::Header)?,
+            opaque,
+        })
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Digest username=")?;
+        write_quoted(f, &self.username)?;
+        f.write_str(", realm=")?;
+        write_quoted(f, &self.realm)?;
+        f.write_str(", uri=")?;
+        write_quoted(f, &self.uri)?;
+        write!(f, ", algorithm={}, nonce=", self.algorithm)?;
+        write_quoted(f, &self.nonce)?;
+        write!(f, ", nc={:08x}, cnonce=", self.nc)?;
+        write_quoted(f, &self.cnonce)?;
+        write!(f, ", qop=auth, response=\"{}\"", self.response)?;
+
+        if let Some(opaque) = &self.opaque {
+            f.write_str(", opaque=")?;
+            write_quoted(f, opaque)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryIntoHeaderValue for Digest {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+/// Challenge for the `Digest` authentication scheme, defined in
+/// [RFC 7616 §3.3](https://datatracker.ietf.org/doc/html/rfc7616#section-3.3).
+///
+/// Challenges always request the `auth` quality of protection. When several algorithms are
+/// offered, one challenge is written for each of them, in order of preference.
+///
+/// Challenges are usually created by [`DigestAuthConfig`](crate::web::DigestAuthConfig), which
+/// generates and checks nonces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestChallenge {
+    realm: Cow<'static, str>,
+    nonce: Cow<'static, str>,
+    opaque: Option<Cow<'static, str>>,
+    algorithms: Vec<DigestAlgorithm>,
+    stale: bool,
+}
+
+impl DigestChallenge {
+    /// Constructs new `Digest` challenge offering the `SHA-256` algorithm.
+    pub fn new<R, N>(realm: R, nonce: N) -> DigestChallenge
+    where
+        R: Into<Cow<'static, str>>,
+        N: Into<Cow<'static, str>>,
+    {
+        DigestChallenge {
+            realm: realm.into(),
+            nonce: nonce.into(),
+            opaque: None,
+            algorithms: vec![DigestAlgorithm::Sha256],
+            stale: false,
+        }
+    }
+
+    /// Sets the offered algorithms, in order of preference.
+    ///
+    /// # Panics
+    /// Panics if `algorithms` is empty.
+    pub fn algorithms(mut self, algorithms: impl IntoIterator<Item = DigestAlgorithm>) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        assert!(
+            !self.algorithms.is_empty(),
+            "Digest challenges must offer an algorithm"
+        );
+        self
+    }
+
+    /// Sets an opaque value that clients must return unchanged.
+    pub fn opaque<T: Into<Cow<'static, str>>>(mut self, opaque: T) -> Self {
+        self.opaque = Some(opaque.into());
+        self
+    }
+
+    /// Marks the challenge as answering credentials whose nonce has expired.
+    ///
+    /// Clients may then retry with the new nonce without prompting for a password.
+    pub fn stale(mut self, stale: bool) -> Self {
+        self.stale = stale;
+        self
+    }
+
+    /// Returns the realm.
+    pub fn get_realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Returns the server nonce.
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Returns the opaque value, if set.
+    pub fn get_opaque(&self) -> Option<&str> {
+        self.opaque.as_deref()
+    }
+
+    /// Returns the offered algorithms.
+    pub fn get_algorithms(&self) -> &[DigestAlgorithm] {
+        &self.algorithms
+    }
+
+    /// Returns true if the challenge answers credentials with an expired nonce.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
+impl Challenge for DigestChallenge {
+    fn parse(value: &str) -> Result<Self, ParseError> {
+        let params = strip_scheme(value, "Digest")
+            .and_then(parse_params)
+            .ok_or(ParseError::Header)?;
+
+        let mut realm = None;
+        let mut nonce = None;
+        let mut challenge_opaque = None;
+        let mut algorithm = DigestAlgorithm::Md5;
+        let mut stale = false;
+
+        for (name, value) in params {
+            match name.as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => challenge_opaque = Some(value),
+                "algorithm" => algorithm = value.parse()?,
+                "stale" => stale = value.eq_ignore_ascii_case("true"),
+                _ => {}
+            }
+        }
+
+        let mut challenge = DigestChallenge::new(
+            realm.ok_or(ParseError::Header)?,
+            nonce.ok_or(ParseError::Header)?,
+        )
+        .algorithms([algorithm])
+        .stale(stale);
+
+        if let Some(opaque) = challenge_opaque {
+            challenge = challenge.opaque(opaque);
+        }
+
+        Ok(challenge)
+    }
+}
+
+impl fmt::Display for DigestChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, algorithm) in self.algorithms.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+
+            f.write_str("Digest realm=")?;
+            write_quoted(f, &self.realm)?;
+            write!(f, ", qop=\"auth\", algorithm={}, nonce=", algorithm)?;
+            write_quoted(f, &self.nonce)?;
+
+            if let Some(opaque) = &self.opaque {
+                f.write_str(", opaque=")?;
+                write_quoted(f, opaque)?;
+            }
+
+            if self.stale {
+                f.write_str(", stale=true")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryIntoHeaderValue for DigestChallenge {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_hash() {
+        assert_eq!(
+            DigestAlgorithm::Md5.hash("abc"),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(
+            DigestAlgorithm::Sha256.hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            "sha-256".parse::<DigestAlgorithm>().unwrap(),
+            DigestAlgorithm::Sha256
+        );
+        assert!("SHA-512-256".parse::<DigestAlgorithm>().is_err());
+    }
+
+    // examples from RFC 7616 §3.9.1
+    const RFC_NONCE: &str = "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v";
+    const RFC_OPAQUE: &str = "FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS";
+    const RFC_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn rfc_challenge(algorithm: DigestAlgorithm) -> DigestChallenge {
+        DigestChallenge::new("http-auth@example.org", RFC_NONCE)
+            .algorithms([algorithm])
+            .opaque(RFC_OPAQUE)
+    }
+
+    #[test]
+    fn rfc_responses() {
+        let md5 = Digest::compute(
+            &rfc_challenge(DigestAlgorithm::Md5),
+            "Mufasa",
+            "Circle of Life",
+            &Method::GET,
+            "/dir/index.html",
+            RFC_CNONCE,
+            1,
+        );
+        assert_eq!(md5.response, "8ca523f5e9506fed4657c9700eebdbec");
+
+        let sha256 = Digest::compute(
+            &rfc_challenge(DigestAlgorithm::Sha256),
+            "Mufasa",
+            "Circle of Life",
+            &Method::GET,
+            "/dir/index.html",
+            RFC_CNONCE,
+            1,
+        );
+        assert_eq!(
+            sha256.response,
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+
+        assert!(sha256.verify("Circle of Life", &Method::GET));
+        assert!(!sha256.verify("Circle of Life", &Method::POST));
+        assert!(!sha256.verify("circle of life", &Method::GET));
+    }
+
+    #[test]
+    fn verify_ha1() {
+        let credentials = Digest::compute(
+            &rfc_challenge(DigestAlgorithm::Sha256),
+            "Mufasa",
+            "Circle of Life",
+            &Method::GET,
+            "/dir/index.html",
+            RFC_CNONCE,
+            1,
+        );
+
+        let ha1 = DigestAlgorithm::Sha256.ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+        assert!(credentials.verify_ha1(&ha1, &Method::GET));
+        assert!(!credentials.verify_ha1(&ha1, &Method::POST));
+
+        // H(A1) depends on the realm and the algorithm
+        let other_realm = DigestAlgorithm::Sha256.ha1("Mufasa", "api", "Circle of Life");
+        assert!(!credentials.verify_ha1(&other_realm, &Method::GET));
+        let other_algorithm =
+            DigestAlgorithm::Md5.ha1("Mufasa", "http-auth@example.org", "Circle of Life");
+        assert!(!credentials.verify_ha1(&other_algorithm, &Method::GET));
+    }
+
+    #[test]
+    fn credentials_round_trip() {
+        let credentials = Digest::compute(
+            &rfc_challenge(DigestAlgorithm::Sha256),
+            "Mufasa",
+            "Circle of Life",
+            &Method::GET,
+            "/dir/index.html",
+            RFC_CNONCE,
+            1,
+        );
+
+        let value = credentials.clone().try_into_value().unwrap();
+        assert!(value
+            .to_str()
+            .unwrap()
+            .starts_with(r#"Digest username="Mufasa", realm="http-auth@example.org""#));
+        assert_eq!(Digest::parse(&value).unwrap(), credentials);
+
+        // qop is required
+        let value = HeaderValue::from_static(
+            r#"Digest username="a", realm="b", uri="/", nonce="c", nc=00000001, cnonce="d", response="e""#,
+        );
+        assert!(Digest::parse(&value).is_err());
+    }
+
+    #[test]
+    fn challenge_round_trip() {
+        let challenge = DigestChallenge::new("api", "abc")
+            .algorithms([DigestAlgorithm::Sha256, DigestAlgorithm::Md5])
+            .stale(true);
+
+        let value = challenge.to_string();
+        assert_eq!(
+            value,
+            r#"Digest realm="api", qop="auth", algorithm=SHA-256, nonce="abc", stale=true, Digest realm="api", qop="auth", algorithm=MD5, nonce="abc", stale=true"#
+        );
+
+        let parsed = DigestChallenge::parse(&value).unwrap();
+        assert_eq!(parsed.get_algorithms(), [DigestAlgorithm::Sha256]);
+        assert_eq!(parsed.get_nonce(), "abc");
+        assert!(parsed.is_stale());
+
+        let parsed = DigestChallenge::parse(r#"Digest realm="api", nonce="abc""#).unwrap();
+        assert_eq!(parsed.get_algorithms(), [DigestAlgorithm::Md5]);
+    }
+}
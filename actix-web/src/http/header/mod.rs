@@ -21,13 +21,19 @@ mod accept_charset;
 mod accept_encoding;
 mod accept_language;
 mod allow;
+mod auth_params;
+mod authorization;
 mod cache_control;
 mod content_disposition;
 mod content_language;
 mod content_length;
 mod content_range;
+mod content_security_policy;
 mod content_type;
+mod cross_origin;
 mod date;
+#[cfg(feature = "digest-auth")]
+mod digest;
 mod encoding;
 mod entity;
 mod etag;
@@ -39,9 +45,14 @@ mod if_range;
 mod if_unmodified_since;
 mod last_modified;
 mod macros;
+mod permissions_policy;
 mod preference;
 mod range;
+mod strict_transport_security;
+mod www_authenticate;
 
+#[cfg(feature = "digest-auth")]
+pub use self::digest::{Digest, DigestAlgorithm, DigestChallenge};
 #[cfg(test)]
 pub(crate) use self::macros::common_header_test;
 pub(crate) use self::macros::{common_header, common_header_test_module};
@@ -51,12 +62,15 @@ pub use self::{
     accept_encoding::AcceptEncoding,
     accept_language::AcceptLanguage,
     allow::Allow,
+    authorization::{Authorization, Basic, Bearer, Scheme},
     cache_control::{CacheControl, CacheDirective},
     content_disposition::{ContentDisposition, DispositionParam, DispositionType},
     content_language::ContentLanguage,
     content_length::ContentLength,
     content_range::{ContentRange, ContentRangeSpec},
+    content_security_policy::{ContentSecurityPolicy, ContentSecurityPolicyReportOnly},
     content_type::ContentType,
+    cross_origin::{CrossOriginEmbedderPolicy, CrossOriginOpenerPolicy},
     date::Date,
     encoding::Encoding,
     entity::EntityTag,
@@ -68,8 +82,11 @@ pub use self::{
     if_range::IfRange,
     if_unmodified_since::IfUnmodifiedSince,
     last_modified::LastModified,
+    permissions_policy::PermissionsPolicy,
     preference::Preference,
     range::{ByteRangeSpec, Range},
+    strict_transport_security::StrictTransportSecurity,
+    www_authenticate::{BasicChallenge, BearerChallenge, BearerError, Challenge, WwwAuthenticate},
 };
 
 /// Format writer ([`fmt::Write`]) for a [`BytesMut`].
@@ -0,0 +1,281 @@
+use std::fmt;
+
+use actix_http::{error::ParseError, header, HttpMessage};
+
+use super::{Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue};
+
+/// `Permissions-Policy` header, defined in
+/// [Permissions Policy §5.2](https://www.w3.org/TR/permissions-policy/#permissions-policy-http-header-field).
+///
+/// The "Permissions-Policy" header field controls which origins may use browser features, such as
+/// the camera or geolocation, in the document and its embedded frames.
+///
+/// Each feature has an allowlist of origins. An allowlist item is either `*` (all origins), `self`
+/// (the document's origin) or a serialized origin such as `https://example.com`. An empty
+/// allowlist disables the feature entirely.
+///
+/// # ABNF
+/// ```plain
+/// Permissions-Policy = sf-dictionary
+/// ```
+///
+/// # Example Values
+/// * `camera=(), microphone=()`
+/// * `geolocation=(self "https://maps.example.com"), fullscreen=*`
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::PermissionsPolicy;
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(
+///     PermissionsPolicy::new()
+///         .deny("camera")
+///         .allow_self("fullscreen")
+///         .feature("geolocation", ["self", "https://maps.example.com"]),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionsPolicy {
+    features: Vec<(String, Vec<String>)>,
+}
+
+impl PermissionsPolicy {
+    /// Constructs an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the allowlist of a feature, replacing any existing allowlist for the feature.
+    ///
+    /// # Panics
+    /// Panics if the feature name is not a lowercase token or if an allowlist item is empty or
+    /// contains whitespace, `"` or `\`.
+    pub fn feature<I>(mut self, name: &str, allowlist: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        assert!(
+            is_feature_name(name),
+            "Invalid Permissions-Policy feature name: {name:?}"
+        );
+
+        let allowlist = allowlist
+            .into_iter()
+            .map(Into::into)
+            .inspect(|item: &String| {
+                assert!(
+                    is_allowlist_item(item),
+                    "Invalid Permissions-Policy allowlist item: {item:?}"
+                );
+            })
+            .collect::<Vec<_>>();
+
+        match self.features.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = allowlist,
+            None => self.features.push((name.to_owned(), allowlist)),
+        }
+
+        self
+    }
+
+    /// Disables a feature for all origins.
+    pub fn deny(self, name: &str) -> Self {
+        self.feature(name, [""; 0])
+    }
+
+    /// Allows a feature for the document's own origin only.
+    pub fn allow_self(self, name: &str) -> Self {
+        self.feature(name, ["self"])
+    }
+
+    /// Allows a feature for all origins.
+    pub fn allow_all(self, name: &str) -> Self {
+        self.feature(name, ["*"])
+    }
+
+    /// Returns the allowlist of the named feature, if present.
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.features
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, allowlist)| allowlist.as_slice())
+    }
+}
+
+impl fmt::Display for PermissionsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (name, allowlist)) in self.features.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{name}=")?;
+
+            if let [item] = allowlist.as_slice() {
+                if item == "*" {
+                    f.write_str("*")?;
+                    continue;
+                }
+            }
+
+            f.write_str("(")?;
+
+            for (idx, item) in allowlist.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(" ")?;
+                }
+
+                match item.as_str() {
+                    "self" | "*" => f.write_str(item)?,
+                    origin => write!(f, "\"{origin}\"")?,
+                }
+            }
+
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Header for PermissionsPolicy {
+    fn name() -> HeaderName {
+        header::PERMISSIONS_POLICY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        let mut policy = PermissionsPolicy::new();
+
+        // dictionary members may be split across several header lines
+        for value in msg.headers().get_all(Self::name()) {
+            let value = value.to_str().map_err(|_| ParseError::Header)?;
+
+            for member in value.split(',').map(str::trim) {
+                if member.is_empty() {
+                    continue;
+                }
+
+                let (name, value) = member.split_once('=').ok_or(ParseError::Header)?;
+
+                if !is_feature_name(name) {
+                    return Err(ParseError::Header);
+                }
+
+                // parameters are allowed by the structured field syntax but have no meaning here
+                let value = value.split(';').next().unwrap_or_default();
+
+                let items = match value.strip_prefix('(') {
+                    Some(list) => list
+                        .strip_suffix(')')
+                        .ok_or(ParseError::Header)?
+                        .split_ascii_whitespace()
+                        .collect(),
+                    None => vec![value],
+                };
+
+                let allowlist = items
+                    .into_iter()
+                    .map(|item| match item {
+                        "self" | "*" => Ok(item.to_owned()),
+                        _ => item
+                            .strip_prefix('"')
+                            .and_then(|item| item.strip_suffix('"'))
+                            .filter(|origin| is_allowlist_item(origin))
+                            .map(str::to_owned)
+                            .ok_or(ParseError::Header),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // later members replace earlier ones, as in structured field dictionaries
+                match policy.features.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, existing)) => *existing = allowlist,
+                    None => policy.features.push((name.to_owned(), allowlist)),
+                }
+            }
+        }
+
+        if policy.features.is_empty() {
+            return Err(ParseError::Header);
+        }
+
+        Ok(policy)
+    }
+}
+
+impl TryIntoHeaderValue for PermissionsPolicy {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+fn is_feature_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.*".contains(&b))
+}
+
+fn is_allowlist_item(item: &str) -> bool {
+    !item.is_empty()
+        && item
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && b != b'"' && b != b'\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    #[test]
+    fn format() {
+        let policy = PermissionsPolicy::new()
+            .deny("camera")
+            .allow_self("fullscreen")
+            .allow_all("autoplay")
+            .feature("geolocation", ["self", "https://a.com"]);
+
+        assert_eq!(
+            policy.to_string(),
+            r#"camera=(), fullscreen=(self), autoplay=*, geolocation=(self "https://a.com")"#
+        );
+
+        let policy = policy.allow_self("camera");
+        assert_eq!(policy.get("camera").unwrap(), ["self"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_item() {
+        let _ = PermissionsPolicy::new().feature("camera", [r#"self), mic=("#]);
+    }
+
+    #[test]
+    fn parse() {
+        let req = TestRequest::default()
+            .append_header((
+                header::PERMISSIONS_POLICY,
+                r#"camera=(), geolocation=(self "https://maps.example.com");report-to=x"#,
+            ))
+            .append_header((header::PERMISSIONS_POLICY, "fullscreen=*, camera=self"))
+            .to_http_request();
+
+        let policy = PermissionsPolicy::parse(&req).unwrap();
+        assert_eq!(policy.get("camera").unwrap(), ["self"]);
+        assert_eq!(
+            policy.get("geolocation").unwrap(),
+            ["self", "https://maps.example.com"]
+        );
+        assert_eq!(policy.get("fullscreen").unwrap(), ["*"]);
+
+        let req = TestRequest::default()
+            .insert_header((header::PERMISSIONS_POLICY, "camera=(https://a.com)"))
+            .to_http_request();
+        assert!(PermissionsPolicy::parse(&req).is_err());
+    }
+}
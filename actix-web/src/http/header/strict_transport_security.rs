@@ -0,0 +1,216 @@
+use std::{fmt, time::Duration};
+
+use actix_http::{error::ParseError, header, HttpMessage};
+
+use super::{Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue};
+
+/// `Strict-Transport-Security` header, defined in
+/// [RFC 6797 §6.1](https://datatracker.ietf.org/doc/html/rfc6797#section-6.1).
+///
+/// The "Strict-Transport-Security" (HSTS) header field instructs user agents to only access the
+/// host over HTTPS for the given duration. It is ignored by user agents when received over an
+/// insecure connection.
+///
+/// The non-standard `preload` directive signals consent to being included in browsers' built-in
+/// HSTS lists. Preload lists typically also require `includeSubDomains` and a max age of at least
+/// one year.
+///
+/// # ABNF
+/// ```plain
+/// Strict-Transport-Security = [ directive ] *( ";" [ directive ] )
+/// directive = directive-name [ "=" directive-value ]
+/// ```
+///
+/// # Example Values
+/// * `max-age=31536000`
+/// * `max-age=63072000; includeSubDomains; preload`
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::StrictTransportSecurity;
+///
+/// let mut builder = HttpResponse::Ok();
+/// builder.insert_header(
+///     StrictTransportSecurity::new(Duration::from_secs(63_072_000))
+///         .include_subdomains()
+///         .preload(),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrictTransportSecurity {
+    max_age: Duration,
+    include_subdomains: bool,
+    preload: bool,
+}
+
+impl StrictTransportSecurity {
+    /// Constructs a policy that lasts for `max_age`, rounded down to whole seconds.
+    ///
+    /// A zero max age instructs user agents to forget the host's HSTS policy.
+    pub fn new(max_age: Duration) -> Self {
+        StrictTransportSecurity {
+            max_age: Duration::from_secs(max_age.as_secs()),
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+
+    /// Applies the policy to all subdomains of the host (`includeSubDomains`).
+    pub fn include_subdomains(mut self) -> Self {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// Adds the `preload` directive.
+    pub fn preload(mut self) -> Self {
+        self.preload = true;
+        self
+    }
+
+    /// Returns the duration of the policy.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Returns true if the policy applies to subdomains.
+    pub fn includes_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Returns true if the `preload` directive is present.
+    pub fn is_preload(&self) -> bool {
+        self.preload
+    }
+}
+
+impl fmt::Display for StrictTransportSecurity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "max-age={}", self.max_age.as_secs())?;
+
+        if self.include_subdomains {
+            f.write_str("; includeSubDomains")?;
+        }
+
+        if self.preload {
+            f.write_str("; preload")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Header for StrictTransportSecurity {
+    fn name() -> HeaderName {
+        header::STRICT_TRANSPORT_SECURITY
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        let value = msg
+            .headers()
+            .get(Self::name())
+            .ok_or(ParseError::Header)?
+            .to_str()
+            .map_err(|_| ParseError::Header)?;
+
+        let mut max_age = None;
+        let mut include_subdomains = false;
+        let mut preload = false;
+
+        for directive in value.split(';').map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+
+            // directives must not appear more than once (RFC 6797 §6.1)
+            if name.eq_ignore_ascii_case("max-age") {
+                let secs = value
+                    .and_then(|value| value.parse().ok())
+                    .ok_or(ParseError::Header)?;
+
+                if max_age.replace(Duration::from_secs(secs)).is_some() {
+                    return Err(ParseError::Header);
+                }
+            } else if name.eq_ignore_ascii_case("includeSubDomains") {
+                if value.is_some() || include_subdomains {
+                    return Err(ParseError::Header);
+                }
+
+                include_subdomains = true;
+            } else if name.eq_ignore_ascii_case("preload") {
+                if value.is_some() || preload {
+                    return Err(ParseError::Header);
+                }
+
+                preload = true;
+            }
+
+            // unknown directives are ignored
+        }
+
+        Ok(StrictTransportSecurity {
+            max_age: max_age.ok_or(ParseError::Header)?,
+            include_subdomains,
+            preload,
+        })
+    }
+}
+
+impl TryIntoHeaderValue for StrictTransportSecurity {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    fn parse(value: &str) -> Result<StrictTransportSecurity, ParseError> {
+        let req = TestRequest::default()
+            .insert_header((header::STRICT_TRANSPORT_SECURITY, value))
+            .to_http_request();
+
+        StrictTransportSecurity::parse(&req)
+    }
+
+    #[test]
+    fn format() {
+        let hsts = StrictTransportSecurity::new(Duration::from_millis(31_536_000_500));
+        assert_eq!(hsts.to_string(), "max-age=31536000");
+
+        let hsts = hsts.include_subdomains().preload();
+        assert_eq!(
+            hsts.to_string(),
+            "max-age=31536000; includeSubDomains; preload"
+        );
+    }
+
+    #[test]
+    fn parse_directives() {
+        let hsts = parse("max-age=63072000; includeSubDomains; preload").unwrap();
+        assert_eq!(hsts.max_age(), Duration::from_secs(63_072_000));
+        assert!(hsts.includes_subdomains());
+        assert!(hsts.is_preload());
+
+        let hsts = parse(r#"includesubdomains ; MAX-AGE="300"; unknown=1"#).unwrap();
+        assert_eq!(hsts.max_age(), Duration::from_secs(300));
+        assert!(hsts.includes_subdomains());
+        assert!(!hsts.is_preload());
+
+        assert!(parse("includeSubDomains").is_err());
+        assert!(parse("max-age=abc").is_err());
+        assert!(parse("max-age=1; max-age=2").is_err());
+        assert!(parse("max-age=1; preload=yes").is_err());
+    }
+}
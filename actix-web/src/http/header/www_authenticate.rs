@@ -0,0 +1,435 @@
+use std::{borrow::Cow, fmt, iter, str::FromStr};
+
+use actix_http::{error::ParseError, header, HttpMessage, StatusCode};
+
+use super::{
+    auth_params::{parse_params, strip_scheme, write_challenge},
+    Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue,
+};
+
+/// `WWW-Authenticate` header, defined in
+/// [RFC 7235 §4.1](https://datatracker.ietf.org/doc/html/rfc7235#section-4.1).
+///
+/// The "WWW-Authenticate" header field indicates the authentication scheme(s) and parameters
+/// applicable to the target resource. It must be included in `401 Unauthorized` responses.
+///
+/// The challenge is typed by its auth scheme, such as [`BasicChallenge`] or [`BearerChallenge`].
+/// When parsing, the first challenge of the requested scheme is used.
+///
+/// # ABNF
+/// ```plain
+/// WWW-Authenticate = 1#challenge
+/// challenge = auth-scheme [ 1*SP ( token68 / #auth-param ) ]
+/// ```
+///
+/// # Example Values
+/// * `Basic realm="admin", charset="UTF-8"`
+/// * `Bearer realm="api", error="invalid_token"`
+///
+/// # Examples
+/// ```
+/// use actix_web::HttpResponse;
+/// use actix_web::http::header::{BasicChallenge, WwwAuthenticate};
+///
+/// let mut builder = HttpResponse::Unauthorized();
+/// builder.insert_header(WwwAuthenticate(BasicChallenge::new("admin")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WwwAuthenticate<C: Challenge>(pub C);
+
+impl<C: Challenge> WwwAuthenticate<C> {
+    /// Unwraps the challenge.
+    pub fn into_challenge(self) -> C {
+        self.0
+    }
+}
+
+impl<C: Challenge> From<C> for WwwAuthenticate<C> {
+    fn from(challenge: C) -> WwwAuthenticate<C> {
+        WwwAuthenticate(challenge)
+    }
+}
+
+impl<C: Challenge> AsRef<C> for WwwAuthenticate<C> {
+    fn as_ref(&self) -> &C {
+        &self.0
+    }
+}
+
+impl<C: Challenge> fmt::Display for WwwAuthenticate<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<C: Challenge> Header for WwwAuthenticate<C> {
+    fn name() -> HeaderName {
+        header::WWW_AUTHENTICATE
+    }
+
+    fn parse<M: HttpMessage>(msg: &M) -> Result<Self, ParseError> {
+        msg.headers()
+            .get_all(Self::name())
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| {
+                // each header value may contain several comma-separated challenges
+                iter::once(value).chain(
+                    value
+                        .match_indices([',', ' '])
+                        .map(move |(idx, _)| value[idx + 1..].trim_start()),
+                )
+            })
+            .find_map(|challenge| C::parse(challenge).ok())
+            .map(WwwAuthenticate)
+            .ok_or(ParseError::Header)
+    }
+}
+
+impl<C: Challenge> TryIntoHeaderValue for WwwAuthenticate<C> {
+    type Error = C::Error;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        self.0.try_into_value()
+    }
+}
+
+/// An authentication challenge, used in a [`WwwAuthenticate`] header.
+pub trait Challenge: TryIntoHeaderValue + fmt::Debug + fmt::Display + Clone + Send + Sync {
+    /// Parses a challenge of this scheme from the start of `value`.
+    ///
+    /// Input following the challenge, such as further challenges, should be ignored.
+    fn parse(value: &str) -> Result<Self, ParseError>;
+
+    /// Returns the status code that responses carrying this challenge should use.
+    ///
+    /// Defaults to `401 Unauthorized`.
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+}
+
+/// Challenge for the `Basic` authentication scheme, defined in
+/// [RFC 7617 §2](https://datatracker.ietf.org/doc/html/rfc7617#section-2).
+///
+/// The challenge always advertises the `UTF-8` charset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicChallenge {
+    realm: Cow<'static, str>,
+}
+
+impl BasicChallenge {
+    /// Constructs new `Basic` challenge for the given protection space.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::header::BasicChallenge;
+    ///
+    /// let challenge = BasicChallenge::new("admin");
+    /// assert_eq!(challenge.to_string(), r#"Basic realm="admin", charset="UTF-8""#);
+    /// ```
+    pub fn new<T>(realm: T) -> BasicChallenge
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        BasicChallenge {
+            realm: realm.into(),
+        }
+    }
+
+    /// Returns the realm.
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+}
+
+impl Challenge for BasicChallenge {
+    fn parse(value: &str) -> Result<Self, ParseError> {
+        let params = strip_scheme(value, "Basic")
+            .and_then(parse_params)
+            .ok_or(ParseError::Header)?;
+
+        params
+            .into_iter()
+            .find(|(name, _)| name == "realm")
+            .map(|(_, realm)| BasicChallenge::new(realm))
+            .ok_or(ParseError::Header)
+    }
+}
+
+impl fmt::Display for BasicChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_challenge(f, "Basic", [("realm", &*self.realm), ("charset", "UTF-8")])
+    }
+}
+
+impl TryIntoHeaderValue for BasicChallenge {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+/// Challenge for the `Bearer` authentication scheme, defined in
+/// [RFC 6750 §3](https://datatracker.ietf.org/doc/html/rfc6750#section-3).
+///
+/// All attributes are optional. Requests that lack credentials should be challenged without an
+/// error code.
+///
+/// # Examples
+/// ```
+/// use actix_web::http::header::{BearerChallenge, BearerError};
+///
+/// let challenge = BearerChallenge::new()
+///     .realm("example")
+///     .error(BearerError::InvalidToken)
+///     .error_description("The access token expired");
+///
+/// assert_eq!(
+///     challenge.to_string(),
+///     r#"Bearer realm="example", error="invalid_token", error_description="The access token expired""#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BearerChallenge {
+    realm: Option<Cow<'static, str>>,
+    scope: Option<Cow<'static, str>>,
+    error: Option<BearerError>,
+    error_description: Option<Cow<'static, str>>,
+    error_uri: Option<Cow<'static, str>>,
+}
+
+impl BearerChallenge {
+    /// Constructs new `Bearer` challenge without attributes.
+    pub fn new() -> BearerChallenge {
+        BearerChallenge::default()
+    }
+
+    /// Sets the realm.
+    pub fn realm<T: Into<Cow<'static, str>>>(mut self, realm: T) -> Self {
+        self.realm = Some(realm.into());
+        self
+    }
+
+    /// Sets the space-delimited list of scopes required to access the resource.
+    pub fn scope<T: Into<Cow<'static, str>>>(mut self, scope: T) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Sets the error code.
+    pub fn error(mut self, error: BearerError) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Sets the human-readable error description.
+    pub fn error_description<T: Into<Cow<'static, str>>>(mut self, description: T) -> Self {
+        self.error_description = Some(description.into());
+        self
+    }
+
+    /// Sets the URI of a human-readable page describing the error.
+    pub fn error_uri<T: Into<Cow<'static, str>>>(mut self, uri: T) -> Self {
+        self.error_uri = Some(uri.into());
+        self
+    }
+
+    /// Returns the realm, if set.
+    pub fn get_realm(&self) -> Option<&str> {
+        self.realm.as_deref()
+    }
+
+    /// Returns the scope, if set.
+    pub fn get_scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Returns the error code, if set.
+    pub fn get_error(&self) -> Option<BearerError> {
+        self.error
+    }
+
+    /// Returns the error description, if set.
+    pub fn get_error_description(&self) -> Option<&str> {
+        self.error_description.as_deref()
+    }
+
+    /// Returns the error URI, if set.
+    pub fn get_error_uri(&self) -> Option<&str> {
+        self.error_uri.as_deref()
+    }
+}
+
+impl Challenge for BearerChallenge {
+    fn parse(value: &str) -> Result<Self, ParseError> {
+        let params = strip_scheme(value, "Bearer")
+            .and_then(parse_params)
+            .ok_or(ParseError::Header)?;
+
+        let mut challenge = BearerChallenge::new();
+
+        for (name, value) in params {
+            match name.as_str() {
+                "realm" => challenge.realm = Some(value.into()),
+                "scope" => challenge.scope = Some(value.into()),
+                "error" => challenge.error = Some(value.parse()?),
+                "error_description" => challenge.error_description = Some(value.into()),
+                "error_uri" => challenge.error_uri = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        Ok(challenge)
+    }
+
+    fn status_code(&self) -> StatusCode {
+        self.error
+            .map_or(StatusCode::UNAUTHORIZED, BearerError::status_code)
+    }
+}
+
+impl fmt::Display for BearerChallenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = [
+            ("realm", self.realm.as_deref()),
+            ("scope", self.scope.as_deref()),
+            ("error", self.error.map(BearerError::as_str)),
+            ("error_description", self.error_description.as_deref()),
+            ("error_uri", self.error_uri.as_deref()),
+        ];
+
+        write_challenge(
+            f,
+            "Bearer",
+            params
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value?))),
+        )
+    }
+}
+
+impl TryIntoHeaderValue for BearerChallenge {
+    type Error = InvalidHeaderValue;
+
+    fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
+        HeaderValue::try_from(self.to_string())
+    }
+}
+
+/// Error codes of the `Bearer` authentication scheme, defined in
+/// [RFC 6750 §3.1](https://datatracker.ietf.org/doc/html/rfc6750#section-3.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BearerError {
+    /// The request is missing a required parameter or is otherwise malformed.
+    InvalidRequest,
+
+    /// The access token is expired, revoked, malformed, or invalid for other reasons.
+    InvalidToken,
+
+    /// The request requires higher privileges than provided by the access token.
+    InsufficientScope,
+}
+
+impl BearerError {
+    /// Returns the error code.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BearerError::InvalidRequest => "invalid_request",
+            BearerError::InvalidToken => "invalid_token",
+            BearerError::InsufficientScope => "insufficient_scope",
+        }
+    }
+
+    /// Returns the status code that responses with this error should use.
+    pub fn status_code(self) -> StatusCode {
+        match self {
+            BearerError::InvalidRequest => StatusCode::BAD_REQUEST,
+            BearerError::InvalidToken => StatusCode::UNAUTHORIZED,
+            BearerError::InsufficientScope => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl FromStr for BearerError {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invalid_request" => Ok(BearerError::InvalidRequest),
+            "invalid_token" => Ok(BearerError::InvalidToken),
+            "insufficient_scope" => Ok(BearerError::InsufficientScope),
+            _ => Err(ParseError::Header),
+        }
+    }
+}
+
+impl fmt::Display for BearerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestRequest;
+
+    #[test]
+    fn basic_challenge() {
+        let challenge = BasicChallenge::new(r#"my "realm""#);
+        assert_eq!(
+            challenge.clone().try_into_value().unwrap(),
+            r#"Basic realm="my \"realm\"", charset="UTF-8""#
+        );
+        assert_eq!(
+            BasicChallenge::parse(&challenge.to_string()).unwrap(),
+            challenge
+        );
+
+        assert!(BasicChallenge::parse("Basic").is_err());
+        assert!(BasicChallenge::parse(r#"Bearer realm="a""#).is_err());
+    }
+
+    #[test]
+    fn bearer_challenge() {
+        assert_eq!(BearerChallenge::new().to_string(), "Bearer");
+
+        let challenge = BearerChallenge::new()
+            .realm("example")
+            .scope("read write")
+            .error(BearerError::InsufficientScope)
+            .error_uri("https://example.com/errors");
+        assert_eq!(
+            challenge.to_string(),
+            r#"Bearer realm="example", scope="read write", error="insufficient_scope", error_uri="https://example.com/errors""#
+        );
+        assert_eq!(
+            BearerChallenge::parse(&challenge.to_string()).unwrap(),
+            challenge
+        );
+
+        assert!(BearerChallenge::parse(r#"Bearer error="unknown""#).is_err());
+    }
+
+    #[test]
+    fn www_authenticate_header() {
+        let req = TestRequest::default()
+            .insert_header((
+                header::WWW_AUTHENTICATE,
+                r#"Newauth realm="apps", type=1, Basic realm="simple""#,
+            ))
+            .to_http_request();
+
+        let header = WwwAuthenticate::<BasicChallenge>::parse(&req).unwrap();
+        assert_eq!(header.as_ref().realm(), "simple");
+
+        let req = TestRequest::default()
+            .append_header((header::WWW_AUTHENTICATE, r#"Basic realm="simple""#))
+            .append_header((header::WWW_AUTHENTICATE, r#"Bearer realm="api""#))
+            .to_http_request();
+
+        let header = WwwAuthenticate::<BearerChallenge>::parse(&req).unwrap();
+        assert_eq!(header.as_ref().get_realm(), Some("api"));
+    }
+}
@@ -69,6 +69,7 @@
 //! - `rustls-0_22` - HTTPS support via `rustls` 0.22 crate, supports `HTTP/2`
 //! - `rustls-0_23` - HTTPS support via `rustls` 0.23 crate, supports `HTTP/2`
 //! - `secure-cookies` - secure cookies support
+//! - `openapi` - OpenAPI document generation from routing macros and extractors
 
 #![doc(html_logo_url = "https://actix.rs/img/logo.png")]
 #![doc(html_favicon_url = "https://actix.rs/favicon.ico")]
@@ -81,9 +82,12 @@ pub use cookie;
 
 mod app;
 mod app_service;
+#[cfg(any(feature = "openssl", feature = "rustls-0_23"))]
+mod cert_store;
 mod config;
 mod data;
 pub mod dev;
+mod dynamic_scope;
 pub mod error;
 mod extract;
 pub mod guard;
@@ -92,6 +96,10 @@ mod helpers;
 pub mod http;
 mod info;
 pub mod middleware;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+#[cfg(feature = "__tls")]
+mod peer_certificates;
 mod redirect;
 mod request;
 mod request_data;
@@ -112,6 +120,7 @@ pub mod web;
 pub use crate::error::Result;
 pub use crate::{
     app::App,
+    dynamic_scope::{DynamicRoute, DynamicRoutes, DynamicScope},
     error::{Error, ResponseError},
     extract::FromRequest,
     handler::Handler,
@@ -146,4 +155,12 @@ codegen_reexport!(connect);
 codegen_reexport!(options);
 codegen_reexport!(scope);
 
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by code generated by the routing macros. Not public API.
+
+    pub use serde::Serialize;
+    pub use url::Url;
+}
+
 pub(crate) type BoxError = Box<dyn std::error::Error>;
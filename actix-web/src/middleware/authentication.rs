@@ -0,0 +1,313 @@
+//! For middleware documentation, see [`HttpAuthentication`].
+
+use std::{future::Future, marker::PhantomData, rc::Rc};
+
+use actix_utils::future::{ready, Ready};
+use futures_core::future::LocalBoxFuture;
+
+use crate::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    web::{BasicAuth, BearerAuth},
+    Error, FromRequest,
+};
+
+/// Middleware for authenticating requests using an async validator.
+///
+/// Credentials are extracted from each request using the `T` extractor, usually [`BasicAuth`],
+/// [`BearerAuth`] or [`DigestAuth`](crate::web::DigestAuth). Requests with missing or malformed
+/// credentials are rejected using the extractor's error, which for the built-in extractors
+/// responds with a `WWW-Authenticate` challenge.
+///
+/// Otherwise, the validator is called with the request and the credentials. It can reject the
+/// request by returning an error, such as an
+/// [`AuthenticationError`](crate::error::AuthenticationError), along with the request, which is
+/// then answered with the error's response. It accepts the request by returning it, optionally
+/// after attaching an identity to the request extensions for handlers to extract with
+/// [`ReqData`](crate::web::ReqData).
+///
+/// # Examples
+/// ```
+/// use actix_web::{
+///     dev::ServiceRequest,
+///     error::AuthenticationError,
+///     http::header::BasicChallenge,
+///     middleware::HttpAuthentication,
+///     web, App, Error, HttpMessage as _,
+/// };
+///
+/// #[derive(Clone)]
+/// struct User(String);
+///
+/// async fn validator(
+///     req: ServiceRequest,
+///     auth: web::BasicAuth,
+/// ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+///     if auth.password() != "open sesame" {
+///         let err = AuthenticationError::new(BasicChallenge::new("admin"));
+///         return Err((err.into(), req));
+///     }
+///
+///     req.extensions_mut().insert(User(auth.user_id().to_owned()));
+///     Ok(req)
+/// }
+///
+/// App::new()
+///     .app_data(web::BasicAuthConfig::default().realm("admin"))
+///     .wrap(HttpAuthentication::basic(validator))
+///     .route("/", web::get().to(|user: web::ReqData<User>| async move {
+///         format!("Hello, {}!", user.0)
+///     }));
+/// ```
+pub struct HttpAuthentication<T, F> {
+    validator: Rc<F>,
+    _extractor: PhantomData<T>,
+}
+
+impl<T, F, Fut> HttpAuthentication<T, F>
+where
+    T: FromRequest,
+    F: Fn(ServiceRequest, T) -> Fut,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>>,
+{
+    /// Constructs new middleware that authenticates requests using credentials extracted as `T`.
+    pub fn with_fn(validator: F) -> Self {
+        HttpAuthentication {
+            validator: Rc::new(validator),
+            _extractor: PhantomData,
+        }
+    }
+}
+
+impl<F, Fut> HttpAuthentication<BasicAuth, F>
+where
+    F: Fn(ServiceRequest, BasicAuth) -> Fut,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>>,
+{
+    /// Constructs new middleware that authenticates requests using `Basic` credentials.
+    pub fn basic(validator: F) -> Self {
+        Self::with_fn(validator)
+    }
+}
+
+impl<F, Fut> HttpAuthentication<BearerAuth, F>
+where
+    F: Fn(ServiceRequest, BearerAuth) -> Fut,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>>,
+{
+    /// Constructs new middleware that authenticates requests using `Bearer` credentials.
+    pub fn bearer(validator: F) -> Self {
+        Self::with_fn(validator)
+    }
+}
+
+#[cfg(feature = "digest-auth")]
+impl<F, Fut> HttpAuthentication<crate::web::DigestAuth, F>
+where
+    F: Fn(ServiceRequest, crate::web::DigestAuth) -> Fut,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>>,
+{
+    /// Constructs new middleware that authenticates requests using `Digest` credentials.
+    ///
+    /// The validator should check the password using
+    /// [`DigestAuth::verify`](crate::web::DigestAuth::verify).
+    pub fn digest(validator: F) -> Self {
+        Self::with_fn(validator)
+    }
+}
+
+impl<T, F> Clone for HttpAuthentication<T, F> {
+    fn clone(&self) -> Self {
+        HttpAuthentication {
+            validator: Rc::clone(&self.validator),
+            _extractor: PhantomData,
+        }
+    }
+}
+
+impl<S, B, T, F, Fut> Transform<S, ServiceRequest> for HttpAuthentication<T, F>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    T: FromRequest + 'static,
+    F: Fn(ServiceRequest, T) -> Fut + 'static,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>> + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = HttpAuthenticationMiddleware<S, T, F>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(HttpAuthenticationMiddleware {
+            service: Rc::new(service),
+            validator: Rc::clone(&self.validator),
+            _extractor: PhantomData,
+        }))
+    }
+}
+
+#[doc(hidden)]
+pub struct HttpAuthenticationMiddleware<S, T, F> {
+    service: Rc<S>,
+    validator: Rc<F>,
+    _extractor: PhantomData<T>,
+}
+
+impl<S, B, T, F, Fut> Service<ServiceRequest> for HttpAuthenticationMiddleware<S, T, F>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    T: FromRequest + 'static,
+    F: Fn(ServiceRequest, T) -> Fut + 'static,
+    Fut: Future<Output = Result<ServiceRequest, (Error, ServiceRequest)>> + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let validator = Rc::clone(&self.validator);
+
+        Box::pin(async move {
+            let credentials = match req.extract::<T>().await {
+                Ok(credentials) => credentials,
+                Err(err) => return Ok(req.error_response(err).map_into_right_body()),
+            };
+
+            match validator(req, credentials).await {
+                Ok(req) => service
+                    .call(req)
+                    .await
+                    .map(ServiceResponse::map_into_left_body),
+                Err((err, req)) => Ok(req.error_response(err).map_into_right_body()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::AuthenticationError,
+        http::{
+            header::{self, Authorization, Basic, Bearer, BearerChallenge, BearerError},
+            StatusCode,
+        },
+        test::{self, TestRequest},
+        web, App, HttpMessage as _, HttpResponse,
+    };
+
+    async fn basic_validator(
+        req: ServiceRequest,
+        auth: BasicAuth,
+    ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+        if auth.password() != "open sesame" {
+            let challenge = web::BasicAuthConfig::default().realm("admin").challenge();
+            return Err((AuthenticationError::new(challenge).into(), req));
+        }
+
+        req.extensions_mut().insert(auth.user_id().to_owned());
+        Ok(req)
+    }
+
+    #[actix_rt::test]
+    async fn basic() {
+        let srv = test::init_service(
+            App::new()
+                .app_data(web::BasicAuthConfig::default().realm("admin"))
+                .wrap(HttpAuthentication::basic(basic_validator))
+                .route(
+                    "/",
+                    web::get().to(|user: web::ReqData<String>| {
+                        HttpResponse::Ok().body(user.into_inner())
+                    }),
+                ),
+        )
+        .await;
+
+        let req = TestRequest::default()
+            .insert_header(Authorization(Basic::new("Aladdin", "open sesame")))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        test::assert_body_eq!(res, b"Aladdin");
+
+        let req = TestRequest::default()
+            .insert_header(Authorization(Basic::new("Aladdin", "wrong")))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Basic realm="admin", charset="UTF-8""#
+        );
+
+        let req = TestRequest::default().to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Basic realm="admin", charset="UTF-8""#
+        );
+    }
+
+    #[actix_rt::test]
+    async fn bearer() {
+        let srv = test::init_service(
+            App::new()
+                .wrap(HttpAuthentication::bearer(
+                    |req: ServiceRequest, auth: BearerAuth| async move {
+                        let challenge = match auth.token() {
+                            "admin" => return Ok(req),
+                            "guest" => BearerChallenge::new()
+                                .scope("admin")
+                                .error(BearerError::InsufficientScope),
+                            _ => BearerChallenge::new().error(BearerError::InvalidToken),
+                        };
+
+                        Err((AuthenticationError::new(challenge).into(), req))
+                    },
+                ))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = TestRequest::default()
+            .insert_header(Authorization(Bearer::new("admin")))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = TestRequest::default()
+            .insert_header(Authorization(Bearer::new("guest")))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Bearer scope="admin", error="insufficient_scope""#
+        );
+
+        let req = TestRequest::default()
+            .insert_header(Authorization(Bearer::new("expired")))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Bearer error="invalid_token""#
+        );
+
+        let req = TestRequest::default().to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            "Bearer"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! For middleware documentation, see [`AutoMethods`].
+
+use std::{
+    convert::Infallible,
+    mem,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use actix_router::{Path, Url};
+use actix_utils::future::{ready, Ready};
+use bytes::Bytes;
+use futures_core::future::LocalBoxFuture;
+
+use crate::{
+    body::{BodySize, BoxBody, EitherBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    guard::RegisteredMethods,
+    http::{header, Method, StatusCode},
+    Error, HttpMessage as _, HttpResponse,
+};
+
+/// Middleware for answering requests whose path matches a route but whose method does not.
+///
+/// Method guards record the methods they were checked against while a request is routed, even when
+/// the matching resource is in a different [`Scope`](crate::Scope) or was registered separately
+/// using [`App::route`](crate::App::route). When a request would otherwise end in a `404 Not Found`
+/// or `405 Method Not Allowed` response, and its method is not one of those recorded, this
+/// middleware instead:
+/// - answers `OPTIONS` requests with `204 No Content`;
+/// - answers `HEAD` requests by calling the `GET` route and discarding the body of its response,
+///   keeping the headers and the body size used for `Content-Length`;
+/// - answers all other requests with `405 Method Not Allowed`.
+///
+/// Both `OPTIONS` and `405` responses include an `Allow` header listing the recorded methods, along
+/// with `HEAD`, if `GET` is allowed, and `OPTIONS`.
+///
+/// Only routes with method guards, such as those created by [`web::get()`](crate::web::get), are
+/// taken into account. Responses produced by handlers are never altered.
+///
+/// # Examples
+/// ```
+/// use actix_web::{middleware::AutoMethods, web, App, HttpResponse};
+///
+/// let app = App::new()
+///     .wrap(AutoMethods::default())
+///     .route("/items", web::get().to(HttpResponse::Ok))
+///     .route("/items", web::post().to(HttpResponse::Created));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct AutoMethods;
+
+impl<S, B> Transform<S, ServiceRequest> for AutoMethods
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AutoMethodsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AutoMethodsMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+#[doc(hidden)]
+pub struct AutoMethodsMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AutoMethodsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        // routing state must be restored before calling the service again for a HEAD request
+        let match_info = (req.method() == Method::HEAD).then(|| req.match_info().clone());
+
+        Box::pin(async move {
+            let res = service.call(req).await?;
+
+            let allowed = match allowed_methods(&res) {
+                Some(allowed) => allowed,
+                None => return Ok(res.map_into_left_body()),
+            };
+
+            let method = res.request().method().clone();
+
+            match method {
+                Method::OPTIONS => Ok(res
+                    .into_response(
+                        HttpResponse::NoContent()
+                            .insert_header(header::Allow(allowed))
+                            .finish(),
+                    )
+                    .map_into_right_body()),
+
+                Method::HEAD if allowed.contains(&Method::GET) => {
+                    call_get_for_head(&service, res, match_info).await
+                }
+
+                _ => Ok(res
+                    .into_response(
+                        HttpResponse::MethodNotAllowed()
+                            .insert_header(header::Allow(allowed))
+                            .finish(),
+                    )
+                    .map_into_right_body()),
+            }
+        })
+    }
+}
+
+/// Returns the methods allowed for the request path if no route handled the request's method.
+fn allowed_methods<B>(res: &ServiceResponse<B>) -> Option<Vec<Method>> {
+    if !matches!(
+        res.status(),
+        StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED
+    ) {
+        return None;
+    }
+
+    let req = res.request();
+    let registered = req.extensions().get::<RegisteredMethods>()?.0.clone();
+
+    if registered.contains(req.method()) {
+        return None;
+    }
+
+    let mut allowed = Vec::with_capacity(registered.len() + 2);
+
+    for method in registered {
+        if !allowed.contains(&method) {
+            allowed.push(method);
+        }
+    }
+
+    if allowed.contains(&Method::GET) && !allowed.contains(&Method::HEAD) {
+        allowed.push(Method::HEAD);
+    }
+
+    if !allowed.contains(&Method::OPTIONS) {
+        allowed.push(Method::OPTIONS);
+    }
+
+    Some(allowed)
+}
+
+/// Calls the service again as a `GET` request, then removes the body from its response.
+async fn call_get_for_head<S, B>(
+    service: &S,
+    res: ServiceResponse<B>,
+    match_info: Option<Path<Url>>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let (req, res) = res.into_parts();
+    drop(res);
+
+    let match_info = match_info.unwrap_or_else(|| req.match_info().clone());
+
+    // the original request may be held elsewhere, such as by a preceding middleware, so the GET
+    // request is a separate copy that borrows the request-local data for the duration of the call
+    let mut extensions = mem::take(&mut *req.extensions_mut());
+    extensions.remove::<RegisteredMethods>();
+
+    let get_req = req.duplicate_with(Method::GET, match_info, extensions);
+    let (get_req, res) = service
+        .call(ServiceRequest::from_request(get_req))
+        .await?
+        .into_parts();
+
+    *req.extensions_mut() = mem::take(&mut *get_req.extensions_mut());
+    drop(get_req);
+
+    let res = res.map_body(|_, body| BoxBody::new(HeadBody(body.size())));
+
+    Ok(ServiceResponse::new(req, res).map_into_right_body())
+}
+
+/// Empty body that reports the size of the body it replaces.
+struct HeadBody(BodySize);
+
+impl MessageBody for HeadBody {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        self.0
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test::{self, TestRequest},
+        web, App, HttpResponse, Responder as _,
+    };
+
+    fn allow_header<B>(res: &ServiceResponse<B>) -> &str {
+        res.headers().get(header::ALLOW).unwrap().to_str().unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn method_not_allowed_across_resources() {
+        let srv = test::init_service(
+            App::new()
+                .wrap(AutoMethods::default())
+                .route("/items", web::get().to(HttpResponse::Ok))
+                .service(web::scope("/api").route("/items", web::get().to(HttpResponse::Ok)))
+                .route("/items", web::post().to(HttpResponse::Created)),
+        )
+        .await;
+
+        let req = TestRequest::put().uri("/items").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(allow_header(&res), "GET, POST, HEAD, OPTIONS");
+
+        let req = TestRequest::delete().uri("/api/items").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(allow_header(&res), "GET, HEAD, OPTIONS");
+
+        let req = TestRequest::post().uri("/items").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+
+        // unknown paths are unaffected
+        let req = TestRequest::put().uri("/unknown").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn options() {
+        let srv = test::init_service(
+            App::new().wrap(AutoMethods::default()).service(
+                web::resource("/items")
+                    .get(HttpResponse::Ok)
+                    .put(HttpResponse::Ok),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::default()
+            .method(Method::OPTIONS)
+            .uri("/items")
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        assert_eq!(allow_header(&res), "GET, PUT, HEAD, OPTIONS");
+    }
+
+    #[actix_rt::test]
+    async fn head_from_get() {
+        let srv = test::init_service(App::new().service(
+            web::scope("/api").wrap(AutoMethods::default()).route(
+                "/items/{id}",
+                web::get().to(|path: web::Path<String>| async move {
+                    "hello"
+                        .customize()
+                        .insert_header(("x-id", path.into_inner()))
+                }),
+            ),
+        ))
+        .await;
+
+        let req = TestRequest::default()
+            .method(Method::HEAD)
+            .uri("/api/items/42")
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.request().method(), Method::HEAD);
+        assert_eq!(res.headers().get("x-id").unwrap(), "42");
+        assert_eq!(res.response().body().size(), BodySize::Sized(5));
+        assert!(test::read_body(res).await.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn head_from_get_with_shared_request() {
+        use std::{cell::RefCell, rc::Rc};
+
+        use crate::{dev::Service as _, HttpMessage as _, HttpRequest};
+
+        struct Marker(&'static str);
+
+        let held = Rc::new(RefCell::new(Vec::<HttpRequest>::new()));
+
+        let srv = test::init_service(
+            App::new()
+                // keeps clones of requests after they are handled
+                .wrap_fn({
+                    let held = Rc::clone(&held);
+
+                    move |req, srv| {
+                        let fut = srv.call(req);
+                        let held = Rc::clone(&held);
+
+                        async move {
+                            let res = fut.await?;
+                            held.borrow_mut().push(res.request().clone());
+                            Ok(res)
+                        }
+                    }
+                })
+                .wrap(AutoMethods::default())
+                .wrap_fn(|req, srv| {
+                    req.extensions_mut().insert(Marker("outer"));
+                    srv.call(req)
+                })
+                .route(
+                    "/items",
+                    web::get().to(|req: HttpRequest| async move {
+                        let marker = req.extensions().get::<Marker>().map(|m| m.0);
+                        marker.unwrap_or("missing").to_owned()
+                    }),
+                ),
+        )
+        .await;
+
+        let req = TestRequest::default()
+            .method(Method::HEAD)
+            .uri("/items")
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.request().method(), Method::HEAD);
+        assert_eq!(res.response().body().size(), BodySize::Sized(5));
+        assert!(res.request().extensions().get::<Marker>().is_some());
+        assert!(test::read_body(res).await.is_empty());
+
+        assert_eq!(held.borrow().len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn handler_responses_unaltered() {
+        let srv = test::init_service(
+            App::new()
+                .wrap(AutoMethods::default())
+                .route("/", web::get().to(HttpResponse::NotFound))
+                .route("/", web::head().to(HttpResponse::NotFound)),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert!(!res.headers().contains_key(header::ALLOW));
+
+        let req = TestRequest::default()
+            .method(Method::HEAD)
+            .uri("/")
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::{
     borrow::Cow,
+    cell::Cell,
     collections::HashSet,
     env,
     fmt::{self, Display as _},
@@ -25,7 +26,7 @@ use regex_lite::Regex;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 use crate::{
-    body::{BodySize, MessageBody},
+    body::{BodySize, FileRegion, MessageBody},
     http::header::HeaderName,
     service::{ServiceRequest, ServiceResponse},
     Error, Result,
@@ -396,6 +397,7 @@ where
             time,
             format,
             size: 0,
+            file_region_len: Cell::new(None),
             log_target,
         })))
     }
@@ -407,6 +409,8 @@ pin_project! {
         body: B,
         format: Option<Format>,
         size: usize,
+        // length of the file region handed to the dispatcher, which sends it without polling
+        file_region_len: Cell<Option<u64>>,
         time: OffsetDateTime,
         log_target: Cow<'static, str>,
     }
@@ -414,9 +418,14 @@ pin_project! {
     impl<B> PinnedDrop for StreamLog<B> {
         fn drop(this: Pin<&mut Self>) {
             if let Some(ref format) = this.format {
+                let size = match this.file_region_len.get() {
+                    Some(len) if this.size == 0 => len as usize,
+                    _ => this.size,
+                };
+
                 let render = |fmt: &mut fmt::Formatter<'_>| {
                     for unit in &format.0 {
-                        unit.render(fmt, this.size, this.time)?;
+                        unit.render(fmt, size, this.time)?;
                     }
                     Ok(())
                 };
@@ -453,6 +462,12 @@ impl<B: MessageBody> MessageBody for StreamLog<B> {
             None => Poll::Ready(None),
         }
     }
+
+    fn file_region(&self) -> Option<FileRegion<'_>> {
+        let region = self.body.file_region()?;
+        self.file_region_len.set(Some(region.len()));
+        Some(region)
+    }
 }
 
 /// A formatting style for the `Logger` consisting of multiple concatenated `FormatText` items.
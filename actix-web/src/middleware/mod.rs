@@ -242,6 +242,8 @@
 //! [`new_transform`]: crate::dev::Transform::new_transform()
 //! [`from_fn`]: crate
 
+mod authentication;
+mod auto_methods;
 mod compat;
 #[cfg(feature = "__compress")]
 mod compress;
@@ -252,10 +254,13 @@ mod from_fn;
 mod identity;
 mod logger;
 mod normalize;
+mod secure_headers;
 
 #[cfg(feature = "__compress")]
 pub use self::compress::Compress;
 pub use self::{
+    authentication::HttpAuthentication,
+    auto_methods::AutoMethods,
     compat::Compat,
     condition::Condition,
     default_headers::DefaultHeaders,
@@ -263,7 +268,8 @@ pub use self::{
     from_fn::{from_fn, Next},
     identity::Identity,
     logger::Logger,
-    normalize::{NormalizePath, TrailingSlash},
+    normalize::{NormalizePath, NormalizePathRedirect, TrailingSlash},
+    secure_headers::{CspNonce, SecureHeaders},
 };
 
 #[cfg(test)]
@@ -1,17 +1,28 @@
 //! For middleware documentation, see [`NormalizePath`].
 
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
 use actix_http::uri::{PathAndQuery, Uri};
 use actix_service::{Service, Transform};
-use actix_utils::future::{ready, Ready};
+use actix_utils::future::{ok, ready, Either, Ready};
 use bytes::Bytes;
+use futures_core::ready;
+use pin_project_lite::pin_project;
 #[cfg(feature = "unicode")]
 use regex::Regex;
 #[cfg(not(feature = "unicode"))]
 use regex_lite::Regex;
 
 use crate::{
+    body::EitherBody,
+    http::{header, StatusCode},
     service::{ServiceRequest, ServiceResponse},
-    Error,
+    Error, HttpResponse,
 };
 
 /// Determines the behavior of the [`NormalizePath`] middleware.
@@ -51,6 +62,11 @@ pub enum TrailingSlash {
 /// trailing slashes or else they will be inaccessible (or vice versa when using the
 /// `TrailingSlash::Always` behavior), as shown in the example tests below.
 ///
+/// # Redirecting
+/// The request path is rewritten in place, so the same resource stays reachable at every URL that
+/// normalizes to its path. Use [`redirect`](Self::redirect()) to instead respond to such requests
+/// with a redirect to the normalized, canonical URL.
+///
 /// # Examples
 /// ```
 /// use actix_web::{web, middleware, App};
@@ -109,6 +125,17 @@ impl NormalizePath {
     pub fn trim() -> Self {
         Self::new(TrailingSlash::Trim)
     }
+
+    /// Responds to requests whose path is not normalized with a redirect to the normalized path,
+    /// instead of rewriting the path in place.
+    ///
+    /// See [`NormalizePathRedirect`] for further options.
+    ///
+    /// # Panics
+    /// Panics if `status` is not one of 301, 302, 303, 307 or 308.
+    pub fn redirect(self, status: StatusCode) -> NormalizePathRedirect {
+        NormalizePathRedirect::new(self.0, status)
+    }
 }
 
 impl<S, B> Transform<S, ServiceRequest> for NormalizePath
@@ -156,20 +183,12 @@ where
         // An empty path here means that the URI has no valid path. We skip normalization in this
         // case, because adding a path can make the URI invalid
         if !original_path.is_empty() {
-            // Either adds a string to the end (duplicates will be removed anyways) or trims all
-            // slashes from the end
-            let path = match self.trailing_slash_behavior {
-                TrailingSlash::Always => format!("{}/", original_path),
-                TrailingSlash::MergeOnly => original_path.to_string(),
-                TrailingSlash::Trim => original_path.trim_end_matches('/').to_string(),
-            };
-
-            // normalize multiple /'s to one /
-            let path = self.merge_slash.replace_all(&path, "/");
-
-            // Ensure root paths are still resolvable. If resulting path is blank after previous
-            // step it means the path was one or more slashes. Reduce to single slash.
-            let path = if path.is_empty() { "/" } else { path.as_ref() };
+            let path = normalize_slashes(
+                &self.merge_slash,
+                self.trailing_slash_behavior,
+                original_path,
+            );
+            let path = path.as_str();
 
             // Check whether the path has been changed
             //
@@ -201,6 +220,283 @@ where
     }
 }
 
+/// Returns `path` with consecutive slashes merged and trailing slashes handled according to
+/// `trailing_slash`.
+fn normalize_slashes(merge_slash: &Regex, trailing_slash: TrailingSlash, path: &str) -> String {
+    // Either adds a string to the end (duplicates will be removed anyways) or trims all
+    // slashes from the end
+    let path = match trailing_slash {
+        TrailingSlash::Always => format!("{}/", path),
+        TrailingSlash::MergeOnly => path.to_string(),
+        TrailingSlash::Trim => path.trim_end_matches('/').to_string(),
+    };
+
+    // normalize multiple /'s to one /
+    let path = merge_slash.replace_all(&path, "/");
+
+    // Ensure root paths are still resolvable. If resulting path is blank after previous
+    // step it means the path was one or more slashes. Reduce to single slash.
+    if path.is_empty() {
+        "/".to_owned()
+    } else {
+        path.into_owned()
+    }
+}
+
+/// Middleware for redirecting requests to their normalized, canonical path.
+///
+/// Constructed using [`NormalizePath::redirect`]. Paths are normalized like [`NormalizePath`]
+/// does, and optionally also converted to lowercase and have their percent-encoding normalized.
+/// Requests whose path changes are responded to with a redirect to the normalized path, keeping
+/// the query string. Other requests are passed on unchanged.
+///
+/// The normalized path always starts with a single slash and backslashes in it are
+/// percent-encoded, since browsers treat `\` like `/` and would otherwise resolve a `Location`
+/// such as `/\example.com` as a URL of another host.
+///
+/// Note that browsers change the method of `301 Moved Permanently` redirects from `POST` to
+/// `GET`; `308 Permanent Redirect` preserves the method and body of the request.
+///
+/// # Examples
+/// ```
+/// use actix_web::{http::StatusCode, middleware::NormalizePath, web, App};
+///
+/// # actix_web::rt::System::new().block_on(async {
+/// let app = App::new()
+///     .wrap(
+///         NormalizePath::trim()
+///             .redirect(StatusCode::MOVED_PERMANENTLY)
+///             .lowercase(true)
+///             .require_resource(true),
+///     )
+///     .route("/docs/intro", web::get().to(|| async { "intro" }));
+///
+/// use actix_web::http::header;
+/// use actix_web::test::{call_service, init_service, TestRequest};
+///
+/// let app = init_service(app).await;
+///
+/// let req = TestRequest::with_uri("/Docs//Intro/?lang=en").to_request();
+/// let res = call_service(&app, req).await;
+/// assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY);
+/// assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/docs/intro?lang=en");
+///
+/// // not redirected, since the normalized path does not match a resource
+/// let req = TestRequest::with_uri("/Blog/").to_request();
+/// let res = call_service(&app, req).await;
+/// assert_eq!(res.status(), StatusCode::NOT_FOUND);
+/// # })
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NormalizePathRedirect {
+    trailing_slash: TrailingSlash,
+    status: StatusCode,
+    require_resource: bool,
+    lowercase: bool,
+    percent_encoding: bool,
+}
+
+impl NormalizePathRedirect {
+    fn new(trailing_slash: TrailingSlash, status: StatusCode) -> Self {
+        assert!(
+            matches!(status.as_u16(), 301 | 302 | 303 | 307 | 308),
+            "NormalizePath redirect status must be 301, 302, 303, 307 or 308, got {status}"
+        );
+
+        Self {
+            trailing_slash,
+            status,
+            require_resource: false,
+            lowercase: false,
+            percent_encoding: false,
+        }
+    }
+
+    /// Only redirects if the normalized path matches a resource of the app.
+    ///
+    /// Other requests are passed on unchanged. This is checked using
+    /// [`ResourceMap::has_resource`](crate::dev::ResourceMap::has_resource), so guards, including
+    /// method guards, are not taken into account.
+    pub fn require_resource(mut self, require_resource: bool) -> Self {
+        self.require_resource = require_resource;
+        self
+    }
+
+    /// Converts paths to lowercase.
+    ///
+    /// Only ASCII letters are converted. Percent-encoded characters are left as-is.
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Normalizes percent-encoding in paths, as described in [RFC 3986 §6.2.2.2].
+    ///
+    /// Percent-encoded unreserved characters (ASCII letters and digits, `-`, `.`, `_` and `~`) are
+    /// decoded and the hexadecimal digits of other percent-encoded octets are converted to
+    /// uppercase. For example, `/%7euser/a%2fb` becomes `/~user/a%2Fb`.
+    ///
+    /// [RFC 3986 §6.2.2.2]: https://datatracker.ietf.org/doc/html/rfc3986#section-6.2.2.2
+    pub fn normalize_percent_encoding(mut self, normalize: bool) -> Self {
+        self.percent_encoding = normalize;
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for NormalizePathRedirect
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = NormalizePathRedirection<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(NormalizePathRedirection {
+            service,
+            merge_slash: Regex::new("//+").unwrap(),
+            config: *self,
+        }))
+    }
+}
+
+pub struct NormalizePathRedirection<S> {
+    service: S,
+    merge_slash: Regex,
+    config: NormalizePathRedirect,
+}
+
+impl<S> NormalizePathRedirection<S> {
+    /// Returns the normalized form of a non-empty path.
+    fn normalize(&self, original_path: &str) -> String {
+        let path = normalize_slashes(&self.merge_slash, self.config.trailing_slash, original_path);
+
+        // prevent the path from being resolved as a URL of another host, like `/\example.com`
+        let path = format!(
+            "/{}",
+            path.trim_start_matches(['/', '\\']).replace('\\', "%5C")
+        );
+
+        if !self.config.percent_encoding && !self.config.lowercase {
+            return path;
+        }
+
+        let mut normalized = String::with_capacity(path.len());
+        let mut rem = path.as_str();
+
+        while let Some(ch) = rem.chars().next() {
+            let escape = rem
+                .strip_prefix('%')
+                .and_then(|hex| hex.get(..2))
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok().map(|byte| (hex, byte)));
+
+            match escape {
+                Some((_, byte)) if self.config.percent_encoding && is_unreserved(byte) => {
+                    let ch = char::from(byte);
+
+                    if self.config.lowercase {
+                        normalized.push(ch.to_ascii_lowercase());
+                    } else {
+                        normalized.push(ch);
+                    }
+                }
+
+                Some((hex, _)) => {
+                    normalized.push('%');
+
+                    if self.config.percent_encoding {
+                        normalized.push_str(&hex.to_ascii_uppercase());
+                    } else {
+                        normalized.push_str(hex);
+                    }
+                }
+
+                None if self.config.lowercase => normalized.push(ch.to_ascii_lowercase()),
+                None => normalized.push(ch),
+            }
+
+            rem = &rem[if escape.is_some() { 3 } else { ch.len_utf8() }..];
+        }
+
+        normalized
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for NormalizePathRedirection<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Either<NormalizePathResponse<S, B>, Ready<Result<Self::Response, Self::Error>>>;
+
+    actix_service::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let original_path = req.head().uri.path();
+
+        // An empty path here means that the URI has no valid path, so there is nothing to
+        // redirect to
+        if !original_path.is_empty() {
+            let path = self.normalize(original_path);
+
+            if path != original_path
+                && (!self.config.require_resource || req.resource_map().has_resource(&path))
+            {
+                let location = match req.query_string() {
+                    "" => path,
+                    query => format!("{}?{}", path, query),
+                };
+
+                let res = HttpResponse::build(self.config.status)
+                    .insert_header((header::LOCATION, location))
+                    .finish();
+
+                return Either::right(ok(req.into_response(res).map_into_right_body()));
+            }
+        }
+
+        Either::left(NormalizePathResponse {
+            fut: self.service.call(req),
+            _phantom: PhantomData,
+        })
+    }
+}
+
+pin_project! {
+    pub struct NormalizePathResponse<S, B>
+    where
+        S: Service<ServiceRequest>,
+    {
+        #[pin]
+        fut: S::Future,
+        _phantom: PhantomData<B>,
+    }
+}
+
+impl<S, B> Future for NormalizePathResponse<S, B>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Output = Result<ServiceResponse<EitherBody<B>>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let res = ready!(self.project().fut.poll(cx))?;
+        Poll::Ready(Ok(res.map_into_left_body()))
+    }
+}
+
+/// Returns `true` if the byte is an unreserved character, as defined in RFC 3986 §2.3.
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
 #[cfg(test)]
 mod tests {
     use actix_http::StatusCode;
@@ -483,4 +779,138 @@ mod tests {
         let res = normalize.call(req).await.unwrap();
         assert!(res.status().is_success());
     }
+
+    #[actix_rt::test]
+    async fn redirect_to_normalized_path() {
+        let app = init_service(
+            App::new()
+                .wrap(NormalizePath::trim().redirect(StatusCode::PERMANENT_REDIRECT))
+                .service(web::resource("/").to(HttpResponse::Ok))
+                .service(web::resource("/v1/something").to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let tests = vec![
+            ("//", Some("/")),
+            ("/v1/something/", Some("/v1/something")),
+            ("//v1//something", Some("/v1/something")),
+            (
+                "/v1//something?query=test",
+                Some("/v1/something?query=test"),
+            ),
+            ("/v2//something//", Some("/v2/something")),
+            ("/", None),
+            ("/v1/something", None),
+            ("/v1/something?query=test", None),
+        ];
+
+        for (uri, location) in tests {
+            let req = TestRequest::with_uri(uri).to_request();
+            let res = call_service(&app, req).await;
+
+            match location {
+                Some(location) => {
+                    assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT, "uri: {}", uri);
+                    assert_eq!(res.headers().get(header::LOCATION).unwrap(), location);
+                }
+                None => assert_eq!(res.status(), StatusCode::OK, "uri: {}", uri),
+            }
+        }
+    }
+
+    #[actix_rt::test]
+    async fn redirect_stays_on_host() {
+        let app = init_service(
+            App::new()
+                .wrap(NormalizePath::trim().redirect(StatusCode::MOVED_PERMANENTLY))
+                .service(web::resource("/").to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let tests = vec![
+            ("/\\evil.com/", "/evil.com"),
+            ("/\\/evil.com", "/evil.com"),
+            ("//\\\\evil.com", "/evil.com"),
+            ("/a\\b/", "/a%5Cb"),
+        ];
+
+        for (uri, location) in tests {
+            let req = TestRequest::with_uri(uri).to_request();
+            let res = call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY, "uri: {}", uri);
+            assert_eq!(res.headers().get(header::LOCATION).unwrap(), location);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn redirect_require_resource() {
+        let app = init_service(
+            App::new()
+                .wrap(
+                    NormalizePath::trim()
+                        .redirect(StatusCode::MOVED_PERMANENTLY)
+                        .require_resource(true),
+                )
+                .service(web::resource("/v1/something").to(HttpResponse::Ok))
+                .service(web::resource("/v2/something/").to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/v1//something/").to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            res.headers().get(header::LOCATION).unwrap(),
+            "/v1/something"
+        );
+
+        // normalized path does not match a resource, so the request is passed on unchanged
+        let req = TestRequest::with_uri("/v2/something/").to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = TestRequest::with_uri("/v3//something").to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    #[should_panic]
+    fn redirect_requires_redirect_status() {
+        let _ = NormalizePath::trim().redirect(StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_rt::test]
+    async fn redirect_lowercase_and_percent_encoding() {
+        async fn location(normalize: NormalizePathRedirect, uri: &str) -> String {
+            let srv = |req: ServiceRequest| ready(Ok(req.into_response(HttpResponse::Ok())));
+
+            let normalize = normalize.new_transform(srv.into_service()).await.unwrap();
+
+            let req = TestRequest::with_uri(uri).to_srv_request();
+            let res = normalize.call(req).await.unwrap();
+            match res.headers().get(header::LOCATION) {
+                Some(location) => location.to_str().unwrap().to_owned(),
+                None => uri.to_owned(),
+            }
+        }
+
+        let redirect = NormalizePath::trim().redirect(StatusCode::MOVED_PERMANENTLY);
+        let lowercase = redirect.lowercase(true);
+        let percent = redirect.normalize_percent_encoding(true);
+        let both = percent.lowercase(true);
+
+        assert_eq!(location(lowercase, "/Users/ALICE/").await, "/users/alice");
+        assert_eq!(
+            location(lowercase, "/%C3%A9T%c3%a9").await,
+            "/%C3%A9t%c3%a9"
+        );
+        assert_eq!(location(lowercase, "/%7E%").await, "/%7E%");
+
+        assert_eq!(location(percent, "/%7euser/a%2fb").await, "/~user/a%2Fb");
+        assert_eq!(location(percent, "/A%41%2D%c3%a9").await, "/AA-%C3%A9");
+        assert_eq!(location(percent, "/%+1%4").await, "/%+1%4");
+
+        assert_eq!(location(both, "/%7EUser/%41%2f").await, "/~user/a%2F");
+    }
 }
@@ -0,0 +1,619 @@
+//! For middleware documentation, see [`SecureHeaders`].
+
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops::Deref,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use actix_utils::future::{err, ok, ready, Ready};
+use base64::prelude::*;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use crate::{
+    dev::{Payload, Service, Transform},
+    error::ErrorInternalServerError,
+    http::header::{
+        self, ContentSecurityPolicy, CrossOriginEmbedderPolicy, CrossOriginOpenerPolicy, HeaderMap,
+        HeaderName, HeaderValue, PermissionsPolicy, StrictTransportSecurity,
+        TryIntoHeaderValue as _,
+    },
+    service::{ServiceRequest, ServiceResponse},
+    Error, FromRequest, HttpMessage as _, HttpRequest,
+};
+
+/// Middleware for setting security-related response headers.
+///
+/// By default, only `X-Content-Type-Options: nosniff` is set. Other headers are opt-in using the
+/// typed builder methods: [`Strict-Transport-Security`](Self::hsts),
+/// [`Content-Security-Policy`](Self::content_security_policy) and its
+/// [report-only](Self::content_security_policy_report_only) variant,
+/// [`Permissions-Policy`](Self::permissions_policy),
+/// [`Cross-Origin-Opener-Policy`](Self::cross_origin_opener_policy) and
+/// [`Cross-Origin-Embedder-Policy`](Self::cross_origin_embedder_policy).
+///
+/// Headers that are already set in a response will *not* be overwritten, allowing handlers to
+/// relax or tighten the policy for specific resources.
+///
+/// # CSP Nonces
+/// When a content security policy contains the [`ContentSecurityPolicy::NONCE`] placeholder, a
+/// fresh random nonce is generated for each request and substituted into the policy. Handlers
+/// and templates can obtain the nonce using the [`CspNonce`] extractor.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use actix_web::{
+///     http::header::{ContentSecurityPolicy, CrossOriginOpenerPolicy, StrictTransportSecurity},
+///     middleware::{CspNonce, SecureHeaders},
+///     web, App, HttpResponse,
+/// };
+///
+/// let app = App::new()
+///     .wrap(
+///         SecureHeaders::new()
+///             .hsts(StrictTransportSecurity::new(Duration::from_secs(31_536_000)))
+///             .content_security_policy(
+///                 ContentSecurityPolicy::new()
+///                     .directive("default-src", ["'self'"])
+///                     .directive("script-src", [ContentSecurityPolicy::NONCE]),
+///             )
+///             .cross_origin_opener_policy(CrossOriginOpenerPolicy::SameOrigin),
+///     )
+///     .route("/", web::get().to(|nonce: CspNonce| async move {
+///         HttpResponse::Ok()
+///             .content_type("text/html")
+///             .body(format!(r#"<script nonce="{nonce}">console.log("hi")</script>"#))
+///     }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecureHeaders {
+    inner: Rc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    headers: HeaderMap,
+    csp: Option<ContentSecurityPolicy>,
+    csp_report_only: Option<ContentSecurityPolicy>,
+}
+
+impl Default for SecureHeaders {
+    fn default() -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        );
+
+        SecureHeaders {
+            inner: Rc::new(Inner {
+                headers,
+                csp: None,
+                csp_report_only: None,
+            }),
+        }
+    }
+}
+
+impl SecureHeaders {
+    /// Constructs new `SecureHeaders` middleware that only sets `X-Content-Type-Options: nosniff`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner {
+        Rc::get_mut(&mut self.inner).expect("All secure headers must be configured before cloning.")
+    }
+
+    fn set(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.inner_mut().headers.insert(name, value);
+        self
+    }
+
+    /// Sets whether to send `X-Content-Type-Options: nosniff`, which stops browsers from guessing
+    /// the content type of responses.
+    ///
+    /// Enabled by default.
+    pub fn nosniff(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.set(
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            )
+        } else {
+            self.inner_mut()
+                .headers
+                .remove(header::X_CONTENT_TYPE_OPTIONS);
+            self
+        }
+    }
+
+    /// Sets the `Strict-Transport-Security` header.
+    ///
+    /// Browsers ignore this header on responses sent over plain HTTP, so it is safe to set
+    /// unconditionally when the application is also served over HTTPS.
+    pub fn hsts(self, hsts: StrictTransportSecurity) -> Self {
+        let value = hsts
+            .try_into_value()
+            .expect("HSTS is always a valid header");
+        self.set(header::STRICT_TRANSPORT_SECURITY, value)
+    }
+
+    /// Sets the enforced `Content-Security-Policy` header.
+    ///
+    /// # Panics
+    /// Panics if the policy is empty.
+    pub fn content_security_policy(mut self, policy: ContentSecurityPolicy) -> Self {
+        assert!(
+            policy.directives().next().is_some(),
+            "Content security policy must not be empty"
+        );
+
+        self.inner_mut().csp = Some(policy);
+        self
+    }
+
+    /// Sets the `Content-Security-Policy-Report-Only` header.
+    ///
+    /// A report-only policy can be set alongside an enforced policy to trial changes to it.
+    ///
+    /// # Panics
+    /// Panics if the policy is empty.
+    pub fn content_security_policy_report_only(mut self, policy: ContentSecurityPolicy) -> Self {
+        assert!(
+            policy.directives().next().is_some(),
+            "Content security policy must not be empty"
+        );
+
+        self.inner_mut().csp_report_only = Some(policy);
+        self
+    }
+
+    /// Sets the `Permissions-Policy` header.
+    ///
+    /// # Panics
+    /// Panics if the policy is empty.
+    pub fn permissions_policy(self, policy: PermissionsPolicy) -> Self {
+        let value = policy
+            .try_into_value()
+            .ok()
+            .filter(|value| !value.is_empty())
+            .expect("Permissions policy must not be empty");
+
+        self.set(header::PERMISSIONS_POLICY, value)
+    }
+
+    /// Sets the `Cross-Origin-Opener-Policy` header.
+    pub fn cross_origin_opener_policy(self, policy: CrossOriginOpenerPolicy) -> Self {
+        self.set(
+            header::CROSS_ORIGIN_OPENER_POLICY,
+            HeaderValue::from_static(policy.as_str()),
+        )
+    }
+
+    /// Sets the `Cross-Origin-Embedder-Policy` header.
+    pub fn cross_origin_embedder_policy(self, policy: CrossOriginEmbedderPolicy) -> Self {
+        self.set(
+            header::CROSS_ORIGIN_EMBEDDER_POLICY,
+            HeaderValue::from_static(policy.as_str()),
+        )
+    }
+}
+
+impl Inner {
+    fn needs_nonce(&self) -> bool {
+        self.csp
+            .iter()
+            .chain(&self.csp_report_only)
+            .any(ContentSecurityPolicy::has_nonce)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for SecureHeaders
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = SecureHeadersMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(SecureHeadersMiddleware {
+            service,
+            inner: Rc::clone(&self.inner),
+        }))
+    }
+}
+
+pub struct SecureHeadersMiddleware<S> {
+    service: S,
+    inner: Rc<Inner>,
+}
+
+impl<S, B> Service<ServiceRequest> for SecureHeadersMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = SecureHeadersFuture<S, B>;
+
+    actix_service::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let nonce = self.inner.needs_nonce().then(CspNonce::generate);
+
+        if let Some(nonce) = &nonce {
+            req.extensions_mut().insert(nonce.clone());
+        }
+
+        SecureHeadersFuture {
+            fut: self.service.call(req),
+            inner: Rc::clone(&self.inner),
+            nonce,
+            _body: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    pub struct SecureHeadersFuture<S: Service<ServiceRequest>, B> {
+        #[pin]
+        fut: S::Future,
+        inner: Rc<Inner>,
+        nonce: Option<CspNonce>,
+        _body: PhantomData<B>,
+    }
+}
+
+impl<S, B> Future for SecureHeadersFuture<S, B>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Output = <S::Future as Future>::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut res = ready!(this.fut.poll(cx))?;
+        let headers = res.headers_mut();
+
+        for (key, value) in this.inner.headers.iter() {
+            if !headers.contains_key(key) {
+                headers.insert(key.clone(), value.clone());
+            }
+        }
+
+        let policies = [
+            (header::CONTENT_SECURITY_POLICY, &this.inner.csp),
+            (
+                header::CONTENT_SECURITY_POLICY_REPORT_ONLY,
+                &this.inner.csp_report_only,
+            ),
+        ];
+
+        for (key, policy) in policies {
+            let Some(policy) = policy else {
+                continue;
+            };
+
+            if headers.contains_key(&key) {
+                continue;
+            }
+
+            let value = match this.nonce {
+                Some(nonce) if policy.has_nonce() => policy.with_nonce(nonce).try_into_value(),
+                _ => policy.clone().try_into_value(),
+            };
+
+            headers.insert(key, value.expect("CSP is always a valid header"));
+        }
+
+        Poll::Ready(Ok(res))
+    }
+}
+
+/// Extractor for the per-request CSP nonce generated by [`SecureHeaders`].
+///
+/// The nonce is base64-encoded and can be used directly in `nonce` attributes of `<script>` and
+/// `<style>` elements. It is only available when a configured content security policy contains the
+/// [`ContentSecurityPolicy::NONCE`] placeholder; otherwise, extraction fails with a
+/// `500 Internal Server Error`. Use `Option<CspNonce>` for handlers that are also served without
+/// a nonce.
+///
+/// # Examples
+/// ```
+/// use actix_web::{middleware::CspNonce, HttpResponse};
+///
+/// async fn index(nonce: CspNonce) -> HttpResponse {
+///     HttpResponse::Ok()
+///         .content_type("text/html")
+///         .body(format!(r#"<style nonce="{nonce}">body {{ margin: 0 }}</style>"#))
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CspNonce(Rc<str>);
+
+impl CspNonce {
+    fn generate() -> Self {
+        let mut bytes = [0; 16];
+        getrandom::getrandom(&mut bytes).expect("failed to generate random bytes");
+        CspNonce(BASE64_STANDARD.encode(bytes).into())
+    }
+
+    /// Returns the nonce as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for CspNonce {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CspNonce {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CspNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for CspNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CspNonce").field(&"********").finish()
+    }
+}
+
+impl FromRequest for CspNonce {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        match req.extensions().get::<CspNonce>() {
+            Some(nonce) => ok(nonce.clone()),
+            None => {
+                log::debug!(
+                    "Failed to extract CSP nonce; SecureHeaders middleware is not configured with \
+                     a nonce-based policy. Request path: {:?}",
+                    req.path(),
+                );
+
+                err(ErrorInternalServerError("Missing CSP nonce"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_service::IntoService;
+
+    use super::*;
+    use crate::{
+        http::StatusCode,
+        test::{self, TestRequest},
+        web, App, HttpResponse,
+    };
+
+    #[actix_rt::test]
+    async fn default_headers() {
+        let mw = SecureHeaders::new()
+            .new_transform(test::ok_service())
+            .await
+            .unwrap();
+
+        let res = mw
+            .call(TestRequest::default().to_srv_request())
+            .await
+            .unwrap();
+        assert_eq!(
+            res.headers().get(header::X_CONTENT_TYPE_OPTIONS).unwrap(),
+            "nosniff"
+        );
+        assert!(!res.headers().contains_key(header::CONTENT_SECURITY_POLICY));
+
+        let mw = SecureHeaders::new()
+            .nosniff(false)
+            .new_transform(test::ok_service())
+            .await
+            .unwrap();
+
+        let res = mw
+            .call(TestRequest::default().to_srv_request())
+            .await
+            .unwrap();
+        assert!(res.headers().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn typed_headers() {
+        let mw = SecureHeaders::new()
+            .hsts(StrictTransportSecurity::new(Duration::from_secs(300)).include_subdomains())
+            .content_security_policy(
+                ContentSecurityPolicy::new().directive("default-src", ["'self'"]),
+            )
+            .content_security_policy_report_only(
+                ContentSecurityPolicy::new()
+                    .directive("default-src", ["'none'"])
+                    .directive("report-uri", ["/csp"]),
+            )
+            .permissions_policy(PermissionsPolicy::new().deny("camera"))
+            .cross_origin_opener_policy(CrossOriginOpenerPolicy::SameOrigin)
+            .cross_origin_embedder_policy(CrossOriginEmbedderPolicy::RequireCorp)
+            .new_transform(test::ok_service())
+            .await
+            .unwrap();
+
+        let req = TestRequest::default().to_srv_request();
+        assert!(req.extensions().get::<CspNonce>().is_none());
+
+        let res = mw.call(req).await.unwrap();
+        let headers = res.headers();
+        assert_eq!(
+            headers.get(header::STRICT_TRANSPORT_SECURITY).unwrap(),
+            "max-age=300; includeSubDomains"
+        );
+        assert_eq!(
+            headers.get(header::CONTENT_SECURITY_POLICY).unwrap(),
+            "default-src 'self'"
+        );
+        assert_eq!(
+            headers
+                .get(header::CONTENT_SECURITY_POLICY_REPORT_ONLY)
+                .unwrap(),
+            "default-src 'none'; report-uri /csp"
+        );
+        assert_eq!(
+            headers.get(header::PERMISSIONS_POLICY).unwrap(),
+            "camera=()"
+        );
+        assert_eq!(
+            headers.get(header::CROSS_ORIGIN_OPENER_POLICY).unwrap(),
+            "same-origin"
+        );
+        assert_eq!(
+            headers.get(header::CROSS_ORIGIN_EMBEDDER_POLICY).unwrap(),
+            "require-corp"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn no_override_existing() {
+        let srv = |req: ServiceRequest| {
+            ok(req.into_response(
+                HttpResponse::Ok()
+                    .insert_header((header::X_CONTENT_TYPE_OPTIONS, "custom"))
+                    .insert_header((header::CONTENT_SECURITY_POLICY, "img-src *"))
+                    .finish(),
+            ))
+        };
+
+        let mw = SecureHeaders::new()
+            .content_security_policy(
+                ContentSecurityPolicy::new()
+                    .directive("script-src", [ContentSecurityPolicy::NONCE]),
+            )
+            .new_transform(srv.into_service())
+            .await
+            .unwrap();
+
+        let res = mw
+            .call(TestRequest::default().to_srv_request())
+            .await
+            .unwrap();
+        assert_eq!(
+            res.headers().get(header::X_CONTENT_TYPE_OPTIONS).unwrap(),
+            "custom"
+        );
+        assert_eq!(
+            res.headers().get(header::CONTENT_SECURITY_POLICY).unwrap(),
+            "img-src *"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn per_request_nonce() {
+        let srv = test::init_service(
+            App::new()
+                .wrap(
+                    SecureHeaders::new()
+                        .content_security_policy(
+                            ContentSecurityPolicy::new()
+                                .directive("script-src", ["'self'", ContentSecurityPolicy::NONCE]),
+                        )
+                        .content_security_policy_report_only(
+                            ContentSecurityPolicy::new()
+                                .directive("script-src", [ContentSecurityPolicy::NONCE])
+                                .directive("report-uri", ["/csp"]),
+                        ),
+                )
+                .route(
+                    "/",
+                    web::get().to(|nonce: CspNonce| HttpResponse::Ok().body(nonce.to_string())),
+                ),
+        )
+        .await;
+
+        let mut nonces = Vec::new();
+
+        for _ in 0..2 {
+            let res = test::call_service(&srv, TestRequest::default().to_request()).await;
+            assert_eq!(res.status(), StatusCode::OK);
+
+            let csp = res.headers().get(header::CONTENT_SECURITY_POLICY).unwrap();
+            let csp = csp.to_str().unwrap().to_owned();
+            let csp_ro = res
+                .headers()
+                .get(header::CONTENT_SECURITY_POLICY_REPORT_ONLY)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_owned();
+
+            let nonce = test::read_body(res).await;
+            let nonce = std::str::from_utf8(&nonce).unwrap().to_owned();
+            assert_eq!(BASE64_STANDARD.decode(&nonce).unwrap().len(), 16);
+
+            assert_eq!(csp, format!("script-src 'self' 'nonce-{nonce}'"));
+            assert_eq!(
+                csp_ro,
+                format!("script-src 'nonce-{nonce}'; report-uri /csp")
+            );
+
+            nonces.push(nonce);
+        }
+
+        assert_ne!(nonces[0], nonces[1]);
+    }
+
+    #[actix_rt::test]
+    async fn missing_nonce() {
+        let srv = test::init_service(
+            App::new()
+                .wrap(SecureHeaders::new())
+                .route("/", web::get().to(|_: CspNonce| HttpResponse::Ok()))
+                .route(
+                    "/opt",
+                    web::get().to(|nonce: Option<CspNonce>| {
+                        assert!(nonce.is_none());
+                        HttpResponse::Ok()
+                    }),
+                ),
+        )
+        .await;
+
+        let res = test::call_service(&srv, TestRequest::default().to_request()).await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let req = TestRequest::default().uri("/opt").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_policy() {
+        let _ = SecureHeaders::new().content_security_policy(ContentSecurityPolicy::new());
+    }
+}
@@ -0,0 +1,1187 @@
+//! OpenAPI document generation.
+//!
+//! When the `openapi` feature is enabled, handlers registered using the [routing macros] describe
+//! their operation using the types of their arguments and return value. Extractors contribute
+//! parameters and request bodies through [`ApiRequest`] and return types contribute responses
+//! through [`ApiResponder`]. Doc comments on the handler become the summary and description of the
+//! operation. Argument and return types that do not implement these traits are left out of the
+//! description.
+//!
+//! The descriptions are collected in the [route table] and assembled into an OpenAPI 3.1 document
+//! using [`OpenApi`], which can be served as JSON.
+//!
+//! Resources registered without the routing macros can be described using
+//! [`Resource::operation`](crate::Resource::operation).
+//!
+//! ```
+//! use actix_web::{
+//!     get,
+//!     openapi::{ApiSchema, OpenApi, Schema},
+//!     web, App, HttpRequest,
+//! };
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Pagination {
+//!     page: u32,
+//! }
+//!
+//! impl ApiSchema for Pagination {
+//!     fn schema() -> Schema {
+//!         Schema::object().property("page", u32::schema(), true)
+//!     }
+//! }
+//!
+//! /// Lists user names.
+//! #[get("/users")]
+//! async fn list_users(query: web::Query<Pagination>) -> web::Json<Vec<String>> {
+//!     web::Json(vec![format!("page {}", query.page)])
+//! }
+//!
+//! #[get("/openapi.json")]
+//! async fn openapi(req: HttpRequest) -> web::Json<OpenApi> {
+//!     let routes = req.resource_map().routes();
+//!     web::Json(OpenApi::new("Users", "1.0").with_routes(&routes))
+//! }
+//!
+//! let app = App::new().service(list_users).service(openapi);
+//! ```
+//!
+//! [routing macros]: crate::get
+//! [route table]: crate::dev::ResourceMap::routes
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap},
+};
+
+use bytes::Bytes;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    dev::RouteInfo,
+    http::{header::Header as ParseHeader, StatusCode},
+    web::{Either, Form, Header, Html, Json, Path, Query, Valid},
+};
+
+/// JSON Schema describing a parameter or body.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Schema(Value);
+
+impl Schema {
+    /// Constructs a schema from its JSON representation.
+    pub fn new(value: Value) -> Self {
+        Schema(value)
+    }
+
+    /// Constructs a schema that accepts any value.
+    pub fn any() -> Self {
+        Schema(json!({}))
+    }
+
+    /// Constructs a schema for an object without properties.
+    ///
+    /// Properties are added using [`property`](Self::property).
+    pub fn object() -> Self {
+        Schema(json!({ "type": "object" }))
+    }
+
+    /// Adds a property to an object schema.
+    pub fn property(mut self, name: &str, schema: Schema, required: bool) -> Self {
+        if let Value::Object(obj) = &mut self.0 {
+            if let Value::Object(props) = obj
+                .entry("properties")
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                props.insert(name.to_owned(), schema.0);
+            }
+
+            if required {
+                if let Value::Array(required) = obj
+                    .entry("required")
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    required.push(Value::String(name.to_owned()));
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Returns the JSON representation of the schema.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Returns the properties of an object schema along with whether they are required.
+    fn properties(&self) -> Vec<(&str, Schema, bool)> {
+        let required = self.0.get("required").and_then(Value::as_array);
+
+        self.0
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(name, schema)| {
+                let is_required =
+                    required.is_some_and(|req| req.iter().any(|r| r.as_str() == Some(name)));
+
+                (name.as_str(), Schema(schema.clone()), is_required)
+            })
+            .collect()
+    }
+}
+
+impl Serialize for Schema {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// A type that can be described using a JSON Schema.
+///
+/// Implemented for primitive types and standard collections. Types used in extractors such as
+/// [`Json`] and [`Query`] implement this trait to be included in generated documents.
+pub trait ApiSchema {
+    /// Returns the schema describing values of this type.
+    fn schema() -> Schema;
+}
+
+macro_rules! schema_impl {
+    ($schema:tt => $($ty:ty),+) => {
+        $(
+            impl ApiSchema for $ty {
+                fn schema() -> Schema {
+                    Schema(json!($schema))
+                }
+            }
+        )+
+    };
+}
+
+schema_impl!({ "type": "boolean" } => bool);
+schema_impl!({ "type": "integer", "format": "int32" } => i8, i16, i32, u8, u16, u32);
+schema_impl!({ "type": "integer", "format": "int64" } => i64, u64, isize, usize);
+schema_impl!({ "type": "number", "format": "float" } => f32);
+schema_impl!({ "type": "number", "format": "double" } => f64);
+schema_impl!({ "type": "string" } => String, str, char);
+schema_impl!({ "type": "null" } => ());
+schema_impl!({} => Value);
+
+impl<T: ApiSchema + ?Sized> ApiSchema for &T {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema + ToOwned + ?Sized> ApiSchema for Cow<'_, T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Box<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    fn schema() -> Schema {
+        Schema(json!({ "anyOf": [T::schema(), { "type": "null" }] }))
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+    fn schema() -> Schema {
+        Schema(json!({ "type": "array", "items": T::schema() }))
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for [T] {
+    fn schema() -> Schema {
+        Schema(json!({ "type": "array", "items": T::schema() }))
+    }
+}
+
+impl<K, V: ApiSchema, S> ApiSchema for HashMap<K, V, S> {
+    fn schema() -> Schema {
+        Schema(json!({ "type": "object", "additionalProperties": V::schema() }))
+    }
+}
+
+impl<K, V: ApiSchema> ApiSchema for BTreeMap<K, V> {
+    fn schema() -> Schema {
+        Schema(json!({ "type": "object", "additionalProperties": V::schema() }))
+    }
+}
+
+macro_rules! tuple_schema_impl {
+    ($($ty:ident),+) => {
+        impl<$($ty: ApiSchema),+> ApiSchema for ($($ty,)+) {
+            fn schema() -> Schema {
+                let items = [$($ty::schema()),+];
+
+                Schema(json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": items.len(),
+                    "maxItems": items.len(),
+                }))
+            }
+        }
+    };
+}
+
+tuple_schema_impl!(A);
+tuple_schema_impl!(A, B);
+tuple_schema_impl!(A, B, C);
+tuple_schema_impl!(A, B, C, D);
+tuple_schema_impl!(A, B, C, D, E);
+
+/// Location of an operation parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterLocation {
+    /// Dynamic segment of the path.
+    Path,
+
+    /// Query string field.
+    Query,
+
+    /// Request header.
+    Header,
+}
+
+impl ParameterLocation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ParameterLocation::Path => "path",
+            ParameterLocation::Query => "query",
+            ParameterLocation::Header => "header",
+        }
+    }
+}
+
+/// Parameter of an operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    name: String,
+    location: ParameterLocation,
+    required: bool,
+    schema: Schema,
+}
+
+impl Parameter {
+    /// Constructs a parameter. Path parameters are required, others are optional.
+    pub fn new(name: impl Into<String>, location: ParameterLocation, schema: Schema) -> Self {
+        Parameter {
+            name: name.into(),
+            location,
+            required: location == ParameterLocation::Path,
+            schema,
+        }
+    }
+
+    /// Sets whether the parameter is required.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the location of the parameter.
+    pub fn location(&self) -> ParameterLocation {
+        self.location
+    }
+
+    /// Returns `true` if the parameter is required.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Returns the schema of the parameter.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn to_value(&self) -> Value {
+        json!({
+            "name": self.name,
+            "in": self.location.as_str(),
+            "required": self.required,
+            "schema": self.schema,
+        })
+    }
+}
+
+/// Response of an operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    description: String,
+    content: BTreeMap<String, Schema>,
+}
+
+impl Response {
+    /// Constructs a response without content.
+    pub fn new(description: impl Into<String>) -> Self {
+        Response {
+            description: description.into(),
+            content: BTreeMap::new(),
+        }
+    }
+
+    /// Constructs a response without content, described by the canonical reason of `status`.
+    pub fn for_status(status: StatusCode) -> Self {
+        Response::new(status.canonical_reason().unwrap_or_default())
+    }
+
+    /// Adds content of the given media type to the response.
+    pub fn content(mut self, media_type: &str, schema: Schema) -> Self {
+        self.content.insert(media_type.to_owned(), schema);
+        self
+    }
+
+    /// Returns the description of the response.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the schema of the response content of the given media type.
+    pub fn content_schema(&self, media_type: &str) -> Option<&Schema> {
+        self.content.get(media_type)
+    }
+
+    fn to_value(&self) -> Value {
+        let mut value = json!({ "description": self.description });
+
+        if !self.content.is_empty() {
+            value["content"] = content_value(&self.content);
+        }
+
+        value
+    }
+}
+
+fn content_value(content: &BTreeMap<String, Schema>) -> Value {
+    content
+        .iter()
+        .map(|(media_type, schema)| (media_type.clone(), json!({ "schema": schema })))
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+/// Description of the operation performed by a handler.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Operation {
+    operation_id: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    parameters: Vec<Parameter>,
+    path_schema: Option<Schema>,
+    request_body: BTreeMap<String, Schema>,
+    request_body_required: bool,
+    responses: BTreeMap<String, Response>,
+}
+
+impl Operation {
+    /// Constructs an empty operation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the operation ID.
+    pub fn operation_id(&mut self, id: impl Into<String>) -> &mut Self {
+        self.operation_id = Some(id.into());
+        self
+    }
+
+    /// Sets the summary.
+    pub fn summary(&mut self, summary: impl Into<String>) -> &mut Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Sets the description.
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the summary and description from a doc comment.
+    ///
+    /// The first paragraph becomes the summary and the rest becomes the description.
+    pub fn doc(&mut self, doc: &str) -> &mut Self {
+        let doc = doc.trim();
+        let (summary, description) = doc.split_once("\n\n").unwrap_or((doc, ""));
+
+        let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !summary.is_empty() {
+            self.summary = Some(summary);
+        }
+
+        let description = description.trim();
+        if !description.is_empty() {
+            self.description = Some(description.to_owned());
+        }
+
+        self
+    }
+
+    /// Adds a parameter, replacing any parameter with the same name and location.
+    pub fn parameter(&mut self, parameter: Parameter) -> &mut Self {
+        self.parameters
+            .retain(|p| p.name != parameter.name || p.location != parameter.location);
+        self.parameters.push(parameter);
+        self
+    }
+
+    /// Sets the schema of the path parameters, as extracted by [`Path`].
+    ///
+    /// When assembling a document, parameters for the dynamic segments of the resource pattern
+    /// take their schema from the matching property of an object schema, the matching item of a
+    /// tuple schema or, for patterns with a single segment, the whole schema.
+    pub fn path_schema(&mut self, schema: Schema) -> &mut Self {
+        self.path_schema = Some(schema);
+        self
+    }
+
+    /// Adds request body content of the given media type.
+    pub fn request_body(&mut self, media_type: &str, schema: Schema) -> &mut Self {
+        self.request_body.insert(media_type.to_owned(), schema);
+        self.request_body_required = true;
+        self
+    }
+
+    /// Adds a response for a status code, or `"default"` for any other status.
+    ///
+    /// If a response already exists for the status, content is merged into it.
+    pub fn response(&mut self, status: &str, response: Response) -> &mut Self {
+        match self.responses.get_mut(status) {
+            Some(existing) => existing.content.extend(response.content),
+            None => {
+                self.responses.insert(status.to_owned(), response);
+            }
+        }
+
+        self
+    }
+
+    /// Returns the operation ID.
+    pub fn get_operation_id(&self) -> Option<&str> {
+        self.operation_id.as_deref()
+    }
+
+    /// Returns the summary.
+    pub fn get_summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Returns the description.
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the parameters.
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Returns the schema of the request body content of the given media type.
+    pub fn request_body_schema(&self, media_type: &str) -> Option<&Schema> {
+        self.request_body.get(media_type)
+    }
+
+    /// Returns the response for a status code or `"default"`.
+    pub fn get_response(&self, status: &str) -> Option<&Response> {
+        self.responses.get(status)
+    }
+
+    /// Marks all parameters and the request body as optional.
+    fn make_optional(&mut self) {
+        for param in &mut self.parameters {
+            param.required = false;
+        }
+
+        self.request_body_required = false;
+    }
+
+    /// Merges parameters and request body of `other` into `self`.
+    fn merge_request(&mut self, other: Operation) {
+        for param in other.parameters {
+            self.parameter(param);
+        }
+
+        if other.path_schema.is_some() {
+            self.path_schema = other.path_schema;
+        }
+
+        if !other.request_body.is_empty() {
+            self.request_body.extend(other.request_body);
+            self.request_body_required = other.request_body_required;
+        }
+    }
+
+    /// Returns a copy of the operation with parameters for the dynamic segments of `pattern`.
+    fn for_pattern(&self, segments: &[&str]) -> Operation {
+        let mut op = self.clone();
+
+        for (idx, name) in segments.iter().enumerate() {
+            let schema = op
+                .path_schema
+                .as_ref()
+                .and_then(|schema| {
+                    let schema = schema.as_value();
+
+                    schema
+                        .get("properties")
+                        .and_then(|props| props.get(*name))
+                        .or_else(|| schema.get("prefixItems").and_then(|items| items.get(idx)))
+                        .or_else(|| {
+                            let is_scalar = schema
+                                .get("type")
+                                .is_some_and(|ty| ty != "object" && ty != "array");
+
+                            (segments.len() == 1 && is_scalar).then_some(schema)
+                        })
+                })
+                .cloned()
+                .map_or_else(String::schema, Schema);
+
+            if !op
+                .parameters
+                .iter()
+                .any(|p| p.location == ParameterLocation::Path && p.name == *name)
+            {
+                op.parameters
+                    .push(Parameter::new(*name, ParameterLocation::Path, schema));
+            }
+        }
+
+        op
+    }
+
+    fn to_value(&self) -> Value {
+        let mut value = Map::new();
+
+        if let Some(ref id) = self.operation_id {
+            value.insert("operationId".to_owned(), id.as_str().into());
+        }
+
+        if let Some(ref summary) = self.summary {
+            value.insert("summary".to_owned(), summary.as_str().into());
+        }
+
+        if let Some(ref description) = self.description {
+            value.insert("description".to_owned(), description.as_str().into());
+        }
+
+        if !self.parameters.is_empty() {
+            let params = self.parameters.iter().map(Parameter::to_value).collect();
+            value.insert("parameters".to_owned(), Value::Array(params));
+        }
+
+        if !self.request_body.is_empty() {
+            value.insert(
+                "requestBody".to_owned(),
+                json!({
+                    "required": self.request_body_required,
+                    "content": content_value(&self.request_body),
+                }),
+            );
+        }
+
+        let mut responses = self
+            .responses
+            .iter()
+            .map(|(status, res)| (status.clone(), res.to_value()))
+            .collect::<Map<_, _>>();
+
+        // at least one response is required by the specification
+        if responses.is_empty() {
+            responses.insert("default".to_owned(), Response::new("Response").to_value());
+        }
+
+        value.insert("responses".to_owned(), Value::Object(responses));
+
+        Value::Object(value)
+    }
+}
+
+/// Converts an actix path pattern to an OpenAPI path template, returning it together with the names
+/// of its dynamic segments.
+///
+/// Custom regexes are removed and tail segments become plain segments. Unnamed tails are dropped.
+fn path_template(pattern: &str) -> (String, Vec<&str>) {
+    let mut template = String::with_capacity(pattern.len());
+    let mut segments = Vec::new();
+    let mut rem = pattern;
+
+    while let Some(start) = rem.find('{') {
+        template.push_str(&rem[..start]);
+
+        // find the matching closing brace, skipping braces in custom regexes
+        let mut depth = 0;
+        let end = rem[start..]
+            .char_indices()
+            .find_map(|(idx, c)| {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+
+                (depth == 0).then_some(start + idx)
+            })
+            .unwrap_or(rem.len() - 1);
+
+        let param = &rem[start + 1..end];
+        let name = param.split_once(':').map_or(param, |(name, _)| name);
+
+        template.push('{');
+        template.push_str(name);
+        template.push('}');
+        segments.push(name);
+
+        rem = &rem[end + 1..];
+        rem = rem.strip_prefix('*').unwrap_or(rem);
+    }
+
+    template.push_str(rem.strip_suffix('*').unwrap_or(rem));
+
+    (template, segments)
+}
+
+/// OpenAPI 3.1 document, assembled from the route table of an app.
+///
+/// Serializes to the JSON representation of the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    paths: BTreeMap<String, BTreeMap<String, Operation>>,
+    operation_ids: BTreeSet<String>,
+}
+
+impl OpenApi {
+    /// Constructs a document without operations, with the given API title and version.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        OpenApi {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            paths: BTreeMap::new(),
+            operation_ids: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the API description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds the operations of the given routes.
+    ///
+    /// See [`add_route`](Self::add_route).
+    pub fn with_routes(mut self, routes: &[RouteInfo]) -> Self {
+        for route in routes {
+            self.add_route(route);
+        }
+
+        self
+    }
+
+    /// Adds the operation of `route`, once for each of its methods.
+    ///
+    /// Routes without an operation, or which are not restricted to some methods, are ignored.
+    ///
+    /// Operation IDs are kept unique across the document. Operations for all but the first method
+    /// of a route have their ID suffixed with the lowercase method name, e.g. `file_head` for a
+    /// route handling `GET` and `HEAD`. IDs that are still taken, such as those of handlers
+    /// registered for several patterns, are suffixed with a number.
+    pub fn add_route(&mut self, route: &RouteInfo) {
+        let (Some(op), Some(methods)) = (route.operation(), route.methods()) else {
+            return;
+        };
+
+        let (template, segments) = path_template(route.pattern());
+        let op = op.for_pattern(&segments);
+
+        for (idx, method) in methods.iter().enumerate() {
+            let method = method.as_str().to_ascii_lowercase();
+
+            let mut op = op.clone();
+            if let Some(id) = op.operation_id.take() {
+                let id = if idx == 0 {
+                    id
+                } else {
+                    format!("{id}_{method}")
+                };
+                op.operation_id = Some(self.unique_operation_id(id));
+            }
+
+            self.paths
+                .entry(template.clone())
+                .or_default()
+                .insert(method, op);
+        }
+    }
+
+    /// Reserves and returns `id`, suffixed with a number if it is used by another operation.
+    fn unique_operation_id(&mut self, id: String) -> String {
+        let id = if self.operation_ids.contains(&id) {
+            (2..)
+                .map(|n| format!("{id}_{n}"))
+                .find(|candidate| !self.operation_ids.contains(candidate))
+                .unwrap()
+        } else {
+            id
+        };
+
+        self.operation_ids.insert(id.clone());
+        id
+    }
+
+    /// Returns the operation for a path template and lowercase method name.
+    pub fn operation(&self, path: &str, method: &str) -> Option<&Operation> {
+        self.paths.get(path)?.get(method)
+    }
+
+    /// Returns the JSON representation of the document.
+    pub fn to_value(&self) -> Value {
+        let mut info = json!({ "title": self.title, "version": self.version });
+
+        if let Some(ref description) = self.description {
+            info["description"] = description.as_str().into();
+        }
+
+        let paths = self
+            .paths
+            .iter()
+            .map(|(path, ops)| {
+                let ops = ops
+                    .iter()
+                    .map(|(method, op)| (method.clone(), op.to_value()))
+                    .collect::<Map<_, _>>();
+
+                (path.clone(), Value::Object(ops))
+            })
+            .collect::<Map<_, _>>();
+
+        json!({
+            "openapi": "3.1.0",
+            "info": info,
+            "paths": paths,
+        })
+    }
+}
+
+impl Serialize for OpenApi {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+/// An extractor that can describe what it extracts from requests.
+///
+/// Implement this trait for custom extractors to include their parameters or request body in
+/// generated documents.
+pub trait ApiRequest {
+    /// Adds the parameters or request body extracted by this type to `op`.
+    fn describe(op: &mut Operation);
+}
+
+impl<T: ApiSchema> ApiRequest for Path<T> {
+    fn describe(op: &mut Operation) {
+        op.path_schema(T::schema());
+    }
+}
+
+impl<T: ApiSchema> ApiRequest for Query<T> {
+    fn describe(op: &mut Operation) {
+        for (name, schema, required) in T::schema().properties() {
+            op.parameter(Parameter::new(name, ParameterLocation::Query, schema).required(required));
+        }
+    }
+}
+
+impl<T: ApiSchema> ApiRequest for Json<T> {
+    fn describe(op: &mut Operation) {
+        op.request_body("application/json", T::schema());
+    }
+}
+
+impl<T: ApiSchema> ApiRequest for Form<T> {
+    fn describe(op: &mut Operation) {
+        op.request_body("application/x-www-form-urlencoded", T::schema());
+    }
+}
+
+impl<T: ParseHeader> ApiRequest for Header<T> {
+    fn describe(op: &mut Operation) {
+        op.parameter(
+            Parameter::new(
+                T::name().as_str(),
+                ParameterLocation::Header,
+                String::schema(),
+            )
+            .required(true),
+        );
+    }
+}
+
+impl ApiRequest for String {
+    fn describe(op: &mut Operation) {
+        op.request_body("text/plain", String::schema());
+    }
+}
+
+impl ApiRequest for Bytes {
+    fn describe(op: &mut Operation) {
+        op.request_body(
+            "application/octet-stream",
+            Schema(json!({ "type": "string", "contentMediaType": "application/octet-stream" })),
+        );
+    }
+}
+
+impl<T: ApiRequest> ApiRequest for Option<T> {
+    fn describe(op: &mut Operation) {
+        let mut inner = Operation::new();
+        T::describe(&mut inner);
+        inner.make_optional();
+        op.merge_request(inner);
+    }
+}
+
+impl<T: ApiRequest> ApiRequest for Valid<T> {
+    fn describe(op: &mut Operation) {
+        T::describe(op);
+    }
+}
+
+/// A responder that can describe the responses it produces.
+///
+/// Implement this trait for custom responders to include their responses in generated documents.
+pub trait ApiResponder {
+    /// Adds the responses produced by this type to `op`.
+    fn describe(op: &mut Operation);
+}
+
+impl ApiResponder for String {
+    fn describe(op: &mut Operation) {
+        op.response(
+            "200",
+            Response::for_status(StatusCode::OK).content("text/plain", String::schema()),
+        );
+    }
+}
+
+impl ApiResponder for &'static str {
+    fn describe(op: &mut Operation) {
+        <String as ApiResponder>::describe(op);
+    }
+}
+
+impl ApiResponder for Bytes {
+    fn describe(op: &mut Operation) {
+        op.response(
+            "200",
+            Response::for_status(StatusCode::OK).content(
+                "application/octet-stream",
+                Schema(json!({ "type": "string", "contentMediaType": "application/octet-stream" })),
+            ),
+        );
+    }
+}
+
+impl ApiResponder for () {
+    fn describe(op: &mut Operation) {
+        op.response("200", Response::for_status(StatusCode::OK));
+    }
+}
+
+impl ApiResponder for Html {
+    fn describe(op: &mut Operation) {
+        op.response(
+            "200",
+            Response::for_status(StatusCode::OK).content("text/html", String::schema()),
+        );
+    }
+}
+
+impl<T: ApiSchema> ApiResponder for Json<T> {
+    fn describe(op: &mut Operation) {
+        op.response(
+            "200",
+            Response::for_status(StatusCode::OK).content("application/json", T::schema()),
+        );
+    }
+}
+
+impl<T: ApiResponder> ApiResponder for Option<T> {
+    fn describe(op: &mut Operation) {
+        T::describe(op);
+        op.response("404", Response::for_status(StatusCode::NOT_FOUND));
+    }
+}
+
+impl<T: ApiResponder, E> ApiResponder for Result<T, E> {
+    fn describe(op: &mut Operation) {
+        T::describe(op);
+        op.response("default", Response::new("Error"));
+    }
+}
+
+impl<L: ApiResponder, R: ApiResponder> ApiResponder for Either<L, R> {
+    fn describe(op: &mut Operation) {
+        L::describe(op);
+        R::describe(op);
+    }
+}
+
+/// Support for the routing macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    use super::{ApiRequest, ApiResponder, Operation};
+
+    /// Describes a type if it implements [`ApiRequest`] or [`ApiResponder`], doing nothing
+    /// otherwise.
+    ///
+    /// Calling the `describe_*` methods on `&Probe<T>` resolves to the implementations for
+    /// `Probe<T>` when their bounds hold, and to the fallback implementations for `&Probe<T>`
+    /// otherwise.
+    pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+    impl<T: ?Sized> Probe<T> {
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Probe(PhantomData)
+        }
+    }
+
+    pub trait DescribeRequest {
+        fn describe_request(&self, op: &mut Operation);
+    }
+
+    impl<T: ApiRequest> DescribeRequest for Probe<T> {
+        fn describe_request(&self, op: &mut Operation) {
+            T::describe(op);
+        }
+    }
+
+    pub trait DescribeRequestFallback {
+        fn describe_request(&self, op: &mut Operation);
+    }
+
+    impl<T: ?Sized> DescribeRequestFallback for &Probe<T> {
+        fn describe_request(&self, _: &mut Operation) {}
+    }
+
+    pub trait DescribeResponse {
+        fn describe_response(&self, op: &mut Operation);
+    }
+
+    impl<T: ApiResponder> DescribeResponse for Probe<T> {
+        fn describe_response(&self, op: &mut Operation) {
+            T::describe(op);
+        }
+    }
+
+    pub trait DescribeResponseFallback {
+        fn describe_response(&self, op: &mut Operation);
+    }
+
+    impl<T: ?Sized> DescribeResponseFallback for &Probe<T> {
+        fn describe_response(&self, _: &mut Operation) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rmap::ResourceMap;
+
+    #[test]
+    fn path_templates() {
+        assert_eq!(path_template("/users"), ("/users".to_owned(), vec![]));
+        assert_eq!(
+            path_template("/users/{id}/posts/{post:\\d{2,}}"),
+            ("/users/{id}/posts/{post}".to_owned(), vec!["id", "post"])
+        );
+        assert_eq!(
+            path_template("/files/{tail}*"),
+            ("/files/{tail}".to_owned(), vec!["tail"])
+        );
+        assert_eq!(path_template("/static/*"), ("/static/".to_owned(), vec![]));
+    }
+
+    #[test]
+    fn extractors_and_responders() {
+        struct Filter;
+
+        impl ApiSchema for Filter {
+            fn schema() -> Schema {
+                Schema::object()
+                    .property("q", String::schema(), true)
+                    .property("limit", u32::schema(), false)
+            }
+        }
+
+        let mut op = Operation::new();
+        <Path<(u64, String)>>::describe(&mut op);
+        <Query<Filter>>::describe(&mut op);
+        <Option<Json<Vec<u8>>> as ApiRequest>::describe(&mut op);
+        <Option<Json<String>> as ApiResponder>::describe(&mut op);
+        <Result<String, crate::Error> as ApiResponder>::describe(&mut op);
+        op.doc(" Finds posts.\n\n Matches `q` in titles. ");
+
+        let op = op.for_pattern(&["user", "slug"]);
+        let value = op.to_value();
+
+        assert_eq!(value["summary"], "Finds posts.");
+        assert_eq!(value["description"], "Matches `q` in titles.");
+
+        assert_eq!(
+            value["parameters"],
+            json!([
+                {
+                    "name": "limit",
+                    "in": "query",
+                    "required": false,
+                    "schema": { "type": "integer", "format": "int32" },
+                },
+                { "name": "q", "in": "query", "required": true, "schema": { "type": "string" } },
+                {
+                    "name": "user",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer", "format": "int64" },
+                },
+                { "name": "slug", "in": "path", "required": true, "schema": { "type": "string" } },
+            ])
+        );
+
+        assert_eq!(value["requestBody"]["required"], false);
+        assert_eq!(
+            value["requestBody"]["content"]["application/json"]["schema"]["items"]["type"],
+            "integer"
+        );
+
+        let responses = value["responses"].as_object().unwrap();
+        assert_eq!(
+            responses.keys().collect::<Vec<_>>(),
+            ["200", "404", "default"]
+        );
+        assert_eq!(
+            responses["200"]["content"],
+            json!({
+                "application/json": { "schema": { "type": "string" } },
+                "text/plain": { "schema": { "type": "string" } },
+            })
+        );
+    }
+
+    // borrows are needed to select the fallback implementations
+    #[allow(clippy::needless_borrow)]
+    #[test]
+    fn probe_fallback() {
+        use __private::{
+            DescribeRequest as _, DescribeRequestFallback as _, DescribeResponse as _,
+            DescribeResponseFallback as _, Probe,
+        };
+
+        struct NotDescribed;
+
+        let mut op = Operation::new();
+        (&Probe::<Json<u32>>::new()).describe_request(&mut op);
+        (&Probe::<NotDescribed>::new()).describe_request(&mut op);
+        (&Probe::<Json<NotDescribed>>::new()).describe_response(&mut op);
+        (&Probe::<String>::new()).describe_response(&mut op);
+
+        assert!(op.request_body_schema("application/json").is_some());
+        assert_eq!(op.responses.keys().collect::<Vec<_>>(), ["200"]);
+        assert!(op
+            .get_response("200")
+            .unwrap()
+            .content
+            .contains_key("text/plain"));
+    }
+
+    #[test]
+    fn document_from_routes() {
+        let mut op = Operation::new();
+        op.operation_id("get_user");
+        <Path<u32>>::describe(&mut op);
+
+        let mut root = ResourceMap::new(actix_router::ResourceDef::prefix(""));
+        let mut details = crate::rmap::ServiceDetails::new(
+            &[Box::new(crate::guard::Get()) as Box<dyn crate::guard::Guard>],
+            0,
+        );
+        details.operation = Some(op);
+        root.add_service(
+            &mut actix_router::ResourceDef::new("/users/{id:\\d+}"),
+            None,
+            details,
+        );
+
+        let mut op = Operation::new();
+        op.operation_id("file");
+        for pattern in ["/files/{tail}*", "/static/{tail}*"] {
+            let mut details = crate::rmap::ServiceDetails::new(
+                &[
+                    Box::new(crate::guard::Any(crate::guard::Get()).or(crate::guard::Head()))
+                        as Box<dyn crate::guard::Guard>,
+                ],
+                0,
+            );
+            details.operation = Some(op.clone());
+            root.add_service(&mut actix_router::ResourceDef::new(pattern), None, details);
+        }
+
+        root.add(&mut actix_router::ResourceDef::new("/undocumented"), None);
+
+        let doc = OpenApi::new("Users", "1.0").with_routes(&root.routes());
+
+        let op = doc.operation("/users/{id}", "get").unwrap();
+        assert_eq!(op.get_operation_id(), Some("get_user"));
+        assert_eq!(op.parameters()[0].schema(), &u32::schema());
+
+        // operation IDs are unique per method and pattern
+        let ids = [
+            ("/files/{tail}", "get"),
+            ("/files/{tail}", "head"),
+            ("/static/{tail}", "get"),
+            ("/static/{tail}", "head"),
+        ]
+        .map(|(path, method)| doc.operation(path, method).unwrap().get_operation_id());
+        assert_eq!(
+            ids,
+            [
+                Some("file"),
+                Some("file_head"),
+                Some("file_2"),
+                Some("file_head_2")
+            ]
+        );
+
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(value["openapi"], "3.1.0");
+        assert_eq!(value["info"], json!({ "title": "Users", "version": "1.0" }));
+        assert_eq!(value["paths"].as_object().unwrap().len(), 3);
+        assert_eq!(
+            value["paths"]["/users/{id}"]["get"]["responses"],
+            json!({ "default": { "description": "Response" } })
+        );
+    }
+}
@@ -0,0 +1,533 @@
+//! Client certificates of mutually authenticated TLS connections.
+
+use std::{
+    any::Any,
+    fmt::{self, Write as _},
+    net::IpAddr,
+    rc::Rc,
+};
+
+use actix_utils::future::{err, ok, Ready};
+use x509_parser::{
+    certificate::X509Certificate,
+    der_parser::asn1_rs::{Tag, ToDer as _},
+    extensions::GeneralName,
+    prelude::FromDer as _,
+    x509::{AttributeTypeAndValue, X509Name},
+};
+
+use crate::{dev::Payload, error::ErrorForbidden, Error, FromRequest, HttpRequest};
+
+/// Extractor for the certificate chain presented by the client of a TLS connection.
+///
+/// When a TLS acceptor is configured to request client certificates (mutual TLS), the verified
+/// chain is made available to handlers by all of the TLS listeners of
+/// [`HttpServer`](crate::HttpServer). The chain is stored as connection data, so it can also be
+/// accessed using [`HttpRequest::conn_data`] and matched during routing using the
+/// [`ClientCert`](crate::guard::ClientCert) guard.
+///
+/// Extraction fails with `403 Forbidden` if the client did not present a certificate. Use
+/// `Option<PeerCertificates>` where client certificates are optional.
+///
+/// The subject, issuer and subject alternative names of the leaf certificate are parsed when the
+/// connection is established. Other fields can be read from the DER-encoded [`chain`](Self::chain)
+/// using an X.509 library.
+///
+/// # Examples
+/// ```
+/// use actix_web::{dev::PeerCertificates, get, Responder};
+///
+/// #[get("/")]
+/// async fn index(certs: PeerCertificates) -> impl Responder {
+///     format!(
+///         "Hello, {}!",
+///         certs.subject().common_name().unwrap_or("anonymous"),
+///     )
+/// }
+/// ```
+#[derive(Clone)]
+pub struct PeerCertificates {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    chain: Vec<Vec<u8>>,
+    subject: DistinguishedName,
+    issuer: DistinguishedName,
+    subject_alt_names: Vec<SubjectAltName>,
+}
+
+impl PeerCertificates {
+    /// Constructs from a DER-encoded certificate chain, leaf certificate first.
+    ///
+    /// Returns `None` if the chain is empty or the leaf is not a valid X.509 certificate. The
+    /// chain is not verified; that is the job of the TLS acceptor.
+    pub fn from_der<I>(chain: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<Vec<u8>>,
+    {
+        let chain = chain.into_iter().map(Into::into).collect::<Vec<_>>();
+        let leaf = parse_certificate(chain.first()?)?;
+
+        Some(PeerCertificates {
+            inner: Rc::new(Inner {
+                chain,
+                subject: leaf.subject,
+                issuer: leaf.issuer,
+                subject_alt_names: leaf.subject_alt_names,
+            }),
+        })
+    }
+
+    /// Reads the client certificate chain from a TLS stream, as passed to an
+    /// [on-connect](crate::HttpServer::on_connect) callback.
+    ///
+    /// Supports the server streams of all enabled TLS features. Returns `None` for other streams,
+    /// if the client did not present a certificate, or if OpenSSL reports that the certificate
+    /// failed verification, even when the acceptor's verify callback accepted it.
+    ///
+    /// `HttpServer` calls this automatically; it is useful for servers built directly on the
+    /// `actix-http` service builder.
+    #[allow(unused_variables)]
+    pub fn from_tls_stream(io: &dyn Any) -> Option<Self> {
+        #[cfg(feature = "openssl")]
+        if let Some(io) =
+            io.downcast_ref::<actix_tls::accept::openssl::TlsStream<actix_rt::net::TcpStream>>()
+        {
+            let ssl = io.ssl();
+
+            // verify callbacks can accept certificates that failed verification
+            if ssl.verify_result() != tls_openssl::x509::X509VerifyResult::OK {
+                return None;
+            }
+
+            let leaf = ssl.peer_certificate()?.to_der().ok()?;
+
+            // on the server side, OpenSSL's peer chain does not contain the leaf certificate
+            let chain = ssl
+                .peer_cert_chain()
+                .into_iter()
+                .flatten()
+                .map(|cert| cert.to_der().ok())
+                .collect::<Option<Vec<_>>>()?;
+
+            return Self::from_der(std::iter::once(leaf).chain(chain));
+        }
+
+        #[cfg(feature = "rustls-0_20")]
+        if let Some(io) =
+            io.downcast_ref::<actix_tls::accept::rustls_0_20::TlsStream<actix_rt::net::TcpStream>>()
+        {
+            let certs = io.get_ref().1.peer_certificates()?;
+            return Self::from_der(certs.iter().map(|cert| cert.0.clone()));
+        }
+
+        #[cfg(feature = "rustls-0_21")]
+        if let Some(io) =
+            io.downcast_ref::<actix_tls::accept::rustls_0_21::TlsStream<actix_rt::net::TcpStream>>()
+        {
+            let certs = io.get_ref().1.peer_certificates()?;
+            return Self::from_der(certs.iter().map(|cert| cert.0.clone()));
+        }
+
+        #[cfg(feature = "rustls-0_22")]
+        if let Some(io) =
+            io.downcast_ref::<actix_tls::accept::rustls_0_22::TlsStream<actix_rt::net::TcpStream>>()
+        {
+            let certs = io.get_ref().1.peer_certificates()?;
+            return Self::from_der(certs.iter().map(|cert| cert.as_ref().to_vec()));
+        }
+
+        #[cfg(feature = "rustls-0_23")]
+        if let Some(io) =
+            io.downcast_ref::<actix_tls::accept::rustls_0_23::TlsStream<actix_rt::net::TcpStream>>()
+        {
+            let certs = io.get_ref().1.peer_certificates()?;
+            return Self::from_der(certs.iter().map(|cert| cert.as_ref().to_vec()));
+        }
+
+        None
+    }
+
+    /// Returns the DER-encoded certificate chain, leaf certificate first.
+    pub fn chain(&self) -> &[Vec<u8>] {
+        &self.inner.chain
+    }
+
+    /// Returns the DER-encoded leaf certificate.
+    pub fn leaf(&self) -> &[u8] {
+        &self.inner.chain[0]
+    }
+
+    /// Returns the subject of the leaf certificate.
+    pub fn subject(&self) -> &DistinguishedName {
+        &self.inner.subject
+    }
+
+    /// Returns the issuer of the leaf certificate.
+    pub fn issuer(&self) -> &DistinguishedName {
+        &self.inner.issuer
+    }
+
+    /// Returns the subject alternative names of the leaf certificate.
+    ///
+    /// Only DNS names, email addresses, URIs and IP addresses are included.
+    pub fn subject_alt_names(&self) -> &[SubjectAltName] {
+        &self.inner.subject_alt_names
+    }
+
+    /// Returns an iterator over the DNS names of the leaf certificate.
+    pub fn dns_names(&self) -> impl Iterator<Item = &str> {
+        self.inner
+            .subject_alt_names
+            .iter()
+            .filter_map(|name| match name {
+                SubjectAltName::Dns(name) => Some(name.as_str()),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Debug for PeerCertificates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeerCertificates")
+            .field("subject", &self.inner.subject)
+            .field("issuer", &self.inner.issuer)
+            .field("subject_alt_names", &self.inner.subject_alt_names)
+            .field("chain_len", &self.inner.chain.len())
+            .finish()
+    }
+}
+
+impl FromRequest for PeerCertificates {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        match req.conn_data::<PeerCertificates>() {
+            Some(certs) => ok(certs.clone()),
+            None => {
+                log::debug!(
+                    "Failed to extract peer certificates; the client did not present any. \
+                     Request path: {:?}",
+                    req.path(),
+                );
+
+                err(ErrorForbidden("Client certificate required"))
+            }
+        }
+    }
+}
+
+/// An X.509 distinguished name, such as a certificate's subject or issuer.
+///
+/// Attributes are named by their short names, such as `CN` and `O`, or by their dotted OID if they
+/// have no well-known short name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistinguishedName {
+    rdns: Vec<Vec<(String, String)>>,
+}
+
+impl DistinguishedName {
+    /// Returns an iterator over attribute names and values, in encoding order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.rdns
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns the value of the last (most specific) attribute with the given name.
+    ///
+    /// Names are compared case-insensitively.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .filter(|(attr, _)| attr.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+            .last()
+    }
+
+    /// Returns the common name (`CN`).
+    pub fn common_name(&self) -> Option<&str> {
+        self.get("CN")
+    }
+}
+
+/// Formats the name as a [RFC 4514](https://datatracker.ietf.org/doc/html/rfc4514) string, such as
+/// `CN=client,O=Example Corp`.
+impl fmt::Display for DistinguishedName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // RFC 4514 lists the most specific RDN first
+        for (idx, rdn) in self.rdns.iter().rev().enumerate() {
+            if idx > 0 {
+                f.write_char(',')?;
+            }
+
+            for (idx, (name, value)) in rdn.iter().enumerate() {
+                if idx > 0 {
+                    f.write_char('+')?;
+                }
+
+                write!(f, "{name}=")?;
+
+                let len = value.chars().count();
+
+                for (idx, ch) in value.chars().enumerate() {
+                    let escape = matches!(ch, '"' | '+' | ',' | ';' | '<' | '>' | '\\')
+                        || (idx == 0 && matches!(ch, ' ' | '#'))
+                        || (idx == len - 1 && ch == ' ');
+
+                    if escape {
+                        f.write_char('\\')?;
+                    }
+
+                    f.write_char(ch)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A subject alternative name of a certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SubjectAltName {
+    /// A DNS name (`dNSName`).
+    Dns(String),
+
+    /// An email address (`rfc822Name`).
+    Email(String),
+
+    /// A URI (`uniformResourceIdentifier`), such as a SPIFFE ID.
+    Uri(String),
+
+    /// An IP address (`iPAddress`).
+    Ip(IpAddr),
+}
+
+impl SubjectAltName {
+    /// Returns true if the names are equal, comparing DNS names and email addresses
+    /// case-insensitively.
+    pub(crate) fn matches(&self, other: &SubjectAltName) -> bool {
+        match (self, other) {
+            (SubjectAltName::Dns(a), SubjectAltName::Dns(b))
+            | (SubjectAltName::Email(a), SubjectAltName::Email(b)) => a.eq_ignore_ascii_case(b),
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for SubjectAltName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubjectAltName::Dns(name) => write!(f, "DNS:{name}"),
+            SubjectAltName::Email(email) => write!(f, "email:{email}"),
+            SubjectAltName::Uri(uri) => write!(f, "URI:{uri}"),
+            SubjectAltName::Ip(ip) => write!(f, "IP:{ip}"),
+        }
+    }
+}
+
+struct Certificate {
+    subject: DistinguishedName,
+    issuer: DistinguishedName,
+    subject_alt_names: Vec<SubjectAltName>,
+}
+
+fn parse_certificate(der: &[u8]) -> Option<Certificate> {
+    let (rest, cert) = X509Certificate::from_der(der).ok()?;
+
+    if !rest.is_empty() {
+        return None;
+    }
+
+    let subject_alt_names = match cert.subject_alternative_name().ok()? {
+        Some(ext) => ext
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::RFC822Name(email) => Some(SubjectAltName::Email((*email).to_owned())),
+                GeneralName::DNSName(name) => Some(SubjectAltName::Dns((*name).to_owned())),
+                GeneralName::URI(uri) => Some(SubjectAltName::Uri((*uri).to_owned())),
+                GeneralName::IPAddress(ip) => match ip.len() {
+                    4 => <[u8; 4]>::try_from(*ip)
+                        .ok()
+                        .map(|ip| SubjectAltName::Ip(ip.into())),
+                    16 => <[u8; 16]>::try_from(*ip)
+                        .ok()
+                        .map(|ip| SubjectAltName::Ip(ip.into())),
+                    _ => None,
+                },
+                // other names, X.400 addresses, directory names, EDI party names and registered IDs
+                _ => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Some(Certificate {
+        subject: parse_name(cert.subject()),
+        issuer: parse_name(cert.issuer()),
+        subject_alt_names,
+    })
+}
+
+fn parse_name(name: &X509Name<'_>) -> DistinguishedName {
+    let rdns = name
+        .iter()
+        .map(|rdn| {
+            rdn.iter()
+                .map(|attr| (attribute_name(attr), attribute_value(attr)))
+                .collect()
+        })
+        .collect();
+
+    DistinguishedName { rdns }
+}
+
+fn attribute_name(attr: &AttributeTypeAndValue<'_>) -> String {
+    let oid = attr.attr_type().to_id_string();
+
+    let name = match oid.as_str() {
+        "2.5.4.3" => "CN",
+        "2.5.4.5" => "serialNumber",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.9" => "STREET",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "0.9.2342.19200300.100.1.1" => "UID",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        _ => return oid,
+    };
+
+    name.to_owned()
+}
+
+fn attribute_value(attr: &AttributeTypeAndValue<'_>) -> String {
+    // UTF8String, PrintableString, NumericString and IA5String
+    if let Ok(value) = attr.as_str() {
+        return value.to_owned();
+    }
+
+    let value = attr.attr_value();
+
+    match value.tag() {
+        Tag::TeletexString => String::from_utf8_lossy(value.data).into_owned(),
+
+        Tag::BmpString => char::decode_utf16(
+            value
+                .data
+                .chunks(2)
+                .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)])),
+        )
+        .map(|ch| ch.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect(),
+
+        // other types are shown as hex-encoded DER, like RFC 4514 does
+        _ => {
+            let mut hex = "#".to_owned();
+
+            for byte in value.to_der_vec().unwrap_or_default() {
+                write!(hex, "{byte:02x}").unwrap();
+            }
+
+            hex
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use rcgen::{CertificateParams, DistinguishedName as RcgenName, DnType, KeyPair, SanType};
+
+    use super::*;
+    use crate::test::TestRequest;
+
+    pub(crate) fn client_cert() -> Vec<u8> {
+        let mut params = CertificateParams::new(["client.example.com".to_owned()]).unwrap();
+
+        let mut name = RcgenName::new();
+        name.push(DnType::CountryName, "GB");
+        name.push(DnType::OrganizationName, "Example, Inc.");
+        name.push(DnType::CommonName, "client");
+        params.distinguished_name = name;
+
+        params.subject_alt_names.extend([
+            SanType::Rfc822Name("ops@example.com".try_into().unwrap()),
+            SanType::URI("spiffe://example.com/client".try_into().unwrap()),
+            SanType::IpAddress("10.0.0.1".parse().unwrap()),
+        ]);
+
+        let key_pair = KeyPair::generate().unwrap();
+        params.self_signed(&key_pair).unwrap().der().to_vec()
+    }
+
+    #[test]
+    fn parse_leaf() {
+        let certs = PeerCertificates::from_der([client_cert()]).unwrap();
+
+        assert_eq!(certs.chain().len(), 1);
+        assert_eq!(certs.subject().common_name(), Some("client"));
+        assert_eq!(certs.subject().get("o"), Some("Example, Inc."));
+        assert_eq!(certs.subject(), certs.issuer());
+        assert_eq!(
+            certs.subject().to_string(),
+            r"CN=client,O=Example\, Inc.,C=GB"
+        );
+
+        assert_eq!(
+            certs.subject_alt_names(),
+            [
+                SubjectAltName::Dns("client.example.com".to_owned()),
+                SubjectAltName::Email("ops@example.com".to_owned()),
+                SubjectAltName::Uri("spiffe://example.com/client".to_owned()),
+                SubjectAltName::Ip("10.0.0.1".parse().unwrap()),
+            ]
+        );
+        assert_eq!(
+            certs.dns_names().collect::<Vec<_>>(),
+            ["client.example.com"]
+        );
+    }
+
+    #[test]
+    fn invalid_der() {
+        assert!(PeerCertificates::from_der(Vec::<Vec<u8>>::new()).is_none());
+        assert!(PeerCertificates::from_der([b"garbage".to_vec()]).is_none());
+
+        let mut cert = client_cert();
+        cert.truncate(cert.len() / 2);
+        assert!(PeerCertificates::from_der([cert]).is_none());
+    }
+
+    #[actix_rt::test]
+    async fn extractor() {
+        let certs = PeerCertificates::from_der([client_cert()]).unwrap();
+
+        let (req, mut pl) = TestRequest::default()
+            .conn_data(certs.clone())
+            .to_http_parts();
+        let extracted = PeerCertificates::from_request(&req, &mut pl).await.unwrap();
+        assert!(Rc::ptr_eq(&extracted.inner, &certs.inner));
+
+        let (req, mut pl) = TestRequest::default().to_http_parts();
+        let res = PeerCertificates::from_request(&req, &mut pl).await;
+        assert_eq!(
+            res.unwrap_err().as_response_error().status_code(),
+            crate::http::StatusCode::FORBIDDEN
+        );
+
+        let opt = Option::<PeerCertificates>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert!(opt.is_none());
+    }
+}
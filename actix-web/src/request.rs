@@ -10,6 +10,7 @@ use actix_router::{Path, Url};
 use actix_utils::future::{ok, Ready};
 #[cfg(feature = "cookies")]
 use cookie::{Cookie, ParseError as CookieParseError};
+use serde::Serialize;
 use smallvec::SmallVec;
 
 use crate::{
@@ -72,6 +73,30 @@ impl HttpRequest {
 }
 
 impl HttpRequest {
+    /// Constructs a separate request with a copy of this request's head, using `method`, the
+    /// routing state `path` and the request-local data `extensions`.
+    ///
+    /// Unlike [`head_mut`](Self::head_mut), this does not require the request to be unshared.
+    pub(crate) fn duplicate_with(
+        &self,
+        method: Method,
+        path: Path<Url>,
+        extensions: Extensions,
+    ) -> HttpRequest {
+        let mut head = Message::<RequestHead>::new();
+        *head = self.head().clone();
+        head.method = method;
+
+        HttpRequest::new(
+            path,
+            head,
+            Rc::clone(&self.inner.app_state),
+            Rc::clone(&self.inner.app_data[0]),
+            self.inner.conn_data.clone(),
+            Rc::new(RefCell::new(extensions)),
+        )
+    }
+
     /// This method returns reference to the request head
     #[inline]
     pub fn head(&self) -> &RequestHead {
@@ -220,6 +245,9 @@ impl HttpRequest {
     /// beforehand without worrying about double encoding. Any other character that is not valid in
     /// a URL path context is escaped using percent-encoding.
     ///
+    /// Elements for [typed segments](crate::dev::Converter), like `{id:u64}`, must conform to
+    /// their converter or [`UrlGenerationError::InvalidElement`] is returned.
+    ///
     /// # Examples
     /// ```
     /// # use actix_web::{web, App, HttpRequest, HttpResponse};
@@ -242,6 +270,45 @@ impl HttpRequest {
         self.resource_map().url_for(self, name, elements)
     }
 
+    /// Generates URL for a named resource, with a query string serialized from `query`.
+    ///
+    /// This method is similar to [`url_for`](Self::url_for) but additionally sets the query string
+    /// of the generated URL. The query is serialized with `serde_urlencoded`, the same format that
+    /// the [`Query`](crate::web::Query) extractor deserializes.
+    ///
+    /// ```
+    /// # use actix_web::{web, App, HttpRequest, HttpResponse};
+    /// # use std::collections::BTreeMap;
+    /// fn index(req: HttpRequest) -> HttpResponse {
+    ///     let query = BTreeMap::from([("page", 2)]);
+    ///     let url = req.url_for_with_query("foo", ["1", "2"], &query); // <- generate URL for "foo" resource
+    ///     assert_eq!(url.unwrap().query(), Some("page=2"));
+    ///     HttpResponse::Ok().into()
+    /// }
+    ///
+    /// let app = App::new()
+    ///     .service(web::resource("/test/{one}/{two}")
+    ///          .name("foo")
+    ///          .route(web::get().to(|| HttpResponse::Ok()))
+    ///     );
+    /// ```
+    pub fn url_for_with_query<U, I, Q>(
+        &self,
+        name: &str,
+        elements: U,
+        query: &Q,
+    ) -> Result<url::Url, UrlGenerationError>
+    where
+        U: IntoIterator<Item = I>,
+        I: AsRef<str>,
+        Q: Serialize + ?Sized,
+    {
+        let mut url = self.url_for(name, elements)?;
+        let query = serde_urlencoded::to_string(query)?;
+        url.set_query((!query.is_empty()).then_some(query.as_str()));
+        Ok(url)
+    }
+
     /// Generate URL for named resource
     ///
     /// This method is similar to `HttpRequest::url_for()` but it can be used
@@ -636,6 +703,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_url_for_typed_segments() {
+        let mut res = ResourceDef::new("/user/{id:u32}");
+        res.set_name("user");
+
+        let mut rmap = ResourceMap::new(ResourceDef::prefix(""));
+        rmap.add(&mut res, None);
+
+        let req = TestRequest::default()
+            .insert_header((header::HOST, "www.rust-lang.org"))
+            .rmap(rmap)
+            .to_http_request();
+
+        assert_eq!(
+            req.url_for("user", ["42"]).unwrap().as_str(),
+            "http://www.rust-lang.org/user/42"
+        );
+        assert_eq!(
+            req.url_for("user", ["alice"]),
+            Err(UrlGenerationError::InvalidElement)
+        );
+        assert_eq!(
+            req.url_for("user", [""; 0]),
+            Err(UrlGenerationError::NotEnoughElements)
+        );
+    }
+
+    #[test]
+    fn test_url_for_with_query() {
+        let mut res = ResourceDef::new("/user/{name}");
+        res.set_name("user");
+
+        let mut rmap = ResourceMap::new(ResourceDef::prefix(""));
+        rmap.add(&mut res, None);
+
+        let req = TestRequest::default()
+            .insert_header((header::HOST, "www.rust-lang.org"))
+            .rmap(rmap)
+            .to_http_request();
+
+        assert_eq!(
+            req.url_for_with_query("user", ["alice"], &[("tab", "posts"), ("q", "a b&c")])
+                .unwrap()
+                .as_str(),
+            "http://www.rust-lang.org/user/alice?tab=posts&q=a+b%26c"
+        );
+        assert_eq!(
+            req.url_for_with_query("user", ["alice"], &[("tab", ""); 0])
+                .unwrap()
+                .as_str(),
+            "http://www.rust-lang.org/user/alice"
+        );
+        assert!(matches!(
+            req.url_for_with_query("user", ["alice"], &42),
+            Err(UrlGenerationError::QueryString(_))
+        ));
+        assert_eq!(
+            req.url_for_with_query("unknown", ["alice"], &[("tab", "posts")]),
+            Err(UrlGenerationError::ResourceNotFound)
+        );
+    }
+
     #[test]
     fn test_url_for_static() {
         let mut rdef = ResourceDef::new("/index.html");
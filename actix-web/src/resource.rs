@@ -16,6 +16,7 @@ use crate::{
     guard::{self, Guard},
     handler::Handler,
     http::header,
+    rmap::ServiceDetails,
     route::{Route, RouteService},
     service::{
         BoxedHttpService, BoxedHttpServiceFactory, HttpServiceFactory, ServiceRequest,
@@ -57,6 +58,9 @@ pub struct Resource<T = ResourceEndpoint> {
     guards: Vec<Box<dyn Guard>>,
     default: BoxedHttpServiceFactory,
     factory_ref: Rc<RefCell<Option<ResourceFactory>>>,
+    middleware: usize,
+    #[cfg(feature = "openapi")]
+    operation: Option<crate::openapi::Operation>,
 }
 
 impl Resource {
@@ -70,6 +74,9 @@ impl Resource {
             name: None,
             endpoint: ResourceEndpoint::new(Rc::clone(&factory_ref)),
             factory_ref,
+            middleware: 0,
+            #[cfg(feature = "openapi")]
+            operation: None,
             guards: Vec::new(),
             app_data: None,
             default: boxed::factory(fn_service(|req: ServiceRequest| async {
@@ -103,6 +110,29 @@ where
         self
     }
 
+    /// Sets the description of the operation performed by this resource, for OpenAPI documents.
+    ///
+    /// The operation is documented for each method handled by the resource. Handlers registered
+    /// using the routing macros set this automatically.
+    ///
+    /// ```
+    /// use actix_web::{openapi::Operation, web, App, HttpResponse};
+    ///
+    /// let mut op = Operation::new();
+    /// op.summary("Checks service health.");
+    ///
+    /// let app = App::new().service(
+    ///     web::resource("/health")
+    ///         .operation(op)
+    ///         .get(HttpResponse::Ok),
+    /// );
+    /// ```
+    #[cfg(feature = "openapi")]
+    pub fn operation(mut self, operation: crate::openapi::Operation) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
     /// Add match guard to a resource.
     ///
     /// ```
@@ -283,6 +313,9 @@ where
             rdef: self.rdef,
             name: self.name,
             guards: self.guards,
+            middleware: self.middleware + 1,
+            #[cfg(feature = "openapi")]
+            operation: self.operation,
             routes: self.routes,
             default: self.default,
             app_data: self.app_data,
@@ -321,6 +354,9 @@ where
             rdef: self.rdef,
             name: self.name,
             guards: self.guards,
+            middleware: self.middleware + 1,
+            #[cfg(feature = "openapi")]
+            operation: self.operation,
             routes: self.routes,
             default: self.default,
             app_data: self.app_data,
@@ -417,6 +453,18 @@ where
     B: MessageBody + 'static,
 {
     fn register(mut self, config: &mut AppService) {
+        #[allow(unused_mut)] // mutated when openapi feature is enabled
+        let mut details = ServiceDetails::resource(
+            &self.guards,
+            self.routes.iter().map(Route::guards),
+            self.middleware,
+        );
+
+        #[cfg(feature = "openapi")]
+        {
+            details.operation = self.operation.take();
+        }
+
         let guards = if self.guards.is_empty() {
             None
         } else {
@@ -451,7 +499,7 @@ where
             async { Ok(fut.await?.map_into_boxed_body()) }
         });
 
-        config.register_service(rdef, guards, endpoint, None)
+        config.register_service_with_details(rdef, guards, endpoint, None, details)
     }
 }
 
@@ -545,7 +593,7 @@ mod tests {
     use crate::{
         http::{header::HeaderValue, Method, StatusCode},
         middleware::DefaultHeaders,
-        test::{call_service, init_service, TestRequest},
+        test::{call_and_read_body, call_service, init_service, TestRequest},
         App, HttpMessage,
     };
 
@@ -750,6 +798,30 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NO_CONTENT);
     }
 
+    #[actix_rt::test]
+    async fn test_typed_segments() {
+        let srv = init_service(
+            App::new()
+                .service(
+                    web::resource("/user/{id:u64}")
+                        .to(|id: web::Path<u64>| async move { format!("id {}", id) }),
+                )
+                .service(
+                    web::resource("/user/{name}")
+                        .to(|name: web::Path<String>| async move { format!("name {}", name) }),
+                ),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/user/42").to_request();
+        let body = call_and_read_body(&srv, req).await;
+        assert_eq!(body, "id 42");
+
+        let req = TestRequest::with_uri("/user/alice").to_request();
+        let body = call_and_read_body(&srv, req).await;
+        assert_eq!(body, "name alice");
+    }
+
     // allow deprecated `{App, Resource}::data`
     #[allow(deprecated)]
     #[actix_rt::test]
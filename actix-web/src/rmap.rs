@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     fmt::Write as _,
     rc::{Rc, Weak},
 };
@@ -9,7 +9,13 @@ use actix_router::ResourceDef;
 use ahash::AHashMap;
 use url::Url;
 
-use crate::{error::UrlGenerationError, request::HttpRequest};
+use crate::{
+    dynamic_scope::DynamicRoutes,
+    error::UrlGenerationError,
+    guard::{self, Guard},
+    http::Method,
+    request::HttpRequest,
+};
 
 const AVG_PATH_LEN: usize = 24;
 
@@ -25,6 +31,152 @@ pub struct ResourceMap {
 
     /// Must be `None` for "edge" nodes.
     nodes: Option<Vec<Rc<ResourceMap>>>,
+
+    /// Guards, methods and middleware of the service, for the route table.
+    details: ServiceDetails,
+
+    /// Host pattern of the scope, if it is restricted to a host.
+    host: Option<ResourceDef>,
+
+    /// Routing table of a dynamic scope. Dynamic scope nodes have no children.
+    dynamic: Option<DynamicRoutes>,
+
+    /// Dynamic scope nodes within the tree.
+    dynamic_nodes: Vec<Rc<ResourceMap>>,
+}
+
+/// Routing details of a registered service, collected for the route table.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ServiceDetails {
+    /// Descriptions of guards, other than method guards.
+    guards: Vec<String>,
+
+    /// Methods handled by the service, or `None` if it is not restricted to some methods.
+    methods: Option<Vec<Method>>,
+
+    /// Number of middleware registered on the service.
+    middleware: usize,
+
+    /// Description of the operation performed by the service.
+    #[cfg(feature = "openapi")]
+    pub(crate) operation: Option<crate::openapi::Operation>,
+}
+
+impl ServiceDetails {
+    /// Collects details from a service's guards and number of middleware.
+    pub(crate) fn new(guards: &[Box<dyn Guard>], middleware: usize) -> Self {
+        let mut details = ServiceDetails {
+            guards: Vec::new(),
+            methods: guard::guard_methods(guards),
+            middleware,
+            #[cfg(feature = "openapi")]
+            operation: None,
+        };
+
+        details.add_guard_descriptions(guards);
+        details
+    }
+
+    /// Collects details from a resource's guards, routes and number of middleware.
+    pub(crate) fn resource<'a>(
+        guards: &[Box<dyn Guard>],
+        route_guards: impl IntoIterator<Item = &'a [Box<dyn Guard>]>,
+        middleware: usize,
+    ) -> Self {
+        let mut details = ServiceDetails::new(guards, middleware);
+        let mut route_methods = Some(Vec::new());
+
+        for route_guards in route_guards {
+            details.add_guard_descriptions(route_guards);
+
+            // a route without method guards handles every method
+            match (&mut route_methods, guard::guard_methods(route_guards)) {
+                (Some(methods), Some(route)) => {
+                    for method in route {
+                        if !methods.contains(&method) {
+                            methods.push(method);
+                        }
+                    }
+                }
+                (methods, _) => *methods = None,
+            }
+        }
+
+        if details.methods.is_none() {
+            details.methods = route_methods.filter(|methods| !methods.is_empty());
+        }
+
+        details
+    }
+
+    fn add_guard_descriptions(&mut self, guards: &[Box<dyn Guard>]) {
+        for guard in guards {
+            if guard.methods().is_some() {
+                continue;
+            }
+
+            let description = guard.description();
+
+            if !self.guards.contains(&description) {
+                self.guards.push(description);
+            }
+        }
+    }
+}
+
+/// A resource registered in an app, as listed in the route table.
+///
+/// See [`ResourceMap::routes`].
+#[derive(Debug, Clone)]
+pub struct RouteInfo {
+    pattern: String,
+    name: Option<String>,
+    methods: Option<Vec<Method>>,
+    guards: Vec<String>,
+    middleware: usize,
+    #[cfg(feature = "openapi")]
+    operation: Option<crate::openapi::Operation>,
+}
+
+impl RouteInfo {
+    /// Returns the full path pattern of the resource, including the prefixes of enclosing scopes.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns the name of the resource, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the methods handled by the resource, or `None` if it handles any method.
+    ///
+    /// Methods are found using the method guards of the resource, its routes and enclosing scopes.
+    pub fn methods(&self) -> Option<&[Method]> {
+        self.methods.as_deref()
+    }
+
+    /// Returns descriptions of the guards of the resource, its routes and enclosing scopes, other
+    /// than method guards.
+    ///
+    /// See [`Guard::description`].
+    pub fn guards(&self) -> &[String] {
+        &self.guards
+    }
+
+    /// Returns the number of middleware that requests to the resource pass through, registered on
+    /// the app, enclosing scopes and the resource itself.
+    pub fn middleware_depth(&self) -> usize {
+        self.middleware
+    }
+
+    /// Returns the description of the operation performed by the resource, if it has one.
+    ///
+    /// See [`openapi`](crate::openapi).
+    #[cfg(feature = "openapi")]
+    pub fn operation(&self) -> Option<&crate::openapi::Operation> {
+        self.operation.as_ref()
+    }
 }
 
 impl ResourceMap {
@@ -35,9 +187,34 @@ impl ResourceMap {
             named: AHashMap::default(),
             parent: RefCell::new(Weak::new()),
             nodes: Some(Vec::new()),
+            details: ServiceDetails::default(),
+            host: None,
+            dynamic: None,
+            dynamic_nodes: Vec::new(),
+        }
+    }
+
+    /// Creates a _container_ node for a dynamic scope, whose resources are in `routes`.
+    pub(crate) fn dynamic(root: ResourceDef, routes: DynamicRoutes) -> Self {
+        ResourceMap {
+            dynamic: Some(routes),
+            ..ResourceMap::new(root)
         }
     }
 
+    /// Creates a _container_ node with the routing details of the service it belongs to.
+    pub(crate) fn with_details(root: ResourceDef, details: ServiceDetails) -> Self {
+        ResourceMap {
+            details,
+            ..ResourceMap::new(root)
+        }
+    }
+
+    /// Sets the host pattern used to generate URLs for resources within this node.
+    pub(crate) fn set_host(&mut self, host: Option<ResourceDef>) {
+        self.host = host;
+    }
+
     /// Format resource map as tree structure (unfinished).
     #[allow(dead_code)]
     pub(crate) fn tree(&self) -> String {
@@ -73,6 +250,19 @@ impl ResourceMap {
     /// To add external resource, supply a pattern without a leading `/`.
     /// The root pattern of `nested`, if present, should match `pattern`.
     pub fn add(&mut self, pattern: &mut ResourceDef, nested: Option<Rc<ResourceMap>>) {
+        self.add_service(pattern, nested, ServiceDetails::default());
+    }
+
+    /// Adds a (possibly nested) resource, with the routing details of its service.
+    ///
+    /// The details are only stored for non-nested resources; nested resource maps should be created
+    /// using [`with_details`](Self::with_details).
+    pub(crate) fn add_service(
+        &mut self,
+        pattern: &mut ResourceDef,
+        nested: Option<Rc<ResourceMap>>,
+        details: ServiceDetails,
+    ) {
         pattern.set_id(self.nodes.as_ref().unwrap().len() as u16);
 
         if let Some(new_node) = nested {
@@ -80,8 +270,15 @@ impl ResourceMap {
                 &new_node.pattern, pattern,
                 "`pattern` and `nested` mismatch"
             );
-            // parents absorb references to the named resources of children
+            // parents absorb references to the named resources and dynamic scopes of children
             self.named.extend(new_node.named.clone());
+            self.dynamic_nodes
+                .extend(new_node.dynamic_nodes.iter().cloned());
+
+            if new_node.dynamic.is_some() {
+                self.dynamic_nodes.push(Rc::clone(&new_node));
+            }
+
             self.nodes.as_mut().unwrap().push(new_node);
         } else {
             let new_node = Rc::new(ResourceMap {
@@ -89,6 +286,10 @@ impl ResourceMap {
                 named: AHashMap::default(),
                 parent: RefCell::new(Weak::new()),
                 nodes: None,
+                details,
+                host: None,
+                dynamic: None,
+                dynamic_nodes: Vec::new(),
             });
 
             if let Some(name) = pattern.name() {
@@ -129,21 +330,83 @@ impl ResourceMap {
     {
         let mut elements = elements.into_iter();
 
-        let path = self
-            .named
-            .get(name)
-            .ok_or(UrlGenerationError::ResourceNotFound)?
-            .root_rmap_fn(String::with_capacity(AVG_PATH_LEN), |mut acc, node| {
-                node.pattern
-                    .resource_path_from_iter(&mut acc, &mut elements)
-                    .then_some(acc)
-            })
-            .ok_or(UrlGenerationError::NotEnoughElements)?;
+        // building fails either when elements run out or when one does not conform to its segment
+        let exhausted = Cell::new(false);
+        let mut elements = std::iter::from_fn(|| {
+            let next = elements.next();
+            exhausted.set(next.is_none());
+            next
+        });
+
+        // host of the innermost scope restricted to a host, if any
+        let mut host = None;
+
+        let mut push_pattern = |mut acc: String,
+                                node_host: Option<&ResourceDef>,
+                                pattern: &ResourceDef| {
+            if let Some(node_host) = node_host {
+                if !node_host.resource_path_from_iter(host.insert(String::new()), &mut elements) {
+                    return None;
+                }
+            }
+
+            pattern
+                .resource_path_from_iter(&mut acc, &mut elements)
+                .then_some(acc)
+        };
+
+        let path = match self.named.get(name) {
+            Some(node) => node.root_rmap_fn(String::with_capacity(AVG_PATH_LEN), |acc, node| {
+                push_pattern(acc, node.host.as_ref(), &node.pattern)
+            }),
+
+            // resources of dynamic scopes are looked up in their current routing table
+            None => {
+                let (node, rdef) = self
+                    .dynamic_nodes
+                    .iter()
+                    .find_map(|node| {
+                        let table = node.dynamic.as_ref()?.snapshot();
+                        let rdef = table.named(name)?.clone();
+                        Some((node, rdef))
+                    })
+                    .ok_or(UrlGenerationError::ResourceNotFound)?;
+
+                node.root_rmap_fn(String::with_capacity(AVG_PATH_LEN), |acc, node| {
+                    push_pattern(acc, node.host.as_ref(), &node.pattern)
+                })
+                .and_then(|acc| push_pattern(acc, None, &rdef))
+            }
+        }
+        .ok_or_else(|| {
+            if exhausted.get() {
+                UrlGenerationError::NotEnoughElements
+            } else {
+                UrlGenerationError::InvalidElement
+            }
+        })?;
 
         let (base, path): (Cow<'_, _>, _) = if path.starts_with('/') {
             // build full URL from connection info parts and resource path
             let conn = req.connection_info();
-            let base = format!("{}://{}", conn.scheme(), conn.host());
+
+            let base = match host {
+                // keep the port of the current request when using the host of a scope
+                Some(host) => {
+                    let port = conn
+                        .host()
+                        .rsplit_once(':')
+                        .map(|(_, port)| port)
+                        .filter(|port| port.bytes().all(|b| b.is_ascii_digit()));
+
+                    match port {
+                        Some(port) => format!("{}://{}:{}", conn.scheme(), host, port),
+                        None => format!("{}://{}", conn.scheme(), host),
+                    }
+                }
+                None => format!("{}://{}", conn.scheme(), conn.host()),
+            };
+
             (Cow::Owned(base), path.as_str())
         } else {
             // external resource; third slash would be the root slash in the path
@@ -171,21 +434,152 @@ impl ResourceMap {
 
     /// Returns the name of the route that matches the given path or None if no full match
     /// is possible or the matching resource is not named.
+    ///
+    /// Always returns `None` for resources of [dynamic scopes](crate::DynamicScope).
     pub fn match_name(&self, path: &str) -> Option<&str> {
-        self.find_matching_node(path)?.pattern.name()
+        self.find_matching_node(path)
+            .filter(|node| node.dynamic.is_none())?
+            .pattern
+            .name()
     }
 
     /// Returns the full resource pattern matched against a path or None if no full match
     /// is possible.
+    ///
+    /// Always returns `None` for resources of [dynamic scopes](crate::DynamicScope).
     pub fn match_pattern(&self, path: &str) -> Option<String> {
-        self.find_matching_node(path)?.root_rmap_fn(
-            String::with_capacity(AVG_PATH_LEN),
-            |mut acc, node| {
+        self.find_matching_node(path)
+            .filter(|node| node.dynamic.is_none())?
+            .root_rmap_fn(String::with_capacity(AVG_PATH_LEN), |mut acc, node| {
                 let pattern = node.pattern.pattern()?;
                 acc.push_str(pattern);
                 Some(acc)
-            },
-        )
+            })
+    }
+
+    /// Returns the route table of the app: every resource registered in it, in registration order.
+    ///
+    /// Scopes are not listed themselves, but their resources are. Resources with multiple patterns
+    /// are listed once for each pattern. External resources are not listed. Resources of
+    /// [dynamic scopes](crate::DynamicScope) are listed as they are at the time of the call.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{get, web, App, HttpRequest, HttpResponse, Responder};
+    ///
+    /// #[get("/debug/routes")]
+    /// async fn routes(req: HttpRequest) -> impl Responder {
+    ///     let mut body = String::new();
+    ///
+    ///     for route in req.resource_map().routes() {
+    ///         let methods = route.methods().map(|methods| format!("{:?}", methods));
+    ///         body.push_str(&format!("{} {}\n", route.pattern(), methods.unwrap_or_default()));
+    ///     }
+    ///
+    ///     body
+    /// }
+    ///
+    /// let app = App::new().service(routes);
+    /// ```
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let mut root = None;
+        let mut parent = self.parent.borrow().upgrade();
+
+        while let Some(node) = parent {
+            parent = node.parent.borrow().upgrade();
+            root = Some(node);
+        }
+
+        let mut routes = Vec::new();
+        root.as_deref()
+            .unwrap_or(self)
+            .collect_routes(&mut routes, "", &[], None, 0);
+        routes
+    }
+
+    fn collect_routes(
+        &self,
+        routes: &mut Vec<RouteInfo>,
+        prefix: &str,
+        guards: &[String],
+        methods: Option<&[Method]>,
+        middleware: usize,
+    ) {
+        let mut guards = guards.to_vec();
+
+        for guard in &self.details.guards {
+            if !guards.contains(guard) {
+                guards.push(guard.clone());
+            }
+        }
+
+        let methods = match (methods, self.details.methods.as_deref()) {
+            (Some(outer), Some(inner)) => Some(
+                inner
+                    .iter()
+                    .filter(|method| outer.contains(method))
+                    .cloned()
+                    .collect(),
+            ),
+            (outer, inner) => outer.or(inner).map(<[_]>::to_vec),
+        };
+
+        let middleware = middleware + self.details.middleware;
+
+        match &self.nodes {
+            Some(_) if self.dynamic.is_some() => {
+                let prefix = format!("{}{}", prefix, self.pattern.pattern().unwrap_or_default());
+                let table = self.dynamic.as_ref().unwrap().snapshot();
+
+                for route in table.routes() {
+                    let methods = match (methods.as_deref(), route.methods()) {
+                        (outer, []) => outer.map(<[_]>::to_vec),
+                        (Some(outer), inner) => Some(
+                            inner
+                                .iter()
+                                .filter(|method| outer.contains(method))
+                                .cloned()
+                                .collect(),
+                        ),
+                        (None, inner) => Some(inner.to_vec()),
+                    };
+
+                    for pattern in route.rdef().pattern_iter() {
+                        routes.push(RouteInfo {
+                            pattern: format!("{}{}", prefix, pattern),
+                            name: route.rdef().name().map(ToOwned::to_owned),
+                            methods: methods.clone(),
+                            guards: guards.clone(),
+                            middleware,
+                            #[cfg(feature = "openapi")]
+                            operation: None,
+                        });
+                    }
+                }
+            }
+
+            Some(nodes) => {
+                let prefix = format!("{}{}", prefix, self.pattern.pattern().unwrap_or_default());
+
+                for node in nodes {
+                    node.collect_routes(routes, &prefix, &guards, methods.as_deref(), middleware);
+                }
+            }
+
+            None => {
+                for pattern in self.pattern.pattern_iter() {
+                    routes.push(RouteInfo {
+                        pattern: format!("{}{}", prefix, pattern),
+                        name: self.pattern.name().map(ToOwned::to_owned),
+                        methods: methods.clone(),
+                        guards: guards.clone(),
+                        middleware,
+                        #[cfg(feature = "openapi")]
+                        operation: self.details.operation.clone(),
+                    });
+                }
+            }
+        }
     }
 
     fn find_matching_node(&self, path: &str) -> Option<&ResourceMap> {
@@ -199,6 +593,10 @@ impl ResourceMap {
         let matched_len = self.pattern.find_match(path)?;
         let path = &path[matched_len..];
 
+        if let Some(routes) = &self.dynamic {
+            return Some(routes.snapshot().is_match(path).then_some(self));
+        }
+
         Some(match &self.nodes {
             // find first sub-node to match remaining path
             Some(nodes) => nodes
@@ -557,4 +955,85 @@ mod tests {
 
         assert!(rmap.url_for(&req, "missing", ["u123"]).is_err());
     }
+
+    #[actix_rt::test]
+    async fn route_table() {
+        use crate::{
+            guard,
+            middleware::{DefaultHeaders, Logger},
+            test, web, App, HttpRequest, HttpResponse,
+        };
+
+        let app = App::new()
+            .wrap(DefaultHeaders::new())
+            .service(
+                web::scope("/api")
+                    .guard(guard::Header("x-api", "1"))
+                    .wrap(Logger::default())
+                    .service(
+                        web::resource("/users/{id}")
+                            .name("user")
+                            .wrap(DefaultHeaders::new())
+                            .get(HttpResponse::Ok)
+                            .put(HttpResponse::Ok),
+                    )
+                    .route("/items", web::post().to(HttpResponse::Ok)),
+            )
+            .service(web::resource(["/a", "/b"]).to(HttpResponse::Ok))
+            .route(
+                "/routes",
+                web::get().to(|req: HttpRequest| async move {
+                    req.resource_map().routes().len().to_string()
+                }),
+            )
+            .external_resource("youtube", "https://youtube.com/watch/{video_id}");
+
+        let routes = test::route_table(app).await;
+        assert_eq!(routes.len(), 5);
+
+        assert_eq!(routes[0].pattern(), "/api/users/{id}");
+        assert_eq!(routes[0].name(), Some("user"));
+        assert_eq!(routes[0].methods(), Some(&[Method::GET, Method::PUT][..]));
+        assert_eq!(routes[0].guards(), ["Header(x-api: 1)"]);
+        assert_eq!(routes[0].middleware_depth(), 3);
+
+        assert_eq!(routes[1].pattern(), "/api/items");
+        assert_eq!(routes[1].name(), None);
+        assert_eq!(routes[1].methods(), Some(&[Method::POST][..]));
+        assert_eq!(routes[1].middleware_depth(), 2);
+
+        assert_eq!(routes[2].pattern(), "/a");
+        assert_eq!(routes[3].pattern(), "/b");
+        assert_eq!(routes[3].methods(), None);
+        assert!(routes[3].guards().is_empty());
+        assert_eq!(routes[3].middleware_depth(), 1);
+
+        assert_eq!(routes[4].pattern(), "/routes");
+    }
+
+    #[actix_rt::test]
+    async fn route_table_at_runtime() {
+        use crate::{test, web, App, HttpRequest};
+
+        let app = test::init_service(
+            App::new()
+                .service(web::scope("/nested").route(
+                    "/routes",
+                    web::get().to(|req: HttpRequest| async move {
+                        let routes = req.resource_map().routes();
+                        routes
+                            .iter()
+                            .map(|route| route.pattern())
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    }),
+                ))
+                .route("/other", web::get().to(|| async { "" })),
+        )
+        .await;
+
+        let req = test::TestRequest::with_uri("/nested/routes").to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(body, "/nested/routes,/other");
+    }
 }
@@ -9,7 +9,7 @@ use actix_service::{
 use futures_core::future::LocalBoxFuture;
 
 use crate::{
-    guard::{self, Guard},
+    guard::{self, Guard, HostParams},
     handler::{handler_service, Handler},
     middleware::Compat,
     service::{BoxedHttpServiceFactory, ServiceRequest, ServiceResponse},
@@ -62,6 +62,10 @@ impl Route {
         }
     }
 
+    pub(crate) fn guards(&self) -> &[Box<dyn Guard>] {
+        &self.guards
+    }
+
     pub(crate) fn take_guards(&mut self) -> Vec<Box<dyn Guard>> {
         mem::take(Rc::get_mut(&mut self.guards).unwrap())
     }
@@ -92,16 +96,17 @@ pub struct RouteService {
 }
 
 impl RouteService {
-    // TODO(breaking): remove pass by ref mut
-    #[allow(clippy::needless_pass_by_ref_mut)]
     pub fn check(&self, req: &mut ServiceRequest) -> bool {
         let guard_ctx = req.guard_ctx();
 
         for guard in self.guards.iter() {
             if !guard.check(&guard_ctx) {
+                HostParams::discard(req);
                 return false;
             }
         }
+
+        HostParams::merge(req);
         true
     }
 }
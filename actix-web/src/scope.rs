@@ -13,8 +13,8 @@ use crate::{
     config::ServiceConfig,
     data::Data,
     dev::AppService,
-    guard::Guard,
-    rmap::ResourceMap,
+    guard::{self, Guard, HostParams},
+    rmap::{ResourceMap, ServiceDetails},
     service::{
         AppServiceFactory, BoxedHttpService, BoxedHttpServiceFactory, HttpServiceFactory,
         ServiceFactoryWrapper, ServiceRequest, ServiceResponse,
@@ -62,7 +62,9 @@ pub struct Scope<T = ScopeEndpoint> {
     guards: Vec<Box<dyn Guard>>,
     default: Option<Rc<BoxedHttpServiceFactory>>,
     external: Vec<ResourceDef>,
+    host: Option<ResourceDef>,
     factory_ref: Rc<RefCell<Option<ScopeFactory>>>,
+    middleware: usize,
 }
 
 impl Scope {
@@ -78,7 +80,9 @@ impl Scope {
             services: Vec::new(),
             default: None,
             external: Vec::new(),
+            host: None,
             factory_ref,
+            middleware: 0,
         }
     }
 }
@@ -110,6 +114,37 @@ where
         self
     }
 
+    /// Restricts the scope to requests for the given host.
+    ///
+    /// This adds a [`Host`](guard::Host) guard, so `host` can be a pattern like
+    /// `{tenant}.example.com` whose dynamic segments are added to the request's match info.
+    /// Additionally, [`url_for`](crate::HttpRequest::url_for) generates URLs for resources in the
+    /// scope with the host, taking elements for the dynamic segments of the host before those of
+    /// the scope's path. Other parts of the URL, like the scheme and port, are taken from the
+    /// current request.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{web, App, HttpRequest, Responder};
+    ///
+    /// async fn home(req: HttpRequest, tenant: web::Path<String>) -> impl Responder {
+    ///     // e.g. "http://acme.example.com/settings"
+    ///     req.url_for("settings", [tenant.as_str()]).unwrap().to_string()
+    /// }
+    ///
+    /// let app = App::new().service(
+    ///     web::scope("")
+    ///         .host("{tenant}.example.com")
+    ///         .route("/", web::get().to(home))
+    ///         .service(web::resource("/settings").name("settings").to(|| async { "" }))
+    /// );
+    /// ```
+    pub fn host(mut self, host: &str) -> Self {
+        self.guards.push(Box::new(guard::Host(host)));
+        self.host = Some(guard::host_pattern(host));
+        self
+    }
+
     /// Add scope data.
     ///
     /// Data of different types from parent contexts will still be accessible. Any `Data<T>` types
@@ -321,9 +356,11 @@ where
             rdef: self.rdef,
             app_data: self.app_data,
             guards: self.guards,
+            middleware: self.middleware + 1,
             services: self.services,
             default: self.default,
             external: self.external,
+            host: self.host,
             factory_ref: self.factory_ref,
         }
     }
@@ -359,9 +396,11 @@ where
             rdef: self.rdef,
             app_data: self.app_data,
             guards: self.guards,
+            middleware: self.middleware + 1,
             services: self.services,
             default: self.default,
             external: self.external,
+            host: self.host,
             factory_ref: self.factory_ref,
         }
     }
@@ -388,7 +427,11 @@ where
             .into_iter()
             .for_each(|mut srv| srv.register(&mut cfg));
 
-        let mut rmap = ResourceMap::new(ResourceDef::root_prefix(&self.rdef));
+        let mut rmap = ResourceMap::with_details(
+            ResourceDef::root_prefix(&self.rdef),
+            ServiceDetails::new(&self.guards, self.middleware),
+        );
+        rmap.set_host(self.host.take());
 
         // external resources
         for mut rdef in mem::take(&mut self.external) {
@@ -402,8 +445,8 @@ where
                 .into_services()
                 .1
                 .into_iter()
-                .map(|(mut rdef, srv, guards, nested)| {
-                    rmap.add(&mut rdef, nested);
+                .map(|(mut rdef, srv, guards, nested, details)| {
+                    rmap.add_service(&mut rdef, nested, details);
                     (rdef, srv, RefCell::new(guards))
                 })
                 .collect::<Vec<_>>()
@@ -512,10 +555,17 @@ impl Service<ServiceRequest> for ScopeService {
     fn call(&self, mut req: ServiceRequest) -> Self::Future {
         let res = self.router.recognize_fn(&mut req, |req, guards| {
             let guard_ctx = req.guard_ctx();
-            guards.iter().all(|guard| guard.check(&guard_ctx))
+            let matched = guards.iter().all(|guard| guard.check(&guard_ctx));
+
+            if !matched && !guards.is_empty() {
+                HostParams::discard(req);
+            }
+
+            matched
         });
 
         if let Some((srv, _info)) = res {
+            HostParams::merge(&mut req);
             srv.call(req)
         } else {
             self.default.call(req)
@@ -1162,6 +1212,78 @@ mod tests {
         );
     }
 
+    #[actix_rt::test]
+    async fn test_scope_host() {
+        let srv = init_service(
+            App::new().service(
+                web::scope("/app").host("{tenant}.example.com").service(
+                    web::resource("/settings")
+                        .name("settings")
+                        .route(
+                            web::get().to(|req: HttpRequest, tenant: web::Path<String>| {
+                                let url = req.url_for("settings", [tenant.as_str()]).unwrap();
+                                HttpResponse::Ok().body(format!("{} {}", tenant, url))
+                            }),
+                        ),
+                ),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/app/settings")
+            .insert_header((header::HOST, "acme.example.com:8080"))
+            .to_request();
+        let resp = call_service(&srv, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_body_eq!(resp, b"acme http://acme.example.com:8080/app/settings");
+
+        let req = TestRequest::with_uri("/app/settings")
+            .insert_header((header::HOST, "example.com"))
+            .to_request();
+        let resp = call_service(&srv, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = TestRequest::with_uri("/app/settings")
+            .insert_header((header::HOST, "a.b.example.com"))
+            .to_request();
+        let resp = call_service(&srv, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn test_host_guard_params_not_leaked() {
+        let srv = init_service(
+            App::new()
+                .service(
+                    web::scope("/{id}")
+                        .guard(guard::Host("{tenant}.example.com"))
+                        .guard(guard::Post())
+                        .route("", web::to(HttpResponse::Created)),
+                )
+                .service(web::scope("/{id}").route(
+                    "",
+                    web::to(|req: HttpRequest| {
+                        HttpResponse::Ok().body(req.match_info().segment_count().to_string())
+                    }),
+                )),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/1")
+            .insert_header((header::HOST, "acme.example.com"))
+            .method(Method::POST)
+            .to_request();
+        let resp = call_service(&srv, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let req = TestRequest::with_uri("/1")
+            .insert_header((header::HOST, "acme.example.com"))
+            .to_request();
+        let resp = call_service(&srv, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_body_eq!(resp, b"1");
+    }
+
     #[actix_rt::test]
     async fn dynamic_scopes() {
         let srv = init_service(
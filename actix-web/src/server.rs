@@ -17,6 +17,8 @@ use actix_service::{
 #[cfg(feature = "openssl")]
 use actix_tls::accept::openssl::reexports::{AlpnError, SslAcceptor, SslAcceptorBuilder};
 
+#[cfg(feature = "__tls")]
+use crate::dev::PeerCertificates;
 use crate::{config::AppConfig, Error};
 
 struct Socket {
@@ -271,7 +273,12 @@ where
     ///   Rustls v0.23.
     /// - `actix_web::rt::net::TcpStream` when no encryption is used.
     ///
+    /// On TLS connections, the client's certificates are available as [`PeerCertificates`]
+    /// connection data without setting up an on-connect callback.
+    ///
     /// See the `on_connect` example for additional details.
+    ///
+    /// [`PeerCertificates`]: crate::dev::PeerCertificates
     pub fn on_connect<CB>(self, f: CB) -> HttpServer<F, I, S, B>
     where
         CB: Fn(&dyn Any, &mut Extensions) + Send + Sync + 'static,
@@ -652,11 +659,10 @@ where
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout);
 
-                    let svc = if let Some(handler) = on_connect_fn.clone() {
-                        svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
-                    } else {
-                        svc
-                    };
+                    let on_connect_fn = on_connect_fn.clone();
+                    let svc = svc.on_connect_ext(move |io: &_, ext: _| {
+                        tls_on_connect(io as &dyn Any, ext, on_connect_fn.as_deref())
+                    });
 
                     let fac = factory()
                         .into_factory()
@@ -703,11 +709,10 @@ where
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout);
 
-                    let svc = if let Some(handler) = on_connect_fn.clone() {
-                        svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
-                    } else {
-                        svc
-                    };
+                    let on_connect_fn = on_connect_fn.clone();
+                    let svc = svc.on_connect_ext(move |io: &_, ext: _| {
+                        tls_on_connect(io as &dyn Any, ext, on_connect_fn.as_deref())
+                    });
 
                     let fac = factory()
                         .into_factory()
@@ -769,11 +774,10 @@ where
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout);
 
-                    let svc = if let Some(handler) = on_connect_fn.clone() {
-                        svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
-                    } else {
-                        svc
-                    };
+                    let on_connect_fn = on_connect_fn.clone();
+                    let svc = svc.on_connect_ext(move |io: &_, ext: _| {
+                        tls_on_connect(io as &dyn Any, ext, on_connect_fn.as_deref())
+                    });
 
                     let fac = factory()
                         .into_factory()
@@ -835,11 +839,10 @@ where
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout);
 
-                    let svc = if let Some(handler) = on_connect_fn.clone() {
-                        svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
-                    } else {
-                        svc
-                    };
+                    let on_connect_fn = on_connect_fn.clone();
+                    let svc = svc.on_connect_ext(move |io: &_, ext: _| {
+                        tls_on_connect(io as &dyn Any, ext, on_connect_fn.as_deref())
+                    });
 
                     let fac = factory()
                         .into_factory()
@@ -901,11 +904,10 @@ where
                         .client_disconnect_timeout(c.client_disconnect_timeout)
                         .local_addr(addr);
 
-                    let svc = if let Some(handler) = on_connect_fn.clone() {
-                        svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
-                    } else {
-                        svc
-                    };
+                    let on_connect_fn = on_connect_fn.clone();
+                    let svc = svc.on_connect_ext(move |io: &_, ext: _| {
+                        tls_on_connect(io as &dyn Any, ext, on_connect_fn.as_deref())
+                    });
 
                     let fac = factory()
                         .into_factory()
@@ -1093,6 +1095,23 @@ fn create_tcp_listener(addr: net::SocketAddr, backlog: u32) -> io::Result<net::T
     Ok(net::TcpListener::from(socket))
 }
 
+/// Stores the client certificates of a TLS connection and calls the user's on-connect callback.
+#[cfg(feature = "__tls")]
+#[allow(clippy::type_complexity)]
+fn tls_on_connect(
+    io: &dyn Any,
+    ext: &mut Extensions,
+    handler: Option<&(dyn Fn(&dyn Any, &mut Extensions) + Send + Sync)>,
+) {
+    if let Some(certs) = PeerCertificates::from_tls_stream(io) {
+        ext.insert(certs);
+    }
+
+    if let Some(handler) = handler {
+        (handler)(io, ext);
+    }
+}
+
 /// Configures OpenSSL acceptor `builder` with ALPN protocols.
 #[cfg(feature = "openssl")]
 fn openssl_acceptor(mut builder: SslAcceptorBuilder) -> io::Result<SslAcceptor> {
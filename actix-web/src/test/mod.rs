@@ -2,6 +2,7 @@
 //!
 //! # Initializing A Test Service
 //! - [`init_service`]
+//! - [`route_table`]
 //!
 //! # Off-The-Shelf Test Services
 //! - [`ok_service`]
@@ -39,7 +40,7 @@ pub use self::{
     test_request::TestRequest,
     test_utils::{
         call_and_read_body, call_and_read_body_json, call_service, init_service, read_body,
-        read_body_json, try_call_and_read_body_json, try_call_service, try_read_body,
+        read_body_json, route_table, try_call_and_read_body_json, try_call_service, try_read_body,
         try_read_body_json,
     },
 };
@@ -65,6 +65,7 @@ pub struct TestRequest {
     path: Path<Url>,
     peer_addr: Option<SocketAddr>,
     app_data: Extensions,
+    conn_data: Option<Extensions>,
     #[cfg(feature = "cookies")]
     cookies: CookieJar,
 }
@@ -78,6 +79,7 @@ impl Default for TestRequest {
             path: Path::new(Url::new(Uri::default())),
             peer_addr: None,
             app_data: Extensions::new(),
+            conn_data: None,
             #[cfg(feature = "cookies")]
             cookies: CookieJar::new(),
         }
@@ -178,6 +180,22 @@ impl TestRequest {
         self
     }
 
+    /// Inserts connection data, as if set by an [on-connect](crate::HttpServer::on_connect)
+    /// callback.
+    ///
+    /// Connection data is only available on requests created by [`to_srv_request`],
+    /// [`to_http_request`] and [`to_http_parts`].
+    ///
+    /// [`to_srv_request`]: Self::to_srv_request
+    /// [`to_http_request`]: Self::to_http_request
+    /// [`to_http_parts`]: Self::to_http_parts
+    pub fn conn_data<T: 'static>(mut self, data: T) -> Self {
+        self.conn_data
+            .get_or_insert_with(Extensions::new)
+            .insert(data);
+        self
+    }
+
     /// Sets request payload.
     pub fn set_payload(mut self, data: impl Into<Bytes>) -> Self {
         self.req.set_payload(data);
@@ -279,7 +297,7 @@ impl TestRequest {
                 head,
                 app_state,
                 Rc::new(self.app_data),
-                None,
+                self.conn_data.map(Rc::new),
                 Default::default(),
             ),
             payload,
@@ -304,7 +322,7 @@ impl TestRequest {
             head,
             app_state,
             Rc::new(self.app_data),
-            None,
+            self.conn_data.map(Rc::new),
             Default::default(),
         )
     }
@@ -322,7 +340,7 @@ impl TestRequest {
             head,
             app_state,
             Rc::new(self.app_data),
-            None,
+            self.conn_data.map(Rc::new),
             Default::default(),
         );
 
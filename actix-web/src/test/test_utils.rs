@@ -7,10 +7,10 @@ use serde::de::DeserializeOwned;
 use crate::{
     body::{self, MessageBody},
     config::AppConfig,
-    dev::{Service, ServiceFactory},
-    service::ServiceResponse,
+    dev::{RouteInfo, Service, ServiceFactory},
+    service::{ServiceRequest, ServiceResponse},
     web::Bytes,
-    Error,
+    App, Error,
 };
 
 /// Initialize service from application builder instance.
@@ -64,6 +64,49 @@ where
     srv.new_service(AppConfig::default()).await
 }
 
+/// Initializes an app and returns its route table.
+///
+/// See [`ResourceMap::routes`](crate::dev::ResourceMap::routes).
+///
+/// # Examples
+/// ```
+/// use actix_web::{http::Method, test, web, App, HttpResponse};
+///
+/// #[actix_web::test]
+/// async fn test_route_table() {
+///     let app = App::new().service(
+///         web::scope("/api").route("/users/{id}", web::get().to(HttpResponse::Ok)),
+///     );
+///
+///     let routes = test::route_table(app).await;
+///     assert_eq!(routes[0].pattern(), "/api/users/{id}");
+///     assert_eq!(routes[0].methods(), Some(&[Method::GET][..]));
+/// }
+/// ```
+///
+/// # Panics
+/// Panics if app initialization returns an error.
+pub async fn route_table<T, B>(app: App<T>) -> Vec<RouteInfo>
+where
+    T: ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse<B>,
+            Error = Error,
+            InitError = (),
+        > + 'static,
+    T::Future: 'static,
+    B: MessageBody,
+{
+    let srv = app
+        .into_factory()
+        .new_service(AppConfig::default())
+        .await
+        .expect("service initialization failed");
+
+    srv.rmap().routes()
+}
+
 /// Calls service and waits for response future completion.
 ///
 /// # Examples
@@ -0,0 +1,316 @@
+//! For authentication extractor documentation, see [`BasicAuth`] and [`BearerAuth`].
+
+use std::borrow::Cow;
+
+use actix_utils::future::{ready, Ready};
+
+use crate::{
+    dev::Payload,
+    error::AuthenticationError,
+    extract::FromRequest,
+    http::header::{
+        Authorization, Basic, BasicChallenge, Bearer, BearerChallenge, BearerError, Header as _,
+    },
+    web, HttpRequest,
+};
+
+/// Extractor for credentials of the `Basic` authentication scheme.
+///
+/// If the `Authorization` header is missing or does not contain valid `Basic` credentials, the
+/// request is rejected with a `401 Unauthorized` response carrying a `WWW-Authenticate` challenge.
+/// The challenge's realm can be configured using [`BasicAuthConfig`].
+///
+/// Checking the credentials is left to the handler, or to the
+/// [`HttpAuthentication`](crate::middleware::HttpAuthentication) middleware.
+///
+/// # Examples
+/// ```
+/// use actix_web::{get, web};
+///
+/// #[get("/")]
+/// async fn index(auth: web::BasicAuth) -> String {
+///     format!("Hello, {}!", auth.user_id())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicAuth(Basic);
+
+impl BasicAuth {
+    /// Returns the user ID.
+    pub fn user_id(&self) -> &str {
+        self.0.user_id()
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &str {
+        self.0.password()
+    }
+
+    /// Unwrap into the inner credentials.
+    pub fn into_inner(self) -> Basic {
+        self.0
+    }
+}
+
+impl From<Basic> for BasicAuth {
+    fn from(basic: Basic) -> Self {
+        BasicAuth(basic)
+    }
+}
+
+impl FromRequest for BasicAuth {
+    type Error = AuthenticationError<BasicChallenge>;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            Authorization::<Basic>::parse(req)
+                .map(|auth| BasicAuth(auth.into_scheme()))
+                .map_err(|_| AuthenticationError::new(BasicAuthConfig::from_req(req).challenge())),
+        )
+    }
+}
+
+/// [`BasicAuth`] extractor configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{web, App};
+///
+/// App::new().app_data(web::BasicAuthConfig::default().realm("admin"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BasicAuthConfig {
+    realm: Option<Cow<'static, str>>,
+}
+
+impl BasicAuthConfig {
+    /// Sets the realm sent in challenges. Defaults to `"Restricted"`.
+    pub fn realm<T: Into<Cow<'static, str>>>(mut self, realm: T) -> Self {
+        self.realm = Some(realm.into());
+        self
+    }
+
+    /// Returns the challenge sent when credentials are missing or invalid.
+    pub fn challenge(&self) -> BasicChallenge {
+        BasicChallenge::new(self.realm.clone().unwrap_or(Cow::Borrowed(DEFAULT_REALM)))
+    }
+
+    /// Extract config from app data. Check both `T` and `Data<T>`, in that order, and fall back to
+    /// the default config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_BASIC_CONFIG)
+    }
+}
+
+pub(super) const DEFAULT_REALM: &str = "Restricted";
+
+/// Allow shared refs used as default.
+const DEFAULT_BASIC_CONFIG: BasicAuthConfig = BasicAuthConfig { realm: None };
+
+/// Extractor for credentials of the `Bearer` authentication scheme.
+///
+/// Requests without an `Authorization` header are rejected with a `401 Unauthorized` response.
+/// Requests with a malformed or non-`Bearer` `Authorization` header are rejected with a
+/// `400 Bad Request` response and the `invalid_request` error code. Both responses carry a
+/// `WWW-Authenticate` challenge, which can be configured using [`BearerAuthConfig`].
+///
+/// Checking the token is left to the handler, or to the
+/// [`HttpAuthentication`](crate::middleware::HttpAuthentication) middleware.
+///
+/// # Examples
+/// ```
+/// use actix_web::{get, web};
+///
+/// #[get("/")]
+/// async fn index(auth: web::BearerAuth) -> String {
+///     format!("Your token is {}", auth.token())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerAuth(Bearer);
+
+impl BearerAuth {
+    /// Returns the bearer token.
+    pub fn token(&self) -> &str {
+        self.0.token()
+    }
+
+    /// Unwrap into the inner credentials.
+    pub fn into_inner(self) -> Bearer {
+        self.0
+    }
+}
+
+impl From<Bearer> for BearerAuth {
+    fn from(bearer: Bearer) -> Self {
+        BearerAuth(bearer)
+    }
+}
+
+impl FromRequest for BearerAuth {
+    type Error = AuthenticationError<BearerChallenge>;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            Authorization::<Bearer>::parse(req)
+                .map(|auth| BearerAuth(auth.into_scheme()))
+                .map_err(|_| {
+                    let mut challenge = BearerAuthConfig::from_req(req).challenge();
+
+                    // RFC 6750 §3.1: requests lacking credentials get a challenge without an error
+                    if req.headers().contains_key(Authorization::<Bearer>::name()) {
+                        challenge = challenge.error(BearerError::InvalidRequest);
+                    }
+
+                    AuthenticationError::new(challenge)
+                }),
+        )
+    }
+}
+
+/// [`BearerAuth`] extractor configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{web, App};
+///
+/// App::new().app_data(web::BearerAuthConfig::default().realm("api").scope("read"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BearerAuthConfig {
+    realm: Option<Cow<'static, str>>,
+    scope: Option<Cow<'static, str>>,
+}
+
+impl BearerAuthConfig {
+    /// Sets the realm sent in challenges.
+    pub fn realm<T: Into<Cow<'static, str>>>(mut self, realm: T) -> Self {
+        self.realm = Some(realm.into());
+        self
+    }
+
+    /// Sets the space-delimited list of scopes sent in challenges.
+    pub fn scope<T: Into<Cow<'static, str>>>(mut self, scope: T) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Returns the challenge sent when credentials are missing or invalid.
+    ///
+    /// Validators can use this challenge, with an error code added, to reject tokens.
+    pub fn challenge(&self) -> BearerChallenge {
+        let mut challenge = BearerChallenge::new();
+
+        if let Some(realm) = &self.realm {
+            challenge = challenge.realm(realm.clone());
+        }
+
+        if let Some(scope) = &self.scope {
+            challenge = challenge.scope(scope.clone());
+        }
+
+        challenge
+    }
+
+    /// Extract config from app data. Check both `T` and `Data<T>`, in that order, and fall back to
+    /// the default config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_BEARER_CONFIG)
+    }
+}
+
+/// Allow shared refs used as default.
+const DEFAULT_BEARER_CONFIG: BearerAuthConfig = BearerAuthConfig {
+    realm: None,
+    scope: None,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{header, StatusCode},
+        test::TestRequest,
+        ResponseError as _,
+    };
+
+    #[actix_rt::test]
+    async fn basic_auth() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header(Authorization(Basic::new("Aladdin", "open sesame")))
+            .to_http_parts();
+
+        let auth = BasicAuth::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(auth.user_id(), "Aladdin");
+        assert_eq!(auth.password(), "open sesame");
+
+        let (req, mut pl) = TestRequest::default().to_http_parts();
+        let res = BasicAuth::from_request(&req, &mut pl)
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Basic realm="Restricted", charset="UTF-8""#
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .app_data(BasicAuthConfig::default().realm("admin"))
+            .insert_header(Authorization(Bearer::new("abc")))
+            .to_http_parts();
+        let res = BasicAuth::from_request(&req, &mut pl)
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Basic realm="admin", charset="UTF-8""#
+        );
+    }
+
+    #[actix_rt::test]
+    async fn bearer_auth() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header(Authorization(Bearer::new("abc")))
+            .to_http_parts();
+
+        let auth = BearerAuth::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(auth.token(), "abc");
+
+        let (req, mut pl) = TestRequest::default()
+            .app_data(web::Data::new(BearerAuthConfig::default().realm("api")))
+            .to_http_parts();
+        let res = BearerAuth::from_request(&req, &mut pl)
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Bearer realm="api""#
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header(Authorization(Basic::new("user", "pass")))
+            .to_http_parts();
+        let res = BearerAuth::from_request(&req, &mut pl)
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            r#"Bearer error="invalid_request""#
+        );
+    }
+}
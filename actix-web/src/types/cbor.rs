@@ -0,0 +1,766 @@
+//! For CBOR helper documentation, see [`Cbor`].
+
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use actix_http::Payload;
+use bytes::BytesMut;
+use futures_core::{ready, Stream as _};
+use mime::Mime;
+use serde::{
+    de::{
+        self,
+        value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Serialize,
+};
+
+#[cfg(feature = "__compress")]
+use crate::dev::Decompress;
+use crate::{
+    body::EitherBody,
+    error::{CborPayloadError, Error},
+    extract::FromRequest,
+    http::header::{ContentLength, Header as _},
+    request::HttpRequest,
+    types::{Codec, CodecError, DecodeFn},
+    web, HttpMessage, HttpResponse, Responder,
+};
+
+/// CBOR extractor and responder.
+///
+/// `Cbor` has two uses: [CBOR](https://cbor.io) responses, and extracting typed data from CBOR
+/// request payloads. It works like [`Json`](web::Json), with the same options available through
+/// [`CborConfig`].
+///
+/// Encoding and decoding is provided by [`ciborium`], with a recursion limit on decoding.
+///
+/// # Extractor
+/// ```
+/// use actix_web::{post, web};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// #[post("/")]
+/// async fn index(info: web::Cbor<Info>) -> String {
+///     format!("Welcome {}!", info.username)
+/// }
+/// ```
+///
+/// # Responder
+/// ```
+/// use actix_web::{get, web};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Info {
+///     name: String,
+/// }
+///
+/// #[get("/{name}")]
+/// async fn index(name: web::Path<String>) -> web::Cbor<Info> {
+///     web::Cbor(Info {
+///         name: name.into_inner(),
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Cbor<T>(pub T);
+
+impl<T> Cbor<T> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for Cbor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for Cbor<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Cbor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: Serialize> Serialize for Cbor<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Creates response with OK status code, correct content type header, and serialized CBOR
+/// payload.
+impl<T: Serialize> Responder for Cbor<T> {
+    type Body = EitherBody<Vec<u8>>;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        match to_vec(&self.0) {
+            Ok(body) => match HttpResponse::Ok()
+                .content_type(APPLICATION_CBOR)
+                .message_body(body)
+            {
+                Ok(res) => res.map_into_left_body(),
+                Err(err) => HttpResponse::from_error(err).map_into_right_body(),
+            },
+
+            Err(err) => {
+                HttpResponse::from_error(CborPayloadError::Serialize(err)).map_into_right_body()
+            }
+        }
+    }
+}
+
+/// See [here](#extractor) for example of usage as an extractor.
+impl<T: DeserializeOwned> FromRequest for Cbor<T> {
+    type Error = Error;
+    type Future = CborExtractFut<T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = CborConfig::from_req(req);
+
+        let limit = config.limit;
+        let ctype_required = config.content_type_required;
+        let ctype_fn = config.content_type.as_deref();
+        let err_handler = config.err_handler.clone();
+
+        CborExtractFut {
+            req: Some(req.clone()),
+            fut: CborBody::new(req, payload, ctype_fn, ctype_required).limit(limit),
+            err_handler,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> web::ValidatedExtractor for Cbor<T> {}
+
+type CborErrorHandler = Option<Arc<dyn Fn(CborPayloadError, &HttpRequest) -> Error + Send + Sync>>;
+
+pub struct CborExtractFut<T> {
+    req: Option<HttpRequest>,
+    fut: CborBody<T>,
+    err_handler: CborErrorHandler,
+}
+
+impl<T: DeserializeOwned> Future for CborExtractFut<T> {
+    type Output = Result<Cbor<T>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let res = ready!(Pin::new(&mut this.fut).poll(cx));
+
+        let res = match res {
+            Err(err) => {
+                let req = this.req.take().unwrap();
+                log::debug!(
+                    "Failed to deserialize CBOR from payload. Request path: {}",
+                    req.path()
+                );
+
+                if let Some(err_handler) = this.err_handler.as_ref() {
+                    Err((*err_handler)(err, &req))
+                } else {
+                    Err(err.into())
+                }
+            }
+            Ok(data) => Ok(Cbor(data)),
+        };
+
+        Poll::Ready(res)
+    }
+}
+
+/// `Cbor` extractor configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+///
+/// let cbor_cfg = web::CborConfig::default()
+///     // limit request payload size
+///     .limit(4096)
+///     // also accept a legacy content type
+///     .content_type(|mime| mime.essence_str() == "application/x-cbor")
+///     // use custom error handler
+///     .error_handler(|err, req| {
+///         error::InternalError::from_response(err, HttpResponse::Conflict().into()).into()
+///     });
+///
+/// App::new().app_data(cbor_cfg);
+/// ```
+#[derive(Clone)]
+pub struct CborConfig {
+    limit: usize,
+    err_handler: CborErrorHandler,
+    content_type: Option<Arc<dyn Fn(Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl CborConfig {
+    /// Set maximum accepted payload size. By default this limit is 2MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set custom error handler.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(CborPayloadError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Set predicate for allowed content types, in addition to the CBOR media types.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Sets whether or not the request must have a `Content-Type` header to be parsed.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    /// Extract payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
+    /// back to the default payload config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+
+/// Allow shared refs used as default.
+const DEFAULT_CONFIG: CborConfig = CborConfig {
+    limit: DEFAULT_LIMIT,
+    err_handler: None,
+    content_type: None,
+    content_type_required: true,
+};
+
+impl Default for CborConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG
+    }
+}
+
+/// Future that resolves to some `T` when parsed from a CBOR payload.
+///
+/// Returns error if:
+/// - `Content-Type` is not a CBOR media type when `ctype_required` (passed to
+///   [`new`][Self::new]) is `true`.
+/// - `Content-Length` is greater than [limit](CborBody::limit()).
+/// - The payload, when consumed, is not valid CBOR.
+pub enum CborBody<T> {
+    Error(Option<CborPayloadError>),
+    Body {
+        limit: usize,
+        /// Length as reported by `Content-Length` header, if present.
+        length: Option<usize>,
+        #[cfg(feature = "__compress")]
+        payload: Decompress<Payload>,
+        #[cfg(not(feature = "__compress"))]
+        payload: Payload,
+        buf: BytesMut,
+        _res: PhantomData<T>,
+    },
+}
+
+impl<T> Unpin for CborBody<T> {}
+
+impl<T: DeserializeOwned> CborBody<T> {
+    /// Create a new future to decode a CBOR request payload.
+    pub fn new(
+        req: &HttpRequest,
+        payload: &mut Payload,
+        ctype_fn: Option<&(dyn Fn(Mime) -> bool + Send + Sync)>,
+        ctype_required: bool,
+    ) -> Self {
+        let can_parse = match (ctype_required, req.mime_type()) {
+            (true, Ok(Some(mime))) => {
+                is_cbor(&mime) || ctype_fn.is_some_and(|predicate| predicate(mime))
+            }
+
+            // if content-type is expected but not parsable as mime type, bail
+            (true, _) => false,
+
+            // if content-type validation is disabled, assume payload is CBOR
+            (false, _) => true,
+        };
+
+        if !can_parse {
+            return CborBody::Error(Some(CborPayloadError::ContentType));
+        }
+
+        let length = ContentLength::parse(req).ok().map(|x| x.0);
+
+        let payload = {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "__compress")] {
+                    Decompress::from_headers(payload.take(), req.headers())
+                } else {
+                    payload.take()
+                }
+            }
+        };
+
+        CborBody::Body {
+            limit: DEFAULT_LIMIT,
+            length,
+            payload,
+            buf: BytesMut::with_capacity(8192),
+            _res: PhantomData,
+        }
+    }
+
+    /// Set maximum accepted payload size. The default limit is 2MB.
+    pub fn limit(self, limit: usize) -> Self {
+        match self {
+            CborBody::Body {
+                length,
+                payload,
+                buf,
+                ..
+            } => {
+                if let Some(len) = length {
+                    if len > limit {
+                        return CborBody::Error(Some(CborPayloadError::OverflowKnownLength {
+                            length: len,
+                            limit,
+                        }));
+                    }
+                }
+
+                CborBody::Body {
+                    limit,
+                    length,
+                    payload,
+                    buf,
+                    _res: PhantomData,
+                }
+            }
+            CborBody::Error(err) => CborBody::Error(err),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Future for CborBody<T> {
+    type Output = Result<T, CborPayloadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match this {
+            CborBody::Body {
+                limit,
+                buf,
+                payload,
+                ..
+            } => loop {
+                let res = ready!(Pin::new(&mut *payload).poll_next(cx));
+                match res {
+                    Some(chunk) => {
+                        let chunk = chunk?;
+                        let buf_len = buf.len() + chunk.len();
+                        if buf_len > *limit {
+                            return Poll::Ready(Err(CborPayloadError::Overflow { limit: *limit }));
+                        } else {
+                            buf.extend_from_slice(&chunk);
+                        }
+                    }
+                    None => {
+                        let data = from_slice::<T>(buf).map_err(CborPayloadError::Deserialize)?;
+                        return Poll::Ready(Ok(data));
+                    }
+                }
+            },
+            CborBody::Error(err) => Poll::Ready(Err(err.take().unwrap())),
+        }
+    }
+}
+
+/// CBOR codec for [`Negotiated`](web::Negotiated).
+///
+/// Responds with `application/cbor` and supports the same media types as the [`Cbor`]
+/// extractor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+impl Codec for CborCodec {
+    fn media_type(&self) -> Mime {
+        APPLICATION_CBOR.parse().unwrap()
+    }
+
+    fn supports(&self, mime: &Mime) -> bool {
+        is_cbor(mime)
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError> {
+        Ok(to_vec(&value)?)
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError> {
+        // ciborium only deserializes into concrete types, so the payload is decoded into its data
+        // model first, which represents all CBOR values
+        let value = from_slice::<ciborium::Value>(body)?;
+        Ok(visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(
+            ValueDeserializer(value),
+        ))?)
+    }
+}
+
+/// Deserializer for a decoded CBOR value, built on the deserializers of [`serde::de::value`].
+struct ValueDeserializer(ciborium::Value);
+
+impl<'de> IntoDeserializer<'de, ciborium::value::Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = ciborium::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        use ciborium::Value;
+
+        match self.0 {
+            Value::Integer(int) => match (u64::try_from(int), i64::try_from(int)) {
+                (Ok(int), _) => visitor.visit_u64(int),
+                (_, Ok(int)) => visitor.visit_i64(int),
+                _ => visitor.visit_i128(int.into()),
+            },
+
+            // positive and negative bignums, as used by ciborium for 128-bit integers
+            Value::Tag(tag @ (2 | 3), value) if matches!(&*value, Value::Bytes(bytes) if bytes.len() <= 16) =>
+            {
+                let Value::Bytes(bytes) = *value else {
+                    unreachable!()
+                };
+
+                let mut buf = [0; 16];
+                buf[16 - bytes.len()..].copy_from_slice(&bytes);
+                let int = u128::from_be_bytes(buf);
+
+                match (tag, i128::try_from(int)) {
+                    (2, _) => visitor.visit_u128(int),
+                    (_, Ok(int)) => visitor.visit_i128(int ^ !0),
+                    _ => Err(de::Error::custom("integer out of range")),
+                }
+            }
+
+            Value::Tag(_, value) => ValueDeserializer(*value).deserialize_any(visitor),
+            Value::Bytes(bytes) => visitor.visit_byte_buf(bytes),
+            Value::Float(float) => visitor.visit_f64(float),
+            Value::Text(text) => visitor.visit_string(text),
+            Value::Bool(bool) => visitor.visit_bool(bool),
+            Value::Null => visitor.visit_unit(),
+
+            Value::Array(items) => {
+                let mut seq = SeqDeserializer::new(items.into_iter().map(ValueDeserializer));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+
+            Value::Map(entries) => {
+                let mut map = MapDeserializer::new(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (ValueDeserializer(key), ValueDeserializer(value))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+
+            _ => Err(de::Error::custom("unsupported CBOR value")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            ciborium::Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        use ciborium::Value;
+
+        // unit variants are encoded as their name and other variants as a single-entry map
+        match self.0 {
+            Value::Text(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Map(entries) if entries.len() == 1 => {
+                visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (ValueDeserializer(key), ValueDeserializer(value))),
+                )))
+            }
+            Value::Tag(_, value) => {
+                ValueDeserializer(*value).deserialize_enum(name, variants, visitor)
+            }
+            _ => Err(de::Error::custom("expected enum")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+const APPLICATION_CBOR: &str = "application/cbor";
+
+/// Returns true for `application/cbor` and the `+cbor` suffix.
+fn is_cbor(mime: &Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && (mime.subtype() == "cbor" || mime.suffix().is_some_and(|suffix| suffix == "cbor"))
+}
+
+fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+    let mut buf = Vec::new();
+    ciborium::into_writer(value, &mut buf)?;
+    Ok(buf)
+}
+
+fn from_slice<T: DeserializeOwned>(body: &[u8]) -> Result<T, ciborium::de::Error<std::io::Error>> {
+    ciborium::from_reader(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bytes::Bytes;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        http::{
+            header::{self, CONTENT_LENGTH, CONTENT_TYPE},
+            StatusCode,
+        },
+        test::{assert_body_eq, TestRequest},
+    };
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct MyObject {
+        name: String,
+    }
+
+    // {"name": "test"}
+    const MY_OBJECT: &[u8] = b"\xa1\x64name\x64test";
+
+    #[actix_rt::test]
+    async fn test_responder() {
+        let req = TestRequest::default().to_http_request();
+
+        let res = Cbor(MyObject {
+            name: "test".to_owned(),
+        })
+        .respond_to(&req);
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            header::HeaderValue::from_static("application/cbor")
+        );
+        assert_body_eq!(res, MY_OBJECT);
+    }
+
+    #[actix_rt::test]
+    async fn test_extract() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/cbor"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let s = Cbor::<MyObject>::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(
+            s.into_inner(),
+            MyObject {
+                name: "test".to_owned()
+            }
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/vnd.example+cbor"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let s = Cbor::<MyObject>::from_request(&req, &mut pl).await;
+        assert!(s.is_ok());
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/msgpack"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let err = Cbor::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/cbor"))
+            .set_payload(Bytes::from_static(b"\x64test"))
+            .to_http_parts();
+
+        let err = Cbor::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CBOR deserialize error"));
+    }
+
+    #[actix_rt::test]
+    async fn test_config() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-cbor"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .app_data(
+                CborConfig::default()
+                    .content_type(|mime| mime.essence_str() == "application/x-cbor"),
+            )
+            .to_http_parts();
+
+        let s = Cbor::<MyObject>::from_request(&req, &mut pl).await;
+        assert!(s.is_ok());
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/cbor"))
+            .insert_header((CONTENT_LENGTH, "10000"))
+            .app_data(CborConfig::default().limit(100))
+            .to_http_parts();
+
+        let err = Cbor::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_body_limit() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/cbor"))
+            .set_payload(Bytes::from_static(&[0xf6; 20]))
+            .to_http_parts();
+
+        let res = CborBody::<MyObject>::new(&req, &mut pl, None, true)
+            .limit(10)
+            .await;
+        assert!(matches!(
+            res.unwrap_err(),
+            CborPayloadError::Overflow { limit: 10 }
+        ));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Level {
+        High,
+    }
+
+    fn decode<T: DeserializeOwned>(body: &[u8]) -> T {
+        let mut data = None;
+
+        CborCodec
+            .decode(body, &mut |de| {
+                data = Some(erased_serde::deserialize(de)?);
+                Ok(())
+            })
+            .unwrap();
+
+        data.unwrap()
+    }
+
+    #[test]
+    fn codec() {
+        let codec = CborCodec;
+        assert!(codec.supports(&"application/cbor".parse().unwrap()));
+        assert!(!codec.supports(&mime::APPLICATION_JSON));
+
+        let body = codec
+            .encode(&MyObject {
+                name: "test".to_owned(),
+            })
+            .unwrap();
+        assert_eq!(body, MY_OBJECT);
+        assert_eq!(
+            decode::<MyObject>(&body),
+            MyObject {
+                name: "test".to_owned()
+            }
+        );
+
+        // byte strings, integer keys and 128-bit integers have no JSON equivalent
+        let value = (
+            ciborium::Value::Bytes(vec![1, 2]),
+            BTreeMap::from([(1u8, u128::MAX)]),
+            i128::MIN,
+            Some(Level::High),
+        );
+        let body = codec.encode(&value).unwrap();
+        assert_eq!(
+            decode::<(ciborium::Value, BTreeMap<u8, u128>, i128, Option<Level>)>(&body),
+            value
+        );
+    }
+}
@@ -0,0 +1,410 @@
+//! For Digest authentication extractor documentation, see [`DigestAuth`].
+
+use std::{
+    borrow::Cow,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use actix_utils::future::{ready, Ready};
+use base64::prelude::*;
+use hmac::{Hmac, Mac as _};
+use once_cell::sync::Lazy;
+use sha2::Sha256;
+
+use super::auth::DEFAULT_REALM;
+use crate::{
+    dev::Payload,
+    error::AuthenticationError,
+    extract::FromRequest,
+    http::{
+        header::{Authorization, Digest, DigestAlgorithm, DigestChallenge, Header as _},
+        Method,
+    },
+    web, HttpRequest,
+};
+
+/// Extractor for credentials of the `Digest` authentication scheme, defined in
+/// [RFC 7616](https://datatracker.ietf.org/doc/html/rfc7616).
+///
+/// Credentials are only extracted if they answer a challenge issued by the [`DigestAuthConfig`]
+/// in app data: the realm, algorithm and nonce must match, the nonce must not have expired and
+/// the URI must be the request's. Otherwise, the request is rejected with a `401 Unauthorized`
+/// response carrying a fresh challenge. Challenges answering expired nonces are marked as stale,
+/// allowing clients to retry without prompting for a password.
+///
+/// The password is checked by calling [`verify`](Self::verify), or [`verify_ha1`](Self::verify_ha1)
+/// when only a hash of it is stored, usually in a handler or in the
+/// validator of the [`HttpAuthentication`](crate::middleware::HttpAuthentication) middleware.
+///
+/// Nonces are not tracked, so a captured request may be replayed until its nonce expires.
+///
+/// # Examples
+/// ```
+/// use actix_web::{get, web, Error};
+///
+/// #[get("/")]
+/// async fn index(auth: web::DigestAuth) -> Result<String, Error> {
+///     auth.verify("Circle of Life")?;
+///     Ok(format!("Hello, {}!", auth.username()))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DigestAuth {
+    credentials: Digest,
+    method: Method,
+    config: DigestAuthConfig,
+}
+
+impl DigestAuth {
+    /// Returns the user name.
+    pub fn username(&self) -> &str {
+        self.credentials.username()
+    }
+
+    /// Checks the credentials against the user's password.
+    ///
+    /// If they do not match, the returned error responds with a fresh challenge.
+    pub fn verify(&self, password: &str) -> Result<(), AuthenticationError<DigestChallenge>> {
+        if self.credentials.verify(password, &self.method) {
+            Ok(())
+        } else {
+            Err(AuthenticationError::new(self.config.challenge()))
+        }
+    }
+
+    /// Returns the hash algorithm chosen by the client.
+    ///
+    /// This is one of the algorithms offered by the config. Servers storing H(A1) values should use
+    /// it to pick the value to pass to [`verify_ha1`](Self::verify_ha1).
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.credentials.algorithm()
+    }
+
+    /// Checks the credentials against the user's H(A1), as computed by [`DigestAlgorithm::ha1`].
+    ///
+    /// This allows servers to store H(A1) instead of plaintext passwords. H(A1) depends on both
+    /// the realm and the algorithm, so it must have been computed with the config's realm and
+    /// with the [`algorithm`](Self::algorithm) chosen by the client. Stored values become invalid
+    /// when the realm changes, and one value is needed for each algorithm offered.
+    ///
+    /// If they do not match, the returned error responds with a fresh challenge.
+    pub fn verify_ha1(&self, ha1: &str) -> Result<(), AuthenticationError<DigestChallenge>> {
+        if self.credentials.verify_ha1(ha1, &self.method) {
+            Ok(())
+        } else {
+            Err(AuthenticationError::new(self.config.challenge()))
+        }
+    }
+
+    /// Unwrap into the inner credentials.
+    pub fn into_inner(self) -> Digest {
+        self.credentials
+    }
+}
+
+impl FromRequest for DigestAuth {
+    type Error = AuthenticationError<DigestChallenge>;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let config = DigestAuthConfig::from_req(req);
+
+        let Ok(Authorization(credentials)) = Authorization::<Digest>::parse(req) else {
+            return ready(Err(AuthenticationError::new(config.challenge())));
+        };
+
+        let uri = req.uri();
+        let uri_matches = *uri == *credentials.uri()
+            || Some(credentials.uri()) == uri.path_and_query().map(|pq| pq.as_str());
+
+        if credentials.realm() != config.realm
+            || !config.algorithms.contains(&credentials.algorithm())
+            || !uri_matches
+        {
+            return ready(Err(AuthenticationError::new(config.challenge())));
+        }
+
+        ready(match config.check_nonce(credentials.nonce()) {
+            NonceStatus::Valid => Ok(DigestAuth {
+                credentials,
+                method: req.method().clone(),
+                config: config.clone(),
+            }),
+            NonceStatus::Stale => Err(AuthenticationError::new(config.challenge().stale(true))),
+            NonceStatus::Invalid => Err(AuthenticationError::new(config.challenge())),
+        })
+    }
+}
+
+/// [`DigestAuth`] extractor configuration.
+///
+/// Nonces are signed with a secret key, which is randomly generated when the config is
+/// constructed. Since app data is constructed for each worker, the config should be constructed
+/// once, outside the server's app factory, and cloned into each app. Use [`secret`](Self::secret)
+/// to share nonces between servers.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use actix_web::{http::header::DigestAlgorithm, web, App, HttpServer};
+///
+/// # fn run() -> std::io::Result<actix_web::dev::Server> {
+/// let config = web::DigestAuthConfig::new("admin")
+///     .algorithms([DigestAlgorithm::Sha256, DigestAlgorithm::Md5])
+///     .nonce_lifetime(Duration::from_secs(60));
+///
+/// Ok(HttpServer::new(move || App::new().app_data(config.clone()))
+///     .bind(("127.0.0.1", 8080))?
+///     .run())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DigestAuthConfig {
+    realm: Cow<'static, str>,
+    algorithms: Vec<DigestAlgorithm>,
+    nonce_lifetime: Duration,
+    secret: Arc<[u8]>,
+}
+
+impl DigestAuthConfig {
+    /// Constructs new config for the given realm, with a random secret key.
+    ///
+    /// By default, challenges offer the `SHA-256` algorithm and nonces expire after 5 minutes.
+    pub fn new<T: Into<Cow<'static, str>>>(realm: T) -> Self {
+        DigestAuthConfig {
+            realm: realm.into(),
+            algorithms: vec![DigestAlgorithm::Sha256],
+            nonce_lifetime: Duration::from_secs(300),
+            secret: Arc::new(random_bytes::<32>()),
+        }
+    }
+
+    /// Sets the algorithms offered in challenges and accepted in credentials, in order of
+    /// preference.
+    ///
+    /// Add [`DigestAlgorithm::Md5`] to support clients that only implement RFC 2617.
+    ///
+    /// # Panics
+    /// Panics if `algorithms` is empty.
+    pub fn algorithms(mut self, algorithms: impl IntoIterator<Item = DigestAlgorithm>) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        assert!(
+            !self.algorithms.is_empty(),
+            "Digest challenges must offer an algorithm"
+        );
+        self
+    }
+
+    /// Sets how long nonces are accepted for after they are issued.
+    pub fn nonce_lifetime(mut self, lifetime: Duration) -> Self {
+        self.nonce_lifetime = lifetime;
+        self
+    }
+
+    /// Sets the secret key used to sign nonces.
+    pub fn secret(mut self, secret: &[u8]) -> Self {
+        self.secret = Arc::from(secret);
+        self
+    }
+
+    /// Returns a challenge with a fresh nonce.
+    pub fn challenge(&self) -> DigestChallenge {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        DigestChallenge::new(self.realm.clone(), self.nonce(timestamp, random_bytes()))
+            .algorithms(self.algorithms.iter().copied())
+    }
+
+    /// Creates a nonce consisting of the issue time, random bytes, and a signature of both.
+    fn nonce(&self, timestamp: u64, random: [u8; NONCE_RANDOM_LEN]) -> String {
+        let mut nonce = Vec::with_capacity(NONCE_LEN);
+        nonce.extend_from_slice(&timestamp.to_be_bytes());
+        nonce.extend_from_slice(&random);
+
+        let tag = self.nonce_mac(&nonce).finalize().into_bytes();
+        nonce.extend_from_slice(&tag[..NONCE_TAG_LEN]);
+
+        BASE64_URL_SAFE_NO_PAD.encode(nonce)
+    }
+
+    fn check_nonce(&self, nonce: &str) -> NonceStatus {
+        let nonce = match BASE64_URL_SAFE_NO_PAD.decode(nonce) {
+            Ok(nonce) if nonce.len() == NONCE_LEN => nonce,
+            _ => return NonceStatus::Invalid,
+        };
+
+        let (data, tag) = nonce.split_at(NONCE_LEN - NONCE_TAG_LEN);
+        if self.nonce_mac(data).verify_truncated_left(tag).is_err() {
+            return NonceStatus::Invalid;
+        }
+
+        let timestamp = u64::from_be_bytes(data[..8].try_into().unwrap());
+        let issued_at = UNIX_EPOCH + Duration::from_secs(timestamp);
+
+        match SystemTime::now().duration_since(issued_at) {
+            Ok(age) if age > self.nonce_lifetime => NonceStatus::Stale,
+            _ => NonceStatus::Valid,
+        }
+    }
+
+    fn nonce_mac(&self, data: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(data);
+        mac
+    }
+
+    /// Extract config from app data. Check both `T` and `Data<T>`, in that order, and fall back to
+    /// the default config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_DIGEST_CONFIG)
+    }
+}
+
+impl fmt::Debug for DigestAuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DigestAuthConfig")
+            .field("realm", &self.realm)
+            .field("algorithms", &self.algorithms)
+            .field("nonce_lifetime", &self.nonce_lifetime)
+            .finish_non_exhaustive()
+    }
+}
+
+const NONCE_RANDOM_LEN: usize = 16;
+const NONCE_TAG_LEN: usize = 16;
+const NONCE_LEN: usize = 8 + NONCE_RANDOM_LEN + NONCE_TAG_LEN;
+
+enum NonceStatus {
+    Valid,
+    Stale,
+    Invalid,
+}
+
+/// Shared by all workers, so that nonces are accepted by any of them.
+static DEFAULT_DIGEST_CONFIG: Lazy<DigestAuthConfig> =
+    Lazy::new(|| DigestAuthConfig::new(DEFAULT_REALM));
+
+/// Returns bytes from the operating system's random number generator.
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).expect("failed to generate random bytes");
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{
+            header::{self, Challenge as _},
+            StatusCode,
+        },
+        test::TestRequest,
+        ResponseError as _,
+    };
+
+    #[actix_rt::test]
+    async fn digest_auth() {
+        let config = DigestAuthConfig::new("api").algorithms([DigestAlgorithm::Md5]);
+        let challenge = config.challenge();
+
+        let credentials = Digest::compute(
+            &challenge,
+            "Mufasa",
+            "Circle of Life",
+            &Method::POST,
+            "/dir?page=1",
+            "0a4f113b",
+            1,
+        );
+        let (req, mut pl) = TestRequest::post()
+            .uri("/dir?page=1")
+            .app_data(config.clone())
+            .insert_header(Authorization(credentials.clone()))
+            .to_http_parts();
+
+        let auth = DigestAuth::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(auth.username(), "Mufasa");
+        assert!(auth.verify("Circle of Life").is_ok());
+        assert_eq!(auth.algorithm(), DigestAlgorithm::Md5);
+        let ha1 = DigestAlgorithm::Md5.ha1("Mufasa", "api", "Circle of Life");
+        assert!(auth.verify_ha1(&ha1).is_ok());
+        assert!(auth
+            .verify_ha1(&DigestAlgorithm::Md5.ha1("Mufasa", "api", "wrong"))
+            .is_err());
+
+        let res = auth.verify("wrong").unwrap_err().error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let value = res.headers().get(header::WWW_AUTHENTICATE).unwrap();
+        let new_challenge = DigestChallenge::parse(value.to_str().unwrap()).unwrap();
+        assert_ne!(new_challenge.get_nonce(), challenge.get_nonce());
+        assert_eq!(new_challenge.get_algorithms(), [DigestAlgorithm::Md5]);
+
+        // other request URI
+        let (req, mut pl) = TestRequest::post()
+            .uri("/other")
+            .app_data(config.clone())
+            .insert_header(Authorization(credentials.clone()))
+            .to_http_parts();
+        assert!(DigestAuth::from_request(&req, &mut pl).await.is_err());
+
+        // nonce signed with another secret
+        let (req, mut pl) = TestRequest::post()
+            .uri("/dir?page=1")
+            .app_data(config.clone().secret(b"other"))
+            .insert_header(Authorization(credentials))
+            .to_http_parts();
+        let err = DigestAuth::from_request(&req, &mut pl).await.unwrap_err();
+        assert!(!err.challenge().is_stale());
+
+        // missing credentials
+        let (req, mut pl) = TestRequest::default().to_http_parts();
+        let res = DigestAuth::from_request(&req, &mut pl)
+            .await
+            .unwrap_err()
+            .error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert!(res
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with(r#"Digest realm="Restricted", qop="auth", algorithm=SHA-256"#));
+    }
+
+    #[actix_rt::test]
+    async fn digest_auth_nonce_expiry() {
+        let config = DigestAuthConfig::new("api").nonce_lifetime(Duration::from_secs(60));
+
+        let issued_at = SystemTime::now() - Duration::from_secs(120);
+        let timestamp = issued_at.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let nonce = config.nonce(timestamp, [0; NONCE_RANDOM_LEN]);
+
+        assert!(matches!(config.check_nonce(&nonce), NonceStatus::Stale));
+        assert!(matches!(
+            config.check_nonce(&config.nonce(timestamp + 90, [0; NONCE_RANDOM_LEN])),
+            NonceStatus::Valid
+        ));
+
+        let challenge = DigestChallenge::new("api", nonce);
+        let credentials =
+            Digest::compute(&challenge, "Mufasa", "pass", &Method::GET, "/", "abc", 1);
+        let (req, mut pl) = TestRequest::default()
+            .app_data(config)
+            .insert_header(Authorization(credentials))
+            .to_http_parts();
+
+        let err = DigestAuth::from_request(&req, &mut pl).await.unwrap_err();
+        assert!(err.challenge().is_stale());
+    }
+}
@@ -20,8 +20,12 @@ use serde::{de::DeserializeOwned, Serialize};
 #[cfg(feature = "__compress")]
 use crate::dev::Decompress;
 use crate::{
-    body::EitherBody, error::UrlencodedError, extract::FromRequest, http::header::CONTENT_LENGTH,
-    web, Error, HttpMessage, HttpRequest, HttpResponse, Responder,
+    body::EitherBody,
+    error::{self, UrlencodedError, ValidationErrors},
+    extract::FromRequest,
+    http::header::CONTENT_LENGTH,
+    web::{self, ValidatedExtractor},
+    Error, HttpMessage, HttpRequest, HttpResponse, Responder,
 };
 
 /// URL encoded payload extractor and responder.
@@ -140,6 +144,21 @@ where
     }
 }
 
+/// Passes violations to the [`FormConfig`] error handler as [`UrlencodedError::Validate`].
+impl<T> ValidatedExtractor for Form<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    fn validation_error(err: ValidationErrors, req: &HttpRequest) -> Error {
+        let err = UrlencodedError::Validate(err);
+
+        match FormConfig::from_req(req).err_handler.as_ref() {
+            Some(err_handler) => (err_handler)(err, req),
+            None => err.into(),
+        }
+    }
+}
+
 type FormErrHandler = Option<Rc<dyn Fn(UrlencodedError, &HttpRequest) -> Error>>;
 
 pub struct FormExtractFut<T> {
@@ -392,15 +411,21 @@ where
                     }
                 }
 
+                let parse = |body: &[u8]| {
+                    serde_urlencoded::from_bytes::<T>(body).map_err(|err| {
+                        UrlencodedError::parse(err, error::urlencoded_field_path::<T>(body))
+                    })
+                };
+
                 if encoding == UTF_8 {
-                    serde_urlencoded::from_bytes::<T>(&body).map_err(UrlencodedError::Parse)
+                    parse(&body)
                 } else {
                     let body = encoding
                         .decode_without_bom_handling_and_without_replacement(&body)
                         .map(Cow::into_owned)
                         .ok_or(UrlencodedError::Encoding)?;
 
-                    serde_urlencoded::from_str::<T>(&body).map_err(UrlencodedError::Parse)
+                    parse(body.as_bytes())
                 }
             }
             .boxed_local(),
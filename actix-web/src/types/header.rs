@@ -72,6 +72,8 @@ where
     }
 }
 
+impl<T> crate::web::ValidatedExtractor for Header<T> where T: ParseHeader {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
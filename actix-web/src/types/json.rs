@@ -19,11 +19,12 @@ use serde::{de::DeserializeOwned, Serialize};
 use crate::dev::Decompress;
 use crate::{
     body::EitherBody,
-    error::{Error, JsonPayloadError},
+    error::{self, Error, JsonPayloadError, ValidationErrors},
     extract::FromRequest,
     http::header::{ContentLength, Header as _},
     request::HttpRequest,
-    web, HttpMessage, HttpResponse, Responder,
+    web::{self, ValidatedExtractor},
+    HttpMessage, HttpResponse, Responder,
 };
 
 /// JSON extractor and responder.
@@ -157,6 +158,18 @@ impl<T: DeserializeOwned> FromRequest for Json<T> {
     }
 }
 
+/// Passes violations to the [`JsonConfig`] error handler as [`JsonPayloadError::Validate`].
+impl<T: DeserializeOwned> ValidatedExtractor for Json<T> {
+    fn validation_error(err: ValidationErrors, req: &HttpRequest) -> Error {
+        let err = JsonPayloadError::Validate(err);
+
+        match JsonConfig::from_req(req).err_handler.as_ref() {
+            Some(err_handler) => (*err_handler)(err, req),
+            None => err.into(),
+        }
+    }
+}
+
 type JsonErrorHandler = Option<Arc<dyn Fn(JsonPayloadError, &HttpRequest) -> Error + Send + Sync>>;
 
 pub struct JsonExtractFut<T> {
@@ -428,8 +441,9 @@ impl<T: DeserializeOwned> Future for JsonBody<T> {
                         }
                     }
                     None => {
-                        let json = serde_json::from_slice::<T>(buf)
-                            .map_err(JsonPayloadError::Deserialize)?;
+                        let json = serde_json::from_slice::<T>(buf).map_err(|err| {
+                            JsonPayloadError::deserialize(err, error::json_field_path::<T>(buf))
+                        })?;
                         return Poll::Ready(Ok(json));
                     }
                 }
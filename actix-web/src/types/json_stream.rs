@@ -0,0 +1,773 @@
+//! For streaming JSON documentation, see [`JsonLines`] and [`JsonStream`].
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use actix_http::Payload;
+use actix_utils::future::{ready, Ready};
+use bytes::{Buf as _, BufMut as _, Bytes, BytesMut};
+use futures_core::{ready, Stream};
+use mime::Mime;
+use pin_project_lite::pin_project;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "__compress")]
+use crate::dev::Decompress;
+use crate::{
+    body::BoxBody,
+    error::{Error, JsonLinesError},
+    extract::FromRequest,
+    request::HttpRequest,
+    web, HttpMessage, HttpResponse, Responder,
+};
+
+/// Record separator which starts each record of a JSON text sequence (RFC 7464).
+const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// Streaming extractor for newline-delimited JSON payloads.
+///
+/// `JsonLines` reads [NDJSON], [JSON Lines] and [JSON text sequence][json-seq] payloads one record
+/// at a time, without buffering the whole body. It is a [`Stream`] of deserialized records, which
+/// makes it suitable for large uploads and ingestion endpoints.
+///
+/// Records are separated by newlines or, for JSON text sequences, record separator characters.
+/// Blank records are skipped. Each record may be at most [`JsonLinesConfig::limit`] bytes long.
+///
+/// Items that fail to deserialize are returned as [`JsonLinesError::Deserialize`] errors, after
+/// which the stream continues with the next record. Any other error ends the stream.
+///
+/// # Content Types
+/// The request `Content-Type` must be one of `application/x-ndjson`, `application/ndjson`,
+/// `application/jsonl`, `application/jsonlines`, `application/x-jsonlines` or
+/// `application/json-seq`, unless allowed by [`JsonLinesConfig::content_type`] or disabled by
+/// [`JsonLinesConfig::content_type_required`]. Payloads are read as JSON text sequences only if
+/// the content type is `application/json-seq` or has a `+json-seq` suffix.
+///
+/// # Examples
+/// ```
+/// use actix_web::{post, web};
+/// use futures_util::StreamExt as _;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Measurement {
+///     value: f64,
+/// }
+///
+/// #[post("/ingest")]
+/// async fn ingest(mut records: web::JsonLines<Measurement>) -> actix_web::Result<String> {
+///     let mut sum = 0.0;
+///
+///     while let Some(record) = records.next().await {
+///         sum += record?.value;
+///     }
+///
+///     Ok(format!("sum: {sum}"))
+/// }
+/// ```
+///
+/// [NDJSON]: https://github.com/ndjson/ndjson-spec
+/// [JSON Lines]: https://jsonlines.org
+/// [json-seq]: https://datatracker.ietf.org/doc/html/rfc7464
+pub struct JsonLines<T> {
+    #[cfg(feature = "__compress")]
+    payload: Decompress<Payload>,
+    #[cfg(not(feature = "__compress"))]
+    payload: Payload,
+    buf: BytesMut,
+    /// Number of bytes at the start of `buf` known not to contain a delimiter.
+    scanned: usize,
+    delimiter: u8,
+    limit: usize,
+    record: usize,
+    eof: bool,
+    done: bool,
+    _record: PhantomData<T>,
+}
+
+impl<T> Unpin for JsonLines<T> {}
+
+impl<T> JsonLines<T> {
+    /// Creates a record stream from a request and its payload, using the default configuration.
+    ///
+    /// Content type checks are skipped. The framing is chosen based on the request `Content-Type`.
+    pub fn new(req: &HttpRequest, payload: &mut Payload) -> Self {
+        let json_seq = matches!(req.mime_type(), Ok(Some(mime)) if is_json_seq(&mime));
+        Self::from_parts(req, payload, json_seq, DEFAULT_LIMIT)
+    }
+
+    /// Sets maximum accepted size of a single record. The default limit is 256kB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn from_parts(req: &HttpRequest, payload: &mut Payload, json_seq: bool, limit: usize) -> Self {
+        let payload = {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "__compress")] {
+                    Decompress::from_headers(payload.take(), req.headers())
+                } else {
+                    let _ = req;
+                    payload.take()
+                }
+            }
+        };
+
+        JsonLines {
+            payload,
+            buf: BytesMut::new(),
+            scanned: 0,
+            delimiter: if json_seq { RECORD_SEPARATOR } else { b'\n' },
+            limit,
+            record: 0,
+            eof: false,
+            done: false,
+            _record: PhantomData,
+        }
+    }
+
+    /// Splits the next complete record off the buffer, if any.
+    fn next_record(&mut self) -> Result<Option<Bytes>, JsonLinesError> {
+        let pos = self.buf[self.scanned..]
+            .iter()
+            .position(|&byte| byte == self.delimiter)
+            .map(|pos| self.scanned + pos);
+
+        match pos {
+            Some(pos) if pos <= self.limit => {
+                let record = self.buf.split_to(pos).freeze();
+                self.buf.advance(1);
+                self.scanned = 0;
+                Ok(Some(record))
+            }
+
+            Some(_) => Err(JsonLinesError::Overflow { limit: self.limit }),
+
+            // the trailing delimiter is optional for the last record
+            None if self.eof && !self.buf.is_empty() => {
+                if self.buf.len() > self.limit {
+                    return Err(JsonLinesError::Overflow { limit: self.limit });
+                }
+
+                self.scanned = 0;
+                Ok(Some(self.buf.split().freeze()))
+            }
+
+            None => {
+                if self.buf.len() > self.limit {
+                    return Err(JsonLinesError::Overflow { limit: self.limit });
+                }
+
+                self.scanned = self.buf.len();
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonLines<T> {
+    type Item = Result<T, JsonLinesError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this.next_record() {
+                Ok(Some(record)) => {
+                    let record = trim_whitespace(&record);
+
+                    // skip blank lines and the empty record before a leading record separator
+                    if record.is_empty() {
+                        continue;
+                    }
+
+                    this.record += 1;
+
+                    let res =
+                        serde_json::from_slice(record).map_err(|err| JsonLinesError::Deserialize {
+                            record: this.record,
+                            source: err,
+                        });
+
+                    return Poll::Ready(Some(res));
+                }
+
+                Ok(None) if this.eof => {
+                    this.done = true;
+                    return Poll::Ready(None);
+                }
+
+                Ok(None) => {}
+
+                Err(err) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+
+            match ready!(Pin::new(&mut this.payload).poll_next(cx)) {
+                Some(Ok(chunk)) => this.buf.extend_from_slice(&chunk),
+
+                Some(Err(err)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+
+                None => this.eof = true,
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for JsonLines<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLines")
+            .field("limit", &self.limit)
+            .field("record", &self.record)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// See [here](#examples) for example of usage as an extractor.
+impl<T: DeserializeOwned> FromRequest for JsonLines<T> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = JsonLinesConfig::from_req(req);
+        let mime = req.mime_type().ok().flatten();
+
+        let can_parse = match (config.content_type_required, &mime) {
+            (true, Some(mime)) => {
+                is_json_lines(mime)
+                    || config
+                        .content_type
+                        .as_deref()
+                        .is_some_and(|predicate| predicate(mime.clone()))
+            }
+
+            // if content-type is expected but not parsable as mime type, bail
+            (true, None) => false,
+
+            // if content-type validation is disabled, assume payload is newline-delimited
+            (false, _) => true,
+        };
+
+        if !can_parse {
+            log::debug!(
+                "Failed to extract JSON lines from payload. Request path: {}",
+                req.path()
+            );
+
+            let err = JsonLinesError::ContentType;
+
+            return ready(Err(match config.err_handler.as_ref() {
+                Some(err_handler) => (*err_handler)(err, req),
+                None => err.into(),
+            }));
+        }
+
+        let json_seq = mime.as_ref().is_some_and(is_json_seq);
+
+        ready(Ok(Self::from_parts(req, payload, json_seq, config.limit)))
+    }
+}
+
+type JsonLinesErrorHandler =
+    Option<Arc<dyn Fn(JsonLinesError, &HttpRequest) -> Error + Send + Sync>>;
+
+/// `JsonLines` extractor configuration.
+///
+/// The error handler is only used for errors that occur before the handler is called, which are
+/// content type errors. Errors that occur while reading records are returned by the stream.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+///
+/// let json_lines_cfg = web::JsonLinesConfig::default()
+///     // limit size of each record
+///     .limit(4096)
+///     // accept any content type
+///     .content_type_required(false)
+///     // use custom error handler
+///     .error_handler(|err, req| {
+///         error::InternalError::from_response(err, HttpResponse::Conflict().into()).into()
+///     });
+///
+/// App::new().app_data(json_lines_cfg);
+/// ```
+#[derive(Clone)]
+pub struct JsonLinesConfig {
+    limit: usize,
+    err_handler: JsonLinesErrorHandler,
+    content_type: Option<Arc<dyn Fn(Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl JsonLinesConfig {
+    /// Set maximum accepted size of a single record. By default this limit is 256kB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set custom error handler.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(JsonLinesError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Set predicate for allowed content types, in addition to the newline-delimited JSON types.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Sets whether or not the request must have a `Content-Type` header to be parsed.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    /// Extract payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
+    /// back to the default payload config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+const DEFAULT_LIMIT: usize = 262_144; // 256 kb
+
+/// Allow shared refs used as default.
+const DEFAULT_CONFIG: JsonLinesConfig = JsonLinesConfig {
+    limit: DEFAULT_LIMIT,
+    err_handler: None,
+    content_type: None,
+    content_type_required: true,
+};
+
+impl Default for JsonLinesConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG
+    }
+}
+
+fn trim_whitespace(mut bytes: &[u8]) -> &[u8] {
+    while let [first, rest @ ..] = bytes {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+
+    while let [rest @ .., last] = bytes {
+        if !last.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+
+    bytes
+}
+
+fn is_json_lines(mime: &Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && matches!(
+            mime.subtype().as_str(),
+            "x-ndjson" | "ndjson" | "jsonl" | "jsonlines" | "x-jsonlines"
+        )
+        || is_json_seq(mime)
+}
+
+fn is_json_seq(mime: &Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && (mime.subtype() == "json-seq"
+            || mime.suffix().is_some_and(|suffix| suffix == "json-seq"))
+}
+
+/// Streaming JSON responder.
+///
+/// Serializes each item of a [`Stream`] as it is produced, either as newline-delimited JSON, as a
+/// JSON text sequence or as the elements of a single JSON array. Items are only pulled from the
+/// stream when the connection is ready to send more data, so slow clients apply backpressure to
+/// the stream.
+///
+/// If an item fails to serialize, the response body ends with an error and the connection is
+/// closed, since the status code has already been sent.
+///
+/// # Examples
+/// ```
+/// use actix_web::{get, web};
+/// use futures_util::stream;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Row {
+///     id: u64,
+/// }
+///
+/// #[get("/export.ndjson")]
+/// async fn export() -> web::JsonStream<impl futures_core::Stream<Item = Row>> {
+///     web::JsonStream::ndjson(stream::iter((0..1000).map(|id| Row { id })))
+/// }
+///
+/// #[get("/export.json")]
+/// async fn export_array() -> web::JsonStream<impl futures_core::Stream<Item = Row>> {
+///     web::JsonStream::array(stream::iter((0..1000).map(|id| Row { id })))
+/// }
+/// ```
+pub struct JsonStream<S> {
+    stream: S,
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Lines,
+    Seq,
+    Array,
+}
+
+impl<S> JsonStream<S> {
+    /// Creates a newline-delimited JSON response, with the `application/x-ndjson` content type.
+    ///
+    /// This is also a valid JSON Lines response.
+    pub fn ndjson(stream: S) -> Self {
+        Self {
+            stream,
+            format: Format::Lines,
+        }
+    }
+
+    /// Creates a JSON text sequence response, with the `application/json-seq` content type.
+    pub fn json_seq(stream: S) -> Self {
+        Self {
+            stream,
+            format: Format::Seq,
+        }
+    }
+
+    /// Creates a response containing a single JSON array, with the `application/json` content
+    /// type.
+    pub fn array(stream: S) -> Self {
+        Self {
+            stream,
+            format: Format::Array,
+        }
+    }
+}
+
+impl<S> fmt::Debug for JsonStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonStream")
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, T> Responder for JsonStream<S>
+where
+    S: Stream<Item = T> + 'static,
+    T: Serialize,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        let content_type = match self.format {
+            Format::Lines => "application/x-ndjson",
+            Format::Seq => "application/json-seq",
+            Format::Array => "application/json",
+        };
+
+        HttpResponse::Ok()
+            .content_type(content_type)
+            .streaming(JsonStreamBody {
+                stream: self.stream,
+                format: self.format,
+                started: false,
+                done: false,
+            })
+    }
+}
+
+pin_project! {
+    struct JsonStreamBody<S> {
+        #[pin]
+        stream: S,
+        format: Format,
+        started: bool,
+        done: bool,
+    }
+}
+
+impl<S, T> Stream for JsonStreamBody<S>
+where
+    S: Stream<Item = T>,
+    T: Serialize,
+{
+    type Item = Result<Bytes, serde_json::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut buf = BytesMut::new();
+
+        match ready!(this.stream.poll_next(cx)) {
+            Some(item) => {
+                match this.format {
+                    Format::Seq => buf.put_u8(RECORD_SEPARATOR),
+                    Format::Array if *this.started => buf.put_u8(b','),
+                    Format::Array => buf.put_u8(b'['),
+                    Format::Lines => {}
+                }
+
+                if let Err(err) = serde_json::to_writer((&mut buf).writer(), &item) {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+
+                if *this.format != Format::Array {
+                    buf.put_u8(b'\n');
+                }
+
+                *this.started = true;
+            }
+
+            None => {
+                *this.done = true;
+
+                if *this.format != Format::Array {
+                    return Poll::Ready(None);
+                }
+
+                buf.extend_from_slice(if *this.started { b"]" } else { b"[]" });
+            }
+        }
+
+        Poll::Ready(Some(Ok(buf.freeze())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt as _};
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        body,
+        http::{
+            header::{CONTENT_ENCODING, CONTENT_TYPE},
+            StatusCode,
+        },
+        test::TestRequest,
+    };
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Item {
+        id: u32,
+    }
+
+    async fn extract(
+        content_type: &str,
+        chunks: &'static [&'static [u8]],
+        config: JsonLinesConfig,
+    ) -> Result<JsonLines<Item>, Error> {
+        let (req, _) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, content_type))
+            .app_data(config)
+            .to_http_parts();
+
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::from_static(chunk)))
+            .collect::<Vec<_>>();
+        let stream: actix_http::BoxedPayloadStream = Box::pin(stream::iter(chunks));
+        let mut payload = Payload::from(stream);
+        JsonLines::<Item>::from_request(&req, &mut payload).await
+    }
+
+    #[actix_rt::test]
+    async fn ndjson_records() {
+        let chunks: &[&[u8]] = &[b"{\"id\":1}\n{\"id\":", b"2}\r\n\n{\"id\"", b":3}"];
+        let lines = extract("application/x-ndjson", chunks, JsonLinesConfig::default())
+            .await
+            .unwrap();
+
+        let items = lines.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(items, [Item { id: 1 }, Item { id: 2 }, Item { id: 3 }]);
+    }
+
+    #[actix_rt::test]
+    async fn json_seq_records() {
+        let chunks: &[&[u8]] = &[b"\x1e{\"id\":1}\n\x1e{\"id\":2}\n"];
+        let lines = extract("application/json-seq", chunks, JsonLinesConfig::default())
+            .await
+            .unwrap();
+
+        let items = lines.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(items, [Item { id: 1 }, Item { id: 2 }]);
+    }
+
+    #[actix_rt::test]
+    async fn record_errors() {
+        let chunks: &[&[u8]] = &[b"{\"id\":1}\n{\"id\":\"two\"}\n{\"id\":3}\n"];
+        let lines = extract("application/jsonl", chunks, JsonLinesConfig::default())
+            .await
+            .unwrap();
+
+        let items = lines.collect::<Vec<_>>().await;
+        assert_eq!(items.len(), 3);
+        assert!(matches!(
+            items[1],
+            Err(JsonLinesError::Deserialize { record: 2, .. })
+        ));
+        assert_eq!(items[2].as_ref().unwrap(), &Item { id: 3 });
+
+        // oversized records end the stream
+        let chunks: &[&[u8]] = &[b"{\"id\":1}\n{\"id\":100000}\n{\"id\":3}\n"];
+        let lines = extract(
+            "application/x-ndjson",
+            chunks,
+            JsonLinesConfig::default().limit(10),
+        )
+        .await
+        .unwrap();
+
+        let items = lines.collect::<Vec<_>>().await;
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            items[1],
+            Err(JsonLinesError::Overflow { limit: 10 })
+        ));
+
+        // limit also applies without a delimiter
+        let chunks: &[&[u8]] = &[b"{\"id\":100000}"];
+        let lines = extract(
+            "application/x-ndjson",
+            chunks,
+            JsonLinesConfig::default().limit(10),
+        )
+        .await
+        .unwrap();
+
+        let items = lines.collect::<Vec<_>>().await;
+        assert!(matches!(
+            items[..],
+            [Err(JsonLinesError::Overflow { limit: 10 })]
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn content_type() {
+        let err = extract("application/json", &[], JsonLinesConfig::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let lines = extract(
+            "text/plain",
+            &[b"{\"id\":1}"],
+            JsonLinesConfig::default().content_type(|mime| mime == mime::TEXT_PLAIN),
+        )
+        .await
+        .unwrap();
+        let items = lines.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(items, [Item { id: 1 }]);
+
+        let err = extract(
+            "text/plain",
+            &[],
+            JsonLinesConfig::default().error_handler(|err, _| {
+                crate::error::InternalError::from_response(err, HttpResponse::Conflict().finish())
+                    .into()
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.as_response_error().error_response().status(),
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[cfg(feature = "compress-gzip")]
+    #[actix_rt::test]
+    async fn compressed() {
+        use std::io::Write as _;
+
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"{\"id\":1}\n{\"id\":2}\n").unwrap();
+
+        let (req, mut payload) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-ndjson"))
+            .insert_header((CONTENT_ENCODING, "gzip"))
+            .set_payload(enc.finish().unwrap())
+            .to_http_parts();
+
+        let lines = JsonLines::<Item>::from_request(&req, &mut payload)
+            .await
+            .unwrap();
+        let items = lines.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(items, [Item { id: 1 }, Item { id: 2 }]);
+    }
+
+    #[actix_rt::test]
+    async fn responder() {
+        let req = TestRequest::default().to_http_request();
+        let items = || stream::iter([Item { id: 1 }, Item { id: 2 }]);
+
+        let res = JsonStream::ndjson(items()).respond_to(&req);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "{\"id\":1}\n{\"id\":2}\n");
+
+        let res = JsonStream::json_seq(items()).respond_to(&req);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json-seq"
+        );
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "\x1e{\"id\":1}\n\x1e{\"id\":2}\n");
+
+        let res = JsonStream::array(items()).respond_to(&req);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "[{\"id\":1},{\"id\":2}]");
+
+        let res = JsonStream::array(stream::empty::<Item>()).respond_to(&req);
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "[]");
+    }
+}
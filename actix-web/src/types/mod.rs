@@ -1,23 +1,43 @@
 //! Common extractors and responders.
 
+mod auth;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "digest-auth")]
+mod digest_auth;
 mod either;
 mod form;
 mod header;
 mod html;
 mod json;
+mod json_stream;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod negotiated;
 mod path;
 mod payload;
 mod query;
 mod readlines;
+mod valid;
 
+#[cfg(feature = "cbor")]
+pub use self::cbor::{Cbor, CborBody, CborCodec, CborConfig};
+#[cfg(feature = "digest-auth")]
+pub use self::digest_auth::{DigestAuth, DigestAuthConfig};
+#[cfg(feature = "msgpack")]
+pub use self::msgpack::{MsgPack, MsgPackBody, MsgPackCodec, MsgPackConfig};
 pub use self::{
+    auth::{BasicAuth, BasicAuthConfig, BearerAuth, BearerAuthConfig},
     either::Either,
     form::{Form, FormConfig, UrlEncoded},
     header::Header,
     html::Html,
     json::{Json, JsonBody, JsonConfig},
+    json_stream::{JsonLines, JsonLinesConfig, JsonStream},
+    negotiated::{Codec, CodecError, DecodeFn, JsonCodec, Negotiated, NegotiatedConfig},
     path::{Path, PathConfig},
     payload::{Payload, PayloadConfig},
     query::{Query, QueryConfig},
     readlines::Readlines,
+    valid::{Valid, ValidConfig, Validate, ValidatedExtractor},
 };
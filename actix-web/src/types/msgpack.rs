@@ -0,0 +1,657 @@
+//! For MessagePack helper documentation, see [`MsgPack`].
+
+use std::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use actix_http::Payload;
+use bytes::BytesMut;
+use futures_core::{ready, Stream as _};
+use mime::Mime;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "__compress")]
+use crate::dev::Decompress;
+use crate::{
+    body::EitherBody,
+    error::{Error, MsgPackPayloadError},
+    extract::FromRequest,
+    http::header::{ContentLength, Header as _},
+    request::HttpRequest,
+    types::{Codec, CodecError, DecodeFn},
+    web, HttpMessage, HttpResponse, Responder,
+};
+
+/// MessagePack extractor and responder.
+///
+/// `MsgPack` has two uses: [MessagePack](https://msgpack.org) responses, and extracting typed data
+/// from MessagePack request payloads. It works like [`Json`](web::Json), with the same options
+/// available through [`MsgPackConfig`].
+///
+/// Encoding and decoding is provided by [`rmp_serde`]. Structs are encoded as maps with their field
+/// names as keys.
+///
+/// # Extractor
+/// ```
+/// use actix_web::{post, web};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// #[post("/")]
+/// async fn index(info: web::MsgPack<Info>) -> String {
+///     format!("Welcome {}!", info.username)
+/// }
+/// ```
+///
+/// # Responder
+/// ```
+/// use actix_web::{get, web};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Info {
+///     name: String,
+/// }
+///
+/// #[get("/{name}")]
+/// async fn index(name: web::Path<String>) -> web::MsgPack<Info> {
+///     web::MsgPack(Info {
+///         name: name.into_inner(),
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MsgPack<T>(pub T);
+
+impl<T> MsgPack<T> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for MsgPack<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for MsgPack<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MsgPack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: Serialize> Serialize for MsgPack<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Creates response with OK status code, correct content type header, and serialized MessagePack
+/// payload.
+impl<T: Serialize> Responder for MsgPack<T> {
+    type Body = EitherBody<Vec<u8>>;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        match to_vec(&self.0) {
+            Ok(body) => match HttpResponse::Ok()
+                .content_type(APPLICATION_MSGPACK)
+                .message_body(body)
+            {
+                Ok(res) => res.map_into_left_body(),
+                Err(err) => HttpResponse::from_error(err).map_into_right_body(),
+            },
+
+            Err(err) => {
+                HttpResponse::from_error(MsgPackPayloadError::Serialize(err)).map_into_right_body()
+            }
+        }
+    }
+}
+
+/// See [here](#extractor) for example of usage as an extractor.
+impl<T: DeserializeOwned> FromRequest for MsgPack<T> {
+    type Error = Error;
+    type Future = MsgPackExtractFut<T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = MsgPackConfig::from_req(req);
+
+        let limit = config.limit;
+        let ctype_required = config.content_type_required;
+        let ctype_fn = config.content_type.as_deref();
+        let err_handler = config.err_handler.clone();
+
+        MsgPackExtractFut {
+            req: Some(req.clone()),
+            fut: MsgPackBody::new(req, payload, ctype_fn, ctype_required).limit(limit),
+            err_handler,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> web::ValidatedExtractor for MsgPack<T> {}
+
+type MsgPackErrorHandler =
+    Option<Arc<dyn Fn(MsgPackPayloadError, &HttpRequest) -> Error + Send + Sync>>;
+
+pub struct MsgPackExtractFut<T> {
+    req: Option<HttpRequest>,
+    fut: MsgPackBody<T>,
+    err_handler: MsgPackErrorHandler,
+}
+
+impl<T: DeserializeOwned> Future for MsgPackExtractFut<T> {
+    type Output = Result<MsgPack<T>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let res = ready!(Pin::new(&mut this.fut).poll(cx));
+
+        let res = match res {
+            Err(err) => {
+                let req = this.req.take().unwrap();
+                log::debug!(
+                    "Failed to deserialize MessagePack from payload. Request path: {}",
+                    req.path()
+                );
+
+                if let Some(err_handler) = this.err_handler.as_ref() {
+                    Err((*err_handler)(err, &req))
+                } else {
+                    Err(err.into())
+                }
+            }
+            Ok(data) => Ok(MsgPack(data)),
+        };
+
+        Poll::Ready(res)
+    }
+}
+
+/// `MsgPack` extractor configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+///
+/// let msgpack_cfg = web::MsgPackConfig::default()
+///     // limit request payload size
+///     .limit(4096)
+///     // also accept a vendor-specific content type
+///     .content_type(|mime| mime.essence_str() == "application/vnd.example.msgpack")
+///     // use custom error handler
+///     .error_handler(|err, req| {
+///         error::InternalError::from_response(err, HttpResponse::Conflict().into()).into()
+///     });
+///
+/// App::new().app_data(msgpack_cfg);
+/// ```
+#[derive(Clone)]
+pub struct MsgPackConfig {
+    limit: usize,
+    err_handler: MsgPackErrorHandler,
+    content_type: Option<Arc<dyn Fn(Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+impl MsgPackConfig {
+    /// Set maximum accepted payload size. By default this limit is 2MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set custom error handler.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(MsgPackPayloadError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Set predicate for allowed content types, in addition to the MessagePack media types.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Sets whether or not the request must have a `Content-Type` header to be parsed.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    /// Extract payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
+    /// back to the default payload config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+
+/// Allow shared refs used as default.
+const DEFAULT_CONFIG: MsgPackConfig = MsgPackConfig {
+    limit: DEFAULT_LIMIT,
+    err_handler: None,
+    content_type: None,
+    content_type_required: true,
+};
+
+impl Default for MsgPackConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG
+    }
+}
+
+/// Future that resolves to some `T` when parsed from a MessagePack payload.
+///
+/// Returns error if:
+/// - `Content-Type` is not a MessagePack media type when `ctype_required` (passed to
+///   [`new`][Self::new]) is `true`.
+/// - `Content-Length` is greater than [limit](MsgPackBody::limit()).
+/// - The payload, when consumed, is not valid MessagePack.
+pub enum MsgPackBody<T> {
+    Error(Option<MsgPackPayloadError>),
+    Body {
+        limit: usize,
+        /// Length as reported by `Content-Length` header, if present.
+        length: Option<usize>,
+        #[cfg(feature = "__compress")]
+        payload: Decompress<Payload>,
+        #[cfg(not(feature = "__compress"))]
+        payload: Payload,
+        buf: BytesMut,
+        _res: PhantomData<T>,
+    },
+}
+
+impl<T> Unpin for MsgPackBody<T> {}
+
+impl<T: DeserializeOwned> MsgPackBody<T> {
+    /// Create a new future to decode a MessagePack request payload.
+    pub fn new(
+        req: &HttpRequest,
+        payload: &mut Payload,
+        ctype_fn: Option<&(dyn Fn(Mime) -> bool + Send + Sync)>,
+        ctype_required: bool,
+    ) -> Self {
+        let can_parse = match (ctype_required, req.mime_type()) {
+            (true, Ok(Some(mime))) => {
+                is_msgpack(&mime) || ctype_fn.is_some_and(|predicate| predicate(mime))
+            }
+
+            // if content-type is expected but not parsable as mime type, bail
+            (true, _) => false,
+
+            // if content-type validation is disabled, assume payload is MessagePack
+            (false, _) => true,
+        };
+
+        if !can_parse {
+            return MsgPackBody::Error(Some(MsgPackPayloadError::ContentType));
+        }
+
+        let length = ContentLength::parse(req).ok().map(|x| x.0);
+
+        let payload = {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "__compress")] {
+                    Decompress::from_headers(payload.take(), req.headers())
+                } else {
+                    payload.take()
+                }
+            }
+        };
+
+        MsgPackBody::Body {
+            limit: DEFAULT_LIMIT,
+            length,
+            payload,
+            buf: BytesMut::with_capacity(8192),
+            _res: PhantomData,
+        }
+    }
+
+    /// Set maximum accepted payload size. The default limit is 2MB.
+    pub fn limit(self, limit: usize) -> Self {
+        match self {
+            MsgPackBody::Body {
+                length,
+                payload,
+                buf,
+                ..
+            } => {
+                if let Some(len) = length {
+                    if len > limit {
+                        return MsgPackBody::Error(Some(
+                            MsgPackPayloadError::OverflowKnownLength { length: len, limit },
+                        ));
+                    }
+                }
+
+                MsgPackBody::Body {
+                    limit,
+                    length,
+                    payload,
+                    buf,
+                    _res: PhantomData,
+                }
+            }
+            MsgPackBody::Error(err) => MsgPackBody::Error(err),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Future for MsgPackBody<T> {
+    type Output = Result<T, MsgPackPayloadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match this {
+            MsgPackBody::Body {
+                limit,
+                buf,
+                payload,
+                ..
+            } => loop {
+                let res = ready!(Pin::new(&mut *payload).poll_next(cx));
+                match res {
+                    Some(chunk) => {
+                        let chunk = chunk?;
+                        let buf_len = buf.len() + chunk.len();
+                        if buf_len > *limit {
+                            return Poll::Ready(Err(MsgPackPayloadError::Overflow {
+                                limit: *limit,
+                            }));
+                        } else {
+                            buf.extend_from_slice(&chunk);
+                        }
+                    }
+                    None => {
+                        let data =
+                            from_slice::<T>(buf).map_err(MsgPackPayloadError::Deserialize)?;
+                        return Poll::Ready(Ok(data));
+                    }
+                }
+            },
+            MsgPackBody::Error(err) => Poll::Ready(Err(err.take().unwrap())),
+        }
+    }
+}
+
+/// MessagePack codec for [`Negotiated`](web::Negotiated).
+///
+/// Responds with `application/msgpack` and supports the same media types as the [`MsgPack`]
+/// extractor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPackCodec;
+
+impl Codec for MsgPackCodec {
+    fn media_type(&self) -> Mime {
+        APPLICATION_MSGPACK.parse().unwrap()
+    }
+
+    fn supports(&self, mime: &Mime) -> bool {
+        is_msgpack(mime)
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError> {
+        Ok(to_vec(&value)?)
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError> {
+        let mut de = rmp_serde::Deserializer::from_read_ref(body);
+        Ok(visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(
+            &mut de,
+        ))?)
+    }
+}
+
+const APPLICATION_MSGPACK: &str = "application/msgpack";
+
+/// Returns true for `application/msgpack`, its unregistered variants and the `+msgpack` suffix.
+fn is_msgpack(mime: &Mime) -> bool {
+    mime.type_() == mime::APPLICATION
+        && (matches!(
+            mime.subtype().as_str(),
+            "msgpack" | "x-msgpack" | "vnd.msgpack"
+        ) || mime.suffix().is_some_and(|suffix| suffix == "msgpack"))
+}
+
+fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    // structs are encoded as maps, which clients in other languages expect
+    rmp_serde::to_vec_named(value)
+}
+
+fn from_slice<T: DeserializeOwned>(body: &[u8]) -> Result<T, rmp_serde::decode::Error> {
+    rmp_serde::from_slice(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bytes::Bytes;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        http::{
+            header::{self, CONTENT_LENGTH, CONTENT_TYPE},
+            StatusCode,
+        },
+        test::{assert_body_eq, TestRequest},
+    };
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct MyObject {
+        name: String,
+    }
+
+    // {"name": "test"}
+    const MY_OBJECT: &[u8] = b"\x81\xa4name\xa4test";
+
+    #[test]
+    fn formats() {
+        // fixed-width integers, float32 and integer map keys
+        let value = from_slice::<(BTreeMap<u8, i16>, f32)>(
+            b"\x92\x82\x01\xcc\x05\x02\xd1\xff\x00\xca\x3f\xc0\x00\x00",
+        )
+        .unwrap();
+        assert_eq!(value, (BTreeMap::from([(1, 5), (2, -256)]), 1.5));
+
+        // truncated payloads and invalid markers
+        assert!(from_slice::<String>(b"\xa4nam").is_err());
+        assert!(from_slice::<Vec<u8>>(b"\x92\x01").is_err());
+        assert!(from_slice::<u8>(b"\xc1").is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_responder() {
+        let req = TestRequest::default().to_http_request();
+
+        let res = MsgPack(MyObject {
+            name: "test".to_owned(),
+        })
+        .respond_to(&req);
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            header::HeaderValue::from_static("application/msgpack")
+        );
+        assert_body_eq!(res, MY_OBJECT);
+    }
+
+    #[actix_rt::test]
+    async fn test_extract() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-msgpack"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let s = MsgPack::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(s.name, "test");
+        assert_eq!(
+            s.into_inner(),
+            MyObject {
+                name: "test".to_owned()
+            }
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/vnd.example+msgpack"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let s = MsgPack::<MyObject>::from_request(&req, &mut pl).await;
+        assert!(s.is_ok());
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .to_http_parts();
+
+        let err = MsgPack::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/msgpack"))
+            .set_payload(Bytes::from_static(b"\xa4test"))
+            .to_http_parts();
+
+        let err = MsgPack::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("MessagePack deserialize error"));
+    }
+
+    #[actix_rt::test]
+    async fn test_config() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "text/plain"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .app_data(MsgPackConfig::default().content_type(|mime| mime == mime::TEXT_PLAIN))
+            .to_http_parts();
+
+        let s = MsgPack::<MyObject>::from_request(&req, &mut pl).await;
+        assert!(s.is_ok());
+
+        let (req, mut pl) = TestRequest::default()
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .app_data(MsgPackConfig::default().content_type_required(false))
+            .to_http_parts();
+
+        let s = MsgPack::<MyObject>::from_request(&req, &mut pl).await;
+        assert!(s.is_ok());
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/msgpack"))
+            .set_payload(Bytes::from_static(MY_OBJECT))
+            .app_data(MsgPackConfig::default().limit(4).error_handler(|err, _| {
+                crate::error::InternalError::from_response(err, HttpResponse::Conflict().finish())
+                    .into()
+            }))
+            .to_http_parts();
+
+        let err = MsgPack::<MyObject>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().error_response().status(),
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_body_limit() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/msgpack"))
+            .insert_header((CONTENT_LENGTH, "10000"))
+            .to_http_parts();
+
+        let res = MsgPackBody::<MyObject>::new(&req, &mut pl, None, true)
+            .limit(100)
+            .await;
+        assert!(matches!(
+            res.unwrap_err(),
+            MsgPackPayloadError::OverflowKnownLength {
+                length: 10000,
+                limit: 100
+            }
+        ));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/msgpack"))
+            .set_payload(Bytes::from_static(&[0xc0; 20]))
+            .to_http_parts();
+
+        let res = MsgPackBody::<MyObject>::new(&req, &mut pl, None, true)
+            .limit(10)
+            .await;
+        assert!(matches!(
+            res.unwrap_err(),
+            MsgPackPayloadError::Overflow { limit: 10 }
+        ));
+    }
+
+    #[test]
+    fn codec() {
+        let codec = MsgPackCodec;
+        assert!(codec.supports(&"application/msgpack".parse().unwrap()));
+        assert!(!codec.supports(&mime::APPLICATION_JSON));
+
+        let value = json!({ "name": "test" });
+        let body = codec.encode(&value).unwrap();
+        assert_eq!(body, MY_OBJECT);
+
+        let mut data = None;
+        codec
+            .decode(&body, &mut |de| {
+                data = Some(erased_serde::deserialize::<serde_json::Value>(de)?);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(data.unwrap(), value);
+    }
+}
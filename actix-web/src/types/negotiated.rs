@@ -0,0 +1,596 @@
+//! For content negotiation helper documentation, see [`Negotiated`].
+
+use std::{error::Error as StdError, fmt, ops, sync::Arc};
+
+use actix_http::Payload;
+use futures_util::future::LocalBoxFuture;
+use mime::Mime;
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    body::EitherBody,
+    error::{Error, NegotiationError},
+    extract::FromRequest,
+    http::header::{self, Accept},
+    request::HttpRequest,
+    types::payload::HttpMessageBody,
+    web, HttpMessage, HttpResponse, Responder,
+};
+
+/// Extractor and responder for data in any of several serialization formats, chosen by content
+/// negotiation.
+///
+/// The formats are provided by the [codecs](Codec) registered in [`NegotiatedConfig`]. By default,
+/// only JSON is supported.
+///
+/// # Extractor
+/// To extract typed data from a request body, the inner type `T` must implement the
+/// [`serde::Deserialize`] trait. The codec is chosen by the request's `Content-Type` header;
+/// extraction fails with `415 Unsupported Media Type` if no registered codec supports it.
+///
+/// ```
+/// use actix_web::{post, web, App};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// #[post("/")]
+/// async fn index(info: web::Negotiated<Info>) -> String {
+///     format!("Welcome {}!", info.username)
+/// }
+/// ```
+///
+/// # Responder
+/// The codec of a response is chosen by the request's `Accept` header, in order of
+/// [preference](Accept::ranked). If the header is missing, the first registered codec is used; if
+/// no codec is acceptable, the response is `406 Not Acceptable`. Responses always include a
+/// `Vary: Accept` header. The type `T` must implement [`serde::Serialize`].
+///
+/// ```
+/// use actix_web::{get, web};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Info {
+///     name: String,
+/// }
+///
+/// #[get("/{name}")]
+/// async fn index(name: web::Path<String>) -> web::Negotiated<Info> {
+///     web::Negotiated(Info {
+///         name: name.into_inner(),
+///     })
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for Negotiated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for Negotiated<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize> Serialize for Negotiated<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Creates response with OK status code and the payload serialized by the negotiated codec.
+impl<T: Serialize> Responder for Negotiated<T> {
+    type Body = EitherBody<Vec<u8>>;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let config = NegotiatedConfig::from_req(req);
+
+        let res = config
+            .select_for_response(req)
+            .ok_or(NegotiationError::NotAcceptable)
+            .and_then(|codec| {
+                let body = codec.encode(&self.0).map_err(NegotiationError::Serialize)?;
+
+                Ok((codec.media_type(), body))
+            });
+
+        let mut res = match res {
+            Ok((media_type, body)) => match HttpResponse::Ok()
+                .content_type(media_type)
+                .message_body(body)
+            {
+                Ok(res) => res.map_into_left_body(),
+                Err(err) => HttpResponse::from_error(err).map_into_right_body(),
+            },
+
+            Err(err) => HttpResponse::from_error(err).map_into_right_body(),
+        };
+
+        res.headers_mut()
+            .append(header::VARY, header::HeaderValue::from_static("Accept"));
+
+        res
+    }
+}
+
+/// See [here](#extractor) for example of usage as an extractor.
+impl<T: DeserializeOwned + 'static> FromRequest for Negotiated<T> {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = NegotiatedConfig::from_req(req);
+        let err_handler = config.err_handler.clone();
+
+        let codec = config.select_for_request(req);
+        let body = HttpMessageBody::new(req, payload).limit(config.limit);
+        let req = req.clone();
+
+        Box::pin(async move {
+            let res = async {
+                let codec = codec.ok_or(NegotiationError::UnsupportedMediaType)?;
+                let body = body.await.map_err(NegotiationError::Payload)?;
+
+                let mut data = None;
+
+                codec
+                    .decode(&body, &mut |de| {
+                        data = Some(erased_serde::deserialize::<T>(de)?);
+                        Ok(())
+                    })
+                    .map_err(NegotiationError::Deserialize)?;
+
+                data.ok_or_else(|| {
+                    NegotiationError::Deserialize("codec did not deserialize the payload".into())
+                })
+            }
+            .await;
+
+            match res {
+                Ok(data) => Ok(Negotiated(data)),
+
+                Err(err) => {
+                    log::debug!(
+                        "Failed to deserialize negotiated payload. Request path: {}",
+                        req.path()
+                    );
+
+                    match err_handler {
+                        Some(err_handler) => Err((err_handler)(err, &req)),
+                        None => Err(err.into()),
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl<T: DeserializeOwned + 'static> web::ValidatedExtractor for Negotiated<T> {}
+
+/// Error type returned by [`Codec`] methods.
+pub type CodecError = Box<dyn StdError + Send + Sync>;
+
+/// Callback passed to [`Codec::decode()`], which deserializes the extracted type.
+pub type DecodeFn<'a> = dyn for<'de> FnMut(&mut dyn erased_serde::Deserializer<'de>) -> Result<(), erased_serde::Error>
+    + 'a;
+
+/// A serialization format used by [`Negotiated`].
+///
+/// Codecs use the [`erased_serde`] versions of the serde traits, so values are serialized and
+/// deserialized directly, without an intermediate data model. Register codecs using
+/// [`NegotiatedConfig::codec()`].
+///
+/// # Examples
+/// ```
+/// use actix_web::web::{Codec, CodecError, DecodeFn};
+///
+/// /// Serializes payloads as JSON with a vendor-specific media type.
+/// struct VendorJson;
+///
+/// impl Codec for VendorJson {
+///     fn media_type(&self) -> mime::Mime {
+///         "application/vnd.example+json".parse().unwrap()
+///     }
+///
+///     fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError> {
+///         Ok(serde_json::to_vec(value)?)
+///     }
+///
+///     fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError> {
+///         let mut de = serde_json::Deserializer::from_slice(body);
+///         visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(&mut de))?;
+///         Ok(de.end()?)
+///     }
+/// }
+/// ```
+pub trait Codec: Send + Sync {
+    /// Returns the media type of the format, used as the `Content-Type` of responses.
+    fn media_type(&self) -> Mime;
+
+    /// Returns true if the codec can read and write payloads of the given media type.
+    ///
+    /// The default implementation compares the type and subtype with [`media_type`], ignoring
+    /// parameters.
+    ///
+    /// [`media_type`]: Self::media_type
+    fn supports(&self, mime: &Mime) -> bool {
+        mime.essence_str()
+            .eq_ignore_ascii_case(self.media_type().essence_str())
+    }
+
+    /// Serializes a value into a payload.
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError>;
+
+    /// Deserializes a payload by passing a deserializer for it to `visit`.
+    ///
+    /// Errors returned by `visit` should be returned as is.
+    fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError>;
+}
+
+/// JSON codec for [`Negotiated`].
+///
+/// Supports `application/json` and media types with a `+json` suffix.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn media_type(&self) -> Mime {
+        mime::APPLICATION_JSON
+    }
+
+    fn supports(&self, mime: &Mime) -> bool {
+        mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON)
+    }
+
+    fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError> {
+        let mut de = serde_json::Deserializer::from_slice(body);
+        visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(&mut de))?;
+        Ok(de.end()?)
+    }
+}
+
+type NegotiationErrorHandler =
+    Option<Arc<dyn Fn(NegotiationError, &HttpRequest) -> Error + Send + Sync>>;
+
+/// [`Negotiated`] extractor and responder configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+///
+/// # struct VendorJson;
+/// # impl web::Codec for VendorJson {
+/// #     fn media_type(&self) -> mime::Mime { "application/vnd.example+json".parse().unwrap() }
+/// #     fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, web::CodecError> { Ok(serde_json::to_vec(value)?) }
+/// #     fn decode(&self, body: &[u8], visit: &mut web::DecodeFn<'_>) -> Result<(), web::CodecError> { Ok(visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(&mut serde_json::Deserializer::from_slice(body)))?) }
+/// # }
+/// let config = web::NegotiatedConfig::default()
+///     // register an additional codec
+///     .codec(VendorJson)
+///     // limit request payload size
+///     .limit(4096)
+///     // use custom error handler
+///     .error_handler(|err, _req| {
+///         error::InternalError::from_response(err, HttpResponse::Conflict().into()).into()
+///     });
+///
+/// App::new().app_data(config);
+/// ```
+#[derive(Clone)]
+pub struct NegotiatedConfig {
+    codecs: Vec<Arc<dyn Codec>>,
+    limit: usize,
+    err_handler: NegotiationErrorHandler,
+}
+
+impl NegotiatedConfig {
+    /// Constructs a configuration without any codecs.
+    pub fn empty() -> Self {
+        NegotiatedConfig {
+            codecs: Vec::new(),
+            limit: DEFAULT_LIMIT,
+            err_handler: None,
+        }
+    }
+
+    /// Registers a codec.
+    ///
+    /// Codecs registered earlier are preferred when the client accepts several formats equally,
+    /// such as with `Accept: */*`.
+    pub fn codec(mut self, codec: impl Codec + 'static) -> Self {
+        self.codecs.push(Arc::new(codec));
+        self
+    }
+
+    /// Set maximum accepted payload size. By default this limit is 2MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set custom error handler for extraction errors.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(NegotiationError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Returns the codec for the request's `Content-Type`.
+    fn select_for_request(&self, req: &HttpRequest) -> Option<Arc<dyn Codec>> {
+        let mime = req.mime_type().ok()??;
+
+        self.codecs
+            .iter()
+            .find(|codec| codec.supports(&mime))
+            .cloned()
+    }
+
+    /// Returns the most preferred codec acceptable to the client.
+    fn select_for_response(&self, req: &HttpRequest) -> Option<Arc<dyn Codec>> {
+        let accept = match req.get_header::<Accept>() {
+            Some(accept) if !accept.is_empty() => accept,
+            _ => return self.codecs.first().cloned(),
+        };
+
+        // codecs explicitly excluded using a zero q-factor
+        let refused = |codec: &Arc<dyn Codec>| {
+            accept.iter().any(|item| {
+                item.quality == header::Quality::ZERO
+                    && item.item.type_() != mime::STAR
+                    && item.item.subtype() != mime::STAR
+                    && codec.supports(&item.item)
+            })
+        };
+
+        accept
+            .ranked()
+            .into_iter()
+            .filter(|range| {
+                accept
+                    .iter()
+                    .any(|item| &item.item == range && item.quality > header::Quality::ZERO)
+            })
+            .find_map(|range| {
+                self.codecs
+                    .iter()
+                    .filter(|codec| !refused(codec))
+                    .find(|codec| match (range.type_(), range.subtype()) {
+                        (mime::STAR, _) => true,
+                        (ty, mime::STAR) => codec.media_type().type_() == ty,
+                        _ => codec.supports(&range),
+                    })
+                    .cloned()
+            })
+    }
+
+    /// Extract config from app data. Check both `T` and `Data<T>`, in that order, and fall back to
+    /// the default config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+const DEFAULT_LIMIT: usize = 2_097_152; // 2 mb
+
+static DEFAULT_CONFIG: Lazy<NegotiatedConfig> = Lazy::new(NegotiatedConfig::default);
+
+/// Constructs a configuration with the [`JsonCodec`].
+impl Default for NegotiatedConfig {
+    fn default() -> Self {
+        NegotiatedConfig::empty().codec(JsonCodec)
+    }
+}
+
+impl fmt::Debug for NegotiatedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NegotiatedConfig")
+            .field(
+                "codecs",
+                &self
+                    .codecs
+                    .iter()
+                    .map(|codec| codec.media_type())
+                    .collect::<Vec<_>>(),
+            )
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use serde::{de::value::StrDeserializer, Deserialize};
+
+    use super::*;
+    use crate::{
+        http::{
+            header::{CONTENT_LENGTH, CONTENT_TYPE},
+            StatusCode,
+        },
+        test::{assert_body_eq, TestRequest},
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Info {
+        name: String,
+    }
+
+    /// Codec for plain text payloads, which are represented as strings.
+    struct TextCodec;
+
+    impl Codec for TextCodec {
+        fn media_type(&self) -> Mime {
+            mime::TEXT_PLAIN
+        }
+
+        fn encode(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, CodecError> {
+            match serde_json::to_value(value)? {
+                serde_json::Value::String(text) => Ok(text.into_bytes()),
+                _ => Err("not a string".into()),
+            }
+        }
+
+        fn decode(&self, body: &[u8], visit: &mut DecodeFn<'_>) -> Result<(), CodecError> {
+            let text = std::str::from_utf8(body)?;
+            let de = StrDeserializer::<serde::de::value::Error>::new(text);
+            Ok(visit(&mut <dyn erased_serde::Deserializer<'_>>::erase(de))?)
+        }
+    }
+
+    fn config() -> NegotiatedConfig {
+        NegotiatedConfig::default().codec(TextCodec)
+    }
+
+    #[actix_rt::test]
+    async fn responder() {
+        let req = TestRequest::default().to_http_request();
+        let res = Negotiated(Info {
+            name: "test".into(),
+        })
+        .respond_to(&req);
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept");
+        assert_body_eq!(res, br#"{"name":"test"}"#);
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "application/xml"))
+            .to_http_request();
+        let res = Negotiated(Info {
+            name: "test".into(),
+        })
+        .respond_to(&req);
+        assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept");
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "application/json;q=0.5, text/*"))
+            .app_data(config())
+            .to_http_request();
+        let res = Negotiated("hello").respond_to(&req);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/plain");
+        assert_body_eq!(res, b"hello");
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "*/*"))
+            .app_data(config())
+            .to_http_request();
+        let res = Negotiated("hello").respond_to(&req);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/json");
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "application/json;q=0, */*;q=0.1"))
+            .app_data(config())
+            .to_http_request();
+        let res = Negotiated("hello").respond_to(&req);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/plain");
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "text/plain"))
+            .app_data(config())
+            .to_http_request();
+        let res = Negotiated(Info {
+            name: "test".into(),
+        })
+        .respond_to(&req);
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[actix_rt::test]
+    async fn extractor() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/problem+json"))
+            .set_payload(Bytes::from_static(br#"{"name":"test"}"#))
+            .to_http_parts();
+        let info = Negotiated::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(
+            info.into_inner(),
+            Info {
+                name: "test".into()
+            }
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "text/plain; charset=utf-8"))
+            .set_payload(Bytes::from_static(b"hello"))
+            .app_data(config())
+            .to_http_parts();
+        let text = Negotiated::<String>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(text.into_inner(), "hello");
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "text/plain"))
+            .set_payload(Bytes::from_static(b"hello"))
+            .to_http_parts();
+        let err = Negotiated::<String>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .set_payload(Bytes::from_static(br#"{"name":1}"#))
+            .to_http_parts();
+        let err = Negotiated::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .insert_header((CONTENT_LENGTH, 16))
+            .set_payload(Bytes::from_static(br#"{"name":"test"}"#))
+            .app_data(
+                NegotiatedConfig::default()
+                    .limit(10)
+                    .error_handler(|err, _| {
+                        crate::error::InternalError::new(err, StatusCode::CONFLICT).into()
+                    }),
+            )
+            .to_http_parts();
+        let err = Negotiated::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_response_error().status_code(), StatusCode::CONFLICT);
+    }
+}
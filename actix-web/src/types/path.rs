@@ -9,8 +9,8 @@ use serde::de;
 
 use crate::{
     dev::Payload,
-    error::{Error, ErrorNotFound, PathError},
-    web::Data,
+    error::{self, Error, ErrorNotFound, PathError, ValidationErrors},
+    web::{Data, ValidatedExtractor},
     FromRequest, HttpRequest,
 };
 
@@ -89,7 +89,9 @@ where
                     );
 
                     if let Some(error_handler) = error_handler {
-                        let err = PathError::Deserialize(err);
+                        let path =
+                            error::field_path::<_, T>(PathDeserializer::new(req.match_info()));
+                        let err = PathError::deserialize(err, path);
                         (error_handler)(err, req)
                     } else {
                         ErrorNotFound(err)
@@ -99,6 +101,25 @@ where
     }
 }
 
+/// Passes violations to the [`PathConfig`] error handler as [`PathError::Validate`].
+impl<T> ValidatedExtractor for Path<T>
+where
+    T: de::DeserializeOwned,
+{
+    fn validation_error(err: ValidationErrors, req: &HttpRequest) -> Error {
+        let err = PathError::Validate(err);
+
+        match req
+            .app_data::<PathConfig>()
+            .or_else(|| req.app_data::<Data<PathConfig>>().map(Data::get_ref))
+            .and_then(|c| c.err_handler.as_ref())
+        {
+            Some(err_handler) => (err_handler)(err, req),
+            None => err.into(),
+        }
+    }
+}
+
 /// Path extractor configuration
 ///
 /// ```
@@ -5,7 +5,12 @@ use std::{fmt, ops, sync::Arc};
 use actix_utils::future::{ok, ready, Ready};
 use serde::de::DeserializeOwned;
 
-use crate::{dev::Payload, error::QueryPayloadError, Error, FromRequest, HttpRequest};
+use crate::{
+    dev::Payload,
+    error::{self, QueryPayloadError, ValidationErrors},
+    web::ValidatedExtractor,
+    Error, FromRequest, HttpRequest,
+};
 
 /// Extract typed information from the request's query.
 ///
@@ -81,7 +86,10 @@ impl<T: DeserializeOwned> Query<T> {
     pub fn from_query(query_str: &str) -> Result<Self, QueryPayloadError> {
         serde_urlencoded::from_str::<T>(query_str)
             .map(Self)
-            .map_err(QueryPayloadError::Deserialize)
+            .map_err(|err| {
+                let path = error::urlencoded_field_path::<T>(query_str.as_bytes());
+                QueryPayloadError::deserialize(err, path)
+            })
     }
 }
 
@@ -119,7 +127,8 @@ impl<T: DeserializeOwned> FromRequest for Query<T> {
         serde_urlencoded::from_str::<T>(req.query_string())
             .map(|val| ok(Query(val)))
             .unwrap_or_else(move |err| {
-                let err = QueryPayloadError::Deserialize(err);
+                let path = error::urlencoded_field_path::<T>(req.query_string().as_bytes());
+                let err = QueryPayloadError::deserialize(err, path);
 
                 log::debug!(
                     "Failed during Query extractor deserialization. \
@@ -138,6 +147,21 @@ impl<T: DeserializeOwned> FromRequest for Query<T> {
     }
 }
 
+/// Passes violations to the [`QueryConfig`] error handler as [`QueryPayloadError::Validate`].
+impl<T: DeserializeOwned> ValidatedExtractor for Query<T> {
+    fn validation_error(err: ValidationErrors, req: &HttpRequest) -> Error {
+        let err = QueryPayloadError::Validate(err);
+
+        match req
+            .app_data::<QueryConfig>()
+            .and_then(|c| c.err_handler.as_ref())
+        {
+            Some(err_handler) => (*err_handler)(err, req),
+            None => err.into(),
+        }
+    }
+}
+
 /// Query extractor configuration.
 ///
 /// # Examples
@@ -0,0 +1,593 @@
+//! For validating extractor documentation, see [`Valid`].
+
+use std::{
+    future::Future,
+    ops,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use actix_http::Payload;
+use futures_core::ready;
+use pin_project_lite::pin_project;
+
+use crate::{
+    error::{Error, ValidationErrors},
+    extract::FromRequest,
+    request::HttpRequest,
+    web,
+};
+
+/// Types that can check their values after extraction.
+///
+/// Implementations should report every violated rule, rather than stopping at the first one, so
+/// that clients can fix all problems at once. Use [`ValidationErrors::nest`] to report violations
+/// of nested values under the path of the field that holds them.
+///
+/// Rules can be written by hand or delegated to a validation library by converting its errors to
+/// [`ValidationErrors`]. With the `validator` feature enabled, this trait is implemented for all
+/// types that implement the [`validator`](https://docs.rs/validator) crate's `Validate` trait.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error::ValidationErrors, web::Validate};
+///
+/// struct Item {
+///     price: i64,
+/// }
+///
+/// struct Order {
+///     email: String,
+///     items: Vec<Item>,
+/// }
+///
+/// impl Validate for Item {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///
+///         if self.price <= 0 {
+///             errors.add("price", "must be positive");
+///         }
+///
+///         errors.into_result()
+///     }
+/// }
+///
+/// impl Validate for Order {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///
+///         if !self.email.contains('@') {
+///             errors.add("email", "must be an email address");
+///         }
+///
+///         errors.nest_each("items", &self.items);
+///
+///         errors.into_result()
+///     }
+/// }
+///
+/// let order = Order {
+///     email: "me".to_owned(),
+///     items: vec![Item { price: 1 }, Item { price: 0 }],
+/// };
+///
+/// let err = order.validate().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Validation failed: email: must be an email address; items[1].price: must be positive",
+/// );
+/// ```
+pub trait Validate {
+    /// Checks the value, returning all violated rules.
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// Checks values with the rules of the `validator` crate.
+#[cfg(feature = "validator")]
+impl<T: validator::Validate + ?Sized> Validate for T {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validator::Validate::validate(self).map_err(Into::into)
+    }
+}
+
+/// Extractors that can be wrapped in [`Valid`].
+///
+/// Decides how violations found in the extracted value are reported when no [`ValidConfig`] error
+/// handler is set. [`Json`](web::Json), [`Query`](web::Query), [`Form`](web::Form) and
+/// [`Path`](web::Path) wrap them in the `Validate` variant of their error type and pass them to
+/// the error handler of their own config, so that one handler can shape all errors of an
+/// extractor. Other extractors report [`ValidationErrors`] unchanged by default.
+///
+/// # Examples
+/// ```
+/// use std::future::{ready, Ready};
+///
+/// use actix_web::{dev::Payload, web, Error, FromRequest, HttpRequest};
+///
+/// struct Tenant(String);
+///
+/// impl std::ops::Deref for Tenant {
+///     type Target = String;
+///
+///     fn deref(&self) -> &String {
+///         &self.0
+///     }
+/// }
+///
+/// impl FromRequest for Tenant {
+///     type Error = Error;
+///     type Future = Ready<Result<Self, Error>>;
+///
+///     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+///         ready(Ok(Tenant(req.match_info().query("tenant").to_owned())))
+///     }
+/// }
+///
+/// impl web::ValidatedExtractor for Tenant {}
+/// ```
+pub trait ValidatedExtractor: FromRequest + ops::Deref {
+    /// Converts violations found in the extracted value into an error response.
+    fn validation_error(err: ValidationErrors, _req: &HttpRequest) -> Error {
+        err.into()
+    }
+}
+
+/// Extractor wrapper that validates the extracted value.
+///
+/// Wraps extractors that dereference to their value, such as [`Json`](web::Json),
+/// [`Query`](web::Query), [`Form`](web::Form) and [`Path`](web::Path). After the inner extractor
+/// succeeds, its value is checked with [`Validate::validate`]. Violations are reported as
+/// [`ValidationErrors`], which respond with `422 Unprocessable Entity` and a JSON list of all
+/// violations by default. They are passed to the [`ValidConfig`] error handler if one is set, or
+/// else to the inner extractor's own error handler, as described by [`ValidatedExtractor`].
+///
+/// Errors of the inner extractor are returned unchanged, after being passed to the inner
+/// extractor's own error handler.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error::ValidationErrors, post, web};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Signup {
+///     username: String,
+/// }
+///
+/// impl web::Validate for Signup {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///
+///         if self.username.len() < 3 {
+///             errors.add("username", "must be at least 3 characters long");
+///         }
+///
+///         errors.into_result()
+///     }
+/// }
+///
+/// #[post("/signup")]
+/// async fn signup(form: web::Valid<web::Json<Signup>>) -> String {
+///     format!("Welcome {}!", form.username)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Valid<E>(pub E);
+
+impl<E> Valid<E> {
+    /// Unwraps into the inner extractor.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E> ops::Deref for Valid<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.0
+    }
+}
+
+impl<E> ops::DerefMut for Valid<E> {
+    fn deref_mut(&mut self) -> &mut E {
+        &mut self.0
+    }
+}
+
+/// See [here](#examples) for example of usage as an extractor.
+impl<E> FromRequest for Valid<E>
+where
+    E: ValidatedExtractor,
+    E::Target: Validate,
+{
+    type Error = Error;
+    type Future = ValidExtractFut<E>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        ValidExtractFut {
+            req: Some(req.clone()),
+            fut: E::from_request(req, payload),
+        }
+    }
+}
+
+pin_project! {
+    pub struct ValidExtractFut<E: FromRequest> {
+        req: Option<HttpRequest>,
+        #[pin]
+        fut: E::Future,
+    }
+}
+
+impl<E> Future for ValidExtractFut<E>
+where
+    E: ValidatedExtractor,
+    E::Target: Validate,
+{
+    type Output = Result<Valid<E>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let value = ready!(this.fut.poll(cx)).map_err(Into::into)?;
+
+        let res = match value.validate() {
+            Ok(()) => Ok(Valid(value)),
+
+            Err(err) => {
+                let req = this.req.take().unwrap();
+                log::debug!(
+                    "Failed to validate extracted value. Request path: {}",
+                    req.path()
+                );
+
+                match ValidConfig::from_req(&req).err_handler.as_ref() {
+                    Some(err_handler) => Err((*err_handler)(err, &req)),
+                    None => Err(E::validation_error(err, &req)),
+                }
+            }
+        };
+
+        Poll::Ready(res)
+    }
+}
+
+type ValidErrorHandler = Option<Arc<dyn Fn(ValidationErrors, &HttpRequest) -> Error + Send + Sync>>;
+
+/// `Valid` extractor configuration.
+///
+/// # Examples
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+///
+/// let valid_cfg = web::ValidConfig::default()
+///     // respond with 400 instead of 422
+///     .error_handler(|err, _req| {
+///         error::InternalError::from_response(err, HttpResponse::BadRequest().into()).into()
+///     });
+///
+/// App::new().app_data(valid_cfg);
+/// ```
+#[derive(Clone, Default)]
+pub struct ValidConfig {
+    err_handler: ValidErrorHandler,
+}
+
+impl ValidConfig {
+    /// Set custom error handler.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(ValidationErrors, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Extract validation config from app data. Check both `T` and `Data<T>`, in that order, and
+    /// fall back to the default config.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+/// Allow shared refs used as default.
+const DEFAULT_CONFIG: ValidConfig = ValidConfig { err_handler: None };
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        body,
+        error::{FieldPath, JsonPayloadError, PathError, PathSegment, Violation},
+        http::{header, StatusCode},
+        test::TestRequest,
+        HttpResponse,
+    };
+
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        price: i64,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Order {
+        name: String,
+        items: Vec<Item>,
+    }
+
+    impl Validate for Item {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            if self.price > 0 {
+                return Ok(());
+            }
+
+            Err(Violation::new("price", "must be positive")
+                .with_code("range")
+                .into())
+        }
+    }
+
+    impl Validate for Order {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+
+            if self.name.is_empty() {
+                errors.add("name", "must not be empty");
+            }
+
+            errors.nest_each("items", &self.items);
+
+            errors.into_result()
+        }
+    }
+
+    fn json_request(body: &'static str) -> (HttpRequest, Payload) {
+        TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(body)
+            .to_http_parts()
+    }
+
+    #[test]
+    fn field_paths() {
+        let path = FieldPath::from("items[3].price");
+        assert_eq!(
+            path.segments(),
+            [
+                PathSegment::Field("items".to_owned()),
+                PathSegment::Index(3),
+                PathSegment::Field("price".to_owned()),
+            ]
+        );
+        assert_eq!(
+            path,
+            FieldPath::new().field("items").index(3).field("price")
+        );
+        assert_eq!(path.to_string(), "items[3].price");
+        assert_eq!(FieldPath::from("[0][1]").to_string(), "[0][1]");
+        assert!(FieldPath::from("").is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn valid_json() {
+        let (req, mut pl) = json_request(r#"{"name": "a", "items": [{"price": 1}]}"#);
+        let order = Valid::<web::Json<Order>>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(order.name, "a");
+        assert_eq!(order.into_inner().into_inner().items.len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn invalid_json() {
+        let (req, mut pl) =
+            json_request(r#"{"name": "", "items": [{"price": 1}, {"price": 0}, {"price": -1}]}"#);
+        let err = Valid::<web::Json<Order>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+
+        let errors = match err.as_error::<JsonPayloadError>().unwrap() {
+            JsonPayloadError::Validate(errors) => errors,
+            err => panic!("unexpected error: {err:?}"),
+        };
+        let paths = errors
+            .violations()
+            .iter()
+            .map(|violation| violation.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["name", "items[1].price", "items[2].price"]);
+
+        let res = err.error_response();
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["error"], "Validation failed");
+        assert_eq!(body["violations"][0]["path"], "name");
+        assert!(body["violations"][0].get("code").is_none());
+        assert_eq!(body["violations"][1]["message"], "must be positive");
+        assert_eq!(body["violations"][1]["code"], "range");
+    }
+
+    #[actix_rt::test]
+    async fn inner_errors_and_config() {
+        // deserialization errors are reported by the inner extractor
+        let (req, mut pl) = json_request(r#"{"name": ""}"#);
+        let err = Valid::<web::Json<Order>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let (req, mut pl) = TestRequest::with_uri("/?price=0")
+            .app_data(ValidConfig::default().error_handler(|err, _| {
+                crate::error::InternalError::from_response(err, HttpResponse::Conflict().finish())
+                    .into()
+            }))
+            .to_http_parts();
+        let err = Valid::<web::Query<Item>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_response().status(), StatusCode::CONFLICT);
+    }
+
+    #[actix_rt::test]
+    async fn extractor_error_handlers() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(r#"{"price": 0}"#)
+            .app_data(web::JsonConfig::default().error_handler(|err, _| {
+                assert!(matches!(err, JsonPayloadError::Validate(_)));
+                crate::error::InternalError::from_response(err, HttpResponse::Conflict().finish())
+                    .into()
+            }))
+            .to_http_parts();
+        let err = Valid::<web::Json<Item>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_response().status(), StatusCode::CONFLICT);
+
+        let (req, mut pl) = TestRequest::default()
+            .param("price", "0")
+            .app_data(web::PathConfig::default().error_handler(|err, _| {
+                assert!(matches!(err, PathError::Validate(_)));
+                crate::error::ErrorForbidden(err)
+            }))
+            .to_http_parts();
+        let err = Valid::<web::Path<Item>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_response().status(), StatusCode::FORBIDDEN);
+
+        // the `Valid` error handler takes precedence
+        let (req, mut pl) = TestRequest::with_uri("/?price=0")
+            .app_data(
+                web::QueryConfig::default()
+                    .error_handler(|err, _| crate::error::ErrorForbidden(err)),
+            )
+            .app_data(
+                ValidConfig::default().error_handler(|err, _| crate::error::ErrorConflict(err)),
+            )
+            .to_http_parts();
+        let err = Valid::<web::Query<Item>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_response().status(), StatusCode::CONFLICT);
+
+        // without handlers, violations keep their response
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload("price=0")
+            .to_http_parts();
+        let err = Valid::<web::Form<Item>>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.error_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn validator_errors() {
+        use std::collections::{BTreeMap, HashMap};
+
+        use validator::{ValidationError, ValidationErrorsKind};
+
+        struct Signup {
+            name: String,
+            tags: Vec<Tag>,
+        }
+
+        struct Tag(String);
+
+        fn field_error(field: &'static str, err: ValidationError) -> validator::ValidationErrors {
+            validator::ValidationErrors(HashMap::from([(
+                field,
+                ValidationErrorsKind::Field(vec![err]),
+            )]))
+        }
+
+        impl validator::Validate for Tag {
+            fn validate(&self) -> Result<(), validator::ValidationErrors> {
+                if self.0.is_empty() {
+                    return Err(field_error("0", ValidationError::new("length")));
+                }
+
+                Ok(())
+            }
+        }
+
+        impl validator::Validate for Signup {
+            fn validate(&self) -> Result<(), validator::ValidationErrors> {
+                let mut errors = validator::ValidationErrors::new();
+
+                if self.name.len() < 3 {
+                    errors.add(
+                        "name",
+                        ValidationError::new("length").with_message("too short".into()),
+                    );
+                }
+
+                let tags = self
+                    .tags
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, tag)| {
+                        Some((idx, Box::new(validator::Validate::validate(tag).err()?)))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+
+                if !tags.is_empty() {
+                    errors.0.insert("tags", ValidationErrorsKind::List(tags));
+                }
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+
+        let signup = Signup {
+            name: "me".to_owned(),
+            tags: vec![Tag("a".to_owned()), Tag(String::new())],
+        };
+
+        let errors = Validate::validate(&signup).unwrap_err();
+        let violations = errors
+            .violations()
+            .iter()
+            .map(|violation| {
+                (
+                    violation.path().to_string(),
+                    violation.message(),
+                    violation.code(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            [
+                ("name".to_owned(), "too short", Some("length")),
+                ("tags[1].0".to_owned(), "length", Some("length")),
+            ]
+        );
+    }
+}
@@ -8,12 +8,21 @@
 //! - [`Query`]: URL query parameters
 //! - [`Header`]: Typed header
 //! - [`Json`]: JSON payload
+//! - [`JsonLines`]: Streamed newline-delimited JSON payload
 //! - [`Form`]: URL-encoded payload
+//! - `MsgPack`: MessagePack payload (requires the `msgpack` feature)
+//! - `Cbor`: CBOR payload (requires the `cbor` feature)
+//! - [`Negotiated`]: Payload in a format chosen by content negotiation
 //! - [`Bytes`]: Raw payload
+//! - [`Valid`]: Wrapper that checks an extracted value with [`Validate`]
 //!
 //! # Responders
 //! - [`Json`]: JSON response
+//! - [`JsonStream`]: Streamed newline-delimited JSON or JSON array response
 //! - [`Form`]: URL-encoded response
+//! - `MsgPack`: MessagePack response (requires the `msgpack` feature)
+//! - `Cbor`: CBOR response (requires the `cbor` feature)
+//! - [`Negotiated`]: Response in a format chosen by content negotiation
 //! - [`Bytes`]: Raw bytes response
 //! - [`Redirect`](Redirect::to): Convenient redirect responses
 
@@ -42,7 +51,9 @@ use crate::{
 ///
 /// By default, each segment matches the regular expression `[^{}/]+`.
 ///
-/// You can also specify a custom regex in the form `{identifier:regex}`:
+/// You can also specify a custom regex in the form `{identifier:regex}`, or the name of a
+/// [converter](crate::dev::Converter) such as `{identifier:u64}` or `{identifier:uuid}`. Paths whose
+/// segments do not conform to a converter do not match the resource.
 ///
 /// For instance, to route `GET`-requests on any route matching `/users/{userid}/{friend}` and store
 /// `userid` and `friend` in the exposed `Path` object:
@@ -135,3 +135,266 @@ async fn test_start_ssl() {
 
     srv.stop(false).await;
 }
+
+#[cfg(all(feature = "openssl", feature = "rustls-0_23"))]
+mod mtls {
+    extern crate tls_rustls as rustls;
+
+    use std::sync::Arc;
+
+    use actix_web::{
+        dev::{PeerCertificates, ServiceFactory, ServiceRequest, ServiceResponse},
+        guard, Error,
+    };
+    use openssl::{
+        pkey::PKey,
+        ssl::{SslAcceptor, SslConnector, SslMethod, SslVerifyMode},
+        x509::X509,
+    };
+    use rcgen::{BasicConstraints, CertificateParams, CertifiedKey, DnType, IsCa, KeyPair};
+    use rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer},
+        server::WebPkiClientVerifier,
+        RootCertStore, ServerConfig,
+    };
+
+    use super::*;
+
+    struct Pki {
+        ca: CertifiedKey,
+        server: CertifiedKey,
+        client: CertifiedKey,
+    }
+
+    fn pki() -> Pki {
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "test ca");
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let mut client_params = CertificateParams::new(["client.example.com".to_owned()]).unwrap();
+        client_params
+            .distinguished_name
+            .push(DnType::CommonName, "client");
+        let client_key = KeyPair::generate().unwrap();
+        let client = client_params.signed_by(&client_key, &ca, &ca_key).unwrap();
+
+        Pki {
+            ca: CertifiedKey {
+                cert: ca,
+                key_pair: ca_key,
+            },
+            server: rcgen::generate_simple_self_signed(["localhost".to_owned()]).unwrap(),
+            client: CertifiedKey {
+                cert: client,
+                key_pair: client_key,
+            },
+        }
+    }
+
+    fn app() -> App<
+        impl ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse,
+            Error = Error,
+            InitError = (),
+        >,
+    > {
+        App::new()
+            .service(
+                web::resource("/admin")
+                    .guard(guard::ClientCert().dns_name("client.example.com"))
+                    .to(|| async { "admin" }),
+            )
+            .service(
+                web::resource("/ops")
+                    .guard(guard::ClientCert().common_name("ops"))
+                    .to(|| async { "ops" }),
+            )
+            .default_service(web::to(|certs: PeerCertificates| async move {
+                format!(
+                    "{} {}",
+                    certs.subject().common_name().unwrap(),
+                    certs.issuer().common_name().unwrap(),
+                )
+            }))
+    }
+
+    fn rustls_config(pki: &Pki) -> ServerConfig {
+        let mut roots = RootCertStore::empty();
+        roots.add(pki.ca.cert.der().clone()).unwrap();
+
+        let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+            .allow_unauthenticated()
+            .build()
+            .unwrap();
+
+        let key = PrivatePkcs8KeyDer::from(pki.server.key_pair.serialize_der());
+
+        ServerConfig::builder()
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(
+                vec![CertificateDer::from(pki.server.cert.der().to_vec())],
+                PrivateKeyDer::Pkcs8(key),
+            )
+            .unwrap()
+    }
+
+    fn openssl_acceptor(pki: &Pki) -> openssl::ssl::SslAcceptorBuilder {
+        let cert = X509::from_der(pki.server.cert.der()).unwrap();
+        let key = PKey::private_key_from_der(&pki.server.key_pair.serialize_der()).unwrap();
+
+        let mut builder = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        builder.set_certificate(&cert).unwrap();
+        builder.set_private_key(&key).unwrap();
+        builder
+            .cert_store_mut()
+            .add_cert(X509::from_der(pki.ca.cert.der()).unwrap())
+            .unwrap();
+        builder.set_verify(SslVerifyMode::PEER);
+
+        builder
+    }
+
+    fn client(pki: &Pki, with_cert: bool) -> awc::Client {
+        client_with(with_cert.then_some(&pki.client))
+    }
+
+    fn client_with(client_cert: Option<&CertifiedKey>) -> awc::Client {
+        let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+        builder.set_verify(SslVerifyMode::NONE);
+
+        if let Some(client_cert) = client_cert {
+            let cert = X509::from_der(client_cert.cert.der()).unwrap();
+            let key = PKey::private_key_from_der(&client_cert.key_pair.serialize_der()).unwrap();
+            builder.set_certificate(&cert).unwrap();
+            builder.set_private_key(&key).unwrap();
+        }
+
+        awc::Client::builder()
+            .connector(awc::Connector::new().openssl(builder.build()))
+            .finish()
+    }
+
+    async fn check_peer_certificates(pki: &Pki, addr: std::net::SocketAddr) {
+        for (path, body) in [
+            ("/", "client test ca"),
+            ("/admin", "admin"),
+            ("/ops", "client test ca"),
+        ] {
+            let mut res = client(pki, true)
+                .get(format!("https://{addr}{path}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), 200);
+            assert_eq!(res.body().await.unwrap(), body);
+        }
+
+        let res = client(pki, false)
+            .get(format!("https://{addr}/admin"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 403);
+    }
+
+    #[actix_rt::test]
+    async fn rustls_peer_certificates() {
+        let pki = pki();
+        let config = rustls_config(&pki);
+
+        let addr = actix_test::unused_addr();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            actix_rt::System::new()
+                .block_on(async {
+                    let srv = HttpServer::new(app)
+                        .workers(1)
+                        .disable_signals()
+                        .bind_rustls_0_23(addr, config)
+                        .unwrap()
+                        .run();
+
+                    tx.send(srv.handle()).unwrap();
+                    srv.await
+                })
+                .unwrap()
+        });
+
+        let srv = rx.recv().unwrap();
+        check_peer_certificates(&pki, addr).await;
+        srv.stop(false).await;
+    }
+
+    fn serve_openssl(
+        builder: openssl::ssl::SslAcceptorBuilder,
+    ) -> (actix_web::dev::ServerHandle, std::net::SocketAddr) {
+        let addr = actix_test::unused_addr();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            actix_rt::System::new()
+                .block_on(async {
+                    let srv = HttpServer::new(app)
+                        .workers(1)
+                        .disable_signals()
+                        .bind_openssl(addr, builder)
+                        .unwrap()
+                        .run();
+
+                    tx.send(srv.handle()).unwrap();
+                    srv.await
+                })
+                .unwrap()
+        });
+
+        (rx.recv().unwrap(), addr)
+    }
+
+    #[actix_rt::test]
+    async fn openssl_peer_certificates() {
+        let pki = pki();
+        let (srv, addr) = serve_openssl(openssl_acceptor(&pki));
+
+        check_peer_certificates(&pki, addr).await;
+        srv.stop(false).await;
+    }
+
+    #[actix_rt::test]
+    async fn openssl_unverified_peer_certificates() {
+        let pki = pki();
+
+        // accept every client certificate, as acceptors doing their own checks might
+        let mut builder = openssl_acceptor(&pki);
+        builder.set_verify_callback(SslVerifyMode::PEER, |_, _| true);
+        let (srv, addr) = serve_openssl(builder);
+
+        check_peer_certificates(&pki, addr).await;
+
+        let mut params = CertificateParams::new(["client.example.com".to_owned()]).unwrap();
+        params.distinguished_name.push(DnType::CommonName, "ops");
+        let key_pair = KeyPair::generate().unwrap();
+        let self_signed = CertifiedKey {
+            cert: params.self_signed(&key_pair).unwrap(),
+            key_pair,
+        };
+
+        // self-signed certificates are not made available to guards and extractors
+        for path in ["/", "/admin", "/ops"] {
+            let res = client_with(Some(&self_signed))
+                .get(format!("https://{addr}{path}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), 403);
+        }
+
+        srv.stop(false).await;
+    }
+}
@@ -0,0 +1,159 @@
+use actix_web::{
+    get,
+    http::header,
+    openapi::{ApiSchema, OpenApi, Schema},
+    post, route, test,
+    web::{self, Json},
+    App, HttpRequest, HttpResponse, Responder,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct NewUser {
+    #[allow(dead_code)]
+    name: String,
+}
+
+impl ApiSchema for NewUser {
+    fn schema() -> Schema {
+        Schema::object().property("name", String::schema(), true)
+    }
+}
+
+#[derive(Deserialize)]
+struct UserPath {
+    #[allow(dead_code)]
+    id: u64,
+}
+
+impl ApiSchema for UserPath {
+    fn schema() -> Schema {
+        Schema::object().property("id", u64::schema(), true)
+    }
+}
+
+/// Not described.
+struct Session;
+
+impl actix_web::FromRequest for Session {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(_: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        std::future::ready(Ok(Session))
+    }
+}
+
+/// Fetches a user.
+///
+/// Returns 404 if the user does not exist.
+#[get("/users/{id:\\d+}")]
+async fn get_user(
+    _path: web::Path<UserPath>,
+    _session: Session,
+    _lang: Option<web::Header<header::AcceptLanguage>>,
+) -> Option<Json<String>> {
+    None
+}
+
+#[post("/users")]
+async fn create_user(_user: Json<NewUser>) -> Result<Json<u64>, actix_web::Error> {
+    Ok(Json(1))
+}
+
+#[route("/files/{tail}*", method = "GET", method = "HEAD")]
+async fn file(_tail: web::Path<String>) -> impl Responder {
+    HttpResponse::Ok()
+}
+
+#[get("/openapi.json")]
+async fn openapi(req: HttpRequest) -> Json<OpenApi> {
+    Json(OpenApi::new("Users", "1.0").with_routes(&req.resource_map().routes()))
+}
+
+#[actix_rt::test]
+async fn document_from_macros() {
+    let app = test::init_service(
+        App::new().service(
+            web::scope("/api")
+                .service(get_user)
+                .service(create_user)
+                .service(file)
+                .service(openapi),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/openapi.json")
+        .to_request();
+    let doc: Value = test::call_and_read_body_json(&app, req).await;
+    let paths = &doc["paths"];
+
+    assert_eq!(doc["openapi"], "3.1.0");
+
+    assert_eq!(
+        paths["/api/users/{id}"]["get"],
+        json!({
+            "operationId": "get_user",
+            "summary": "Fetches a user.",
+            "description": "Returns 404 if the user does not exist.",
+            "parameters": [
+                {
+                    "name": "accept-language",
+                    "in": "header",
+                    "required": false,
+                    "schema": { "type": "string" },
+                },
+                {
+                    "name": "id",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer", "format": "int64" },
+                },
+            ],
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "content": { "application/json": { "schema": { "type": "string" } } },
+                },
+                "404": { "description": "Not Found" },
+            },
+        })
+    );
+
+    let create = &paths["/api/users"]["post"];
+    assert_eq!(create["requestBody"]["required"], true);
+    assert_eq!(
+        create["requestBody"]["content"]["application/json"]["schema"]["required"],
+        json!(["name"])
+    );
+    assert_eq!(
+        create["responses"]["200"]["content"]["application/json"]["schema"]["format"],
+        "int64"
+    );
+    assert!(create["responses"]["default"].is_object());
+
+    let files = &paths["/api/files/{tail}"];
+    assert_eq!(files["get"]["parameters"][0]["name"], "tail");
+    assert_eq!(files["get"]["operationId"], "file");
+    assert_eq!(files["head"]["operationId"], "file_head");
+
+    // operation IDs are unique across the document
+    let mut ids = paths
+        .as_object()
+        .unwrap()
+        .values()
+        .flat_map(|ops| ops.as_object().unwrap().values())
+        .filter_map(|op| op["operationId"].as_str())
+        .collect::<Vec<_>>();
+    let count = ids.len();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), count);
+    assert_eq!(
+        files["get"]["responses"],
+        json!({ "default": { "description": "Response" } })
+    );
+}
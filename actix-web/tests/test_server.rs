@@ -940,3 +940,120 @@ async fn test_accept_encoding_no_match() {
 
     srv.stop().await;
 }
+
+#[cfg(feature = "openssl")]
+mod cert_store {
+    use std::net::{SocketAddr, TcpStream};
+
+    use actix_test::{SelfSignedCert, TestServerConfig};
+    use actix_web::dev::CertStore;
+    use openssl::ssl::{SslConnector, SslStream, SslVerifyMode};
+
+    use super::*;
+
+    fn connect(addr: SocketAddr, server_name: Option<&str>) -> SslStream<TcpStream> {
+        let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+        builder.set_verify(SslVerifyMode::NONE);
+
+        let config = builder
+            .build()
+            .configure()
+            .unwrap()
+            .use_server_name_indication(server_name.is_some())
+            .verify_hostname(false);
+
+        let stream = TcpStream::connect(addr).unwrap();
+        config.connect(server_name.unwrap_or(""), stream).unwrap()
+    }
+
+    fn served_cert(addr: SocketAddr, server_name: Option<&str>) -> Vec<u8> {
+        let stream = connect(addr, server_name);
+        stream.ssl().peer_certificate().unwrap().to_der().unwrap()
+    }
+
+    fn get(stream: &mut SslStream<TcpStream>) -> String {
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: example.org\r\n\r\n")
+            .unwrap();
+
+        let mut res = Vec::new();
+        let mut buf = [0; 1024];
+
+        while !res.ends_with(b"\r\n\r\nok") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "connection closed");
+            res.extend_from_slice(&buf[..n]);
+        }
+
+        String::from_utf8(res).unwrap()
+    }
+
+    async fn sni_and_reload(config: impl FnOnce(&CertStore) -> TestServerConfig) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = |name: &str| dir.path().join(name);
+
+        let com = SelfSignedCert::generate(["example.com", "*.example.com"]);
+        com.write(path("com.pem"), path("com.key")).unwrap();
+        let org = SelfSignedCert::generate(["example.org"]);
+        org.write(path("org.pem"), path("org.key")).unwrap();
+
+        let store = CertStore::new()
+            .add(path("com.pem"), path("com.key"))
+            .unwrap()
+            .add(path("org.pem"), path("org.key"))
+            .unwrap();
+
+        let srv = actix_test::start_with(config(&store), || {
+            App::new().default_service(web::to(|| async { "ok" }))
+        });
+        let addr = srv.addr();
+
+        assert_eq!(served_cert(addr, Some("example.org")), org.cert_der());
+        assert_eq!(served_cert(addr, Some("WWW.example.com")), com.cert_der());
+        assert_eq!(served_cert(addr, Some("example.net")), com.cert_der());
+        assert_eq!(served_cert(addr, None), com.cert_der());
+
+        let mut conn = connect(addr, Some("example.org"));
+        assert!(get(&mut conn).starts_with("HTTP/1.1 200 OK"));
+
+        let new_org = SelfSignedCert::generate(["example.org"]);
+        new_org.write(path("org.pem"), path("org.key")).unwrap();
+        store.reload().unwrap();
+
+        assert_eq!(served_cert(addr, Some("example.org")), new_org.cert_der());
+        assert_eq!(served_cert(addr, Some("example.com")), com.cert_der());
+
+        // connections established before the reload are kept
+        assert!(get(&mut conn).starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(
+            conn.ssl().peer_certificate().unwrap().to_der().unwrap(),
+            org.cert_der()
+        );
+
+        srv.stop().await;
+    }
+
+    #[actix_rt::test]
+    async fn openssl_sni_and_reload() {
+        sni_and_reload(|store| {
+            actix_test::config()
+                .h1()
+                .openssl(store.openssl_acceptor().unwrap().build())
+        })
+        .await;
+    }
+
+    #[cfg(feature = "rustls-0_23")]
+    #[actix_rt::test]
+    async fn rustls_sni_and_reload() {
+        use std::sync::Arc;
+
+        sni_and_reload(|store| {
+            let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+            actix_test::config()
+                .h1()
+                .rustls_0_23(store.rustls_0_23_config(provider).unwrap())
+        })
+        .await;
+    }
+}
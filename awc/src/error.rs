@@ -79,3 +79,39 @@ pub enum JsonPayloadError {
 }
 
 impl std::error::Error for JsonPayloadError {}
+
+/// A set of errors that can occur during parsing MessagePack payloads
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Display, From)]
+pub enum MsgPackPayloadError {
+    /// Content type error
+    #[display("Content type error")]
+    ContentType,
+    /// Deserialize error
+    #[display("MessagePack deserialize error: {}", _0)]
+    Deserialize(rmp_serde::decode::Error),
+    /// Payload error
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+#[cfg(feature = "msgpack")]
+impl std::error::Error for MsgPackPayloadError {}
+
+/// A set of errors that can occur during parsing CBOR payloads
+#[cfg(feature = "cbor")]
+#[derive(Debug, Display, From)]
+pub enum CborPayloadError {
+    /// Content type error
+    #[display("Content type error")]
+    ContentType,
+    /// Deserialize error
+    #[display("CBOR deserialize error: {}", _0)]
+    Deserialize(ciborium::de::Error<std::io::Error>),
+    /// Payload error
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+#[cfg(feature = "cbor")]
+impl std::error::Error for CborPayloadError {}
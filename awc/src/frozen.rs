@@ -69,6 +69,30 @@ impl FrozenClientRequest {
         )
     }
 
+    /// Send a MessagePack body.
+    #[cfg(feature = "msgpack")]
+    pub fn send_msgpack<T: Serialize>(&self, value: &T) -> SendClientRequest {
+        RequestSender::Rc(Rc::clone(&self.head), None).send_msgpack(
+            self.addr,
+            self.response_decompress,
+            self.timeout,
+            &self.config,
+            value,
+        )
+    }
+
+    /// Send a CBOR body.
+    #[cfg(feature = "cbor")]
+    pub fn send_cbor<T: Serialize>(&self, value: &T) -> SendClientRequest {
+        RequestSender::Rc(Rc::clone(&self.head), None).send_cbor(
+            self.addr,
+            self.response_decompress,
+            self.timeout,
+            &self.config,
+            value,
+        )
+    }
+
     /// Send an urlencoded body.
     pub fn send_form<T: Serialize>(&self, value: &T) -> SendClientRequest {
         RequestSender::Rc(Rc::clone(&self.head), None).send_form(
@@ -175,6 +199,38 @@ impl FrozenSendBuilder {
         )
     }
 
+    /// Complete request construction and send a MessagePack body.
+    #[cfg(feature = "msgpack")]
+    pub fn send_msgpack(self, value: impl Serialize) -> SendClientRequest {
+        if let Some(err) = self.err {
+            return err.into();
+        }
+
+        RequestSender::Rc(self.req.head, Some(self.extra_headers)).send_msgpack(
+            self.req.addr,
+            self.req.response_decompress,
+            self.req.timeout,
+            &self.req.config,
+            value,
+        )
+    }
+
+    /// Complete request construction and send a CBOR body.
+    #[cfg(feature = "cbor")]
+    pub fn send_cbor(self, value: impl Serialize) -> SendClientRequest {
+        if let Some(err) = self.err {
+            return err.into();
+        }
+
+        RequestSender::Rc(self.req.head, Some(self.extra_headers)).send_cbor(
+            self.req.addr,
+            self.req.response_decompress,
+            self.req.timeout,
+            &self.req.config,
+            value,
+        )
+    }
+
     /// Complete request construction and send an urlencoded body.
     pub fn send_form(self, value: impl Serialize) -> SendClientRequest {
         if let Some(err) = self.err {
@@ -134,6 +134,10 @@ pub mod http {
     pub use actix_http::{header, uri, ConnectionType, Error, Method, StatusCode, Uri, Version};
 }
 
+#[cfg(feature = "cbor")]
+pub use self::responses::CborBody;
+#[cfg(feature = "msgpack")]
+pub use self::responses::MsgPackBody;
 #[allow(deprecated)]
 pub use self::responses::{ClientResponse, JsonBody, MessageBody, ResponseBody};
 pub use self::{
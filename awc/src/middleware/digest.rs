@@ -0,0 +1,560 @@
+use std::{fmt::Write as _, rc::Rc};
+
+use actix_http::{
+    header::{self, HeaderMap, HeaderValue},
+    Method, RequestHeadType, StatusCode,
+};
+use actix_service::Service;
+use futures_core::future::LocalBoxFuture;
+use md5::Md5;
+use sha2::{Digest as _, Sha256};
+
+use super::Transform;
+use crate::{
+    any_body::AnyBody,
+    client::SendRequestError,
+    connect::{ConnectRequest, ConnectResponse},
+};
+
+/// Middleware that answers `Digest` authentication challenges, defined in
+/// [RFC 7616](https://datatracker.ietf.org/doc/html/rfc7616).
+///
+/// When a response is `401 Unauthorized` with a `Digest` challenge, the request is sent again
+/// with an `Authorization` header computed from the challenge. Like a
+/// [`FrozenClientRequest`](crate::FrozenClientRequest), the resent request shares the original
+/// request head, with the `Authorization` header added as an extra header.
+///
+/// The `SHA-256` and `MD5` algorithms (and their `-sess` variants) are supported, with the `auth`
+/// quality of protection or, for RFC 2069 servers, none. The strongest offered algorithm is used.
+///
+/// Requests are not resent if they already have an `Authorization` header or if their body is a
+/// stream, which can only be sent once. In those cases, the `401` response is returned as is.
+///
+/// # Examples
+/// ```no_run
+/// use awc::{middleware::DigestAuth, Client};
+///
+/// # #[actix_rt::main]
+/// # async fn main() {
+/// let client = Client::builder()
+///     .wrap(DigestAuth::new("Mufasa", "Circle of Life"))
+///     .finish();
+///
+/// let res = client.get("http://camera.local/snapshot").send().await;
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DigestAuth {
+    credentials: Rc<Credentials>,
+}
+
+#[derive(Debug)]
+struct Credentials {
+    username: String,
+    password: String,
+}
+
+impl DigestAuth {
+    /// Constructs new middleware answering challenges with the given user name and password.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            credentials: Rc::new(Credentials {
+                username: username.into(),
+                password: password.into(),
+            }),
+        }
+    }
+}
+
+impl<S> Transform<S, ConnectRequest> for DigestAuth
+where
+    S: Service<ConnectRequest, Response = ConnectResponse, Error = SendRequestError> + 'static,
+{
+    type Transform = DigestAuthService<S>;
+
+    fn new_transform(self, service: S) -> Self::Transform {
+        DigestAuthService {
+            connector: Rc::new(service),
+            credentials: self.credentials,
+        }
+    }
+}
+
+pub struct DigestAuthService<S> {
+    connector: Rc<S>,
+    credentials: Rc<Credentials>,
+}
+
+impl<S> Service<ConnectRequest> for DigestAuthService<S>
+where
+    S: Service<ConnectRequest, Response = ConnectResponse, Error = SendRequestError> + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = LocalBoxFuture<'static, Result<ConnectResponse, SendRequestError>>;
+
+    actix_service::forward_ready!(connector);
+
+    fn call(&self, req: ConnectRequest) -> Self::Future {
+        let (head, body, addr) = match req {
+            ConnectRequest::Client(head, body, addr) => (head, body, addr),
+            ConnectRequest::Tunnel(..) => return Box::pin(self.connector.call(req)),
+        };
+
+        // share the request head so the request can be resent, like `FrozenClientRequest`
+        let (head, extra_headers) = match head {
+            RequestHeadType::Owned(head) => (Rc::new(head), None),
+            RequestHeadType::Rc(head, extra_headers) => (head, extra_headers),
+        };
+
+        let has_authorization = head.headers.contains_key(header::AUTHORIZATION)
+            || extra_headers
+                .as_ref()
+                .is_some_and(|headers| headers.contains_key(header::AUTHORIZATION));
+
+        let resend_body = match &body {
+            _ if has_authorization => None,
+            AnyBody::None => Some(AnyBody::None),
+            AnyBody::Bytes { body } => Some(AnyBody::Bytes { body: body.clone() }),
+            AnyBody::Body { .. } => None,
+        };
+
+        let connector = Rc::clone(&self.connector);
+        let credentials = Rc::clone(&self.credentials);
+
+        Box::pin(async move {
+            let first_head = RequestHeadType::Rc(Rc::clone(&head), extra_headers.clone());
+            let res = connector
+                .call(ConnectRequest::Client(first_head, body, addr))
+                .await?;
+
+            let (res, body) = match (res, resend_body) {
+                (ConnectResponse::Client(res), Some(body))
+                    if res.status() == StatusCode::UNAUTHORIZED =>
+                {
+                    (res, body)
+                }
+                (res, _) => return Ok(res),
+            };
+
+            let challenge = res
+                .headers()
+                .get_all(header::WWW_AUTHENTICATE)
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(parse_challenges)
+                .filter_map(|(scheme, params)| {
+                    scheme
+                        .eq_ignore_ascii_case("Digest")
+                        .then(|| Challenge::from_params(params))
+                        .flatten()
+                })
+                .max_by_key(|challenge| challenge.algorithm.preference());
+
+            let Some(challenge) = challenge else {
+                return Ok(ConnectResponse::Client(res));
+            };
+
+            let uri = head
+                .uri
+                .path_and_query()
+                .map_or("/", |path_and_query| path_and_query.as_str());
+            let cnonce = format!("{:032x}", rand::random::<u128>());
+
+            let authorization = challenge.authorization(
+                &credentials.username,
+                &credentials.password,
+                &head.method,
+                uri,
+                &cnonce,
+            );
+
+            let Ok(authorization) = HeaderValue::try_from(authorization) else {
+                return Ok(ConnectResponse::Client(res));
+            };
+
+            let mut extra_headers = extra_headers.unwrap_or_else(HeaderMap::new);
+            extra_headers.insert(header::AUTHORIZATION, authorization);
+
+            let head = RequestHeadType::Rc(head, Some(extra_headers));
+            connector
+                .call(ConnectRequest::Client(head, body, addr))
+                .await
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl Algorithm {
+    fn parse(name: &str) -> Option<Self> {
+        [
+            ("MD5", Algorithm::Md5),
+            ("MD5-sess", Algorithm::Md5Sess),
+            ("SHA-256", Algorithm::Sha256),
+            ("SHA-256-sess", Algorithm::Sha256Sess),
+        ]
+        .into_iter()
+        .find(|(alg_name, _)| alg_name.eq_ignore_ascii_case(name))
+        .map(|(_, algorithm)| algorithm)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Md5Sess => "MD5-sess",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn preference(self) -> u8 {
+        match self {
+            Algorithm::Md5 | Algorithm::Md5Sess => 0,
+            Algorithm::Sha256 | Algorithm::Sha256Sess => 1,
+        }
+    }
+
+    fn hash(self, data: &str) -> String {
+        let digest = match self {
+            Algorithm::Md5 | Algorithm::Md5Sess => Md5::digest(data).to_vec(),
+            Algorithm::Sha256 | Algorithm::Sha256Sess => Sha256::digest(data).to_vec(),
+        };
+
+        digest.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Challenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: Algorithm,
+
+    /// True if the `auth` quality of protection is offered; false for RFC 2069 challenges.
+    qop_auth: bool,
+}
+
+impl Challenge {
+    fn from_params(params: Vec<(String, String)>) -> Option<Self> {
+        let mut realm = None;
+        let mut nonce = None;
+        let mut opaque = None;
+        let mut algorithm = Algorithm::Md5;
+        let mut qop = None;
+
+        for (name, value) in params {
+            match name.as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => opaque = Some(value),
+                "algorithm" => algorithm = Algorithm::parse(&value)?,
+                "qop" => qop = Some(value),
+                _ => {}
+            }
+        }
+
+        let qop_auth = match qop {
+            None => false,
+            Some(qop) if qop.split(',').any(|qop| qop.trim() == "auth") => true,
+            // only `auth-int` is offered
+            Some(_) => return None,
+        };
+
+        Some(Challenge {
+            realm: realm?,
+            nonce: nonce?,
+            opaque,
+            algorithm,
+            qop_auth,
+        })
+    }
+
+    /// Returns the `Authorization` header value answering this challenge.
+    fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &Method,
+        uri: &str,
+        cnonce: &str,
+    ) -> String {
+        const NC: &str = "00000001";
+
+        let hash = |data: String| self.algorithm.hash(&data);
+
+        let mut ha1 = hash(format!("{}:{}:{}", username, self.realm, password));
+        if let Algorithm::Md5Sess | Algorithm::Sha256Sess = self.algorithm {
+            ha1 = hash(format!("{}:{}:{}", ha1, self.nonce, cnonce));
+        }
+
+        let ha2 = hash(format!("{}:{}", method, uri));
+
+        let response = if self.qop_auth {
+            hash(format!(
+                "{}:{}:{}:{}:auth:{}",
+                ha1, self.nonce, NC, cnonce, ha2
+            ))
+        } else {
+            hash(format!("{}:{}:{}", ha1, self.nonce, ha2))
+        };
+
+        let mut value = format!(
+            "Digest username={}, realm={}, nonce={}, uri={}, algorithm={}, response=\"{}\"",
+            quote(username),
+            quote(&self.realm),
+            quote(&self.nonce),
+            quote(uri),
+            self.algorithm.name(),
+            response,
+        );
+
+        if self.qop_auth {
+            let _ = write!(value, ", qop=auth, nc={}, cnonce={}", NC, quote(cnonce));
+        }
+
+        if let Some(opaque) = &self.opaque {
+            let _ = write!(value, ", opaque={}", quote(opaque));
+        }
+
+        value
+    }
+}
+
+/// Formats `value` as a `quoted-string`.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Parses the challenges of a `WWW-Authenticate` header value into their schemes and lowercased
+/// parameters.
+///
+/// See [RFC 7235 §4.1](https://datatracker.ietf.org/doc/html/rfc7235#section-4.1).
+fn parse_challenges(value: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+
+    let mut challenges: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    let mut rest = value;
+
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+
+        let token_len = rest.find(|c| !is_tchar(c)).unwrap_or(rest.len());
+        if token_len == 0 {
+            // end of input, or invalid syntax
+            return challenges;
+        }
+
+        let (token, after_token) = rest.split_at(token_len);
+
+        let Some(after_eq) = after_token.trim_start().strip_prefix('=') else {
+            challenges.push((token.to_owned(), Vec::new()));
+            rest = after_token;
+            continue;
+        };
+        let after_eq = after_eq.trim_start();
+
+        let (value, remaining) = match after_eq.strip_prefix('"') {
+            Some(quoted) => {
+                let mut value = String::new();
+                let mut chars = quoted.char_indices();
+                let mut end = None;
+
+                while let Some((idx, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            end = Some(idx + 1);
+                            break;
+                        }
+                        '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                        c => value.push(c),
+                    }
+                }
+
+                match end {
+                    Some(end) => (value, &quoted[end..]),
+                    None => return challenges,
+                }
+            }
+            None => {
+                // token values, or the trailing `=` of a token68
+                let len = after_eq
+                    .find(|c| !is_tchar(c) && c != '=')
+                    .unwrap_or(after_eq.len());
+                let (value, remaining) = after_eq.split_at(len);
+                (value.to_owned(), remaining)
+            }
+        };
+
+        if let Some((_, params)) = challenges.last_mut() {
+            params.push((token.to_ascii_lowercase(), value));
+        }
+
+        rest = remaining;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        dev::ServiceRequest,
+        http::header::{DigestAlgorithm, WWW_AUTHENTICATE},
+        middleware::HttpAuthentication,
+        web, App, Error, HttpResponse,
+    };
+
+    use super::*;
+    use crate::ClientBuilder;
+
+    #[test]
+    fn challenges() {
+        let challenges = parse_challenges(
+            r#"Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple", Negotiate abc==, Digest realm="a, b", nonce=xyz"#,
+        );
+
+        assert_eq!(
+            challenges,
+            vec![
+                (
+                    "Newauth".to_owned(),
+                    vec![
+                        ("realm".to_owned(), "apps".to_owned()),
+                        ("type".to_owned(), "1".to_owned()),
+                        ("title".to_owned(), r#"Login to "apps""#.to_owned()),
+                    ]
+                ),
+                (
+                    "Basic".to_owned(),
+                    vec![("realm".to_owned(), "simple".to_owned())]
+                ),
+                (
+                    "Negotiate".to_owned(),
+                    vec![("abc".to_owned(), "=".to_owned())]
+                ),
+                (
+                    "Digest".to_owned(),
+                    vec![
+                        ("realm".to_owned(), "a, b".to_owned()),
+                        ("nonce".to_owned(), "xyz".to_owned()),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rfc_7616_example() {
+        // RFC 7616 §3.9.1
+        let (_, params) = parse_challenges(concat!(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, "#,
+            r#"nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", "#,
+            r#"opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        ))
+        .remove(0);
+        let challenge = Challenge::from_params(params).unwrap();
+
+        let authorization = challenge.authorization(
+            "Mufasa",
+            "Circle of Life",
+            &Method::GET,
+            "/dir/index.html",
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        );
+
+        assert!(authorization.contains(
+            r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1""#
+        ));
+        assert!(authorization.contains(", qop=auth, nc=00000001, "));
+        assert!(
+            authorization.ends_with(r#", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#)
+        );
+    }
+
+    #[test]
+    fn rfc_2069_challenge() {
+        let (_, params) =
+            parse_challenges(r#"Digest realm="testrealm@host.com", nonce="abc""#).remove(0);
+        let challenge = Challenge::from_params(params).unwrap();
+        assert_eq!(challenge.algorithm, Algorithm::Md5);
+        assert!(!challenge.qop_auth);
+
+        let authorization = challenge.authorization("user", "pass", &Method::GET, "/", "def");
+        assert!(!authorization.contains("qop="));
+
+        let (_, params) =
+            parse_challenges(r#"Digest realm="a", nonce="b", qop="auth-int""#).remove(0);
+        assert!(Challenge::from_params(params).is_none());
+    }
+
+    async fn validator(
+        req: ServiceRequest,
+        auth: web::DigestAuth,
+    ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+        match auth.verify("Circle of Life") {
+            Ok(()) => Ok(req),
+            Err(err) => Err((err.into(), req)),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn answers_challenge() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .app_data(
+                    web::DigestAuthConfig::new("api")
+                        .algorithms([DigestAlgorithm::Md5, DigestAlgorithm::Sha256]),
+                )
+                .wrap(HttpAuthentication::digest(validator))
+                .route(
+                    "/",
+                    web::post().to(|body: String| async move { HttpResponse::Ok().body(body) }),
+                )
+        });
+
+        let client = ClientBuilder::new()
+            .wrap(DigestAuth::new("Mufasa", "Circle of Life"))
+            .finish();
+
+        let mut res = client
+            .post(srv.url("/?a=1"))
+            .send_body("hello")
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.body().await.unwrap(), "hello");
+
+        // frozen requests are answered too
+        let req = client.post(srv.url("/")).freeze().unwrap();
+        let res = req.send_body("hello").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let client = ClientBuilder::new()
+            .wrap(DigestAuth::new("Mufasa", "wrong"))
+            .finish();
+
+        let res = client.post(srv.url("/")).send_body("hello").await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert!(res.headers().contains_key(WWW_AUTHENTICATE));
+    }
+}
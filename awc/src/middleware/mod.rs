@@ -1,9 +1,13 @@
+#[cfg(feature = "digest-auth")]
+mod digest;
 mod redirect;
 
 use std::marker::PhantomData;
 
 use actix_service::Service;
 
+#[cfg(feature = "digest-auth")]
+pub use self::digest::DigestAuth;
 pub use self::redirect::Redirect;
 
 /// Trait for transform a type to another one.
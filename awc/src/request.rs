@@ -360,6 +360,40 @@ impl ClientRequest {
         )
     }
 
+    /// Set a MessagePack body and generate `ClientRequest`
+    #[cfg(feature = "msgpack")]
+    pub fn send_msgpack<T: Serialize>(self, value: &T) -> SendClientRequest {
+        let slf = match self.prep_for_sending() {
+            Ok(slf) => slf,
+            Err(err) => return err.into(),
+        };
+
+        RequestSender::Owned(slf.head).send_msgpack(
+            slf.addr,
+            slf.response_decompress,
+            slf.timeout,
+            &slf.config,
+            value,
+        )
+    }
+
+    /// Set a CBOR body and generate `ClientRequest`
+    #[cfg(feature = "cbor")]
+    pub fn send_cbor<T: Serialize>(self, value: &T) -> SendClientRequest {
+        let slf = match self.prep_for_sending() {
+            Ok(slf) => slf,
+            Err(err) => return err.into(),
+        };
+
+        RequestSender::Owned(slf.head).send_cbor(
+            slf.addr,
+            slf.response_decompress,
+            slf.timeout,
+            &slf.config,
+            value,
+        )
+    }
+
     /// Set a urlencoded body and generate `ClientRequest`
     ///
     /// `ClientRequestBuilder` can not be used after this call.
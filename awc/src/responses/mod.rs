@@ -3,11 +3,19 @@ use std::{future::Future, io, pin::Pin, task::Context};
 use actix_http::error::PayloadError;
 use actix_rt::time::Sleep;
 
+#[cfg(feature = "cbor")]
+mod cbor_body;
 mod json_body;
+#[cfg(feature = "msgpack")]
+mod msgpack_body;
 mod read_body;
 mod response;
 mod response_body;
 
+#[cfg(feature = "cbor")]
+pub use self::cbor_body::CborBody;
+#[cfg(feature = "msgpack")]
+pub use self::msgpack_body::MsgPackBody;
 #[allow(deprecated)]
 pub use self::response_body::{MessageBody, ResponseBody};
 pub use self::{json_body::JsonBody, response::ClientResponse};
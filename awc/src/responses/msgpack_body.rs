@@ -0,0 +1,163 @@
+use std::{
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_http::{error::PayloadError, header, HttpMessage};
+use bytes::Bytes;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+use serde::de::DeserializeOwned;
+
+use super::{read_body::ReadBody, ResponseTimeout, DEFAULT_BODY_LIMIT};
+use crate::{error::MsgPackPayloadError, ClientResponse};
+
+pin_project! {
+    /// A `Future` that reads a body stream, parses MessagePack, resolving to a deserialized `T`.
+    ///
+    /// # Errors
+    /// `Future` implementation returns error if:
+    /// - content type is not a MessagePack media type;
+    /// - content length is greater than [limit](MsgPackBody::limit) (default: 2 MiB).
+    pub struct MsgPackBody<S, T> {
+        #[pin]
+        body: Option<ReadBody<S>>,
+        length: Option<usize>,
+        timeout: ResponseTimeout,
+        err: Option<MsgPackPayloadError>,
+        _phantom: PhantomData<T>,
+    }
+}
+
+impl<S, T> MsgPackBody<S, T>
+where
+    S: Stream<Item = Result<Bytes, PayloadError>>,
+    T: DeserializeOwned,
+{
+    /// Creates a MessagePack body stream reader from a response by taking its payload.
+    pub fn new(res: &mut ClientResponse<S>) -> Self {
+        // check content-type
+        let msgpack = if let Ok(Some(mime)) = res.mime_type() {
+            mime.type_() == mime::APPLICATION
+                && (matches!(
+                    mime.subtype().as_str(),
+                    "msgpack" | "x-msgpack" | "vnd.msgpack"
+                ) || mime.suffix().is_some_and(|suffix| suffix == "msgpack"))
+        } else {
+            false
+        };
+
+        if !msgpack {
+            return MsgPackBody {
+                length: None,
+                body: None,
+                timeout: ResponseTimeout::default(),
+                err: Some(MsgPackPayloadError::ContentType),
+                _phantom: PhantomData,
+            };
+        }
+
+        let length = res
+            .headers()
+            .get(&header::CONTENT_LENGTH)
+            .and_then(|len_hdr| len_hdr.to_str().ok())
+            .and_then(|len_str| len_str.parse::<usize>().ok());
+
+        MsgPackBody {
+            body: Some(ReadBody::new(res.take_payload(), DEFAULT_BODY_LIMIT)),
+            length,
+            timeout: mem::take(&mut res.timeout),
+            err: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Change max size of payload. Default limit is 2 MiB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        if let Some(ref mut fut) = self.body {
+            fut.limit = limit;
+        }
+
+        self
+    }
+}
+
+impl<S, T> Future for MsgPackBody<S, T>
+where
+    S: Stream<Item = Result<Bytes, PayloadError>>,
+    T: DeserializeOwned,
+{
+    type Output = Result<T, MsgPackPayloadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if let Some(err) = this.err.take() {
+            return Poll::Ready(Err(err));
+        }
+
+        if let Some(len) = this.length.take() {
+            let body = Option::as_ref(&this.body).unwrap();
+            if len > body.limit {
+                return Poll::Ready(Err(MsgPackPayloadError::Payload(PayloadError::Overflow)));
+            }
+        }
+
+        this.timeout
+            .poll_timeout(cx)
+            .map_err(MsgPackPayloadError::Payload)?;
+
+        let body = ready!(this.body.as_pin_mut().unwrap().poll(cx))?;
+        Poll::Ready(rmp_serde::from_slice::<T>(&body).map_err(MsgPackPayloadError::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::test::TestResponse;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct MyObject {
+        name: String,
+    }
+
+    #[actix_rt::test]
+    async fn read_msgpack_body() {
+        let mut res = TestResponse::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .finish();
+        let err = MsgPackBody::<_, MyObject>::new(&mut res).await.unwrap_err();
+        assert!(matches!(err, MsgPackPayloadError::ContentType));
+
+        let mut res = TestResponse::default()
+            .insert_header((header::CONTENT_TYPE, "application/msgpack"))
+            .insert_header((header::CONTENT_LENGTH, "10000"))
+            .finish();
+        let err = MsgPackBody::<_, MyObject>::new(&mut res)
+            .limit(100)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            MsgPackPayloadError::Payload(PayloadError::Overflow)
+        ));
+
+        let mut res = TestResponse::default()
+            .insert_header((header::CONTENT_TYPE, "application/msgpack"))
+            .set_payload(Bytes::from_static(b"\x81\xa4name\xa4test"))
+            .finish();
+        let obj = MsgPackBody::<_, MyObject>::new(&mut res).await.unwrap();
+        assert_eq!(
+            obj,
+            MyObject {
+                name: "test".to_owned()
+            }
+        );
+    }
+}
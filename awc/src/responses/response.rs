@@ -16,6 +16,10 @@ use futures_core::Stream;
 use pin_project_lite::pin_project;
 use serde::de::DeserializeOwned;
 
+#[cfg(feature = "cbor")]
+use super::CborBody;
+#[cfg(feature = "msgpack")]
+use super::MsgPackBody;
 use super::{JsonBody, ResponseBody, ResponseTimeout};
 #[cfg(feature = "cookies")]
 use crate::cookie::{Cookie, ParseError as CookieParseError};
@@ -204,6 +208,36 @@ where
     pub fn json<T: DeserializeOwned>(&mut self) -> JsonBody<S, T> {
         JsonBody::new(self)
     }
+
+    /// Returns a [`Future`] consumes the body stream, parses MessagePack, and resolves to a
+    /// deserialized `T` value.
+    ///
+    /// # Errors
+    /// Future returns error if:
+    /// - content type is not a MessagePack media type;
+    /// - content length is greater than [limit](MsgPackBody::limit) (default: 2 MiB);
+    /// - payload is not valid MessagePack or cannot be deserialized as `T`.
+    ///
+    /// [`Future`]: std::future::Future
+    #[cfg(feature = "msgpack")]
+    pub fn msgpack<T: DeserializeOwned>(&mut self) -> MsgPackBody<S, T> {
+        MsgPackBody::new(self)
+    }
+
+    /// Returns a [`Future`] consumes the body stream, parses CBOR, and resolves to a deserialized
+    /// `T` value.
+    ///
+    /// # Errors
+    /// Future returns error if:
+    /// - content type is not `application/cbor`;
+    /// - content length is greater than [limit](CborBody::limit) (default: 2 MiB);
+    /// - payload is not valid CBOR or cannot be deserialized as `T`.
+    ///
+    /// [`Future`]: std::future::Future
+    #[cfg(feature = "cbor")]
+    pub fn cbor<T: DeserializeOwned>(&mut self) -> CborBody<S, T> {
+        CborBody::new(self)
+    }
 }
 
 impl<S> fmt::Debug for ClientResponse<S> {
@@ -34,6 +34,10 @@ pub(crate) enum PrepForSendingError {
     Http(HttpError),
     Json(serde_json::Error),
     Form(serde_urlencoded::ser::Error),
+    #[cfg(feature = "msgpack")]
+    MsgPack(rmp_serde::encode::Error),
+    #[cfg(feature = "cbor")]
+    Cbor(ciborium::ser::Error<std::io::Error>),
 }
 
 impl From<PrepForSendingError> for FreezeRequestError {
@@ -47,6 +51,14 @@ impl From<PrepForSendingError> for FreezeRequestError {
             PrepForSendingError::Form(err) => {
                 FreezeRequestError::Custom(Box::new(err), Box::new("form serialization error"))
             }
+            #[cfg(feature = "msgpack")]
+            PrepForSendingError::MsgPack(err) => {
+                FreezeRequestError::Custom(Box::new(err), Box::new("msgpack serialization error"))
+            }
+            #[cfg(feature = "cbor")]
+            PrepForSendingError::Cbor(err) => {
+                FreezeRequestError::Custom(Box::new(err), Box::new("cbor serialization error"))
+            }
         }
     }
 }
@@ -62,6 +74,14 @@ impl From<PrepForSendingError> for SendRequestError {
             PrepForSendingError::Form(err) => {
                 SendRequestError::Custom(Box::new(err), Box::new("form serialization error"))
             }
+            #[cfg(feature = "msgpack")]
+            PrepForSendingError::MsgPack(err) => {
+                SendRequestError::Custom(Box::new(err), Box::new("msgpack serialization error"))
+            }
+            #[cfg(feature = "cbor")]
+            PrepForSendingError::Cbor(err) => {
+                SendRequestError::Custom(Box::new(err), Box::new("cbor serialization error"))
+            }
         }
     }
 }
@@ -226,6 +246,49 @@ impl RequestSender {
         self.send_body(addr, response_decompress, timeout, config, body)
     }
 
+    #[cfg(feature = "msgpack")]
+    pub(crate) fn send_msgpack(
+        mut self,
+        addr: Option<net::SocketAddr>,
+        response_decompress: bool,
+        timeout: Option<Duration>,
+        config: &ClientConfig,
+        value: impl Serialize,
+    ) -> SendClientRequest {
+        let body = match rmp_serde::to_vec_named(&value) {
+            Ok(body) => body,
+            Err(err) => return PrepForSendingError::MsgPack(err).into(),
+        };
+
+        if let Err(err) = self.set_header_if_none(header::CONTENT_TYPE, "application/msgpack") {
+            return err.into();
+        }
+
+        self.send_body(addr, response_decompress, timeout, config, body)
+    }
+
+    #[cfg(feature = "cbor")]
+    pub(crate) fn send_cbor(
+        mut self,
+        addr: Option<net::SocketAddr>,
+        response_decompress: bool,
+        timeout: Option<Duration>,
+        config: &ClientConfig,
+        value: impl Serialize,
+    ) -> SendClientRequest {
+        let mut body = Vec::new();
+
+        if let Err(err) = ciborium::into_writer(&value, &mut body) {
+            return PrepForSendingError::Cbor(err).into();
+        }
+
+        if let Err(err) = self.set_header_if_none(header::CONTENT_TYPE, "application/cbor") {
+            return err.into();
+        }
+
+        self.send_body(addr, response_decompress, timeout, config, body)
+    }
+
     pub(crate) fn send_form(
         mut self,
         addr: Option<net::SocketAddr>,
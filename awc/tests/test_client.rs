@@ -88,6 +88,57 @@ async fn form() {
     assert!(response.status().is_success());
 }
 
+#[cfg(feature = "msgpack")]
+#[actix_rt::test]
+async fn msgpack() {
+    let srv = actix_test::start(|| {
+        App::new().service(web::resource("/").route(web::to(
+            |req: HttpRequest, body: Bytes| async move {
+                HttpResponse::Ok()
+                    .insert_header((
+                        header::CONTENT_TYPE,
+                        req.headers().get(header::CONTENT_TYPE).unwrap().clone(),
+                    ))
+                    .body(body)
+            },
+        )))
+    });
+
+    let mut data = HashMap::new();
+    data.insert("key".to_owned(), vec![1, 2, 3]);
+
+    let mut res = srv.post("/").send_msgpack(&data).await.unwrap();
+    assert!(res.status().is_success());
+    assert_eq!(
+        res.msgpack::<HashMap<String, Vec<u8>>>().await.unwrap(),
+        data
+    );
+}
+
+#[cfg(feature = "cbor")]
+#[actix_rt::test]
+async fn cbor() {
+    let srv = actix_test::start(|| {
+        App::new().service(web::resource("/").route(web::to(
+            |req: HttpRequest, body: Bytes| async move {
+                HttpResponse::Ok()
+                    .insert_header((
+                        header::CONTENT_TYPE,
+                        req.headers().get(header::CONTENT_TYPE).unwrap().clone(),
+                    ))
+                    .body(body)
+            },
+        )))
+    });
+
+    let mut data = HashMap::new();
+    data.insert("key".to_owned(), vec![1, 2, 3]);
+
+    let mut res = srv.post("/").send_cbor(&data).await.unwrap();
+    assert!(res.status().is_success());
+    assert_eq!(res.cbor::<HashMap<String, Vec<u8>>>().await.unwrap(), data);
+}
+
 #[actix_rt::test]
 async fn timeout() {
     let srv = actix_test::start(|| {